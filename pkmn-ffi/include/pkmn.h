@@ -0,0 +1,65 @@
+/*
+ * The pkmn Pokédex and calculators, from libpkmn_ffi.
+ *
+ * Every function returns a JSON string owned by the caller, to be freed with
+ * pkmn_string_free: the result on success, or {"error": "..."}, including
+ * for a panic inside the library. String arguments are NUL-terminated UTF-8;
+ * those marked optional may be NULL.
+ */
+
+#ifndef PKMN_H
+#define PKMN_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The Pokémon with this Pokédex number or slug (e.g. "charizard-mega-x"), as
+ * an object of its Pokédex fields. */
+char *pkmn_find(const char *id);
+
+/* The `limit` closest names to `query`, best first, each with its
+ * "similarity" (0 to 1) and edit "distance". */
+char *pkmn_search(const char *query, size_t limit);
+
+/* Damage multiplier, as a number, of an `attacker` type move against one or
+ * two types. `second_defender` is optional. */
+char *pkmn_effectiveness(const char *attacker, const char *defender,
+                         const char *second_defender);
+
+/* Damage multiplier of each attacking type against a Pokémon, as
+ * [type, multiplier] pairs from most to least effective. */
+char *pkmn_type_defenses(const char *id);
+
+/* A Pokémon's stats at `level` (1 to 100), as an object keyed by stat name.
+ * `nature` is optional and neutral by default. `ivs` and `evs` are optional,
+ * one value for every stat (e.g. "31") or six (e.g. "4/252/0/0/0/252"), by
+ * default 31 IVs and no EVs. */
+char *pkmn_stats(const char *id, uint16_t level, const char *nature,
+                 const char *ivs, const char *evs);
+
+/* Chance, between 0 and 1, that a move hits from Generation 5 onwards, with
+ * stages from -6 to 6. `ability` is optional: "compound-eyes", "hustle", "victory-star" or
+ * "no-guard". */
+char *pkmn_hit_chance(const char *move_name, int8_t accuracy_stage,
+                      int8_t evasion_stage, const char *ability);
+
+/* Exact chance of catching a Pokémon in Generation 1. `ball` is optional:
+ * "poke" (the default), "great", "ultra", "safari" or "master". `status` is
+ * optional: "none" (the default), "sleep", "freeze", "paralysis", "burn" or
+ * "poison". */
+char *pkmn_gen1_catch_chance(uint8_t catch_rate, uint16_t max_hp,
+                             uint16_t current_hp, const char *ball,
+                             const char *status);
+
+/* Frees a string returned by any other function. NULL is ignored. */
+void pkmn_string_free(char *value);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* PKMN_H */
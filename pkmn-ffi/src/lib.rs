@@ -0,0 +1,406 @@
+//! A C ABI for the pkmn Pokédex and calculators, declared in
+//! `include/pkmn.h`, for integrations without Rust bindings (e.g. an OBS
+//! plugin or a game mod).
+//!
+//! Every function returns a JSON string the caller owns and frees with
+//! [`pkmn_string_free`]: the result on success, or `{"error": "..."}`. String
+//! arguments are NUL-terminated UTF-8, and those documented as optional may
+//! be NULL. Panics are caught and returned as errors, since unwinding into
+//! the caller would abort its process.
+
+use std::any::Any;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use pkmn::calc::accuracy::{self, AccuracyAbility};
+use pkmn::calc::retro::{self, Ball, Status};
+use pkmn::calc::stats::{self, Nature, Stat, Stats};
+use pkmn::moves;
+use pkmn::pokedex::{self, Pokemon};
+use pkmn::types::Type;
+
+type Result<T> = std::result::Result<T, String>;
+
+/// The message a panic was started with.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => String::from("unknown panic"),
+        },
+    }
+}
+
+/// Runs `f` and hands its result to the caller as a JSON string, with a
+/// panic as an error.
+fn respond<T: Serialize, F: FnOnce() -> Result<T>>(f: F) -> *mut c_char {
+    let result = panic::catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|payload| Err(format!("pkmn panicked: {}", panic_message(payload))));
+    let value = match result.map(serde_json::to_value) {
+        Ok(Ok(value)) => value,
+        Ok(Err(err)) => json!({ "error": err.to_string() }),
+        Err(err) => json!({ "error": err }),
+    };
+    // JSON escapes control characters, so it never contains a NUL
+    CString::new(value.to_string())
+        .unwrap_or_default()
+        .into_raw()
+}
+
+/// # Safety
+///
+/// `value` is NULL or a NUL-terminated string valid for the call.
+unsafe fn optional_str<'a>(value: *const c_char) -> Result<Option<&'a str>> {
+    if value.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(value)
+        .to_str()
+        .map(Some)
+        .map_err(|_| String::from("Arguments must be UTF-8"))
+}
+
+/// # Safety
+///
+/// As for [`optional_str`].
+unsafe fn required_str<'a>(value: *const c_char, name: &str) -> Result<&'a str> {
+    optional_str(value)?.ok_or_else(|| format!("{} is required", name))
+}
+
+fn find_pokemon(id: &str) -> Result<Pokemon> {
+    pokedex::find_by_id(id).ok_or_else(|| format!("No Pokémon \"{}\"", id))
+}
+
+fn parse_type(name: &str) -> Result<Type> {
+    Type::from_name(name).ok_or_else(|| format!("Unknown type \"{}\"", name))
+}
+
+/// The Pokémon with this Pokédex number or slug, e.g. `charizard-mega-x`, as
+/// an object of its Pokédex fields.
+///
+/// # Safety
+///
+/// `id` is a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn pkmn_find(id: *const c_char) -> *mut c_char {
+    respond(|| required_str(id, "id").and_then(find_pokemon))
+}
+
+/// The `limit` closest names to `query`, best first, each with its
+/// `similarity` (0 to 1) and edit `distance`.
+///
+/// # Safety
+///
+/// `query` is a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn pkmn_search(query: *const c_char, limit: usize) -> *mut c_char {
+    respond(|| {
+        required_str(query, "query").map(|query| {
+            pokedex::search_by_name(query, limit)
+                .into_iter()
+                .map(|found| {
+                    let mut value = serde_json::to_value(&found.pokemon).unwrap_or_default();
+                    value["similarity"] = json!(found.score.similarity);
+                    value["distance"] = json!(found.score.distance);
+                    value
+                })
+                .collect::<Vec<Value>>()
+        })
+    })
+}
+
+/// Damage multiplier, as a number, of an `attacker` type move against a
+/// `defender` type and optionally a second type.
+///
+/// # Safety
+///
+/// `attacker` and `defender` are NUL-terminated strings, and `second_defender`
+/// is one or NULL.
+#[no_mangle]
+pub unsafe extern "C" fn pkmn_effectiveness(
+    attacker: *const c_char,
+    defender: *const c_char,
+    second_defender: *const c_char,
+) -> *mut c_char {
+    respond(|| -> Result<f32> {
+        let mut defenders = vec![parse_type(required_str(defender, "defender")?)?];
+        if let Some(second) = optional_str(second_defender)? {
+            defenders.push(parse_type(second)?);
+        }
+        Ok(parse_type(required_str(attacker, "attacker")?)?.effectiveness_against(&defenders))
+    })
+}
+
+/// Damage multiplier of each attacking type against a Pokémon, as
+/// `[type, multiplier]` pairs from most to least effective.
+///
+/// # Safety
+///
+/// `id` is a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn pkmn_type_defenses(id: *const c_char) -> *mut c_char {
+    respond(|| {
+        required_str(id, "id")
+            .and_then(find_pokemon)
+            .map(|pokemon| {
+                pokemon
+                    .type_defenses()
+                    .into_iter()
+                    .map(|(attacker, multiplier)| json!([attacker.name(), multiplier]))
+                    .collect::<Vec<Value>>()
+            })
+    })
+}
+
+/// A Pokémon's stats at `level`, as an object keyed by stat name. IVs and EVs
+/// are one value for every stat (e.g. `31`) or six (e.g. `4/252/0/0/0/252`),
+/// by default 31 IVs and no EVs; the nature is neutral by default.
+///
+/// # Safety
+///
+/// `id` is a NUL-terminated string; `nature`, `ivs` and `evs` are each one
+/// or NULL.
+#[no_mangle]
+pub unsafe extern "C" fn pkmn_stats(
+    id: *const c_char,
+    level: u16,
+    nature: *const c_char,
+    ivs: *const c_char,
+    evs: *const c_char,
+) -> *mut c_char {
+    respond(|| -> Result<Value> {
+        let pokemon = find_pokemon(required_str(id, "id")?)?;
+        if !(1..=100).contains(&level) {
+            return Err(String::from("Level is between 1 and 100"));
+        }
+        let nature = match optional_str(nature)? {
+            Some(name) => {
+                Nature::from_name(name).ok_or_else(|| format!("Unknown nature \"{}\"", name))?
+            }
+            None => stats::NATURES[0],
+        };
+        let ivs = Stats::parse(optional_str(ivs)?.unwrap_or("31"), stats::MAX_IV)?;
+        let evs = Stats::parse(optional_str(evs)?.unwrap_or("0"), stats::MAX_STAT_EVS)?;
+        let values = stats::stats(&pokemon, &ivs, &evs, level, &nature);
+        Ok(Stat::ALL
+            .iter()
+            .map(|stat| (stat.name().to_string(), json!(values.get(*stat))))
+            .collect::<serde_json::Map<String, Value>>()
+            .into())
+    })
+}
+
+/// Chance, between 0 and 1, that a move hits from Generation 5 onwards, with
+/// the attacker's accuracy and the target's evasion stages, each -6 to 6, and
+/// optionally an ability (`compound-eyes`, `hustle`, `victory-star` or
+/// `no-guard`).
+///
+/// # Safety
+///
+/// `move_name` is a NUL-terminated string, and `ability` is one or NULL.
+#[no_mangle]
+pub unsafe extern "C" fn pkmn_hit_chance(
+    move_name: *const c_char,
+    accuracy_stage: i8,
+    evasion_stage: i8,
+    ability: *const c_char,
+) -> *mut c_char {
+    respond(|| -> Result<f64> {
+        for stage in [accuracy_stage, evasion_stage] {
+            if !(-6..=6).contains(&stage) {
+                return Err(String::from("Stages are between -6 and 6"));
+            }
+        }
+        let name = required_str(move_name, "move")?;
+        let m = moves::find_move(name).ok_or_else(|| format!("No move \"{}\"", name))?;
+        let ability = match optional_str(ability)? {
+            Some(name) => Some(
+                AccuracyAbility::from_name(name)
+                    .ok_or_else(|| format!("Unknown ability \"{}\"", name))?,
+            ),
+            None => None,
+        };
+        Ok(accuracy::hit_chance(
+            &m,
+            accuracy_stage,
+            evasion_stage,
+            ability,
+        ))
+    })
+}
+
+/// Exact chance of catching a Pokémon in Generation 1, with a ball (`poke`,
+/// `great`, `ultra`, `safari` or `master`, by default `poke`) and status
+/// condition (by default `none`).
+///
+/// # Safety
+///
+/// `ball` and `status` are each a NUL-terminated string or NULL.
+#[no_mangle]
+pub unsafe extern "C" fn pkmn_gen1_catch_chance(
+    catch_rate: u8,
+    max_hp: u16,
+    current_hp: u16,
+    ball: *const c_char,
+    status: *const c_char,
+) -> *mut c_char {
+    respond(|| -> Result<f64> {
+        let ball = match optional_str(ball)? {
+            Some(name) => {
+                Ball::from_name(name).ok_or_else(|| format!("Unknown ball \"{}\"", name))?
+            }
+            None => Ball::Poke,
+        };
+        let status = match optional_str(status)? {
+            Some(name) => {
+                Status::from_name(name).ok_or_else(|| format!("Unknown status \"{}\"", name))?
+            }
+            None => Status::None,
+        };
+        Ok(retro::gen1_catch_chance(
+            catch_rate, max_hp, current_hp, ball, status,
+        ))
+    })
+}
+
+/// Frees a string returned by any other function. NULL is ignored.
+///
+/// # Safety
+///
+/// `value` was returned by this library and isn't used or freed again.
+#[no_mangle]
+pub unsafe extern "C" fn pkmn_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        drop(CString::from_raw(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+
+    use super::*;
+
+    fn call(response: *mut c_char) -> Value {
+        let value = unsafe { CStr::from_ptr(response) }
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        unsafe { pkmn_string_free(response) };
+        value
+    }
+
+    fn c(value: &str) -> CString {
+        CString::new(value).unwrap()
+    }
+
+    #[test]
+    fn lookups() {
+        unsafe {
+            assert_eq!(call(pkmn_find(c("pikachu").as_ptr()))["type_1"], "Electric");
+            assert_eq!(
+                call(pkmn_find(c("missingno").as_ptr()))["error"],
+                "No Pokémon \"missingno\""
+            );
+            assert_eq!(call(pkmn_find(ptr::null()))["error"], "id is required");
+            assert_eq!(
+                call(pkmn_search(c("charmnder").as_ptr(), 1))[0]["name"],
+                "Charmander"
+            );
+        }
+    }
+
+    #[test]
+    fn calculators() {
+        unsafe {
+            let ground = c("ground");
+            let (fire, flying) = (c("fire"), c("flying"));
+            assert_eq!(
+                call(pkmn_effectiveness(
+                    ground.as_ptr(),
+                    fire.as_ptr(),
+                    flying.as_ptr()
+                )),
+                0.0
+            );
+            assert_eq!(call(pkmn_type_defenses(c("charizard").as_ptr()))[0][1], 4.0);
+
+            let evs = c("4/252/0/0/0/252");
+            let stats = call(pkmn_stats(
+                c("garchomp").as_ptr(),
+                50,
+                c("jolly").as_ptr(),
+                ptr::null(),
+                evs.as_ptr(),
+            ));
+            assert_eq!(stats["Speed"], 169);
+            assert!(call(pkmn_stats(
+                c("garchomp").as_ptr(),
+                0,
+                ptr::null(),
+                ptr::null(),
+                ptr::null()
+            ))
+            .get("error")
+            .is_some());
+
+            assert_eq!(
+                call(pkmn_hit_chance(
+                    c("thunder").as_ptr(),
+                    0,
+                    0,
+                    c("no-guard").as_ptr()
+                )),
+                1.0
+            );
+            assert_eq!(
+                call(pkmn_gen1_catch_chance(
+                    45,
+                    100,
+                    1,
+                    c("master").as_ptr(),
+                    ptr::null()
+                )),
+                1.0
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_out_of_range_stages() {
+        unsafe {
+            assert_eq!(
+                call(pkmn_hit_chance(c("thunder").as_ptr(), 127, -1, ptr::null()))["error"],
+                "Stages are between -6 and 6"
+            );
+        }
+    }
+
+    #[test]
+    fn catches_panics() {
+        let response = respond(|| -> Result<()> { panic!("boom") });
+        assert_eq!(call(response)["error"], "pkmn panicked: boom");
+    }
+
+    #[test]
+    fn header_declares_every_function() {
+        let header = include_str!("../include/pkmn.h");
+        for name in [
+            "pkmn_find",
+            "pkmn_search",
+            "pkmn_effectiveness",
+            "pkmn_type_defenses",
+            "pkmn_stats",
+            "pkmn_hit_chance",
+            "pkmn_gen1_catch_chance",
+            "pkmn_string_free",
+        ] {
+            assert!(header.contains(&format!("{}(", name)), "{}", name);
+        }
+    }
+}
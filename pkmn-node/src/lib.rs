@@ -0,0 +1,212 @@
+//! Node.js bindings for the pkmn Pokédex and damage calculator, running the
+//! same code as the command line tool, e.g. for Discord bots. Pokémon are
+//! plain objects with the Pokédex's fields. Panics are thrown as errors
+//! instead of crashing Node.
+//!
+//! ```js
+//! const pkmn = require("pkmn");
+//!
+//! pkmn.search("charmnder")[0].name; // "Charmander"
+//! pkmn.getPokemon("garchomp").type_1; // "Dragon"
+//! pkmn.calcDamage(
+//!   { pokemon: "garchomp", nature: "jolly", evs: [4, 252, 0, 0, 0, 252] },
+//!   { pokemon: "heatran" },
+//!   "earthquake",
+//! ).maxPercent;
+//! ```
+
+use napi::{Error, Result};
+use napi_derive::napi;
+use serde_json::{json, Value};
+
+use pkmn::calc::damage;
+use pkmn::calc::stats::{self, Nature, Stat, Stats};
+use pkmn::moves;
+use pkmn::pokedex::{self, Pokemon};
+
+fn find_pokemon(id: &str) -> Result<Pokemon> {
+    pokedex::find_by_id(id).ok_or_else(|| Error::from_reason(format!("No Pokémon \"{}\"", id)))
+}
+
+/// IVs or EVs as one value for every stat or a list of six, by default
+/// `default` for every stat.
+fn parse_stats(values: Option<&[u32]>, default: u16, max: u16) -> Result<Stats> {
+    match values {
+        Some(values) => Stats::from_values(values, max).map_err(Error::from_reason),
+        None => Ok(Stats::uniform(default)),
+    }
+}
+
+/// A Pokémon in battle. IVs and EVs are one value for all stats or a list of
+/// six, by default 31 IVs and no EVs; the nature is neutral by default.
+#[napi(object)]
+pub struct Side {
+    /// Pokédex number or slug, e.g. `charizard-mega-x`.
+    pub pokemon: String,
+    /// By default 50, as in official battles.
+    pub level: Option<u32>,
+    pub nature: Option<String>,
+    pub ivs: Option<Vec<u32>>,
+    pub evs: Option<Vec<u32>>,
+}
+
+impl Side {
+    fn level(&self) -> Result<u16> {
+        match self.level.unwrap_or(50) {
+            level @ 1..=100 => Ok(level as u16),
+            _ => Err(Error::from_reason("Level is between 1 and 100")),
+        }
+    }
+
+    fn stats(&self, pokemon: &Pokemon) -> Result<Stats> {
+        let nature = match &self.nature {
+            Some(name) => Nature::from_name(name)
+                .ok_or_else(|| Error::from_reason(format!("Unknown nature \"{}\"", name)))?,
+            None => stats::NATURES[0],
+        };
+        let ivs = parse_stats(self.ivs.as_deref(), stats::MAX_IV, stats::MAX_IV)?;
+        let evs = parse_stats(self.evs.as_deref(), 0, stats::MAX_STAT_EVS)?;
+        Ok(stats::stats(pokemon, &ivs, &evs, self.level()?, &nature))
+    }
+}
+
+#[napi(object)]
+pub struct DamageRange {
+    /// Damage for each random roll, from the lowest (85%) to the highest.
+    pub rolls: Vec<u32>,
+    pub min: u32,
+    pub max: u32,
+    /// `min` and `max` as percentages of the defender's HP.
+    pub min_percent: f64,
+    pub max_percent: f64,
+    pub effectiveness: f64,
+    pub stab: bool,
+}
+
+/// The Pokémon with this Pokédex number or slug, e.g. `charizard-mega-x`, or
+/// null.
+#[napi(catch_unwind)]
+pub fn get_pokemon(id: String) -> Option<Value> {
+    pokedex::find_by_id(&id).and_then(|pokemon| serde_json::to_value(pokemon).ok())
+}
+
+/// The closest names to `query`, best first, each with its `similarity`
+/// (0 to 1) and edit `distance`.
+#[napi(catch_unwind)]
+pub fn search(query: String, limit: Option<u32>) -> Vec<Value> {
+    pokedex::search_by_name(&query, limit.unwrap_or(5) as usize)
+        .into_iter()
+        .map(|found| {
+            let mut value = serde_json::to_value(&found.pokemon).unwrap_or_default();
+            value["similarity"] = json!(found.score.similarity);
+            value["distance"] = json!(found.score.distance);
+            value
+        })
+        .collect()
+}
+
+/// Damage a move does from `attacker` to `defender` from Generation 5 onwards,
+/// without weather, items or abilities. Throws for status moves and moves
+/// with variable power.
+#[napi(catch_unwind)]
+pub fn calc_damage(
+    attacker: Side,
+    defender: Side,
+    move_name: String,
+    critical: Option<bool>,
+) -> Result<DamageRange> {
+    let m = moves::find_move(&move_name)
+        .ok_or_else(|| Error::from_reason(format!("No move \"{}\"", move_name)))?;
+    let attacking = find_pokemon(&attacker.pokemon)?;
+    let defending = find_pokemon(&defender.pokemon)?;
+    let defender_stats = defender.stats(&defending)?;
+    let damage = damage::damage(
+        &m,
+        attacker.level()?,
+        &attacking,
+        &attacker.stats(&attacking)?,
+        &defending,
+        &defender_stats,
+        critical.unwrap_or(false),
+    )
+    .ok_or_else(|| Error::from_reason(format!("{} has no fixed power", m.name)))?;
+
+    let percent = |value: u16| f64::from(value) * 100.0 / f64::from(defender_stats.get(Stat::Hp));
+    Ok(DamageRange {
+        rolls: damage.rolls.iter().map(|roll| u32::from(*roll)).collect(),
+        min: u32::from(damage.min()),
+        max: u32::from(damage.max()),
+        min_percent: percent(damage.min()),
+        max_percent: percent(damage.max()),
+        effectiveness: f64::from(damage.effectiveness),
+        stab: damage.stab,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn side(pokemon: &str) -> Side {
+        Side {
+            pokemon: pokemon.to_string(),
+            level: None,
+            nature: None,
+            ivs: None,
+            evs: None,
+        }
+    }
+
+    #[test]
+    fn lookups() {
+        assert_eq!(get_pokemon("pikachu".into()).unwrap()["type_1"], "Electric");
+        assert!(get_pokemon("missingno".into()).is_none());
+        assert_eq!(search("charmnder".into(), Some(1))[0]["name"], "Charmander");
+    }
+
+    #[test]
+    fn damage() {
+        let attacker = Side {
+            nature: Some("jolly".into()),
+            evs: Some(vec![4, 252, 0, 0, 0, 252]),
+            ..side("garchomp")
+        };
+        let range = calc_damage(attacker, side("heatran"), "earthquake".into(), None).unwrap();
+        assert!(range.stab);
+        assert_eq!(range.effectiveness, 4.0);
+        assert_eq!(range.rolls.len(), 16);
+        assert!(range.min < range.max && range.max_percent > 100.0);
+
+        let error = |result: Result<DamageRange>| result.err().unwrap().reason;
+        assert_eq!(
+            error(calc_damage(
+                side("garchomp"),
+                side("heatran"),
+                "swords-dance".into(),
+                None
+            )),
+            "Swords Dance has no fixed power"
+        );
+        let level = Side {
+            level: Some(0),
+            ..side("garchomp")
+        };
+        let evs = Side {
+            evs: Some(vec![252, 252]),
+            ..side("garchomp")
+        };
+        assert_eq!(
+            error(calc_damage(evs, side("heatran"), "earthquake".into(), None)),
+            "Expected one value, or six as HP/Atk/Def/SpA/SpD/Spe"
+        );
+        assert_eq!(
+            error(calc_damage(
+                level,
+                side("heatran"),
+                "earthquake".into(),
+                None
+            )),
+            "Level is between 1 and 100"
+        );
+    }
+}
@@ -0,0 +1,280 @@
+//! Python bindings for the pkmn Pokédex: finding and searching Pokémon, the
+//! type chart and the calculators, running the same code as the command line
+//! tool. Pokémon are plain dicts with the Pokédex's fields, so a list of them
+//! goes straight into `pandas.DataFrame`.
+//!
+//! ```python
+//! import pkmn
+//!
+//! pkmn.search("charmnder")[0]["name"]  # "Charmander"
+//! pkmn.effectiveness("ground", ["fire", "flying"])  # 0.0
+//! pkmn.stats("garchomp", level=50, nature="jolly", evs=[4, 252, 0, 0, 0, 252])
+//! ```
+
+// #[pyfunction] expands to a PyErr-to-PyErr conversion on every fallible
+// function
+#![allow(clippy::useless_conversion)]
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use serde_json::Value;
+
+use pkmn::calc::accuracy::{self, AccuracyAbility};
+use pkmn::calc::retro::{self, Ball, Status};
+use pkmn::calc::stats::{self, Nature, Stat, Stats};
+use pkmn::moves;
+use pkmn::pokedex::{self, Pokemon};
+use pkmn::types::Type;
+
+fn to_python(py: Python<'_>, value: &Value) -> PyResult<PyObject> {
+    Ok(match value {
+        Value::Null => py.None(),
+        Value::Bool(value) => value.into_py(py),
+        Value::Number(number) => match number.as_i64() {
+            Some(int) => int.into_py(py),
+            None => number.as_f64().into_py(py),
+        },
+        Value::String(value) => value.into_py(py),
+        Value::Array(values) => {
+            let list = PyList::empty_bound(py);
+            for value in values {
+                list.append(to_python(py, value)?)?;
+            }
+            list.into_py(py)
+        }
+        Value::Object(fields) => {
+            let dict = PyDict::new_bound(py);
+            for (name, value) in fields {
+                dict.set_item(name, to_python(py, value)?)?;
+            }
+            dict.into_py(py)
+        }
+    })
+}
+
+/// A Pokémon as a dict of its Pokédex fields.
+fn pokemon_dict(py: Python<'_>, pokemon: &Pokemon) -> PyResult<PyObject> {
+    let value =
+        serde_json::to_value(pokemon).map_err(|err| PyValueError::new_err(err.to_string()))?;
+    to_python(py, &value)
+}
+
+fn find_pokemon(id: &str) -> PyResult<Pokemon> {
+    pokedex::find_by_id(id).ok_or_else(|| PyValueError::new_err(format!("No Pokémon \"{}\"", id)))
+}
+
+fn parse_type(name: &str) -> PyResult<Type> {
+    Type::from_name(name).ok_or_else(|| PyValueError::new_err(format!("Unknown type \"{}\"", name)))
+}
+
+/// IVs or EVs as one value for every stat or a list of six, by default
+/// `default` for every stat.
+fn parse_stats(values: Option<Vec<u16>>, default: u16, max: u16) -> PyResult<Stats> {
+    match values {
+        Some(values) => Stats::from_values(&values, max).map_err(PyValueError::new_err),
+        None => Ok(Stats::uniform(default)),
+    }
+}
+
+/// The Pokémon with this Pokédex number or slug, e.g. `"charizard-mega-x"`,
+/// or None.
+#[pyfunction]
+fn find(py: Python<'_>, id: &str) -> PyResult<Option<PyObject>> {
+    pokedex::find_by_id(id)
+        .map(|pokemon| pokemon_dict(py, &pokemon))
+        .transpose()
+}
+
+/// The closest names to `query`, best first, each with its `similarity`
+/// (0 to 1) and edit `distance`.
+#[pyfunction]
+#[pyo3(signature = (query, limit = 5))]
+fn search(py: Python<'_>, query: &str, limit: usize) -> PyResult<Vec<PyObject>> {
+    pokedex::search_by_name(query, limit)
+        .iter()
+        .map(|found| {
+            let dict = pokemon_dict(py, &found.pokemon)?;
+            let bound = dict.downcast_bound::<PyDict>(py)?;
+            bound.set_item("similarity", found.score.similarity)?;
+            bound.set_item("distance", found.score.distance)?;
+            Ok(dict)
+        })
+        .collect()
+}
+
+/// Every Pokémon and form, in Pokédex order.
+#[pyfunction]
+#[pyo3(name = "pokedex")]
+fn all_pokemon(py: Python<'_>) -> PyResult<Vec<PyObject>> {
+    pokedex::entries()
+        .map_err(|err| PyValueError::new_err(err.to_string()))?
+        .iter()
+        .map(|pokemon| pokemon_dict(py, pokemon))
+        .collect()
+}
+
+/// Damage multiplier of an `attacker` type move against one or two
+/// `defenders` types.
+#[pyfunction]
+fn effectiveness(attacker: &str, defenders: Vec<String>) -> PyResult<f32> {
+    let defenders = defenders
+        .iter()
+        .map(|name| parse_type(name))
+        .collect::<PyResult<Vec<Type>>>()?;
+    Ok(parse_type(attacker)?.effectiveness_against(&defenders))
+}
+
+/// Damage multiplier of each attacking type against a Pokémon, from most to
+/// least effective.
+#[pyfunction]
+fn type_defenses(id: &str) -> PyResult<Vec<(&'static str, f32)>> {
+    Ok(find_pokemon(id)?
+        .type_defenses()
+        .into_iter()
+        .map(|(attacker, multiplier)| (attacker.name(), multiplier))
+        .collect())
+}
+
+/// A Pokémon's stats at `level` with the given nature, IVs and EVs, keyed by
+/// stat name. IVs and EVs are one value for all stats or a list of six.
+#[pyfunction]
+#[pyo3(name = "stats", signature = (id, level = 50, nature = "hardy", ivs = None, evs = None))]
+fn calculate_stats<'py>(
+    py: Python<'py>,
+    id: &str,
+    level: u16,
+    nature: &str,
+    ivs: Option<Vec<u16>>,
+    evs: Option<Vec<u16>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let pokemon = find_pokemon(id)?;
+    let nature = Nature::from_name(nature)
+        .ok_or_else(|| PyValueError::new_err(format!("Unknown nature \"{}\"", nature)))?;
+    if !(1..=100).contains(&level) {
+        return Err(PyValueError::new_err("Level is between 1 and 100"));
+    }
+    let ivs = parse_stats(ivs, stats::MAX_IV, stats::MAX_IV)?;
+    let evs = parse_stats(evs, 0, stats::MAX_STAT_EVS)?;
+    let values = stats::stats(&pokemon, &ivs, &evs, level, &nature);
+    let dict = PyDict::new_bound(py);
+    for stat in Stat::ALL {
+        dict.set_item(stat.name(), values.get(stat))?;
+    }
+    Ok(dict)
+}
+
+/// Chance, between 0 and 1, that a move hits from Generation 5 onwards.
+#[pyfunction]
+#[pyo3(signature = (move_name, accuracy_stage = 0, evasion_stage = 0, ability = None))]
+fn hit_chance(
+    move_name: &str,
+    accuracy_stage: i8,
+    evasion_stage: i8,
+    ability: Option<&str>,
+) -> PyResult<f64> {
+    let m = moves::find_move(move_name)
+        .ok_or_else(|| PyValueError::new_err(format!("No move \"{}\"", move_name)))?;
+    let ability = ability
+        .map(|name| {
+            AccuracyAbility::from_name(name)
+                .ok_or_else(|| PyValueError::new_err(format!("Unknown ability \"{}\"", name)))
+        })
+        .transpose()?;
+    Ok(accuracy::hit_chance(
+        &m,
+        accuracy_stage,
+        evasion_stage,
+        ability,
+    ))
+}
+
+/// Exact chance of catching a Pokémon in Generation 1 with a ball (`poke`,
+/// `great`, `ultra`, `safari` or `master`) and status condition.
+#[pyfunction]
+#[pyo3(signature = (catch_rate, max_hp, current_hp, ball = "poke", status = "none"))]
+fn gen1_catch_chance(
+    catch_rate: u8,
+    max_hp: u16,
+    current_hp: u16,
+    ball: &str,
+    status: &str,
+) -> PyResult<f64> {
+    let ball = Ball::from_name(ball)
+        .ok_or_else(|| PyValueError::new_err(format!("Unknown ball \"{}\"", ball)))?;
+    let status = Status::from_name(status)
+        .ok_or_else(|| PyValueError::new_err(format!("Unknown status \"{}\"", status)))?;
+    Ok(retro::gen1_catch_chance(
+        catch_rate, max_hp, current_hp, ball, status,
+    ))
+}
+
+#[pymodule]
+#[pyo3(name = "pkmn")]
+fn pkmn_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(find, m)?)?;
+    m.add_function(wrap_pyfunction!(search, m)?)?;
+    m.add_function(wrap_pyfunction!(all_pokemon, m)?)?;
+    m.add_function(wrap_pyfunction!(effectiveness, m)?)?;
+    m.add_function(wrap_pyfunction!(type_defenses, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(hit_chance, m)?)?;
+    m.add_function(wrap_pyfunction!(gen1_catch_chance, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_module<F: FnOnce(Python<'_>, &Bound<'_, PyModule>)>(f: F) {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let module = PyModule::new_bound(py, "pkmn").unwrap();
+            pkmn_module(&module).unwrap();
+            f(py, &module)
+        });
+    }
+
+    #[test]
+    fn lookups() {
+        with_module(|py, module| {
+            let run = |code: &str| py.eval_bound(code, Some(&module.dict()), None).unwrap();
+            assert_eq!(
+                run("find('pikachu')['type_1']")
+                    .extract::<String>()
+                    .unwrap(),
+                "Electric"
+            );
+            assert!(run("find('missingno') is None").extract::<bool>().unwrap());
+            assert_eq!(
+                run("search('charmnder', 1)[0]['name']")
+                    .extract::<String>()
+                    .unwrap(),
+                "Charmander"
+            );
+            assert_eq!(
+                run("len(pokedex())").extract::<usize>().unwrap(),
+                pokedex::entries().unwrap().len()
+            );
+        });
+    }
+
+    #[test]
+    fn calculators() {
+        with_module(|py, module| {
+            let run = |code: &str| py.eval_bound(code, Some(&module.dict()), None);
+            let float = |code: &str| run(code).unwrap().extract::<f64>().unwrap();
+            assert_eq!(float("effectiveness('ground', ['fire', 'flying'])"), 0.0);
+            assert_eq!(float("type_defenses('charizard')[0][1]"), 4.0);
+            assert_eq!(
+                float("stats('garchomp', nature='jolly', evs=[4, 252, 0, 0, 0, 252])['Speed']"),
+                169.0
+            );
+            assert_eq!(float("hit_chance('thunder', ability='no-guard')"), 1.0);
+            assert_eq!(float("gen1_catch_chance(45, 100, 1, ball='master')"), 1.0);
+            assert!(run("effectiveness('sound', ['fire'])").is_err());
+            assert!(run("stats('pikachu', ivs=[31, 31])").is_err());
+        });
+    }
+}
@@ -0,0 +1,120 @@
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{self, PkmnError};
+use crate::fuzzy::{self, Match, MatchScore};
+use crate::pokedex::Pokemon;
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Ability {
+    pub name: String,
+    /// What the ability does, in the games' terse style.
+    pub description: String,
+}
+
+pub static ABILITIES_CSV: &[u8] = include_bytes!("../data/abilities.csv");
+
+/// Every bundled ability.
+pub fn all_abilities() -> Result<Vec<Ability>, PkmnError> {
+    error::read_csv("abilities.csv", ABILITIES_CSV)
+}
+
+/// Finds an ability by name, ignoring case and treating hyphens as spaces,
+/// e.g. "swift-swim".
+pub fn find_ability(name: &str) -> Option<Ability> {
+    let slug = |name: &str| name.trim().to_lowercase().replace('-', " ");
+    let name = slug(name);
+    all_abilities()
+        .unwrap_or_default()
+        .into_iter()
+        .find(|ability| slug(&ability.name) == name)
+}
+
+/// Abilities whose description contains a phrase closest to `query`, best
+/// first.
+pub fn search_by_effect(query: &str, limit: usize) -> Vec<Match<Ability>> {
+    let mut results = all_abilities()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|ability| Match {
+            score: fuzzy::phrase_score(&ability.description, query),
+            value: ability,
+        })
+        .collect::<Vec<Match<Ability>>>();
+    results.sort_by(|a, b| match MatchScore::compare(&a.score, &b.score) {
+        Ordering::Equal => a.value.name.cmp(&b.value.name),
+        ordering => ordering,
+    });
+    results.truncate(limit);
+    results
+}
+
+/// Pokémon that can have the ability, with whether it's their hidden ability.
+pub fn holders<'a>(pokedex: &'a [Pokemon], ability: &str) -> Vec<(&'a Pokemon, bool)> {
+    pokedex
+        .iter()
+        .filter_map(|pokemon| {
+            if *pokemon.ability_1 == *ability || *pokemon.ability_2 == *ability {
+                Some((pokemon, false))
+            } else if *pokemon.ability_hidden == *ability {
+                Some((pokemon, true))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::all_pokemon;
+
+    #[test]
+    fn every_ability_is_described() {
+        let abilities = all_abilities().unwrap();
+        for pokemon in all_pokemon().unwrap() {
+            for ability in [
+                &pokemon.ability_1,
+                &pokemon.ability_2,
+                &pokemon.ability_hidden,
+            ] {
+                assert!(
+                    ability.is_empty() || abilities.iter().any(|known| *known.name == **ability),
+                    "{} has no description",
+                    ability
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn find_by_name() {
+        let ability = find_ability("swift-swim").unwrap();
+        assert_eq!(ability.name, "Swift Swim");
+        assert_eq!(find_ability("INTIMIDATE").unwrap().name, "Intimidate");
+        assert!(find_ability("intimidat").is_none());
+    }
+
+    #[test]
+    fn search_effect() {
+        let results = search_by_effect("ignores the target's stat changes", 3);
+        assert_eq!(results[0].value.name, "Unaware");
+
+        let results = search_by_effect("immunity to ground moves", 3);
+        assert!(results.iter().any(|result| result.value.name == "Levitate"));
+    }
+
+    #[test]
+    fn hidden_holders() {
+        let pokedex = all_pokemon().unwrap();
+        let holders = holders(&pokedex, "Unaware");
+        assert!(holders
+            .iter()
+            .any(|(pokemon, hidden)| pokemon.name == "Quagsire" && *hidden));
+        assert!(holders
+            .iter()
+            .any(|(pokemon, hidden)| pokemon.name == "Bibarel" && !*hidden));
+    }
+}
@@ -0,0 +1,93 @@
+//! Anime trivia: the episode or film a Pokémon debuted in, and who it's
+//! remembered with.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{self, PkmnError};
+use crate::pokedex::{self, Pokemon};
+
+pub static ANIME_CSV: &[u8] = include_bytes!("../data/anime.csv");
+
+#[derive(Deserialize)]
+struct Row {
+    name: String,
+    debut: String,
+    /// Separated by semicolons.
+    notable_trainers: String,
+    voice_actor: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Trivia {
+    pub name: String,
+    /// Episode or film code and title, e.g. `EP001 Pokémon - I Choose You!`.
+    pub debut: String,
+    pub notable_trainers: Vec<String>,
+    /// The Pokémon's Japanese voice, for those voiced by someone other than
+    /// the trainers' cast.
+    pub voice_actor: Option<String>,
+}
+
+impl From<Row> for Trivia {
+    fn from(row: Row) -> Self {
+        Trivia {
+            name: row.name,
+            debut: row.debut,
+            notable_trainers: row
+                .notable_trainers
+                .split(';')
+                .map(|trainer| trainer.trim().to_string())
+                .filter(|trainer| !trainer.is_empty())
+                .collect(),
+            voice_actor: Some(row.voice_actor).filter(|actor| !actor.is_empty()),
+        }
+    }
+}
+
+/// Every bundled entry, in Pokédex order. Only well-known Pokémon from the
+/// early series and films are covered.
+pub fn all_trivia() -> Result<Vec<Trivia>, PkmnError> {
+    let rows: Vec<Row> = error::read_csv("anime.csv", ANIME_CSV)?;
+    Ok(rows.into_iter().map(Trivia::from).collect())
+}
+
+/// Trivia of the Pokémon's species, which its forms share.
+pub fn trivia_for(pokemon: &Pokemon) -> Option<Trivia> {
+    let species = pokedex::species_of(pokemon)?;
+    all_trivia()
+        .unwrap_or_default()
+        .into_iter()
+        .find(|trivia| trivia.name == species.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::find_by_id;
+
+    #[test]
+    fn every_entry_is_a_pokemon() {
+        for trivia in all_trivia().unwrap() {
+            assert!(
+                pokedex::entries()
+                    .unwrap()
+                    .iter()
+                    .any(|pokemon| pokemon.name == trivia.name),
+                "{} isn't in the Pokédex",
+                trivia.name
+            );
+        }
+    }
+
+    #[test]
+    fn forms_share_trivia() {
+        let trivia = trivia_for(&find_by_id("mewtwo-mega-x").unwrap()).unwrap();
+        assert_eq!(trivia.name, "Mewtwo");
+        assert_eq!(trivia.notable_trainers, vec!["Giovanni"]);
+        assert_eq!(trivia.voice_actor, None);
+
+        let pikachu = trivia_for(&find_by_id("pikachu").unwrap()).unwrap();
+        assert_eq!(pikachu.voice_actor.as_deref(), Some("Ikue Ōtani"));
+        assert!(trivia_for(&find_by_id("rattata").unwrap()).is_none());
+    }
+}
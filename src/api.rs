@@ -0,0 +1,389 @@
+//! PokéAPI (<https://pokeapi.co>), queried with `--online` to fill in fields
+//! the bundled Pokédex is missing, e.g. catch rates of newer forms. Only
+//! missing fields are filled; bundled values always win. Responses are kept
+//! in `<cache dir>/pkmn/pokeapi`, as PokéAPI asks, since they rarely change.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::cache::cache_dir;
+use crate::intern::intern;
+use crate::pokedex::{self, Pokemon};
+
+const API_URL: &str = "https://pokeapi.co/api/v2";
+
+/// How long to wait for each request, so an unreachable API doesn't hold up
+/// the card for long.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Retries after a timeout, dropped connection or server error, waiting
+/// twice as long before each.
+const RETRIES: u32 = 2;
+const RETRY_DELAY: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error("PokéAPI has no {0}")]
+    NotFound(String),
+    #[error("PokéAPI responded {0}")]
+    Status(StatusCode),
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+    #[error("unexpected response from PokéAPI: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+impl ApiError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            ApiError::Status(status) => {
+                status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            ApiError::Request(err) => err.is_timeout() || err.is_connect(),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct NamedResource {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct AbilitySlot {
+    ability: NamedResource,
+    is_hidden: bool,
+}
+
+/// `/pokemon/{slug}`, a form's own data.
+#[derive(Deserialize)]
+struct PokemonResponse {
+    /// In decimetres.
+    height: Option<u16>,
+    /// In hectograms.
+    weight: Option<u32>,
+    base_experience: Option<u16>,
+    abilities: Vec<AbilitySlot>,
+}
+
+/// `/pokemon-species/{number}`, data its forms share.
+#[derive(Deserialize)]
+struct SpeciesResponse {
+    capture_rate: Option<u16>,
+    base_happiness: Option<u16>,
+    growth_rate: Option<NamedResource>,
+    egg_groups: Vec<NamedResource>,
+    /// Chance of being female in eighths, or -1 for genderless.
+    gender_rate: i8,
+    hatch_counter: Option<u16>,
+}
+
+fn cache_path(path: &str) -> Option<PathBuf> {
+    let dir = cache_dir()?.join("pokeapi");
+    Some(dir.join(format!("{}.json", path.replace('/', "-"))))
+}
+
+async fn fetch(client: &reqwest::Client, path: &str) -> Result<Vec<u8>, ApiError> {
+    let url = format!("{}/{}", API_URL, path);
+    info!("fetching \"{}\"", url);
+    let res = client.get(&url).send().await?;
+    match res.status() {
+        status if status.is_success() => Ok(res.bytes().await?.to_vec()),
+        StatusCode::NOT_FOUND => Err(ApiError::NotFound(path.to_string())),
+        status => Err(ApiError::Status(status)),
+    }
+}
+
+/// The response to `path`, from the cache or PokéAPI.
+async fn get<T: DeserializeOwned>(client: &reqwest::Client, path: &str) -> Result<T, ApiError> {
+    let cache_path = cache_path(path);
+    if let Some(bytes) = cache_path.as_ref().and_then(|path| fs::read(path).ok()) {
+        match serde_json::from_slice(&bytes) {
+            Ok(value) => return Ok(value),
+            Err(err) => info!("ignoring cached \"{}\": {}", path, err),
+        }
+    }
+
+    let mut attempt = 0;
+    let bytes = loop {
+        match fetch(client, path).await {
+            Err(err) if err.is_retryable() && attempt < RETRIES => {
+                let delay = RETRY_DELAY * 2u32.pow(attempt);
+                info!("retrying \"{}\" in {:?}: {}", path, delay, err);
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => break result?,
+        }
+    };
+    let value = serde_json::from_slice(&bytes)?;
+
+    if let Some(cache_path) = cache_path {
+        let written = match cache_path.parent() {
+            Some(dir) => fs::create_dir_all(dir).and_then(|_| fs::write(&cache_path, &bytes)),
+            None => fs::write(&cache_path, &bytes),
+        };
+        if let Err(err) = written {
+            warn!("couldn't cache PokéAPI response: {}", err);
+        }
+        crate::cache::prune();
+    }
+    Ok(value)
+}
+
+/// `run-away` → `Run Away`, the way the Pokédex writes names.
+fn title_case(slug: &str) -> String {
+    slug.split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// An ability's name as the Pokédex writes it, e.g. `Compound Eyes` for
+/// `compound-eyes`.
+fn ability_name(slug: &str) -> Arc<str> {
+    let simplify = |name: &str| {
+        name.to_lowercase()
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect::<String>()
+    };
+    let wanted = simplify(slug);
+    pokedex::entries()
+        .unwrap_or_default()
+        .iter()
+        .flat_map(|pokemon| {
+            [
+                &pokemon.ability_1,
+                &pokemon.ability_2,
+                &pokemon.ability_hidden,
+            ]
+        })
+        .find(|name| !name.is_empty() && simplify(name) == wanted)
+        .cloned()
+        .unwrap_or_else(|| intern(&title_case(slug)))
+}
+
+fn egg_group_name(slug: &str) -> Arc<str> {
+    match slug {
+        "ground" => intern("Field"),
+        "plant" => intern("Grass"),
+        "humanshape" => intern("Human-Like"),
+        "indeterminate" => intern("Amorphous"),
+        "no-eggs" => intern("Undiscovered"),
+        slug => match slug.strip_prefix("water") {
+            Some(number) => intern(&format!("Water {}", number)),
+            None => intern(&title_case(slug)),
+        },
+    }
+}
+
+fn growth_rate_name(slug: &str) -> Arc<str> {
+    match slug {
+        "medium" => intern("Medium Fast"),
+        "slow-then-very-fast" => intern("Erratic"),
+        "fast-then-very-slow" => intern("Fluctuating"),
+        slug => intern(&title_case(slug)),
+    }
+}
+
+/// Fills the form's own fields missing from `pokemon`, returning their names.
+fn merge_pokemon(pokemon: &mut Pokemon, response: PokemonResponse) -> Vec<&'static str> {
+    let mut filled = Vec::new();
+    if pokemon.height_m.is_none() {
+        if let Some(height) = response.height {
+            pokemon.height_m = Some(f32::from(height) / 10.0);
+            filled.push("height_m");
+        }
+    }
+    if pokemon.weight_kg.is_none() {
+        if let Some(weight) = response.weight {
+            pokemon.weight_kg = Some(weight as f32 / 10.0);
+            filled.push("weight_kg");
+        }
+    }
+    if pokemon.base_experience.is_none() && response.base_experience.is_some() {
+        pokemon.base_experience = response.base_experience;
+        filled.push("base_experience");
+    }
+    if pokemon.ability_1.is_empty() && !response.abilities.is_empty() {
+        let (hidden, regular): (Vec<AbilitySlot>, Vec<AbilitySlot>) = response
+            .abilities
+            .into_iter()
+            .partition(|slot| slot.is_hidden);
+        let mut regular = regular
+            .into_iter()
+            .map(|slot| ability_name(&slot.ability.name));
+        pokemon.ability_1 = regular.next().unwrap_or_else(|| intern(""));
+        pokemon.ability_2 = regular.next().unwrap_or_else(|| intern(""));
+        pokemon.ability_hidden = hidden
+            .first()
+            .map(|slot| ability_name(&slot.ability.name))
+            .unwrap_or_else(|| intern(""));
+        pokemon.abilities_number = [
+            &pokemon.ability_1,
+            &pokemon.ability_2,
+            &pokemon.ability_hidden,
+        ]
+        .iter()
+        .filter(|name| !name.is_empty())
+        .count() as u8;
+        filled.extend(["ability_1", "ability_2", "ability_hidden"]);
+    }
+    filled
+}
+
+/// Fills the species' fields missing from `pokemon`, returning their names.
+fn merge_species(pokemon: &mut Pokemon, response: SpeciesResponse) -> Vec<&'static str> {
+    let mut filled = Vec::new();
+    if pokemon.catch_rate.is_none() && response.capture_rate.is_some() {
+        pokemon.catch_rate = response.capture_rate;
+        filled.push("catch_rate");
+    }
+    if pokemon.base_friendship.is_none() && response.base_happiness.is_some() {
+        pokemon.base_friendship = response.base_happiness;
+        filled.push("base_friendship");
+    }
+    if pokemon.growth_rate.is_empty() {
+        if let Some(growth_rate) = response.growth_rate {
+            pokemon.growth_rate = growth_rate_name(&growth_rate.name);
+            filled.push("growth_rate");
+        }
+    }
+    if pokemon.egg_type_1.is_empty() && !response.egg_groups.is_empty() {
+        let mut groups = response
+            .egg_groups
+            .iter()
+            .map(|group| egg_group_name(&group.name));
+        pokemon.egg_type_1 = groups.next().unwrap_or_else(|| intern(""));
+        pokemon.egg_type_2 = groups.next().unwrap_or_else(|| intern(""));
+        pokemon.egg_type_number = response.egg_groups.len().min(2) as u8;
+        filled.extend(["egg_type_1", "egg_type_2"]);
+    }
+    // Genderless Pokémon have no percentage, so it's only missing otherwise
+    if pokemon.percentage_male.is_none() && response.gender_rate >= 0 {
+        pokemon.percentage_male = Some(f32::from(8 - response.gender_rate) / 8.0 * 100.0);
+        filled.push("percentage_male");
+    }
+    if pokemon.egg_cycles.is_none() && response.hatch_counter.is_some() {
+        pokemon.egg_cycles = response.hatch_counter;
+        filled.push("egg_cycles");
+    }
+    filled
+}
+
+/// Fills the fields missing from `pokemon` with PokéAPI's data, returning
+/// the names of those filled. Nothing is fetched when no field is missing,
+/// and nothing is filled unless every fetch succeeds.
+pub async fn fill_missing(pokemon: &mut Pokemon) -> Result<Vec<&'static str>, ApiError> {
+    let client = reqwest::Client::builder()
+        .timeout(TIMEOUT)
+        .build()
+        .unwrap_or_default();
+
+    let mut pokemon_response = None;
+    if pokemon.height_m.is_none()
+        || pokemon.weight_kg.is_none()
+        || pokemon.base_experience.is_none()
+        || pokemon.ability_1.is_empty()
+    {
+        let path = format!("pokemon/{}", pokemon.sprite_name_slug());
+        match get(&client, &path).await {
+            Ok(response) => pokemon_response = Some(response),
+            // Some forms are named differently there, yet share the species
+            Err(err @ ApiError::NotFound(_)) => info!("{}", err),
+            Err(err) => return Err(err),
+        }
+    }
+    let mut species_response = None;
+    if pokemon.catch_rate.is_none()
+        || pokemon.base_friendship.is_none()
+        || pokemon.growth_rate.is_empty()
+        || pokemon.egg_type_1.is_empty()
+        || pokemon.percentage_male.is_none()
+        || pokemon.egg_cycles.is_none()
+    {
+        let path = format!("pokemon-species/{}", pokemon.pokedex_number);
+        species_response = Some(get(&client, &path).await?);
+    }
+
+    let mut filled = Vec::new();
+    if let Some(response) = pokemon_response {
+        filled.extend(merge_pokemon(pokemon, response));
+    }
+    if let Some(response) = species_response {
+        filled.extend(merge_species(pokemon, response));
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::find_by_id;
+
+    #[test]
+    fn names() {
+        assert_eq!(&*ability_name("compound-eyes"), "Compound Eyes");
+        assert_eq!(&*ability_name("made-up-ability"), "Made Up Ability");
+        assert_eq!(&*egg_group_name("water1"), "Water 1");
+        assert_eq!(&*egg_group_name("humanshape"), "Human-Like");
+        assert_eq!(&*egg_group_name("monster"), "Monster");
+        assert_eq!(&*growth_rate_name("medium-slow"), "Medium Slow");
+        assert_eq!(&*growth_rate_name("slow-then-very-fast"), "Erratic");
+    }
+
+    #[test]
+    fn fills_only_missing_fields() {
+        let mut meowth = find_by_id("meowth-galar").unwrap();
+        assert_eq!(meowth.catch_rate, None);
+        let growth_rate = meowth.growth_rate.clone();
+
+        let species: SpeciesResponse = serde_json::from_value(serde_json::json!({
+            "capture_rate": 255,
+            "base_happiness": 50,
+            "growth_rate": {"name": "fast"},
+            "egg_groups": [{"name": "ground"}],
+            "gender_rate": 4,
+            "hatch_counter": 20,
+        }))
+        .unwrap();
+        let filled = merge_species(&mut meowth, species);
+        assert!(filled.contains(&"catch_rate"));
+        assert!(!filled.contains(&"growth_rate"));
+        assert_eq!(meowth.catch_rate, Some(255));
+        assert_eq!(meowth.percentage_male, Some(50.0));
+        assert_eq!(meowth.growth_rate, growth_rate);
+
+        let mut partner = find_by_id("partner-pikachu").unwrap();
+        let response: PokemonResponse = serde_json::from_value(serde_json::json!({
+            "height": 4,
+            "weight": 60,
+            "base_experience": null,
+            "abilities": [
+                {"ability": {"name": "static"}, "is_hidden": false},
+                {"ability": {"name": "lightning-rod"}, "is_hidden": true},
+            ],
+        }))
+        .unwrap();
+        merge_pokemon(&mut partner, response);
+        assert_eq!(&*partner.ability_1, "Static");
+        assert_eq!(&*partner.ability_2, "");
+        assert_eq!(&*partner.ability_hidden, "Lightning Rod");
+        assert_eq!(partner.abilities_number, 2);
+    }
+}
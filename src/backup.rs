@@ -0,0 +1,275 @@
+//! Backups of everything pkmn keeps between runs, as a zstd-compressed tar:
+//! the config directory (settings, hunts, teams, learning profiles,
+//! extensions) under `config/`, and the cache directory (sprites, TCG cards)
+//! under `cache/`. A `backup.toml` manifest comes first, so a restore can
+//! check which version made the backup before unpacking anything.
+
+use std::error::Error;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::cache::cache_dir;
+use crate::config::config_dir;
+
+/// Version of the archive layout, bumped when restoring needs to change.
+pub const FORMAT: u32 = 1;
+
+const MANIFEST: &str = "backup.toml";
+
+/// The cache's sprites directory, which can be left out of backups since
+/// the sprites can be downloaded again.
+const SPRITES: &str = "sprites";
+
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct Manifest {
+    pub format: u32,
+    pub pkmn_version: String,
+    pub created: DateTime<Utc>,
+    /// Whether cached sprites are included.
+    pub sprites: bool,
+}
+
+/// Directories backed up and restored to.
+pub struct Roots {
+    pub config: PathBuf,
+    pub cache: PathBuf,
+}
+
+impl Roots {
+    /// This machine's config and cache directories for pkmn.
+    pub fn local() -> Option<Self> {
+        Some(Roots {
+            config: config_dir()?,
+            cache: cache_dir()?,
+        })
+    }
+}
+
+/// Parses a version like `0.1.1` into comparable numbers.
+fn version(version: &str) -> Vec<u32> {
+    version
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+/// Adds every file in `dir` to the archive under `name`, skipping
+/// directories named in `skip` at the top level.
+fn append_dir<W: Write>(
+    archive: &mut tar::Builder<W>,
+    name: &str,
+    dir: &Path,
+    skip: &[&str],
+) -> Result<(), Box<dyn Error>> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        if skip.iter().any(|skipped| file_name == *skipped) {
+            continue;
+        }
+        let path = Path::new(name).join(&file_name);
+        match entry.file_type()?.is_dir() {
+            true => archive.append_dir_all(path, entry.path())?,
+            false => archive.append_path_with_name(entry.path(), path)?,
+        }
+    }
+    Ok(())
+}
+
+/// Writes a backup of `roots` to `writer`, with or without cached sprites.
+pub fn create<W: Write>(
+    roots: &Roots,
+    sprites: bool,
+    writer: W,
+) -> Result<Manifest, Box<dyn Error>> {
+    let manifest = Manifest {
+        format: FORMAT,
+        pkmn_version: env!("CARGO_PKG_VERSION").to_string(),
+        created: Utc::now(),
+        sprites,
+    };
+    let mut archive = tar::Builder::new(zstd::Encoder::new(writer, 0)?);
+
+    let contents = toml::to_string(&manifest)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(manifest.created.timestamp().max(0) as u64);
+    header.set_cksum();
+    archive.append_data(&mut header, MANIFEST, contents.as_bytes())?;
+
+    append_dir(&mut archive, "config", &roots.config, &[])?;
+    let skip: &[&str] = match sprites {
+        true => &[],
+        false => &[SPRITES],
+    };
+    append_dir(&mut archive, "cache", &roots.cache, skip)?;
+    archive.into_inner()?.finish()?.flush()?;
+    Ok(manifest)
+}
+
+/// Restores a backup read from `reader` into `roots`, replacing files of the
+/// same names and returning the backup's manifest with the number of files
+/// restored. Backups in a newer format are refused, as are those made by a
+/// newer pkmn unless `force` is given.
+pub fn restore<R: Read>(
+    roots: &Roots,
+    reader: R,
+    force: bool,
+) -> Result<(Manifest, usize), Box<dyn Error>> {
+    let mut archive = tar::Archive::new(zstd::Decoder::new(reader)?);
+    let mut entries = archive.entries()?;
+
+    let manifest: Manifest = match entries.next() {
+        Some(entry) => {
+            let mut entry = entry?;
+            if entry.path()?.as_ref() != Path::new(MANIFEST) {
+                return Err(format!("not a pkmn backup: {} is missing", MANIFEST).into());
+            }
+            let mut contents = String::new();
+            entry.read_to_string(&mut contents)?;
+            toml::from_str(&contents)?
+        }
+        None => return Err("the backup is empty".into()),
+    };
+    if manifest.format > FORMAT {
+        return Err(format!(
+            "the backup is in format {}, newer than this pkmn reads ({}); update pkmn to restore it",
+            manifest.format, FORMAT
+        )
+        .into());
+    }
+    if version(&manifest.pkmn_version) > version(env!("CARGO_PKG_VERSION")) && !force {
+        return Err(format!(
+            "the backup was made by pkmn {}, newer than this one ({}); --force restores it anyway",
+            manifest.pkmn_version,
+            env!("CARGO_PKG_VERSION")
+        )
+        .into());
+    }
+
+    let mut restored = 0;
+    for entry in entries {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        let (root, relative) = match path.strip_prefix("config") {
+            Ok(relative) => (&roots.config, relative),
+            Err(_) => match path.strip_prefix("cache") {
+                Ok(relative) => (&roots.cache, relative),
+                Err(_) => {
+                    warn!("skipping \"{}\", outside config and cache", path.display());
+                    continue;
+                }
+            },
+        };
+        // Refuses paths escaping the root, e.g. with `..`
+        if !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            warn!("skipping \"{}\", outside config and cache", path.display());
+            continue;
+        }
+        if relative.as_os_str().is_empty() || !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let destination = root.join(relative);
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        entry.unpack(&destination)?;
+        restored += 1;
+    }
+    Ok((manifest, restored))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roots(dir: &Path) -> Roots {
+        Roots {
+            config: dir.join("config"),
+            cache: dir.join("cache"),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let dir = std::env::temp_dir().join(format!("pkmn-backup-{}", std::process::id()));
+        let from = roots(&dir.join("from"));
+        fs::create_dir_all(from.config.join("learn")).unwrap();
+        fs::create_dir_all(from.cache.join("sprites")).unwrap();
+        fs::write(from.config.join("hunts.toml"), "active = \"ralts\"\n").unwrap();
+        fs::write(from.config.join("learn").join("default.toml"), "").unwrap();
+        fs::write(from.cache.join("sprites").join("ralts.png"), b"png").unwrap();
+
+        let mut backup = Vec::new();
+        let manifest = create(&from, false, &mut backup).unwrap();
+        assert!(!manifest.sprites);
+
+        let to = roots(&dir.join("to"));
+        let (restored, files) = restore(&to, backup.as_slice(), false).unwrap();
+        assert_eq!(restored, manifest);
+        assert_eq!(files, 2);
+        assert_eq!(
+            fs::read_to_string(to.config.join("hunts.toml")).unwrap(),
+            "active = \"ralts\"\n"
+        );
+        assert!(to.config.join("learn").join("default.toml").is_file());
+        assert!(!to.cache.join("sprites").exists());
+
+        let mut backup = Vec::new();
+        create(&from, true, &mut backup).unwrap();
+        restore(&to, backup.as_slice(), false).unwrap();
+        assert_eq!(
+            fs::read(to.cache.join("sprites").join("ralts.png")).unwrap(),
+            b"png"
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn checks_versions() {
+        assert!(version("0.10.0") > version("0.9.2"));
+
+        let backup = |manifest: &Manifest| {
+            let mut archive = tar::Builder::new(zstd::Encoder::new(Vec::new(), 0).unwrap());
+            let contents = toml::to_string(manifest).unwrap();
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_cksum();
+            archive
+                .append_data(&mut header, MANIFEST, contents.as_bytes())
+                .unwrap();
+            archive.into_inner().unwrap().finish().unwrap()
+        };
+        let dir = std::env::temp_dir().join(format!("pkmn-backup-versions-{}", std::process::id()));
+        let roots = roots(&dir);
+        let mut manifest = Manifest {
+            format: FORMAT,
+            pkmn_version: String::from("999.0.0"),
+            created: Utc::now(),
+            sprites: true,
+        };
+
+        let newer = backup(&manifest);
+        assert!(restore(&roots, newer.as_slice(), false)
+            .unwrap_err()
+            .to_string()
+            .contains("--force"));
+        assert!(restore(&roots, newer.as_slice(), true).is_ok());
+
+        manifest.format = FORMAT + 1;
+        assert!(restore(&roots, backup(&manifest).as_slice(), true).is_err());
+        assert!(restore(&roots, b"not a backup".as_slice(), true).is_err());
+    }
+}
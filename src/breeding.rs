@@ -0,0 +1,73 @@
+use serde::Deserialize;
+
+use crate::error::{self, PkmnError};
+use crate::pokedex::Pokemon;
+
+/// A baby Pokémon that only hatches when one of the parents holds an incense.
+#[derive(Deserialize)]
+pub struct IncenseBaby {
+    pub baby: String,
+    pub parent: String,
+    pub incense: String,
+    /// What hatches when neither parent holds the incense.
+    pub offspring_without_incense: String,
+}
+
+pub static INCENSE_BABIES_CSV: &[u8] = include_bytes!("../data/incense_babies.csv");
+
+pub(crate) fn load() -> Result<Vec<IncenseBaby>, PkmnError> {
+    error::read_csv("incense_babies.csv", INCENSE_BABIES_CSV)
+}
+
+fn incense_babies() -> Vec<IncenseBaby> {
+    load().unwrap_or_default()
+}
+
+/// The incense rule for eggs laid by `pokemon`, if it has one.
+pub fn incense_offspring(pokemon: &Pokemon) -> Option<IncenseBaby> {
+    incense_babies()
+        .into_iter()
+        .find(|baby| baby.parent == pokemon.name)
+}
+
+/// The incense needed to hatch `pokemon`, if it's an incense baby. Entries
+/// for each parent species are folded into one.
+pub fn incense_requirement(pokemon: &Pokemon) -> Option<IncenseBaby> {
+    let mut requirements = incense_babies()
+        .into_iter()
+        .filter(|baby| baby.baby == pokemon.name);
+
+    let mut requirement = requirements.next()?;
+    for other in requirements {
+        requirement.parent = format!("{} or {}", requirement.parent, other.parent);
+    }
+    Some(requirement)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::find_by_id;
+
+    #[test]
+    fn parent_offspring() {
+        let baby = incense_offspring(&find_by_id("azumarill").unwrap()).unwrap();
+        assert_eq!(baby.baby, "Azurill");
+        assert_eq!(baby.incense, "Sea Incense");
+        assert_eq!(baby.offspring_without_incense, "Marill");
+    }
+
+    #[test]
+    fn baby_requirement() {
+        let baby = incense_requirement(&find_by_id("happiny").unwrap()).unwrap();
+        assert_eq!(baby.parent, "Chansey or Blissey");
+        assert_eq!(baby.incense, "Luck Incense");
+    }
+
+    #[test]
+    fn no_incense() {
+        let pikachu = find_by_id("pikachu").unwrap();
+        assert!(incense_offspring(&pikachu).is_none());
+        assert!(incense_requirement(&pikachu).is_none());
+    }
+}
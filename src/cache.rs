@@ -0,0 +1,280 @@
+//! Limits on the cache directory, `<cache dir>/pkmn`, which holds sprites
+//! and TCG responses. Files go least recently used first: a sprite counts as
+//! used whenever it's read from the cache, other files when they were written.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::Deserialize;
+
+use crate::config::Config;
+
+/// The directory every cache lives in, e.g. `~/.cache/pkmn` on Linux.
+pub fn cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")))
+}
+
+/// Limits applied whenever something is cached, from the `[cache]` table of
+/// the config.
+///
+/// ```toml
+/// [cache]
+/// max_size = "200MB"
+/// max_unused_days = 90
+/// ```
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    pub max_size: Option<String>,
+    pub max_unused_days: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    pub max_size: Option<u64>,
+    /// Files unused for longer are removed.
+    pub max_unused: Option<Duration>,
+}
+
+impl Limits {
+    pub fn is_empty(&self) -> bool {
+        self.max_size.is_none() && self.max_unused.is_none()
+    }
+}
+
+impl CacheConfig {
+    pub fn limits(&self) -> Result<Limits, String> {
+        Ok(Limits {
+            max_size: self.max_size.as_deref().map(parse_size).transpose()?,
+            max_unused: self.max_unused_days.map(days).transpose()?,
+        })
+    }
+}
+
+/// `days` as a duration, unless it's too long to measure.
+pub fn days(days: u64) -> Result<Duration, String> {
+    days.checked_mul(24 * 60 * 60)
+        .map(Duration::from_secs)
+        .ok_or_else(|| format!("{} days is too long", days))
+}
+
+/// Parses a size like `200MB`, `1.5GB` or `4096`, in bytes. Units are
+/// multiples of 1024.
+pub fn parse_size(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let multiplier: u64 = match unit.trim().to_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        _ => {
+            return Err(format!(
+                "Unknown size unit \"{}\", expected KB, MB or GB",
+                unit
+            ))
+        }
+    };
+    match number.parse::<f64>() {
+        Ok(number) if number >= 0.0 => Ok((number * multiplier as f64) as u64),
+        _ => Err(format!("Expected a size like 200MB, got \"{}\"", value)),
+    }
+}
+
+/// `1572864` → `1.5 MB`
+pub fn format_size(bytes: u64) -> String {
+    match bytes {
+        bytes if bytes < 1 << 10 => format!("{} B", bytes),
+        bytes if bytes < 1 << 20 => format!("{:.1} KB", bytes as f64 / (1 << 10) as f64),
+        bytes if bytes < 1 << 30 => format!("{:.1} MB", bytes as f64 / (1 << 20) as f64),
+        bytes => format!("{:.1} GB", bytes as f64 / (1 << 30) as f64),
+    }
+}
+
+/// Marks a cached file as just used, so it's evicted last.
+pub fn touch(path: &Path) {
+    let touched = fs::File::options()
+        .write(true)
+        .open(path)
+        .and_then(|file| file.set_modified(SystemTime::now()));
+    if let Err(err) = touched {
+        info!("couldn't mark \"{}\" as used: {}", path.display(), err);
+    }
+}
+
+struct Entry {
+    path: PathBuf,
+    size: u64,
+    used: SystemTime,
+}
+
+fn entries(dir: &Path, into: &mut Vec<Entry>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            entries(&entry.path(), into)?;
+        } else {
+            into.push(Entry {
+                path: entry.path(),
+                size: metadata.len(),
+                used: metadata.modified()?,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// What a collection removed, or would have with `dry_run`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Collection {
+    pub removed: usize,
+    pub freed: u64,
+    pub kept: usize,
+    /// Size of the files kept.
+    pub size: u64,
+}
+
+/// Removes files in `dir` unused for longer than the limit, then the least
+/// recently used until the rest fit within the size limit.
+pub fn collect(
+    dir: &Path,
+    limits: Limits,
+    now: SystemTime,
+    dry_run: bool,
+) -> io::Result<Collection> {
+    let mut files = Vec::new();
+    if dir.is_dir() {
+        entries(dir, &mut files)?;
+    }
+    // Most recently used first, so eviction pops from the end
+    files.sort_by_key(|entry| std::cmp::Reverse(entry.used));
+
+    let is_stale = |entry: &Entry| {
+        limits.max_unused.is_some_and(|max| {
+            now.duration_since(entry.used)
+                .is_ok_and(|unused| unused > max)
+        })
+    };
+    let mut size: u64 = files
+        .iter()
+        .filter(|entry| !is_stale(entry))
+        .map(|entry| entry.size)
+        .sum();
+    let mut collection = Collection::default();
+    while let Some(entry) = files.last() {
+        let over = limits.max_size.is_some_and(|max| size > max);
+        if !is_stale(entry) && !over {
+            break;
+        }
+        if !is_stale(entry) {
+            size -= entry.size;
+        }
+        if !dry_run {
+            fs::remove_file(&entry.path)?;
+        }
+        collection.removed += 1;
+        collection.freed += entry.size;
+        files.pop();
+    }
+    collection.kept = files.len();
+    collection.size = size;
+    Ok(collection)
+}
+
+/// Applies the configured limits, if any, e.g. after adding to the cache.
+pub fn prune() {
+    let limits = match Config::load().cache.limits() {
+        Ok(limits) if !limits.is_empty() => limits,
+        Ok(_) => return,
+        Err(err) => return warn!("ignoring the cache limits in the config: {}", err),
+    };
+    let dir = match cache_dir() {
+        Some(dir) => dir,
+        None => return,
+    };
+    match collect(&dir, limits, SystemTime::now(), false) {
+        Ok(collection) if collection.removed > 0 => info!(
+            "pruned {} cached files, freeing {}",
+            collection.removed,
+            format_size(collection.freed)
+        ),
+        Ok(_) => {}
+        Err(err) => warn!("couldn't prune the cache: {}", err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes() {
+        assert_eq!(parse_size("200MB"), Ok(200 << 20));
+        assert_eq!(parse_size("1.5 gb"), Ok(3 << 29));
+        assert_eq!(parse_size("4096"), Ok(4096));
+        assert!(parse_size("200XB").is_err());
+        assert!(parse_size("MB").is_err());
+
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(3 << 19), "1.5 MB");
+    }
+
+    #[test]
+    fn bounds_days() {
+        assert_eq!(days(2), Ok(Duration::from_secs(2 * 24 * 60 * 60)));
+        assert!(days(u64::MAX).is_err());
+        let config = CacheConfig {
+            max_size: None,
+            max_unused_days: Some(u64::MAX / 2),
+        };
+        assert!(config.limits().is_err());
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let dir = std::env::temp_dir().join(format!("pkmn-cache-gc-{}", std::process::id()));
+        fs::create_dir_all(dir.join("sprites")).unwrap();
+        let now = SystemTime::now();
+        for (name, days_ago) in [("sprites/a.png", 1), ("sprites/b.png", 10), ("c.json", 100)] {
+            let path = dir.join(name);
+            fs::write(&path, [0; 100]).unwrap();
+            let file = fs::File::options().write(true).open(&path).unwrap();
+            file.set_modified(now - days(days_ago).unwrap()).unwrap();
+        }
+
+        let limits = Limits {
+            max_size: Some(150),
+            max_unused: Some(days(30).unwrap()),
+        };
+        let dry_run = collect(&dir, limits, now, true).unwrap();
+        assert_eq!(
+            dry_run,
+            Collection {
+                removed: 2,
+                freed: 200,
+                kept: 1,
+                size: 100,
+            }
+        );
+        assert!(dir.join("c.json").exists());
+
+        assert_eq!(collect(&dir, limits, now, false).unwrap(), dry_run);
+        assert!(dir.join("sprites/a.png").exists());
+        assert!(!dir.join("sprites/b.png").exists());
+        assert!(!dir.join("c.json").exists());
+        assert_eq!(
+            collect(&dir, Limits::default(), now, false)
+                .unwrap()
+                .removed,
+            0
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,166 @@
+//! Hit chance from Generation 5 onwards, following the games' integer
+//! arithmetic.
+
+use super::Step;
+use crate::moves::{Category, Move};
+
+/// Attacker abilities that change a move's accuracy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccuracyAbility {
+    CompoundEyes,
+    Hustle,
+    VictoryStar,
+    NoGuard,
+}
+
+impl AccuracyAbility {
+    pub const NAMES: [&'static str; 4] = ["compound-eyes", "hustle", "victory-star", "no-guard"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace(' ', "-").as_str() {
+            "compound-eyes" => Some(AccuracyAbility::CompoundEyes),
+            "hustle" => Some(AccuracyAbility::Hustle),
+            "victory-star" => Some(AccuracyAbility::VictoryStar),
+            "no-guard" => Some(AccuracyAbility::NoGuard),
+            _ => None,
+        }
+    }
+
+    /// Multiplier out of 4096, if the ability applies to `m`.
+    fn modifier(&self, m: &Move) -> Option<u32> {
+        match self {
+            AccuracyAbility::CompoundEyes => Some(5325),
+            AccuracyAbility::Hustle if m.category == Category::Physical => Some(3277),
+            AccuracyAbility::VictoryStar => Some(4506),
+            _ => None,
+        }
+    }
+}
+
+/// Combined accuracy/evasion stage, capped at ±6, as a fraction.
+fn stage_fraction(accuracy_stage: i8, evasion_stage: i8) -> (u32, u32) {
+    let stage = accuracy_stage.saturating_sub(evasion_stage).clamp(-6, 6);
+    match stage {
+        stage if stage >= 0 => (3 + stage as u32, 3),
+        stage => (3, 3 + stage.unsigned_abs() as u32),
+    }
+}
+
+/// Chance, between 0 and 1, that `m` hits given the attacker's accuracy
+/// stage and the target's evasion stage.
+pub fn hit_chance(
+    m: &Move,
+    accuracy_stage: i8,
+    evasion_stage: i8,
+    ability: Option<AccuracyAbility>,
+) -> f64 {
+    explain_hit_chance(m, accuracy_stage, evasion_stage, ability).0
+}
+
+/// [`hit_chance`], with each step of the calculation.
+pub fn explain_hit_chance(
+    m: &Move,
+    accuracy_stage: i8,
+    evasion_stage: i8,
+    ability: Option<AccuracyAbility>,
+) -> (f64, Vec<Step>) {
+    let accuracy = match m.accuracy {
+        Some(_) if ability == Some(AccuracyAbility::NoGuard) => {
+            return (1.0, vec![Step::new("No Guard", "every move hits", "100%")]);
+        }
+        Some(accuracy) => u32::from(accuracy),
+        None => return (1.0, vec![Step::new("Accuracy", "never misses", "100%")]),
+    };
+    let mut steps = vec![Step::new("Accuracy", &m.name, format!("{}%", accuracy))];
+
+    let (numerator, denominator) = stage_fraction(accuracy_stage, evasion_stage);
+    let mut accuracy = accuracy * numerator / denominator;
+    steps.push(Step::new(
+        "Stages",
+        format!(
+            "{:+} accuracy − {:+} evasion → ×{}/{}, rounded down",
+            accuracy_stage, evasion_stage, numerator, denominator
+        ),
+        accuracy,
+    ));
+
+    if let Some(modifier) = ability.and_then(|ability| ability.modifier(m)) {
+        // Rounds half down, like the games' other 4096-based modifiers
+        accuracy = (accuracy * modifier + 2047) / 4096;
+        steps.push(Step::new(
+            "Ability",
+            format!("×{}/4096, rounded half down", modifier),
+            accuracy,
+        ));
+    }
+
+    let chance = accuracy.min(100);
+    steps.push(Step::new(
+        "Hit Chance",
+        "capped at 100",
+        format!("{}%", chance),
+    ));
+
+    (f64::from(chance) / 100.0, steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::moves::find_move;
+
+    #[test]
+    fn plain() {
+        let focus_blast = find_move("focus-blast").unwrap();
+        assert_eq!(hit_chance(&focus_blast, 0, 0, None), 0.7);
+    }
+
+    #[test]
+    fn stages() {
+        let focus_blast = find_move("focus-blast").unwrap();
+        assert_eq!(hit_chance(&focus_blast, 0, 1, None), 0.52);
+        assert_eq!(hit_chance(&focus_blast, 1, 0, None), 0.93);
+        assert_eq!(hit_chance(&focus_blast, -1, 1, None), 0.42);
+        assert_eq!(hit_chance(&focus_blast, -6, 6, None), 0.23);
+        // Out-of-range stages are capped instead of overflowing
+        assert_eq!(hit_chance(&focus_blast, 127, -1, None), 1.0);
+        assert_eq!(hit_chance(&focus_blast, -128, 127, None), 0.23);
+    }
+
+    #[test]
+    fn abilities() {
+        let focus_blast = find_move("focus-blast").unwrap();
+        let compound_eyes = Some(AccuracyAbility::CompoundEyes);
+        assert_eq!(hit_chance(&focus_blast, 0, 0, compound_eyes), 0.91);
+        assert_eq!(hit_chance(&focus_blast, -1, 1, compound_eyes), 0.55);
+
+        // Hustle only affects physical moves
+        let hustle = Some(AccuracyAbility::Hustle);
+        assert_eq!(hit_chance(&focus_blast, 0, 0, hustle), 0.7);
+        let stone_edge = find_move("Stone Edge").unwrap();
+        assert_eq!(hit_chance(&stone_edge, 0, 0, hustle), 0.64);
+    }
+
+    #[test]
+    fn explains_each_step() {
+        let focus_blast = find_move("focus-blast").unwrap();
+        let (chance, steps) =
+            explain_hit_chance(&focus_blast, -1, 1, Some(AccuracyAbility::CompoundEyes));
+        assert_eq!(chance, 0.55);
+        let values = steps
+            .iter()
+            .map(|step| step.value.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(values, ["70%", "42", "55", "55%"]);
+    }
+
+    #[test]
+    fn never_misses() {
+        let aerial_ace = find_move("Aerial Ace").unwrap();
+        assert_eq!(hit_chance(&aerial_ace, 0, 6, None), 1.0);
+
+        let no_guard = Some(AccuracyAbility::NoGuard);
+        let focus_blast = find_move("focus-blast").unwrap();
+        assert_eq!(hit_chance(&focus_blast, 0, 6, no_guard), 1.0);
+    }
+}
@@ -0,0 +1,174 @@
+//! Damage from Generation 5 onwards, following the games' integer arithmetic,
+//! without weather, items, abilities or other field effects.
+
+use super::stats::{Stat, Stats};
+use crate::moves::{Category, Move};
+use crate::pokedex::Pokemon;
+use crate::types::Type;
+
+/// Lowest and highest random roll, as a percentage.
+const ROLLS: std::ops::RangeInclusive<u32> = 85..=100;
+
+/// Every damage a move can do, one per random roll.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Damage {
+    /// From the lowest roll (85%) to the highest (100%).
+    pub rolls: Vec<u16>,
+    pub effectiveness: f32,
+    pub stab: bool,
+}
+
+impl Damage {
+    pub fn min(&self) -> u16 {
+        self.rolls.first().copied().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> u16 {
+        self.rolls.last().copied().unwrap_or_default()
+    }
+}
+
+/// Applies a multiplier out of 4096, rounding half down.
+fn modify(value: u32, modifier: u32) -> u32 {
+    (value * modifier + 2047) / 4096
+}
+
+/// Damage `m` does from `attacker` at `level` to `defender`, with each one's
+/// stats, or `None` for status moves and moves with variable power. A
+/// critical hit does 1.5× damage, as from Generation 6.
+pub fn damage(
+    m: &Move,
+    level: u16,
+    attacker: &Pokemon,
+    attacker_stats: &Stats,
+    defender: &Pokemon,
+    defender_stats: &Stats,
+    critical: bool,
+) -> Option<Damage> {
+    let (attack, defense) = match m.category {
+        Category::Physical => (Stat::Attack, Stat::Defense),
+        Category::Special => (Stat::SpAttack, Stat::SpDefense),
+        Category::Status => return None,
+    };
+    let power = u32::from(m.power?);
+    let move_type = Type::from_name(&m.move_type)?;
+    let attack = u32::from(attacker_stats.get(attack));
+    let defense = u32::from(defender_stats.get(defense)).max(1);
+
+    let mut base = (2 * u32::from(level) / 5 + 2) * power * attack / defense / 50 + 2;
+    if critical {
+        base = base * 3 / 2;
+    }
+    let stab = attacker.types().contains(&move_type);
+    let effectiveness = move_type.effectiveness_against(&defender.types());
+    let rolls = ROLLS
+        .map(|roll| {
+            let mut damage = base * roll / 100;
+            if stab {
+                damage = modify(damage, 6144);
+            }
+            // Multiplying by each type's ×2 or ×½ in turn rounds down the
+            // same as multiplying by their product
+            damage = (damage as f32 * effectiveness) as u32;
+            match damage {
+                0 if effectiveness > 0.0 => 1,
+                damage => damage.min(u32::from(u16::MAX)) as u16,
+            }
+        })
+        .collect();
+
+    Some(Damage {
+        rolls,
+        effectiveness,
+        stab,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::stats::Stat::*;
+    use crate::moves::find_move;
+    use crate::pokedex::find_by_id;
+
+    fn stats(values: &[(Stat, u16)]) -> Stats {
+        let mut stats = Stats::uniform(100);
+        for (stat, value) in values {
+            stats.set(*stat, *value);
+        }
+        stats
+    }
+
+    #[test]
+    fn glaceon_ice_fang() {
+        // The worked example from Bulbapedia's damage article
+        let glaceon = find_by_id("glaceon").unwrap();
+        let garchomp = find_by_id("garchomp").unwrap();
+        let ice_fang = find_move("ice-fang").unwrap();
+        let damage = damage(
+            &ice_fang,
+            75,
+            &glaceon,
+            &stats(&[(Attack, 123)]),
+            &garchomp,
+            &stats(&[(Defense, 163)]),
+            false,
+        )
+        .unwrap();
+        assert!(damage.stab);
+        assert_eq!(damage.effectiveness, 4.0);
+        assert_eq!(damage.rolls.len(), 16);
+        assert_eq!((damage.min(), damage.max()), (168, 196));
+    }
+
+    #[test]
+    fn critical_hits() {
+        let pikachu = find_by_id("pikachu").unwrap();
+        let thunderbolt = find_move("thunderbolt").unwrap();
+        let hit = |critical| {
+            damage(
+                &thunderbolt,
+                50,
+                &pikachu,
+                &stats(&[]),
+                &pikachu,
+                &stats(&[]),
+                critical,
+            )
+            .unwrap()
+        };
+        // ½ × 1.5 STAB on base damage 41
+        assert_eq!(hit(false).max(), 30);
+        assert_eq!(hit(true).max(), 45);
+    }
+
+    #[test]
+    fn immunities_and_status_moves() {
+        let garchomp = find_by_id("garchomp").unwrap();
+        let charizard = find_by_id("charizard").unwrap();
+        let earthquake = find_move("earthquake").unwrap();
+        let spread = stats(&[]);
+        let immune = damage(
+            &earthquake,
+            50,
+            &garchomp,
+            &spread,
+            &charizard,
+            &spread,
+            false,
+        );
+        assert_eq!(immune.unwrap().max(), 0);
+
+        let swords_dance = find_move("swords-dance").unwrap();
+        assert!(damage(
+            &swords_dance,
+            50,
+            &garchomp,
+            &spread,
+            &charizard,
+            &spread,
+            false
+        )
+        .is_none());
+    }
+}
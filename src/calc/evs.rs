@@ -0,0 +1,246 @@
+//! Suggested EV spreads: EVs are first spent on the benchmarks asked for, such
+//! as outspeeding a threat, and the rest by the Pokémon's role.
+
+use std::fmt;
+
+use super::stats::{stat, Nature, Stat, Stats, MAX_IV, MAX_STAT_EVS, MAX_TOTAL_EVS};
+use crate::pokedex::Pokemon;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    BulkyPivot,
+    PhysicalSweeper,
+    SpecialSweeper,
+    PhysicalWall,
+    SpecialWall,
+}
+
+impl Role {
+    pub const NAMES: [&'static str; 5] = [
+        "bulky-pivot",
+        "physical-sweeper",
+        "special-sweeper",
+        "physical-wall",
+        "special-wall",
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "bulky-pivot" => Some(Role::BulkyPivot),
+            "physical-sweeper" => Some(Role::PhysicalSweeper),
+            "special-sweeper" => Some(Role::SpecialSweeper),
+            "physical-wall" => Some(Role::PhysicalWall),
+            "special-wall" => Some(Role::SpecialWall),
+            _ => None,
+        }
+    }
+
+    /// Stats the role invests in, most important first.
+    fn priorities(&self) -> &'static [Stat] {
+        match self {
+            Role::BulkyPivot => &[Stat::Hp, Stat::Defense, Stat::SpDefense],
+            Role::PhysicalSweeper => &[Stat::Attack, Stat::Speed, Stat::Hp],
+            Role::SpecialSweeper => &[Stat::SpAttack, Stat::Speed, Stat::Hp],
+            Role::PhysicalWall => &[Stat::Hp, Stat::Defense, Stat::SpDefense],
+            Role::SpecialWall => &[Stat::Hp, Stat::SpDefense, Stat::Defense],
+        }
+    }
+}
+
+/// A property the HP stat should have.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HpBenchmark {
+    /// A multiple of 16, so no Leftovers recovery is lost to rounding.
+    Leftovers,
+    /// One more than a multiple of 4, so four Substitutes can be made.
+    Substitute,
+    AtLeast(u16),
+}
+
+impl HpBenchmark {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "leftovers" => Some(HpBenchmark::Leftovers),
+            "substitute" => Some(HpBenchmark::Substitute),
+            value => value.parse().ok().map(HpBenchmark::AtLeast),
+        }
+    }
+
+    pub fn is_met(&self, hp: u16) -> bool {
+        match self {
+            HpBenchmark::Leftovers => hp.is_multiple_of(16),
+            HpBenchmark::Substitute => hp % 4 == 1,
+            HpBenchmark::AtLeast(minimum) => hp >= *minimum,
+        }
+    }
+}
+
+impl fmt::Display for HpBenchmark {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HpBenchmark::Leftovers => f.write_str("Leftovers"),
+            HpBenchmark::Substitute => f.write_str("Substitute"),
+            HpBenchmark::AtLeast(minimum) => write!(f, "at least {}", minimum),
+        }
+    }
+}
+
+pub struct Goals {
+    pub level: u16,
+    pub nature: Nature,
+    pub role: Role,
+    /// A Speed stat to beat.
+    pub outspeed: Option<u16>,
+    pub hp: Option<HpBenchmark>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SpreadError {
+    /// Even with the most Speed EVs, Speed only reaches `max`.
+    CantOutspeed {
+        max: u16,
+    },
+    HpUnreachable(HpBenchmark),
+}
+
+impl fmt::Display for SpreadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpreadError::CantOutspeed { max } => {
+                write!(f, "Can't outspeed it, even at {} Speed", max)
+            }
+            SpreadError::HpUnreachable(benchmark) => {
+                write!(f, "No HP EVs reach the {} benchmark", benchmark)
+            }
+        }
+    }
+}
+
+/// EVs meeting `goals`, assuming perfect IVs. EVs only count in steps of 4,
+/// so spreads never waste any on rounding.
+pub fn suggest(pokemon: &Pokemon, goals: &Goals) -> Result<Stats, SpreadError> {
+    let value =
+        |s: Stat, evs: u16| stat(s, s.base(pokemon), MAX_IV, evs, goals.level, &goals.nature);
+    let steps = || (0..=MAX_STAT_EVS).step_by(4);
+
+    let mut evs = Stats::default();
+    let mut fixed = Vec::new();
+
+    if let Some(target) = goals.outspeed {
+        let speed_evs = steps()
+            .find(|&speed_evs| value(Stat::Speed, speed_evs) > target)
+            .ok_or(SpreadError::CantOutspeed {
+                max: value(Stat::Speed, MAX_STAT_EVS),
+            })?;
+        evs.set(Stat::Speed, speed_evs);
+        fixed.push(Stat::Speed);
+    }
+
+    if let Some(benchmark) = goals.hp {
+        let available = MAX_TOTAL_EVS - evs.total();
+        let mut candidates = steps()
+            .take_while(|&hp_evs| hp_evs <= available)
+            .filter(|&hp_evs| benchmark.is_met(value(Stat::Hp, hp_evs)));
+        // Roles investing in HP want as much as the benchmark allows, others
+        // as little
+        let hp_evs = match goals.role.priorities().contains(&Stat::Hp) {
+            true => candidates.last(),
+            false => candidates.next(),
+        }
+        .ok_or(SpreadError::HpUnreachable(benchmark))?;
+        evs.set(Stat::Hp, hp_evs);
+        fixed.push(Stat::Hp);
+    }
+
+    let mut open = goals
+        .role
+        .priorities()
+        .iter()
+        .copied()
+        .filter(|s| !fixed.contains(s))
+        .collect::<Vec<Stat>>();
+    // Whatever's left over after the role's stats goes to the defensive stats
+    for s in [Stat::Hp, Stat::Defense, Stat::SpDefense, Stat::Speed] {
+        if !open.contains(&s) && !fixed.contains(&s) {
+            open.push(s);
+        }
+    }
+
+    for s in &open {
+        let available = (MAX_TOTAL_EVS - evs.total()) / 4 * 4;
+        // A bulky pivot splits what HP leaves over between both defenses
+        let share = match (goals.role, s) {
+            (Role::BulkyPivot, Stat::Defense) => available / 8 * 4,
+            _ => available,
+        };
+        evs.set(*s, share.min(MAX_STAT_EVS));
+    }
+
+    Ok(evs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::stats::stats;
+    use crate::pokedex::find_by_id;
+
+    fn goals(role: Role, nature: &str) -> Goals {
+        Goals {
+            level: 100,
+            nature: Nature::from_name(nature).unwrap(),
+            role,
+            outspeed: None,
+            hp: None,
+        }
+    }
+
+    fn spread(evs: Stats) -> Vec<u16> {
+        Stat::ALL.iter().map(|s| evs.get(*s)).collect()
+    }
+
+    #[test]
+    fn role_heuristics() {
+        let garchomp = find_by_id("garchomp").unwrap();
+        let evs = suggest(&garchomp, &goals(Role::PhysicalSweeper, "jolly")).unwrap();
+        assert_eq!(spread(evs), vec![4, 252, 0, 0, 0, 252]);
+
+        let evs = suggest(&garchomp, &goals(Role::BulkyPivot, "impish")).unwrap();
+        assert_eq!(spread(evs), vec![252, 0, 128, 0, 128, 0]);
+    }
+
+    #[test]
+    fn outspeeds_with_fewest_evs() {
+        let garchomp = find_by_id("garchomp").unwrap();
+        let mut goals = goals(Role::PhysicalSweeper, "adamant");
+        goals.outspeed = Some(300);
+        let evs = suggest(&garchomp, &goals).unwrap();
+        let speed = stats(&garchomp, &Stats::uniform(MAX_IV), &evs, 100, &goals.nature);
+        assert_eq!(evs.get(Stat::Speed), 244);
+        assert_eq!(speed.get(Stat::Speed), 301);
+        assert_eq!(evs.total(), 508);
+
+        goals.outspeed = Some(400);
+        assert_eq!(
+            suggest(&garchomp, &goals),
+            Err(SpreadError::CantOutspeed { max: 303 })
+        );
+    }
+
+    #[test]
+    fn hp_benchmarks() {
+        let garchomp = find_by_id("garchomp").unwrap();
+        let mut goals = goals(Role::PhysicalWall, "impish");
+        goals.hp = Some(HpBenchmark::Leftovers);
+        let evs = suggest(&garchomp, &goals).unwrap();
+        let hp = stats(&garchomp, &Stats::uniform(MAX_IV), &evs, 100, &goals.nature);
+        assert_eq!(hp.get(Stat::Hp), 416);
+        assert_eq!(evs.get(Stat::Defense), 252);
+
+        assert!(HpBenchmark::Substitute.is_met(357));
+        assert_eq!(
+            HpBenchmark::from_name("300"),
+            Some(HpBenchmark::AtLeast(300))
+        );
+    }
+}
@@ -0,0 +1,22 @@
+pub mod accuracy;
+pub mod damage;
+pub mod evs;
+pub mod retro;
+pub mod stats;
+
+/// One term of a calculator's formula, shown by `--explain`.
+pub struct Step {
+    pub label: &'static str,
+    pub formula: String,
+    pub value: String,
+}
+
+impl Step {
+    fn new<F: ToString, V: ToString>(label: &'static str, formula: F, value: V) -> Self {
+        Step {
+            label,
+            formula: formula.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
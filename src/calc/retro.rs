@@ -0,0 +1,343 @@
+//! Mechanics of the Generation 1 and 2 games (Red/Blue/Yellow, Gold/Silver/Crystal).
+
+use super::Step;
+
+const HIDDEN_POWER_TYPES: [&str; 16] = [
+    "Fighting", "Flying", "Poison", "Ground", "Rock", "Bug", "Ghost", "Steel", "Fire", "Water",
+    "Grass", "Electric", "Psychic", "Ice", "Dragon", "Dark",
+];
+
+/// Determinant values, each 0–15. The HP DV isn't stored; it's derived from
+/// the others.
+pub struct Dvs {
+    pub attack: u8,
+    pub defense: u8,
+    pub speed: u8,
+    pub special: u8,
+}
+
+impl Dvs {
+    pub fn hp(&self) -> u8 {
+        ((self.attack & 1) << 3)
+            | ((self.defense & 1) << 2)
+            | ((self.speed & 1) << 1)
+            | (self.special & 1)
+    }
+
+    /// Shininess in Generation 2, which is purely a function of the DVs.
+    pub fn is_shiny(&self) -> bool {
+        self.defense == 10 && self.speed == 10 && self.special == 10 && self.attack & 2 == 2
+    }
+
+    pub fn hidden_power_type(&self) -> &'static str {
+        HIDDEN_POWER_TYPES[usize::from(((self.attack % 4) << 2) | (self.defense % 4))]
+    }
+
+    pub fn hidden_power_power(&self) -> u8 {
+        let msb = |dv: u8| u16::from(dv >> 3);
+        let bits =
+            msb(self.special) + 2 * msb(self.speed) + 4 * msb(self.defense) + 8 * msb(self.attack);
+        ((5 * bits + u16::from(self.special % 4)) / 2 + 31) as u8
+    }
+
+    /// Whether a Pokémon with these DVs is female in Generation 2, given its
+    /// male ratio. `None` for genderless species.
+    pub fn is_female(&self, percentage_male: Option<f32>) -> Option<bool> {
+        let percentage_male = percentage_male?;
+        let female_max_attack = match percentage_male {
+            p if p >= 100.0 => return Some(false),
+            p if p <= 0.0 => return Some(true),
+            p if p >= 87.5 => 1,
+            p if p >= 75.0 => 3,
+            p if p >= 50.0 => 7,
+            _ => 11,
+        };
+        Some(self.attack <= female_max_attack)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Ball {
+    Poke,
+    Great,
+    Ultra,
+    Safari,
+    Master,
+}
+
+impl Ball {
+    pub const ALL: [Ball; 5] = [
+        Ball::Poke,
+        Ball::Great,
+        Ball::Ultra,
+        Ball::Safari,
+        Ball::Master,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().trim_end_matches("ball").trim() {
+            "poke" | "poké" => Some(Ball::Poke),
+            "great" => Some(Ball::Great),
+            "ultra" => Some(Ball::Ultra),
+            "safari" => Some(Ball::Safari),
+            "master" => Some(Ball::Master),
+            _ => None,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Ball::Poke => "Poké Ball",
+            Ball::Great => "Great Ball",
+            Ball::Ultra => "Ultra Ball",
+            Ball::Safari => "Safari Ball",
+            Ball::Master => "Master Ball",
+        }
+    }
+
+    fn max_random(&self) -> u16 {
+        match self {
+            Ball::Poke => 255,
+            Ball::Great => 200,
+            _ => 150,
+        }
+    }
+
+    fn hp_factor(&self) -> u32 {
+        match self {
+            Ball::Great => 8,
+            _ => 12,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Status {
+    None,
+    Sleep,
+    Freeze,
+    Paralysis,
+    Burn,
+    Poison,
+}
+
+impl Status {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "none" => Some(Status::None),
+            "sleep" | "asleep" | "slp" => Some(Status::Sleep),
+            "freeze" | "frozen" | "frz" => Some(Status::Freeze),
+            "paralysis" | "paralyzed" | "par" => Some(Status::Paralysis),
+            "burn" | "burned" | "brn" => Some(Status::Burn),
+            "poison" | "poisoned" | "psn" => Some(Status::Poison),
+            _ => None,
+        }
+    }
+
+    fn catch_bonus(&self) -> u16 {
+        match self {
+            Status::None => 0,
+            Status::Sleep | Status::Freeze => 25,
+            Status::Paralysis | Status::Burn | Status::Poison => 12,
+        }
+    }
+}
+
+/// Exact chance of catching a Pokémon in Generation 1, following the game's
+/// two-roll algorithm (including its quirks, e.g. the Ultra Ball sometimes
+/// being worse than the Great Ball).
+pub fn gen1_catch_chance(
+    catch_rate: u8,
+    max_hp: u16,
+    current_hp: u16,
+    ball: Ball,
+    status: Status,
+) -> f64 {
+    explain_gen1_catch_chance(catch_rate, max_hp, current_hp, ball, status).0
+}
+
+/// [`gen1_catch_chance`], with each step of the calculation.
+pub fn explain_gen1_catch_chance(
+    catch_rate: u8,
+    max_hp: u16,
+    current_hp: u16,
+    ball: Ball,
+    status: Status,
+) -> (f64, Vec<Step>) {
+    if let Ball::Master = ball {
+        return (
+            1.0,
+            vec![Step::new("Master Ball", "always catches", "100%")],
+        );
+    }
+
+    let max_random = ball.max_random();
+    let bonus = status.catch_bonus();
+    let mut steps = vec![Step::new(
+        "First Roll",
+        format!("outcomes, from 0 to {}", max_random),
+        max_random + 1,
+    )];
+
+    let status_catches = bonus.min(max_random + 1);
+    steps.push(Step::new(
+        "Status",
+        "rolls below the status bonus catch outright",
+        status_catches,
+    ));
+
+    let second_rolls = if bonus > max_random {
+        0
+    } else {
+        max_random.min(bonus + u16::from(catch_rate)) - bonus + 1
+    };
+    steps.push(Step::new(
+        "Catch Rate",
+        format!(
+            "outcomes from {} to {} + {} roll again",
+            bonus, bonus, catch_rate
+        ),
+        second_rolls,
+    ));
+
+    let hp_divisor = u32::from((current_hp / 4).max(1));
+    let hp_factor = (u32::from(max_hp) * 255 / ball.hp_factor() / hp_divisor).min(255);
+    steps.push(Step::new(
+        "HP Factor",
+        format!(
+            "{} × 255 / {} / max(1, {} / 4), at most 255",
+            max_hp,
+            ball.hp_factor(),
+            current_hp
+        ),
+        hp_factor,
+    ));
+
+    let second_roll_chance = (hp_factor + 1) as f64 / 256.0;
+    steps.push(Step::new(
+        "Second Roll",
+        format!("({} + 1) / 256", hp_factor),
+        format!("{:.2}%", second_roll_chance * 100.0),
+    ));
+
+    let chance = (f64::from(status_catches) + f64::from(second_rolls) * second_roll_chance)
+        / f64::from(max_random + 1);
+    steps.push(Step::new(
+        "Catch Chance",
+        format!(
+            "({} + {} × second roll) / {}",
+            status_catches,
+            second_rolls,
+            max_random + 1
+        ),
+        format!("{:.2}%", chance * 100.0),
+    ));
+
+    (chance, steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hp_dv() {
+        let dvs = Dvs {
+            attack: 15,
+            defense: 10,
+            speed: 9,
+            special: 11,
+        };
+        assert_eq!(dvs.hp(), 0b1011);
+    }
+
+    #[test]
+    fn shiny() {
+        let dvs = Dvs {
+            attack: 14,
+            defense: 10,
+            speed: 10,
+            special: 10,
+        };
+        assert!(dvs.is_shiny());
+        assert!(!Dvs { attack: 13, ..dvs }.is_shiny());
+    }
+
+    #[test]
+    fn hidden_power_max() {
+        let dvs = Dvs {
+            attack: 15,
+            defense: 15,
+            speed: 15,
+            special: 15,
+        };
+        assert_eq!(dvs.hidden_power_type(), "Dark");
+        assert_eq!(dvs.hidden_power_power(), 70);
+    }
+
+    #[test]
+    fn hidden_power_shiny() {
+        let dvs = Dvs {
+            attack: 10,
+            defense: 10,
+            speed: 10,
+            special: 10,
+        };
+        assert_eq!(dvs.hidden_power_type(), "Grass");
+        assert_eq!(dvs.hidden_power_power(), 69);
+    }
+
+    #[test]
+    fn gender() {
+        let dvs = Dvs {
+            attack: 1,
+            defense: 0,
+            speed: 0,
+            special: 0,
+        };
+        assert_eq!(dvs.is_female(Some(87.5)), Some(true));
+        assert_eq!(Dvs { attack: 2, ..dvs }.is_female(Some(87.5)), Some(false));
+        assert_eq!(dvs.is_female(None), None);
+    }
+
+    #[test]
+    fn master_ball_always_catches() {
+        assert_eq!(
+            gen1_catch_chance(3, 100, 100, Ball::Master, Status::None),
+            1.0
+        );
+    }
+
+    #[test]
+    fn full_hp_mewtwo() {
+        let chance = gen1_catch_chance(3, 100, 100, Ball::Ultra, Status::None);
+        assert!((chance - 4.0 / 151.0 * (86.0 / 256.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn explains_each_step() {
+        let (chance, steps) = explain_gen1_catch_chance(45, 100, 50, Ball::Great, Status::Sleep);
+        assert_eq!(
+            chance,
+            gen1_catch_chance(45, 100, 50, Ball::Great, Status::Sleep)
+        );
+        let values = steps
+            .iter()
+            .map(|step| step.value.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(values, ["201", "25", "46", "255", "100.00%", "35.32%"]);
+    }
+
+    #[test]
+    fn sleep_bonus() {
+        let chance = gen1_catch_chance(3, 100, 100, Ball::Ultra, Status::Sleep);
+        assert!(chance > 25.0 / 151.0);
+    }
+
+    #[test]
+    fn great_ball_beats_ultra_ball_at_high_catch_rate() {
+        let great = gen1_catch_chance(255, 100, 100, Ball::Great, Status::None);
+        let ultra = gen1_catch_chance(255, 100, 100, Ball::Ultra, Status::None);
+        assert!(great > ultra);
+    }
+}
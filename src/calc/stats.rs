@@ -0,0 +1,440 @@
+//! Stats from base stats, IVs, EVs, level and nature, from Generation 3 onwards.
+
+use super::Step;
+use crate::pokedex::Pokemon;
+
+/// Most EVs a Pokémon can have in total.
+pub const MAX_TOTAL_EVS: u16 = 510;
+/// Most EVs a Pokémon can have in a single stat.
+pub const MAX_STAT_EVS: u16 = 252;
+pub const MAX_IV: u16 = 31;
+/// Highest base stat in the games (Blissey's HP).
+pub const MAX_BASE_STAT: u16 = 255;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stat {
+    Hp,
+    Attack,
+    Defense,
+    SpAttack,
+    SpDefense,
+    Speed,
+}
+
+impl Stat {
+    pub const ALL: [Stat; 6] = [
+        Stat::Hp,
+        Stat::Attack,
+        Stat::Defense,
+        Stat::SpAttack,
+        Stat::SpDefense,
+        Stat::Speed,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stat::Hp => "HP",
+            Stat::Attack => "Attack",
+            Stat::Defense => "Defense",
+            Stat::SpAttack => "Sp. Attack",
+            Stat::SpDefense => "Sp. Defense",
+            Stat::Speed => "Speed",
+        }
+    }
+
+    /// A stat by slug, e.g. `sp-attack`, or its competitive shorthand, e.g.
+    /// `spa`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().replace(['_', ' '], "-").as_str() {
+            "hp" => Some(Stat::Hp),
+            "attack" | "atk" => Some(Stat::Attack),
+            "defense" | "def" => Some(Stat::Defense),
+            "sp-attack" | "spa" => Some(Stat::SpAttack),
+            "sp-defense" | "spd" => Some(Stat::SpDefense),
+            "speed" | "spe" => Some(Stat::Speed),
+            _ => None,
+        }
+    }
+
+    /// The berry flavor liked by natures raising this stat and disliked by
+    /// those lowering it. No nature changes HP, so it has none.
+    pub fn flavor(&self) -> Option<&'static str> {
+        match self {
+            Stat::Hp => None,
+            Stat::Attack => Some("Spicy"),
+            Stat::Defense => Some("Sour"),
+            Stat::SpAttack => Some("Dry"),
+            Stat::SpDefense => Some("Bitter"),
+            Stat::Speed => Some("Sweet"),
+        }
+    }
+
+    fn index(&self) -> usize {
+        Stat::ALL.iter().position(|stat| stat == self).unwrap()
+    }
+
+    pub fn base(&self, pokemon: &Pokemon) -> u16 {
+        match self {
+            Stat::Hp => pokemon.hp,
+            Stat::Attack => pokemon.attack,
+            Stat::Defense => pokemon.defense,
+            Stat::SpAttack => pokemon.sp_attack,
+            Stat::SpDefense => pokemon.sp_defense,
+            Stat::Speed => pokemon.speed,
+        }
+    }
+}
+
+/// One value per stat, e.g. an EV spread.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats([u16; 6]);
+
+impl Stats {
+    pub fn uniform(value: u16) -> Self {
+        Stats([value; 6])
+    }
+
+    pub fn get(&self, stat: Stat) -> u16 {
+        self.0[stat.index()]
+    }
+
+    pub fn set(&mut self, stat: Stat, value: u16) {
+        self.0[stat.index()] = value;
+    }
+
+    pub fn total(&self) -> u16 {
+        self.0.iter().sum()
+    }
+
+    /// One value for every stat, or six in the order of [`Stat::ALL`], each at
+    /// most `max`, e.g. IVs or EVs passed to a binding as a list.
+    pub fn from_values<T: Copy + Into<u32>>(values: &[T], max: u16) -> Result<Self, String> {
+        let values = values
+            .iter()
+            .map(|value| match u16::try_from((*value).into()) {
+                Ok(number) if number <= max => Ok(number),
+                _ => Err(format!(
+                    "\"{}\" isn't a number from 0 to {}",
+                    (*value).into(),
+                    max
+                )),
+            })
+            .collect::<Result<Vec<u16>, String>>()?;
+        match values.as_slice() {
+            [value] => Ok(Stats::uniform(*value)),
+            [hp, attack, defense, sp_attack, sp_defense, speed] => Ok(Stats([
+                *hp,
+                *attack,
+                *defense,
+                *sp_attack,
+                *sp_defense,
+                *speed,
+            ])),
+            _ => Err(String::from(
+                "Expected one value, or six as HP/Atk/Def/SpA/SpD/Spe",
+            )),
+        }
+    }
+
+    /// Parses one value for every stat, e.g. `31`, or six in the order of
+    /// [`Stat::ALL`], e.g. `0/252/0/0/4/252`, each at most `max`.
+    pub fn parse(value: &str, max: u16) -> Result<Self, String> {
+        let values = value
+            .split('/')
+            .map(|part| {
+                part.trim()
+                    .parse::<u32>()
+                    .map_err(|_| format!("\"{}\" isn't a number from 0 to {}", part, max))
+            })
+            .collect::<Result<Vec<u32>, String>>()?;
+        Stats::from_values(&values, max)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Nature {
+    pub name: &'static str,
+    /// The stat raised by 10%, if any. Neutral natures raise and lower the
+    /// same stat, so they're stored without either.
+    pub increased: Option<Stat>,
+    pub decreased: Option<Stat>,
+}
+
+const fn nature(name: &'static str, increased: Stat, decreased: Stat) -> Nature {
+    Nature {
+        name,
+        increased: Some(increased),
+        decreased: Some(decreased),
+    }
+}
+
+const fn neutral(name: &'static str) -> Nature {
+    Nature {
+        name,
+        increased: None,
+        decreased: None,
+    }
+}
+
+use Stat::{Attack, Defense, SpAttack, SpDefense, Speed};
+
+pub static NATURES: [Nature; 25] = [
+    neutral("Hardy"),
+    nature("Lonely", Attack, Defense),
+    nature("Brave", Attack, Speed),
+    nature("Adamant", Attack, SpAttack),
+    nature("Naughty", Attack, SpDefense),
+    nature("Bold", Defense, Attack),
+    neutral("Docile"),
+    nature("Relaxed", Defense, Speed),
+    nature("Impish", Defense, SpAttack),
+    nature("Lax", Defense, SpDefense),
+    nature("Timid", Speed, Attack),
+    nature("Hasty", Speed, Defense),
+    neutral("Serious"),
+    nature("Jolly", Speed, SpAttack),
+    nature("Naive", Speed, SpDefense),
+    nature("Modest", SpAttack, Attack),
+    nature("Mild", SpAttack, Defense),
+    nature("Quiet", SpAttack, Speed),
+    neutral("Bashful"),
+    nature("Rash", SpAttack, SpDefense),
+    nature("Calm", SpDefense, Attack),
+    nature("Gentle", SpDefense, Defense),
+    nature("Sassy", SpDefense, Speed),
+    nature("Careful", SpDefense, SpAttack),
+    neutral("Quirky"),
+];
+
+impl Nature {
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim();
+        NATURES
+            .iter()
+            .find(|nature| nature.name.eq_ignore_ascii_case(name))
+            .copied()
+    }
+
+    /// The flavor of berries the Pokémon likes, which confuse it less often
+    /// when eaten at low HP. Neutral natures like and dislike every flavor
+    /// equally.
+    pub fn liked_flavor(&self) -> Option<&'static str> {
+        self.increased.and_then(|stat| stat.flavor())
+    }
+
+    pub fn disliked_flavor(&self) -> Option<&'static str> {
+        self.decreased.and_then(|stat| stat.flavor())
+    }
+
+    /// The nature's effect on `stat`, in percent.
+    fn percent(&self, stat: Stat) -> u16 {
+        if self.increased == Some(stat) {
+            110
+        } else if self.decreased == Some(stat) {
+            90
+        } else {
+            100
+        }
+    }
+}
+
+/// A stat at `level` with the given IV and EVs. HP ignores the nature, and is
+/// always 1 for a base of 1 (Shedinja).
+pub fn stat(stat: Stat, base: u16, iv: u16, evs: u16, level: u16, nature: &Nature) -> u16 {
+    explain_stat(stat, base, iv, evs, level, nature).0
+}
+
+/// [`stat`], with each step of the calculation.
+pub fn explain_stat(
+    stat: Stat,
+    base: u16,
+    iv: u16,
+    evs: u16,
+    level: u16,
+    nature: &Nature,
+) -> (u16, Vec<Step>) {
+    if stat == Stat::Hp && base == 1 {
+        return (1, vec![Step::new("HP", "always 1 for a base of 1", 1)]);
+    }
+
+    // Products overflow u16 for the highest base stats, e.g. Eternamax's
+    // Defense with a raising nature
+    let (base, iv, evs, level) = (
+        u32::from(base),
+        u32::from(iv),
+        u32::from(evs),
+        u32::from(level),
+    );
+    let scaled = (2 * base + iv + evs / 4) * level / 100;
+    let mut steps = vec![Step::new(
+        "Scaled",
+        format!(
+            "(2×{} base + {} IV + {} EVs/4) × {}/100, rounded down",
+            base, iv, evs, level
+        ),
+        scaled,
+    )];
+
+    let value = match stat {
+        Stat::Hp => {
+            let value = scaled + level + 10;
+            steps.push(Step::new(
+                "Level Bonus",
+                format!("+ {} level + 10", level),
+                value,
+            ));
+            value
+        }
+        _ => {
+            let before = scaled + 5;
+            steps.push(Step::new("Base Value", "+ 5", before));
+            let percent = nature.percent(stat);
+            let value = before * u32::from(percent) / 100;
+            steps.push(Step::new(
+                "Nature",
+                format!("×{}/100, rounded down", percent),
+                value,
+            ));
+            value
+        }
+    };
+
+    (u16::try_from(value).unwrap_or(u16::MAX), steps)
+}
+
+/// All six stats of `pokemon` with the given IVs and EVs.
+pub fn stats(pokemon: &Pokemon, ivs: &Stats, evs: &Stats, level: u16, nature: &Nature) -> Stats {
+    let mut stats = Stats::default();
+    for s in Stat::ALL {
+        let value = stat(s, s.base(pokemon), ivs.get(s), evs.get(s), level, nature);
+        stats.set(s, value);
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::find_by_id;
+
+    #[test]
+    fn natures() {
+        let adamant = Nature::from_name("adamant").unwrap();
+        assert_eq!(adamant.increased, Some(Attack));
+        assert_eq!(adamant.decreased, Some(SpAttack));
+        assert_eq!(Nature::from_name("Serious").unwrap().increased, None);
+        assert!(Nature::from_name("grumpy").is_none());
+    }
+
+    #[test]
+    fn flavors() {
+        let adamant = Nature::from_name("adamant").unwrap();
+        assert_eq!(adamant.liked_flavor(), Some("Spicy"));
+        assert_eq!(adamant.disliked_flavor(), Some("Dry"));
+        let timid = Nature::from_name("timid").unwrap();
+        assert_eq!(timid.liked_flavor(), Some("Sweet"));
+        assert_eq!(timid.disliked_flavor(), Some("Spicy"));
+        assert_eq!(Nature::from_name("hardy").unwrap().liked_flavor(), None);
+    }
+
+    #[test]
+    fn stat_names() {
+        assert_eq!(Stat::from_name("sp-attack"), Some(SpAttack));
+        assert_eq!(Stat::from_name("SpD"), Some(SpDefense));
+        assert_eq!(Stat::from_name("sp_defense"), Some(SpDefense));
+        assert_eq!(Stat::from_name("spe"), Some(Speed));
+        assert!(Stat::from_name("total").is_none());
+    }
+
+    #[test]
+    fn garchomp() {
+        // The worked example from Bulbapedia's stat article
+        let garchomp = find_by_id("garchomp").unwrap();
+        let ivs = Stats([24, 12, 30, 16, 23, 5]);
+        let evs = Stats([74, 190, 91, 48, 84, 23]);
+        let adamant = Nature::from_name("adamant").unwrap();
+        assert_eq!(
+            stats(&garchomp, &ivs, &evs, 78, &adamant),
+            Stats([289, 278, 193, 135, 171, 171])
+        );
+    }
+
+    #[test]
+    fn competitive_garchomp() {
+        // A common Jolly set, as shown in the games' summary screen
+        let garchomp = find_by_id("garchomp").unwrap();
+        let evs = Stats::parse("0/252/0/0/4/252", MAX_STAT_EVS).unwrap();
+        let jolly = Nature::from_name("jolly").unwrap();
+        assert_eq!(
+            stats(&garchomp, &Stats::uniform(MAX_IV), &evs, 100, &jolly),
+            Stats([357, 359, 226, 176, 207, 333])
+        );
+        assert_eq!(
+            stats(&garchomp, &Stats::uniform(MAX_IV), &evs, 50, &jolly),
+            Stats([183, 182, 115, 90, 106, 169])
+        );
+    }
+
+    #[test]
+    fn parses_spreads() {
+        assert_eq!(Stats::parse("31", MAX_IV), Ok(Stats::uniform(31)));
+        assert_eq!(
+            Stats::parse("0/252/0/0/4/252", MAX_STAT_EVS),
+            Ok(Stats([0, 252, 0, 0, 4, 252]))
+        );
+        assert!(Stats::parse("32", MAX_IV).is_err());
+        assert!(Stats::parse("252/252", MAX_STAT_EVS).is_err());
+        assert!(Stats::parse("0/x/0/0/0/0", MAX_STAT_EVS).is_err());
+    }
+
+    #[test]
+    fn validates_values() {
+        assert_eq!(Stats::from_values(&[31u16], MAX_IV), Ok(Stats::uniform(31)));
+        assert_eq!(
+            Stats::from_values(&[4u32, 252, 0, 0, 0, 252], MAX_STAT_EVS),
+            Ok(Stats([4, 252, 0, 0, 0, 252]))
+        );
+        assert_eq!(
+            Stats::from_values(&[70_000u32], MAX_IV),
+            Err(String::from("\"70000\" isn't a number from 0 to 31"))
+        );
+        assert!(Stats::from_values(&[31u16, 31], MAX_IV).is_err());
+    }
+
+    #[test]
+    fn explains_each_step() {
+        let jolly = Nature::from_name("jolly").unwrap();
+        let (speed, steps) = explain_stat(Stat::Speed, 102, 31, 252, 100, &jolly);
+        assert_eq!(speed, 333);
+        let values = steps
+            .iter()
+            .map(|step| step.value.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(values, ["298", "303", "333"]);
+
+        let (hp, steps) = explain_stat(Stat::Hp, 108, 31, 0, 100, &jolly);
+        assert_eq!((hp, steps.len()), (357, 2));
+    }
+
+    #[test]
+    fn shedinja() {
+        let serious = Nature::from_name("serious").unwrap();
+        assert_eq!(stat(Stat::Hp, 1, 31, 252, 100, &serious), 1);
+    }
+
+    #[test]
+    fn highest_base_stats() {
+        // (2 × 250 + 31 + 63 + 5) × 110 overflows u16
+        let eternamax = find_by_id("eternatus-eternamax").unwrap();
+        let evs = Stats::parse("0/0/252/0/0/0", MAX_STAT_EVS).unwrap();
+        let bold = Nature::from_name("bold").unwrap();
+        assert_eq!(
+            stats(&eternamax, &Stats::uniform(MAX_IV), &evs, 100, &bold),
+            Stats([651, 239, 658, 286, 536, 296])
+        );
+
+        let blissey = find_by_id("blissey").unwrap();
+        let hp = stat(Stat::Hp, blissey.hp, MAX_IV, MAX_STAT_EVS, 100, &bold);
+        assert_eq!(hp, 714);
+    }
+}
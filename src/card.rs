@@ -0,0 +1,305 @@
+//! Card layout drawn onto a [`Canvas`], so each image format only has to
+//! implement a handful of drawing primitives.
+
+use std::fmt::Write;
+
+use serde::Deserialize;
+
+use crate::calc::stats::MAX_BASE_STAT;
+use crate::pokedex::Pokemon;
+use crate::types::Type;
+
+pub const WIDTH: f32 = 480.0;
+pub const HEIGHT: f32 = 300.0;
+
+/// Unfilled part of the stat bars, translucent to suit any background.
+const BAR_TRACK: &str = "#8882";
+
+#[derive(Clone, Copy)]
+pub enum Anchor {
+    Start,
+    Middle,
+    End,
+}
+
+/// Drawing primitives the card is made of. Coordinates are in pixels from
+/// the top left; text is positioned by its baseline.
+pub trait Canvas {
+    fn rect(&mut self, x: f32, y: f32, width: f32, height: f32, radius: f32, fill: &str);
+    fn text(&mut self, x: f32, y: f32, size: f32, fill: &str, anchor: Anchor, text: &str);
+    fn image(&mut self, x: f32, y: f32, width: f32, height: f32, href: &str);
+}
+
+/// Colors and font of exported cards, from the `[theme]` table of the config
+/// or flags.
+///
+/// ```toml
+/// [theme]
+/// background = "#101014"
+/// accent = "#9146FF"
+/// font = "Inter, sans-serif"
+/// ```
+#[derive(Clone, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub background: String,
+    /// Leaves the background out entirely, e.g. for stream overlays.
+    pub transparent: bool,
+    /// Color of the top strip and stat bars. `None` uses the color of the
+    /// Pokémon's first type.
+    pub accent: Option<String>,
+    /// CSS font family list.
+    pub font: String,
+    pub text: String,
+    pub muted_text: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            background: String::from("#FAFAFA"),
+            transparent: false,
+            accent: None,
+            font: String::from("Helvetica, Arial, sans-serif"),
+            text: String::from("#222222"),
+            muted_text: String::from("#666666"),
+        }
+    }
+}
+
+/// The games' usual color for each type.
+fn type_color(pokemon_type: Type) -> &'static str {
+    match pokemon_type {
+        Type::Normal => "#A8A77A",
+        Type::Fire => "#EE8130",
+        Type::Water => "#6390F0",
+        Type::Electric => "#F7D02C",
+        Type::Grass => "#7AC74C",
+        Type::Ice => "#96D9D6",
+        Type::Fighting => "#C22E28",
+        Type::Poison => "#A33EA1",
+        Type::Ground => "#E2BF65",
+        Type::Flying => "#A98FF3",
+        Type::Psychic => "#F95587",
+        Type::Bug => "#A6B91A",
+        Type::Rock => "#B6A136",
+        Type::Ghost => "#735797",
+        Type::Dragon => "#6F35FC",
+        Type::Dark => "#705746",
+        Type::Steel => "#B7B7CE",
+        Type::Fairy => "#D685AD",
+    }
+}
+
+/// Lays out a Pokémon's card: name and number, type badges, sprite and base
+/// stat bars.
+pub fn draw_card(pokemon: &Pokemon, theme: &Theme, canvas: &mut dyn Canvas) {
+    let types = pokemon.types();
+    let accent = match &theme.accent {
+        Some(accent) => accent.as_str(),
+        None => types.first().map_or("#888888", |t| type_color(*t)),
+    };
+    let text = theme.text.as_str();
+    let muted_text = theme.muted_text.as_str();
+
+    if !theme.transparent {
+        canvas.rect(0.0, 0.0, WIDTH, HEIGHT, 16.0, &theme.background);
+    }
+    canvas.rect(0.0, 0.0, WIDTH, 8.0, 0.0, accent);
+
+    canvas.text(24.0, 48.0, 28.0, text, Anchor::Start, &pokemon.name);
+    canvas.text(
+        WIDTH - 24.0,
+        48.0,
+        18.0,
+        muted_text,
+        Anchor::End,
+        &format!("#{:03}", pokemon.pokedex_number),
+    );
+    canvas.text(
+        24.0,
+        72.0,
+        14.0,
+        muted_text,
+        Anchor::Start,
+        &pokemon.species,
+    );
+
+    for (i, pokemon_type) in types.iter().enumerate() {
+        let x = 24.0 + i as f32 * 88.0;
+        canvas.rect(x, 84.0, 80.0, 22.0, 11.0, type_color(*pokemon_type));
+        canvas.text(
+            x + 40.0,
+            100.0,
+            12.0,
+            "#FFFFFF",
+            Anchor::Middle,
+            &pokemon_type.name().to_uppercase(),
+        );
+    }
+
+    canvas.image(24.0, 120.0, 160.0, 160.0, &pokemon.sprite_url());
+
+    let stats = [
+        ("HP", pokemon.hp),
+        ("Atk", pokemon.attack),
+        ("Def", pokemon.defense),
+        ("SpA", pokemon.sp_attack),
+        ("SpD", pokemon.sp_defense),
+        ("Spe", pokemon.speed),
+    ];
+    let bar_x = 264.0;
+    let bar_width = WIDTH - bar_x - 24.0;
+    for (i, (label, value)) in stats.iter().enumerate() {
+        let y = 136.0 + i as f32 * 24.0;
+        canvas.text(212.0, y, 13.0, muted_text, Anchor::Start, label);
+        canvas.text(bar_x - 8.0, y, 13.0, text, Anchor::End, &value.to_string());
+        canvas.rect(bar_x, y - 10.0, bar_width, 10.0, 5.0, BAR_TRACK);
+        let filled = bar_width * (f32::from(*value) / f32::from(MAX_BASE_STAT)).min(1.0);
+        canvas.rect(bar_x, y - 10.0, filled, 10.0, 5.0, accent);
+    }
+    canvas.text(
+        WIDTH - 24.0,
+        136.0 + 6.0 * 24.0,
+        13.0,
+        text,
+        Anchor::End,
+        &format!("Total {}", pokemon.total_points),
+    );
+}
+
+pub fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Collects SVG elements for a standalone document.
+#[derive(Default)]
+pub struct SvgCanvas {
+    elements: String,
+}
+
+impl SvgCanvas {
+    pub fn finish(self, width: f32, height: f32, font: &str) -> String {
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"{}\">\n{}</svg>",
+            escape_xml(font),
+            self.elements,
+            w = width,
+            h = height
+        )
+    }
+}
+
+impl Canvas for SvgCanvas {
+    fn rect(&mut self, x: f32, y: f32, width: f32, height: f32, radius: f32, fill: &str) {
+        // Writing to a String can't fail
+        let _ = writeln!(
+            self.elements,
+            "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\" fill=\"{}\"/>",
+            x,
+            y,
+            width,
+            height,
+            radius,
+            escape_xml(fill)
+        );
+    }
+
+    fn text(&mut self, x: f32, y: f32, size: f32, fill: &str, anchor: Anchor, text: &str) {
+        let anchor = match anchor {
+            Anchor::Start => "start",
+            Anchor::Middle => "middle",
+            Anchor::End => "end",
+        };
+        let _ = writeln!(
+            self.elements,
+            "  <text x=\"{}\" y=\"{}\" font-size=\"{}\" fill=\"{}\" text-anchor=\"{}\">{}</text>",
+            x,
+            y,
+            size,
+            escape_xml(fill),
+            anchor,
+            escape_xml(text)
+        );
+    }
+
+    fn image(&mut self, x: f32, y: f32, width: f32, height: f32, href: &str) {
+        let _ = writeln!(
+            self.elements,
+            "  <image x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" href=\"{}\"/>",
+            x,
+            y,
+            width,
+            height,
+            escape_xml(href)
+        );
+    }
+}
+
+/// The card as a standalone SVG document, linking to the sprite.
+pub fn render_svg(pokemon: &Pokemon, theme: &Theme) -> String {
+    let mut canvas = SvgCanvas::default();
+    draw_card(pokemon, theme, &mut canvas);
+    canvas.finish(WIDTH, HEIGHT, &theme.font)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::find_by_id;
+
+    #[test]
+    fn svg() {
+        let svg = render_svg(&find_by_id("charizard").unwrap(), &Theme::default());
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>"));
+        assert!(svg.contains(">Charizard</text>"));
+        assert!(svg.contains(">FLYING</text>"));
+        assert!(svg.contains("href=\"https://"));
+        assert_eq!(svg.matches("<text").count(), 3 + 2 + 12 + 1);
+    }
+
+    #[test]
+    fn themed() {
+        let theme = Theme {
+            transparent: true,
+            accent: Some(String::from("#9146FF")),
+            font: String::from("Inter"),
+            ..Theme::default()
+        };
+        let svg = render_svg(&find_by_id("charizard").unwrap(), &theme);
+        assert!(svg.contains("font-family=\"Inter\""));
+        assert!(!svg.contains(&Theme::default().background));
+        assert_eq!(svg.matches("fill=\"#9146FF\"").count(), 1 + 6);
+    }
+
+    #[test]
+    fn escapes_text() {
+        let mut canvas = SvgCanvas::default();
+        canvas.text(
+            0.0,
+            0.0,
+            10.0,
+            "#000",
+            Anchor::Start,
+            "Farfetch'd & <Sirfetch'd>",
+        );
+        assert!(canvas
+            .finish(1.0, 1.0, "serif")
+            .contains(">Farfetch'd &amp; &lt;Sirfetch'd&gt;</text>"));
+    }
+
+    #[test]
+    fn escapes_fills() {
+        // Theme colors come from the user's config
+        let mut canvas = SvgCanvas::default();
+        canvas.rect(0.0, 0.0, 1.0, 1.0, 0.0, "red\" onload=\"x");
+        canvas.text(0.0, 0.0, 10.0, "<red>", Anchor::Start, "");
+        let svg = canvas.finish(1.0, 1.0, "serif");
+        assert!(svg.contains("fill=\"red&quot; onload=&quot;x\""));
+        assert!(svg.contains("fill=\"&lt;red&gt;\""));
+    }
+}
@@ -0,0 +1,249 @@
+//! Weekly challenge runs, e.g. "Mono-Bug Emerald run, no items in battle",
+//! picked from a seed and the week so everyone using the same seed gets the
+//! same challenge.
+//!
+//! Themes are only offered for games with enough Pokémon to build a team
+//! from. A game is taken to have every Pokémon introduced by its generation,
+//! like `pkmn mono --game` does, so regional Pokédexes aren't accounted for.
+
+use chrono::{Datelike, Duration, NaiveDate};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::Serialize;
+
+use crate::games::{self, Game};
+use crate::pokedex::{self, Pokemon, PokemonStatus};
+use crate::provenance::fingerprint;
+use crate::types::Type;
+
+/// Fewest Pokémon a theme must allow in a game, so a full team can be built.
+pub const MIN_POOL: usize = 6;
+
+/// Base stat total caps for low-stat runs.
+const TOTAL_CAPS: [u16; 3] = [400, 450, 500];
+
+/// Rules added on top of the theme, as they read after it in the title.
+const RULES: [&str; 6] = [
+    "no items in battle",
+    "set battle style",
+    "only the first encounter on each route",
+    "fainted Pokémon can't be used again",
+    "no more than four Pokémon in the party",
+    "no trading",
+];
+
+/// Games left out because their Pokédex is much smaller than their
+/// generation's, e.g. Let's Go only has Kanto Pokémon.
+const EXCLUDED_GAMES: [&str; 1] = ["Let's Go"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Theme {
+    /// Only Pokémon of one type.
+    Monotype { pokemon_type: Type },
+    /// Only Pokémon with a base stat total of at most `cap`.
+    TotalCap { cap: u16 },
+}
+
+impl Theme {
+    fn all() -> Vec<Theme> {
+        Type::ALL
+            .iter()
+            .map(|pokemon_type| Theme::Monotype {
+                pokemon_type: *pokemon_type,
+            })
+            .chain(TOTAL_CAPS.iter().map(|cap| Theme::TotalCap { cap: *cap }))
+            .collect()
+    }
+
+    /// Short name as used in titles, e.g. `Mono-Bug`.
+    pub fn name(&self) -> String {
+        match self {
+            Theme::Monotype { pokemon_type } => format!("Mono-{}", pokemon_type),
+            Theme::TotalCap { cap } => format!("{} BST", cap),
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            Theme::Monotype { pokemon_type } => {
+                format!(
+                    "Only {} type Pokémon, which may have a second type",
+                    pokemon_type
+                )
+            }
+            Theme::TotalCap { cap } => {
+                format!("Only Pokémon with a base stat total of {} or less", cap)
+            }
+        }
+    }
+
+    fn allows(&self, pokemon: &Pokemon, generation: u8) -> bool {
+        match self {
+            Theme::Monotype { pokemon_type } => pokemon.types_in(generation).contains(pokemon_type),
+            Theme::TotalCap { cap } => pokemon.total_points <= *cap,
+        }
+    }
+
+    /// Species the theme allows in `game`, leaving out Legendary and Mythical
+    /// Pokémon, in Pokédex order. Types are the ones the species had in the
+    /// game's generation. Empty if the theme's type isn't in the game.
+    pub fn pool(&self, game: &Game) -> Vec<&'static Pokemon> {
+        if let Theme::Monotype { pokemon_type } = self {
+            if pokemon_type.introduced_in() > game.generation {
+                return Vec::new();
+            }
+        }
+
+        let mut species = pokedex::entries()
+            .unwrap_or_default()
+            .iter()
+            .collect::<Vec<&Pokemon>>();
+        species.dedup_by_key(|pokemon| pokemon.pokedex_number);
+        species
+            .into_iter()
+            .filter(|pokemon| pokemon.generation <= game.generation)
+            .filter(|pokemon| matches!(pokemon.status, PokemonStatus::Normal))
+            .filter(|pokemon| self.allows(pokemon, game.generation))
+            .collect()
+    }
+}
+
+#[derive(Serialize)]
+pub struct Challenge {
+    /// Monday of the challenge's week.
+    pub week_of: NaiveDate,
+    pub game: String,
+    pub theme: Theme,
+    pub rules: Vec<String>,
+    /// Species the theme allows in the game.
+    pub pool: Vec<String>,
+}
+
+impl Challenge {
+    /// The challenge for the week `date` falls in. The same `seed` and week
+    /// always give the same challenge.
+    pub fn for_week(seed: &str, date: NaiveDate) -> Self {
+        let week_of = date - Duration::days(i64::from(date.weekday().num_days_from_monday()));
+        let week = date.iso_week();
+        let key = format!("{}:{}-W{:02}", seed, week.year(), week.week());
+        let mut rng = StdRng::seed_from_u64(
+            u64::from_str_radix(&fingerprint(key.as_bytes()), 16).expect("fingerprints are hex"),
+        );
+
+        // Every combination that can be completed, so the pick always is
+        let games = games::all_games()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|game| !EXCLUDED_GAMES.iter().any(|name| game.name.contains(name)))
+            .collect::<Vec<Game>>();
+        let candidates = games
+            .iter()
+            .flat_map(|game| Theme::all().into_iter().map(move |theme| (game, theme)))
+            .map(|(game, theme)| (game, theme, theme.pool(game)))
+            .filter(|(_, _, pool)| pool.len() >= MIN_POOL)
+            .collect::<Vec<_>>();
+        let (game, theme, pool) = candidates
+            .choose(&mut rng)
+            .expect("some themes always have a full team");
+
+        let rules = RULES
+            .choose_multiple(&mut rng, 2)
+            .map(|rule| rule.to_string())
+            .collect();
+
+        Challenge {
+            week_of,
+            game: game.name.clone(),
+            theme: *theme,
+            rules,
+            pool: pool.iter().map(|pokemon| pokemon.name.clone()).collect(),
+        }
+    }
+
+    /// e.g. `Mono-Bug Emerald run, no items in battle`.
+    pub fn title(&self) -> String {
+        let game = self.game.trim_start_matches("Pokémon ");
+        match self.rules.first() {
+            Some(rule) => format!("{} {} run, {}", self.theme.name(), game, rule),
+            None => format!("{} {} run", self.theme.name(), game),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+
+    #[test]
+    fn same_week_same_challenge() {
+        let monday = Challenge::for_week("pkmn", date(5, 6));
+        let sunday = Challenge::for_week("pkmn", date(5, 12));
+        assert_eq!(monday.week_of, date(5, 6));
+        assert_eq!(sunday.week_of, date(5, 6));
+        assert_eq!(monday.title(), sunday.title());
+        assert_eq!(monday.rules, sunday.rules);
+    }
+
+    #[test]
+    fn challenges_can_be_completed() {
+        for week in 0..8 {
+            let challenge = Challenge::for_week("pkmn", date(1, 1) + Duration::weeks(week));
+            assert!(challenge.pool.len() >= MIN_POOL, "{}", challenge.title());
+            assert_eq!(challenge.rules.len(), 2);
+            assert_ne!(challenge.rules[0], challenge.rules[1]);
+            assert!(!challenge.game.contains("Let's Go"));
+        }
+    }
+
+    #[test]
+    fn pools_respect_the_game() {
+        let emerald = games::find_game("emerald").unwrap();
+        let fairy = Theme::Monotype {
+            pokemon_type: Type::Fairy,
+        };
+        assert!(fairy.pool(&emerald).is_empty());
+
+        let bugs = Theme::Monotype {
+            pokemon_type: Type::Bug,
+        }
+        .pool(&emerald);
+        assert!(bugs.iter().any(|pokemon| pokemon.name == "Beautifly"));
+        assert!(bugs.iter().all(|pokemon| pokemon.generation <= 3));
+        assert!(!bugs.iter().any(|pokemon| pokemon.name.starts_with("Mega ")));
+    }
+
+    #[test]
+    fn pools_use_the_typing_of_the_generation() {
+        let normal = Theme::Monotype {
+            pokemon_type: Type::Normal,
+        };
+        let steel = Theme::Monotype {
+            pokemon_type: Type::Steel,
+        };
+        let names = |pool: Vec<&Pokemon>| {
+            pool.iter()
+                .map(|pokemon| pokemon.name.to_string())
+                .collect::<Vec<String>>()
+        };
+
+        let emerald = games::find_game("emerald").unwrap();
+        assert!(names(normal.pool(&emerald)).contains(&"Clefairy".to_string()));
+        assert!(!names(steel.pool(&emerald)).contains(&"Mega Mawile".to_string()));
+        let x = games::find_game("x & y").unwrap();
+        assert!(!names(normal.pool(&x)).contains(&"Clefairy".to_string()));
+
+        let gold = games::find_game("gold").unwrap();
+        assert!(names(steel.pool(&gold)).contains(&"Magnemite".to_string()));
+        let electric = Theme::Monotype {
+            pokemon_type: Type::Electric,
+        };
+        let red = games::find_game("red").unwrap();
+        assert!(names(electric.pool(&red)).contains(&"Magnemite".to_string()));
+    }
+}
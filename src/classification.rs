@@ -0,0 +1,100 @@
+use serde::Deserialize;
+
+use crate::error::{self, PkmnError};
+use crate::pokedex::Pokemon;
+
+/// The Pokédex's color categories, used e.g. by the Pokédex search.
+pub const COLORS: [&str; 10] = [
+    "black", "blue", "brown", "gray", "green", "pink", "purple", "red", "white", "yellow",
+];
+
+/// The Pokédex's body shapes, named after their Bulbapedia descriptions.
+pub const SHAPES: [&str; 14] = [
+    "head",
+    "serpentine",
+    "fins",
+    "head-arms",
+    "head-base",
+    "bipedal-tail",
+    "head-legs",
+    "quadruped",
+    "wings",
+    "tentacles",
+    "multiple",
+    "bipedal",
+    "multi-wings",
+    "insectoid",
+];
+
+/// Habitats from the FireRed and LeafGreen Pokédex.
+pub const HABITATS: [&str; 9] = [
+    "cave",
+    "forest",
+    "grassland",
+    "mountain",
+    "rare",
+    "rough-terrain",
+    "sea",
+    "urban",
+    "waters-edge",
+];
+
+/// A species' official color, body shape and habitat. The bundled data only covers
+/// Gen 1 species, and not all of those have a shape recorded.
+#[derive(Deserialize)]
+pub struct Classification {
+    pub pokedex_number: u16,
+    pub color: String,
+    pub shape: Option<String>,
+    pub habitat: Option<String>,
+}
+
+pub static CLASSIFICATIONS_CSV: &[u8] = include_bytes!("../data/classifications.csv");
+
+/// Every bundled classification.
+pub fn all_classifications() -> Result<Vec<Classification>, PkmnError> {
+    error::read_csv("classifications.csv", CLASSIFICATIONS_CSV)
+}
+
+/// The classification of the Pokémon's species, shared by all its forms.
+pub fn classification_of(pokemon: &Pokemon) -> Option<Classification> {
+    all_classifications()
+        .unwrap_or_default()
+        .into_iter()
+        .find(|classification| classification.pokedex_number == pokemon.pokedex_number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::find_by_id;
+
+    #[test]
+    fn known_values() {
+        for classification in all_classifications().unwrap() {
+            assert!(COLORS.contains(&classification.color.as_str()));
+            if let Some(shape) = &classification.shape {
+                assert!(SHAPES.contains(&shape.as_str()), "unknown shape {}", shape);
+            }
+            if let Some(habitat) = &classification.habitat {
+                assert!(
+                    HABITATS.contains(&habitat.as_str()),
+                    "unknown habitat {}",
+                    habitat
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn species() {
+        let pikachu = classification_of(&find_by_id("pikachu").unwrap()).unwrap();
+        assert_eq!(pikachu.color, "yellow");
+
+        let ekans = classification_of(&find_by_id("ekans").unwrap()).unwrap();
+        assert_eq!(ekans.shape.as_deref(), Some("serpentine"));
+
+        let zubat = classification_of(&find_by_id("zubat").unwrap()).unwrap();
+        assert_eq!(zubat.habitat.as_deref(), Some("cave"));
+    }
+}
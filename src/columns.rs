@@ -0,0 +1,195 @@
+//! The Pokédex's numeric fields stored column by column, each sorted, so
+//! percentiles and other aggregates over every form are a binary search or
+//! a slice scan instead of a pass over whole entries. Stats are kept in
+//! Pokédex order too, so a condition on one stat is checked for every entry
+//! without reading the rest.
+
+use std::sync::OnceLock;
+
+use crate::calc::stats::Stat;
+use crate::pokedex::{self, Pokemon};
+
+/// Sorted columns of base stats, totals, heights and weights, plus the
+/// stats and totals in entry order.
+#[derive(Clone, Debug, Default)]
+pub struct Columns {
+    stats: [Vec<u16>; 6],
+    totals: Vec<u16>,
+    entry_stats: [Vec<u16>; 6],
+    entry_totals: Vec<u16>,
+    heights: Vec<f32>,
+    weights: Vec<f32>,
+}
+
+/// Aggregates of one column.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Summary {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub median: f64,
+}
+
+fn sorted<T: Ord>(values: impl Iterator<Item = T>) -> Vec<T> {
+    let mut values = values.collect::<Vec<T>>();
+    values.sort_unstable();
+    values
+}
+
+/// Like [`sorted`], ordering floats with [`f32::total_cmp`] so a NaN in the
+/// data can't panic the sort.
+fn sorted_floats(values: impl Iterator<Item = f32>) -> Vec<f32> {
+    let mut values = values.collect::<Vec<f32>>();
+    values.sort_unstable_by(f32::total_cmp);
+    values
+}
+
+/// Share of `values` (sorted) strictly less than `value`, as a percentage.
+pub fn percentile<T: PartialOrd>(values: &[T], value: T) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let below = values.partition_point(|other| *other < value);
+    below as f64 * 100.0 / values.len() as f64
+}
+
+/// Min, max, mean and median of `values` (sorted), if there are any.
+pub fn summary<T: Copy + Into<f64>>(values: &[T]) -> Option<Summary> {
+    let (first, last) = (values.first()?, values.last()?);
+    let middle = values.len() / 2;
+    let median = match values.len() % 2 {
+        0 => (values[middle - 1].into() + values[middle].into()) / 2.0,
+        _ => values[middle].into(),
+    };
+    Some(Summary {
+        min: (*first).into(),
+        max: (*last).into(),
+        mean: values.iter().map(|value| (*value).into()).sum::<f64>() / values.len() as f64,
+        median,
+    })
+}
+
+impl Columns {
+    pub fn new(pokemon: &[Pokemon]) -> Self {
+        let entry_stats = Stat::ALL.map(|stat| {
+            pokemon
+                .iter()
+                .map(|pokemon| stat.base(pokemon))
+                .collect::<Vec<u16>>()
+        });
+        let entry_totals = pokemon
+            .iter()
+            .map(|pokemon| pokemon.total_points)
+            .collect::<Vec<u16>>();
+        Columns {
+            stats: entry_stats.clone().map(|column| sorted(column.into_iter())),
+            totals: sorted(entry_totals.iter().copied()),
+            entry_stats,
+            entry_totals,
+            heights: sorted_floats(pokemon.iter().filter_map(|pokemon| pokemon.height_m)),
+            weights: sorted_floats(pokemon.iter().filter_map(|pokemon| pokemon.weight_kg)),
+        }
+    }
+
+    /// Columns of [`pokedex::entries`], built on first use.
+    pub fn pokedex() -> &'static Columns {
+        static COLUMNS: OnceLock<Columns> = OnceLock::new();
+        COLUMNS.get_or_init(|| Columns::new(pokedex::entries().unwrap_or_default()))
+    }
+
+    /// Every base value of `stat`, lowest first.
+    pub fn stat(&self, stat: Stat) -> &[u16] {
+        &self.stats[Stat::ALL.iter().position(|other| *other == stat).unwrap()]
+    }
+
+    /// Every base value of `stat`, in the order of the entries the columns
+    /// were built from.
+    pub fn stat_by_entry(&self, stat: Stat) -> &[u16] {
+        &self.entry_stats[Stat::ALL.iter().position(|other| *other == stat).unwrap()]
+    }
+
+    /// Every base stat total, in the order of the entries the columns were
+    /// built from.
+    pub fn totals_by_entry(&self) -> &[u16] {
+        &self.entry_totals
+    }
+
+    /// Every base stat total, lowest first.
+    pub fn totals(&self) -> &[u16] {
+        &self.totals
+    }
+
+    /// Every known height in meters, shortest first.
+    pub fn heights(&self) -> &[f32] {
+        &self.heights
+    }
+
+    /// Every known weight in kilograms, lightest first.
+    pub fn weights(&self) -> &[f32] {
+        &self.weights
+    }
+
+    /// Percentage of Pokémon with a lower base `stat` than `pokemon`.
+    pub fn stat_percentile(&self, stat: Stat, pokemon: &Pokemon) -> f64 {
+        percentile(self.stat(stat), stat.base(pokemon))
+    }
+
+    /// Percentage of Pokémon with a lower base stat total than `pokemon`.
+    pub fn total_percentile(&self, pokemon: &Pokemon) -> f64 {
+        percentile(&self.totals, pokemon.total_points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::find_by_id;
+
+    #[test]
+    fn percentile_bounds() {
+        let values = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(percentile(&values, 1.0), 0.0);
+        assert_eq!(percentile(&values, 3.0), 50.0);
+        assert_eq!(percentile(&values, 5.0), 100.0);
+        assert_eq!(percentile::<u16>(&[], 1), 0.0);
+    }
+
+    #[test]
+    fn sorts_nan_without_panicking() {
+        let values = sorted_floats([2.0, f32::NAN, 1.0].into_iter());
+        assert_eq!(&values[..2], [1.0, 2.0]);
+        assert!(values[2].is_nan());
+    }
+
+    #[test]
+    fn summaries() {
+        let summary = summary(&[1u16, 2, 3, 10]).unwrap();
+        assert_eq!((summary.min, summary.max), (1.0, 10.0));
+        assert_eq!((summary.mean, summary.median), (4.0, 2.5));
+        assert_eq!(super::summary::<u16>(&[]), None);
+    }
+
+    #[test]
+    fn pokedex_columns() {
+        let columns = Columns::pokedex();
+        assert_eq!(columns.totals().len(), pokedex::entries().unwrap().len());
+        assert!(columns
+            .stat(Stat::Hp)
+            .windows(2)
+            .all(|pair| pair[0] <= pair[1]));
+
+        let blissey = find_by_id("blissey").unwrap();
+        assert!(columns.stat_percentile(Stat::Hp, &blissey) > 99.0);
+        assert!(columns.stat_percentile(Stat::Defense, &blissey) < 5.0);
+        let arceus = find_by_id("arceus").unwrap();
+        assert!(columns.total_percentile(&arceus) > 95.0);
+
+        let entries = pokedex::entries().unwrap();
+        let blissey = entries.iter().position(|p| p.name == "Blissey").unwrap();
+        assert_eq!(columns.stat_by_entry(Stat::Hp)[blissey], 255);
+        assert_eq!(
+            columns.totals_by_entry()[blissey],
+            entries[blissey].total_points
+        );
+    }
+}
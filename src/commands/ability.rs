@@ -0,0 +1,71 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::abilities::{self, Ability};
+use crate::fuzzy;
+use crate::pokedex::{self, Pokemon};
+use crate::print::Printer;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("ability")
+        .about("Describes an ability and lists the Pokémon that can have it")
+        .arg(
+            Arg::with_name("ability")
+                .value_name("ABILITY")
+                .help("An ability's name, e.g. intimidate or swift-swim")
+                .required(true),
+        )
+}
+
+/// Prints the ability's description and its holders, hidden ones marked.
+pub fn print_ability(ability: &Ability, pokedex: &[Pokemon], printer: &Printer) {
+    printer.print_section_heading(&ability.name);
+    printer.print_paragraph(&ability.description, |line| style(line).dim());
+    println!();
+
+    for (pokemon, hidden) in abilities::holders(pokedex, &ability.name) {
+        printer.print_info(
+            format!(
+                "{} {}",
+                style(format!("#{:03}", pokemon.pokedex_number)).dim(),
+                pokemon.name
+            ),
+            match hidden {
+                true => style("Hidden").dim(),
+                false => style("Regular").cyan(),
+            },
+        );
+    }
+}
+
+pub fn run(matches: &ArgMatches) {
+    match pokedex::entries() {
+        Ok(pokedex) => lookup(matches.value_of("ability").unwrap_or_default(), pokedex),
+        Err(err) => Printer::new().print_failure(&err.to_string()),
+    }
+}
+
+/// Prints the ability named `query` with its holders among `pokedex`, for
+/// `ability` and `pkmn --ability`.
+pub fn lookup(query: &str, pokedex: &[Pokemon]) {
+    let printer = Printer::new();
+
+    let ability = match abilities::find_ability(query) {
+        Some(ability) => ability,
+        None => {
+            let names = abilities::all_abilities()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|ability| ability.name);
+            return printer.print_failure(&match fuzzy::did_you_mean(names, query) {
+                Some(suggestion) => format!(
+                    "Unknown ability \"{}\", did you mean \"{}\"?",
+                    query, suggestion
+                ),
+                None => format!("Unknown ability \"{}\"", query),
+            });
+        }
+    };
+
+    print_ability(&ability, pokedex, &printer);
+}
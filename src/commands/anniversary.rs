@@ -0,0 +1,97 @@
+use chrono::{Local, NaiveDate};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::games::{self, Game};
+use crate::generation::GenerationSummary;
+use crate::print::Printer;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("anniversary")
+        .about("Reports Pokémon game release anniversaries for today")
+        .arg(
+            Arg::with_name("date")
+                .long("date")
+                .value_name("YYYY-MM-DD")
+                .help("Checks another date instead of today")
+                .takes_value(true),
+        )
+}
+
+fn print_game(game: &Game, date: NaiveDate, printer: &Printer) {
+    let years = game.age_on(date);
+
+    printer.print_section_heading(&game.name);
+    printer.print_info(
+        "Released",
+        format!(
+            "{} {}",
+            style(game.release_date).cyan(),
+            style(format!(
+                "({} year{} ago)",
+                years,
+                if years == 1 { "" } else { "s" }
+            ))
+            .dim()
+        ),
+    );
+    printer.print_info("Generation", style(game.generation).cyan());
+
+    if !game.introduced_generation {
+        return;
+    }
+
+    if let Some(summary) = GenerationSummary::new(game.generation) {
+        printer.print_info(
+            "Introduced",
+            format!(
+                "{} {}",
+                style(format!("{} Pokémon", summary.species)).yellow(),
+                style(format!(
+                    "(№ {}–{})",
+                    summary.first_number, summary.last_number
+                ))
+                .dim()
+            ),
+        );
+        for name in &summary.legendaries {
+            printer.print_info("", style(name).cyan());
+        }
+    }
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    let date = match matches.value_of("date") {
+        None => Local::now().naive_local().date(),
+        Some(value) => match value.parse::<NaiveDate>() {
+            Ok(date) => date,
+            Err(err) => return printer.print_failure(&format!("Invalid date: {}", err)),
+        },
+    };
+
+    let games = games::anniversaries_on(date);
+
+    if games.is_empty() {
+        printer.print_center(format!("No anniversaries on {}", date.format("%B %-d")));
+
+        if let Some((next, games)) = games::next_anniversaries(date) {
+            let names = games
+                .iter()
+                .map(|game| game.name.as_str())
+                .collect::<Vec<&str>>()
+                .join(", ");
+            printer
+                .print_center(style(format!("Next: {} on {}", names, next.format("%B %-d"))).dim());
+        }
+        return;
+    }
+
+    printer.print_center(style(format!("On {}", date.format("%B %-d"))).yellow());
+    for game in &games {
+        println!();
+        print_game(game, date, &printer);
+    }
+    println!();
+}
@@ -0,0 +1,97 @@
+use std::fs::{self, File};
+use std::io::BufWriter;
+
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::backup::{self, Roots};
+use crate::print::Printer;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("backup")
+        .about("Backs up or restores config, hunts, teams, learning profiles and caches")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("create")
+                .about("Writes everything pkmn keeps to a .tar.zst archive")
+                .arg(
+                    Arg::with_name("out")
+                        .value_name("FILE")
+                        .help("e.g. pkmn-backup.tar.zst")
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("no-sprites")
+                        .long("no-sprites")
+                        .help("Leaves out cached sprites, which can be downloaded again"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("restore")
+                .about("Restores a backup, replacing files of the same names")
+                .arg(Arg::with_name("backup").value_name("FILE").required(true))
+                .arg(
+                    Arg::with_name("force")
+                        .long("force")
+                        .help("Restores a backup made by a newer version of pkmn"),
+                ),
+        )
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+    let roots = match Roots::local() {
+        Some(roots) => roots,
+        None => return printer.print_failure("No config or cache directory on this system"),
+    };
+
+    match matches.subcommand() {
+        ("create", Some(matches)) => {
+            let out = matches.value_of("out").unwrap_or_default();
+            let sprites = !matches.is_present("no-sprites");
+            let result = File::create(out)
+                .map_err(|err| err.into())
+                .and_then(|file| backup::create(&roots, sprites, BufWriter::new(file)));
+            if let Err(err) = result {
+                return printer.print_failure(&format!("Couldn't back up to {}: {}", out, err));
+            }
+            let size = fs::metadata(out)
+                .map(|metadata| metadata.len())
+                .unwrap_or(0);
+            printer.print_info(
+                "Backup",
+                format!(
+                    "{} {}",
+                    style(out).cyan(),
+                    style(format!("({} KB)", size.div_ceil(1024))).dim()
+                ),
+            );
+            if !sprites {
+                printer.print_info("Sprites", style("Left out").dim());
+            }
+        }
+        ("restore", Some(matches)) => {
+            let path = matches.value_of("backup").unwrap_or_default();
+            let result = File::open(path)
+                .map_err(|err| err.into())
+                .and_then(|file| backup::restore(&roots, file, matches.is_present("force")));
+            let (manifest, files) = match result {
+                Ok(restored) => restored,
+                Err(err) => {
+                    return printer.print_failure(&format!("Couldn't restore {}: {}", path, err))
+                }
+            };
+            printer.print_info("Restored", style(format!("{} files", files)).cyan());
+            printer.print_info(
+                "Backed up",
+                style(format!(
+                    "{} by pkmn {}",
+                    manifest.created.format("%Y-%m-%d %H:%M"),
+                    manifest.pkmn_version
+                ))
+                .dim(),
+            );
+        }
+        _ => unreachable!("backup requires a subcommand"),
+    }
+}
@@ -0,0 +1,112 @@
+use std::time::SystemTime;
+
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::cache::{self, format_size, Limits};
+use crate::config::Config;
+use crate::print::Printer;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("cache")
+        .about("Manages the sprite and TCG card caches")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("gc")
+                .about("Removes unused cached files, least recently used first")
+                .arg(
+                    Arg::with_name("max-size")
+                        .long("max-size")
+                        .value_name("SIZE")
+                        .help("e.g. 200MB, defaults to max_size under [cache] in the config")
+                        .validator(|value| cache::parse_size(&value).map(|_| ())),
+                )
+                .arg(
+                    Arg::with_name("max-age")
+                        .long("max-age")
+                        .value_name("DAYS")
+                        .help(
+                            "Removes files unused for longer, defaults to max_unused_days under \
+                             [cache] in the config",
+                        )
+                        .validator(|value| match value.parse::<u64>() {
+                            Ok(days) => cache::days(days).map(|_| ()),
+                            Err(_) => Err(String::from("Expected a number of days")),
+                        }),
+                )
+                .arg(
+                    Arg::with_name("dry-run")
+                        .long("dry-run")
+                        .help("Lists what would be removed without removing it"),
+                ),
+        )
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+    let dir = match cache::cache_dir() {
+        Some(dir) => dir,
+        None => return printer.print_failure("No cache directory on this system"),
+    };
+
+    match matches.subcommand() {
+        ("gc", Some(matches)) => {
+            let configured = match Config::load().cache.limits() {
+                Ok(limits) => limits,
+                Err(err) => {
+                    warn!("ignoring the cache limits in the config: {}", err);
+                    Limits::default()
+                }
+            };
+            let limits = Limits {
+                max_size: matches
+                    .value_of("max-size")
+                    .and_then(|size| cache::parse_size(size).ok())
+                    .or(configured.max_size),
+                max_unused: matches
+                    .value_of("max-age")
+                    .and_then(|days| cache::days(days.parse().ok()?).ok())
+                    .or(configured.max_unused),
+            };
+            if limits.is_empty() {
+                return printer.print_failure(
+                    "No limits to collect by, give --max-size or --max-age, or set them under \
+                     [cache] in the config",
+                );
+            }
+
+            let dry_run = matches.is_present("dry-run");
+            let collection = match cache::collect(&dir, limits, SystemTime::now(), dry_run) {
+                Ok(collection) => collection,
+                Err(err) => {
+                    return printer.print_failure(&format!(
+                        "Couldn't collect {}: {}",
+                        dir.display(),
+                        err
+                    ))
+                }
+            };
+            let removed = match dry_run {
+                true => "Would remove",
+                false => "Removed",
+            };
+            printer.print_info(
+                removed,
+                format!(
+                    "{} {}",
+                    style(format!("{} files", collection.removed)).cyan(),
+                    style(format!("({})", format_size(collection.freed))).dim()
+                ),
+            );
+            printer.print_info(
+                "Cache",
+                format!(
+                    "{} {}",
+                    style(format!("{} files", collection.kept)).cyan(),
+                    style(format!("({})", format_size(collection.size))).dim()
+                ),
+            );
+        }
+        _ => unreachable!("cache requires a subcommand"),
+    }
+}
@@ -0,0 +1,371 @@
+use clap::{value_t, App, AppSettings, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::calc::accuracy::{self, AccuracyAbility};
+use crate::calc::retro::{self, Ball, Dvs, Status};
+use crate::calc::Step;
+use crate::moves::{self, MAX_PP_UPS};
+use crate::pokedex;
+use crate::print::Printer;
+
+fn validate_dv(value: String) -> Result<(), String> {
+    match value.parse::<u8>() {
+        Ok(dv) if dv <= 15 => Ok(()),
+        _ => Err(String::from("DVs range from 0 to 15")),
+    }
+}
+
+fn validate_pp_ups(value: String) -> Result<(), String> {
+    match value.parse::<u8>() {
+        Ok(ups) if ups <= MAX_PP_UPS => Ok(()),
+        _ => Err(format!("PP Ups range from 0 to {}", MAX_PP_UPS)),
+    }
+}
+
+fn validate_stage(value: String) -> Result<(), String> {
+    match value.parse::<i8>() {
+        Ok(stage) if (-6..=6).contains(&stage) => Ok(()),
+        _ => Err(String::from("Stages range from -6 to +6")),
+    }
+}
+
+fn stage_arg(name: &'static str) -> Arg<'static, 'static> {
+    Arg::with_name(name)
+        .long(name)
+        .takes_value(true)
+        .allow_hyphen_values(true)
+        .default_value("0")
+        .validator(validate_stage)
+}
+
+pub fn explain_arg() -> Arg<'static, 'static> {
+    Arg::with_name("explain")
+        .long("explain")
+        .help("Shows each term of the formula")
+}
+
+fn dv_arg(name: &'static str) -> Arg<'static, 'static> {
+    Arg::with_name(name)
+        .long(name)
+        .takes_value(true)
+        .required(true)
+        .validator(validate_dv)
+}
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("calc")
+        .about("Calculators for game mechanics")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("gen1-dv")
+                .about("Derived values of Gen 1–2 DVs: HP DV, shininess, Hidden Power and gender")
+                .arg(dv_arg("attack"))
+                .arg(dv_arg("defense"))
+                .arg(dv_arg("speed"))
+                .arg(dv_arg("special"))
+                .arg(
+                    Arg::with_name("pokemon")
+                        .long("pokemon")
+                        .value_name("Pokémon, for its Gen 2 gender")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("gen1-catch")
+                .about("Chance of catching a Pokémon in Gen 1, per ball")
+                .arg(
+                    Arg::with_name("pokemon")
+                        .long("pokemon")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("max-hp")
+                        .long("max-hp")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("current-hp")
+                        .long("current-hp")
+                        .value_name("Defaults to max HP")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("ball")
+                        .long("ball")
+                        .value_name("poke, great, ultra, safari or master; defaults to all")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("status")
+                        .long("status")
+                        .value_name("none, sleep, freeze, paralysis, burn or poison")
+                        .takes_value(true),
+                )
+                .arg(explain_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("hit")
+                .about("Chance of a move hitting, from Gen 5 onwards")
+                .arg(
+                    Arg::with_name("move")
+                        .long("move")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(stage_arg("accuracy-stage"))
+                .arg(stage_arg("evasion-stage"))
+                .arg(
+                    Arg::with_name("ability")
+                        .long("ability")
+                        .help("The attacker's ability, if it affects accuracy")
+                        .possible_values(&AccuracyAbility::NAMES)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("damage")
+                        .long("damage")
+                        .help("Damage per hit, e.g. from a damage calculator, for the expected damage per turn")
+                        .takes_value(true),
+                )
+                .arg(explain_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("pp")
+                .about("A move's PP after PP Ups")
+                .arg(Arg::with_name("move").value_name("MOVE").required(true))
+                .arg(
+                    Arg::with_name("ups")
+                        .long("ups")
+                        .value_name("Number of PP Ups, 0–3; defaults to all")
+                        .takes_value(true)
+                        .validator(validate_pp_ups),
+                ),
+        )
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    match matches.subcommand() {
+        ("gen1-dv", Some(matches)) => print_gen1_dv(matches, &printer),
+        ("gen1-catch", Some(matches)) => print_gen1_catch(matches, &printer),
+        ("hit", Some(matches)) => print_hit(matches, &printer),
+        ("pp", Some(matches)) => print_pp(matches, &printer),
+        _ => unreachable!("calc requires a subcommand"),
+    }
+}
+
+pub fn print_steps(steps: &[Step], printer: &Printer) {
+    for step in steps {
+        printer.print_info(
+            step.label,
+            format!(
+                "{}  {}",
+                style(&step.value).cyan(),
+                style(&step.formula).dim()
+            ),
+        );
+    }
+}
+
+fn print_gen1_dv(matches: &ArgMatches, printer: &Printer) {
+    let dvs = Dvs {
+        attack: value_t!(matches, "attack", u8).unwrap_or_else(|err| err.exit()),
+        defense: value_t!(matches, "defense", u8).unwrap_or_else(|err| err.exit()),
+        speed: value_t!(matches, "speed", u8).unwrap_or_else(|err| err.exit()),
+        special: value_t!(matches, "special", u8).unwrap_or_else(|err| err.exit()),
+    };
+
+    printer.print_section_heading("Gen 1–2 DVs");
+    printer.print_info("HP", style(dvs.hp()).cyan());
+    printer.print_info("Attack", style(dvs.attack).cyan());
+    printer.print_info("Defense", style(dvs.defense).cyan());
+    printer.print_info("Speed", style(dvs.speed).cyan());
+    printer.print_info("Special", style(dvs.special).cyan());
+    println!();
+
+    printer.print_section_heading("Gen 2");
+    printer.print_info(
+        "Shiny",
+        match dvs.is_shiny() {
+            true => style("Yes").yellow().bold(),
+            false => style("No").cyan(),
+        },
+    );
+    printer.print_info(
+        "Hidden Power",
+        style(format!(
+            "{} {}",
+            dvs.hidden_power_type(),
+            dvs.hidden_power_power()
+        ))
+        .magenta(),
+    );
+
+    if let Some(query) = matches.value_of("pokemon") {
+        if let Some(result) = pokedex::search_by_name(query, 1).first() {
+            let pokemon = &result.pokemon;
+            let gender = match dvs.is_female(pokemon.percentage_male) {
+                None => "Genderless",
+                Some(true) => "Female",
+                Some(false) => "Male",
+            };
+            printer.print_info(
+                "Gender",
+                format!(
+                    "{} {}",
+                    style(gender).cyan(),
+                    style(format!("({})", pokemon.name)).dim()
+                ),
+            );
+        }
+    }
+}
+
+fn print_gen1_catch(matches: &ArgMatches, printer: &Printer) {
+    let query = matches.value_of("pokemon").unwrap_or_default();
+    let pokemon = match pokedex::search_by_name(query, 1).into_iter().next() {
+        Some(result) => result.pokemon,
+        None => return printer.print_failure("Couldn't find any matches"),
+    };
+
+    let catch_rate = match pokemon.catch_rate {
+        Some(catch_rate) => catch_rate.min(255) as u8,
+        None => return printer.print_failure(&format!("{} has no known catch rate", pokemon.name)),
+    };
+
+    let max_hp = value_t!(matches, "max-hp", u16).unwrap_or_else(|err| err.exit());
+    let current_hp = match matches.value_of("current-hp") {
+        Some(_) => value_t!(matches, "current-hp", u16).unwrap_or_else(|err| err.exit()),
+        None => max_hp,
+    };
+
+    let status = match matches.value_of("status").map(Status::from_name) {
+        None => Status::None,
+        Some(Some(status)) => status,
+        Some(None) => return printer.print_failure("Unknown status condition"),
+    };
+
+    let balls = match matches.value_of("ball").map(Ball::from_name) {
+        None => Ball::ALL.to_vec(),
+        Some(Some(ball)) => vec![ball],
+        Some(None) => return printer.print_failure("Unknown ball"),
+    };
+
+    printer.print_section_heading("Gen 1 Catch Chance");
+    printer.print_info("Pokémon", style(&pokemon.name).yellow());
+    printer.print_info("Catch Rate", style(catch_rate).cyan());
+    printer.print_info(
+        "HP",
+        style(format!("{}/{}", current_hp.min(max_hp), max_hp)).cyan(),
+    );
+    println!();
+
+    for ball in balls {
+        if matches.is_present("explain") {
+            let (_, steps) =
+                retro::explain_gen1_catch_chance(catch_rate, max_hp, current_hp, ball, status);
+            printer.print_section_heading(ball.display_name());
+            print_steps(&steps, printer);
+            println!();
+            continue;
+        }
+
+        let chance = retro::gen1_catch_chance(catch_rate, max_hp, current_hp, ball, status);
+        printer.print_info(
+            ball.display_name(),
+            style(format!("{:.2}%", chance * 100.0)).cyan(),
+        );
+    }
+}
+
+fn print_hit(matches: &ArgMatches, printer: &Printer) {
+    let name = matches.value_of("move").unwrap_or_default();
+    let m = match moves::find_move(name) {
+        Some(m) => m,
+        None => return printer.print_failure(&format!("Unknown move \"{}\"", name)),
+    };
+
+    let accuracy_stage = value_t!(matches, "accuracy-stage", i8).unwrap_or_else(|err| err.exit());
+    let evasion_stage = value_t!(matches, "evasion-stage", i8).unwrap_or_else(|err| err.exit());
+    let ability = matches
+        .value_of("ability")
+        .and_then(AccuracyAbility::from_name);
+
+    printer.print_section_heading(&format!("{} Hit Chance", m.name));
+
+    let chance = if matches.is_present("explain") {
+        let (chance, steps) =
+            accuracy::explain_hit_chance(&m, accuracy_stage, evasion_stage, ability);
+        print_steps(&steps, printer);
+        chance
+    } else {
+        print_hit_summary(&m, accuracy_stage, evasion_stage, ability, printer)
+    };
+
+    if matches.value_of("damage").is_some() {
+        let damage = value_t!(matches, "damage", f64).unwrap_or_else(|err| err.exit());
+        printer.print_info(
+            "Expected Damage",
+            format!(
+                "{} {}",
+                style(format!("{:.1}", damage * chance)).cyan(),
+                style("per turn").dim()
+            ),
+        );
+    }
+}
+
+fn print_hit_summary(
+    m: &moves::Move,
+    accuracy_stage: i8,
+    evasion_stage: i8,
+    ability: Option<AccuracyAbility>,
+    printer: &Printer,
+) -> f64 {
+    let chance = accuracy::hit_chance(m, accuracy_stage, evasion_stage, ability);
+    printer.print_info(
+        "Accuracy",
+        match m.accuracy {
+            Some(accuracy) => style(format!("{}%", accuracy)).cyan(),
+            None => style(String::from("Never misses")).cyan(),
+        },
+    );
+    printer.print_info(
+        "Stages",
+        style(format!("{:+} / {:+}", accuracy_stage, evasion_stage)).cyan(),
+    );
+    printer.print_info(
+        "Hit Chance",
+        style(format!("{:.0}%", chance * 100.0)).cyan(),
+    );
+    chance
+}
+
+fn print_pp(matches: &ArgMatches, printer: &Printer) {
+    let name = matches.value_of("move").unwrap_or_default();
+    let m = match moves::find_move(name) {
+        Some(m) => m,
+        None => return printer.print_failure(&format!("Unknown move \"{}\"", name)),
+    };
+
+    let ups = match matches.value_of("ups") {
+        Some(_) => {
+            let ups = value_t!(matches, "ups", u8).unwrap_or_else(|err| err.exit());
+            vec![ups]
+        }
+        None => (0..=MAX_PP_UPS).collect(),
+    };
+
+    printer.print_section_heading(&format!("{} PP", m.name));
+    for ups in ups {
+        let label = match ups {
+            0 => String::from("Base"),
+            1 => String::from("1 PP Up"),
+            ups => format!("{} PP Ups", ups),
+        };
+        printer.print_info(label, style(m.pp_with_ups(ups)).cyan());
+    }
+}
@@ -0,0 +1,86 @@
+use chrono::{Duration, Local, NaiveDate};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::challenge::Challenge;
+use crate::print::Printer;
+
+/// How many of the allowed Pokémon to name before summarising the rest.
+const POOL_PREVIEW: usize = 12;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("challenge")
+        .about("Generates this week's challenge run, e.g. a monotype run of a game")
+        .arg(
+            Arg::with_name("seed")
+                .long("seed")
+                .help("Gives everyone using the same seed the same challenges, e.g. a group's name")
+                .takes_value(true)
+                .default_value("pkmn"),
+        )
+        .arg(
+            Arg::with_name("date")
+                .long("date")
+                .value_name("YYYY-MM-DD")
+                .help("Generates the challenge of another week instead of this one")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .possible_values(&["terminal", "json"])
+                .default_value("terminal"),
+        )
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    let date = match matches.value_of("date") {
+        None => Local::now().naive_local().date(),
+        Some(value) => match value.parse::<NaiveDate>() {
+            Ok(date) => date,
+            Err(err) => return printer.print_failure(&format!("Invalid date: {}", err)),
+        },
+    };
+    let seed = matches.value_of("seed").unwrap_or("pkmn");
+    let challenge = Challenge::for_week(seed, date);
+
+    if matches.value_of("output") == Some("json") {
+        return match serde_json::to_string_pretty(&challenge) {
+            Ok(json) => println!("{}", json),
+            Err(err) => printer.print_failure(&format!("Couldn't serialize challenge: {}", err)),
+        };
+    }
+
+    printer.print_center(style(challenge.title()).yellow().bold());
+    printer.print_center(
+        style(format!(
+            "Week of {} to {}",
+            challenge.week_of,
+            challenge.week_of + Duration::days(6)
+        ))
+        .dim(),
+    );
+    println!();
+
+    printer.print_info("Game", style(&challenge.game).cyan());
+    printer.print_info("Theme", style(challenge.theme.description()).cyan());
+    for rule in &challenge.rules {
+        printer.print_info("Rule", style(rule).cyan());
+    }
+
+    let mut preview = challenge
+        .pool
+        .iter()
+        .take(POOL_PREVIEW)
+        .cloned()
+        .collect::<Vec<String>>();
+    if challenge.pool.len() > POOL_PREVIEW {
+        preview.push(format!("and {} more", challenge.pool.len() - POOL_PREVIEW));
+    }
+    printer.print_info(
+        format!("Allowed ({})", challenge.pool.len()),
+        style(preview.join(", ")).cyan(),
+    );
+}
@@ -0,0 +1,114 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use console::{style, StyledObject};
+
+use crate::pokedex::{self, Pokemon};
+use crate::print::{styled_empty_value, Printer};
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("compare")
+        .about("Shows two Pokémon side by side")
+        .arg(Arg::with_name("first").value_name("POKEMON").required(true))
+        .arg(
+            Arg::with_name("second")
+                .value_name("POKEMON")
+                .required(true),
+        )
+}
+
+fn types(pokemon: &Pokemon) -> String {
+    pokemon
+        .types()
+        .iter()
+        .map(|t| style(t.name()).magenta().to_string())
+        .collect::<Vec<String>>()
+        .join(" | ")
+}
+
+fn abilities(pokemon: &Pokemon) -> Vec<String> {
+    let mut abilities = [&pokemon.ability_1, &pokemon.ability_2]
+        .iter()
+        .filter(|ability| !ability.is_empty())
+        .map(|ability| style(ability).cyan().to_string())
+        .collect::<Vec<String>>();
+    if !pokemon.ability_hidden.is_empty() {
+        abilities.push(format!(
+            "{} {}",
+            style(&pokemon.ability_hidden).cyan(),
+            style("(hidden)").dim()
+        ));
+    }
+    abilities
+}
+
+/// Both values, with the higher one highlighted.
+fn highlight(left: u16, right: u16) -> (StyledObject<u16>, StyledObject<u16>) {
+    match left.cmp(&right) {
+        std::cmp::Ordering::Greater => (style(left).green().bold(), style(right).cyan()),
+        std::cmp::Ordering::Less => (style(left).cyan(), style(right).green().bold()),
+        std::cmp::Ordering::Equal => (style(left).cyan(), style(right).cyan()),
+    }
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    let mut pair = Vec::new();
+    for query in [matches.value_of("first"), matches.value_of("second")] {
+        let query = query.unwrap_or_default();
+        match pokedex::search_by_name(query, 1).into_iter().next() {
+            Some(result) => pair.push(result.pokemon),
+            None => return printer.print_failure(&format!("Couldn't find \"{}\"", query)),
+        }
+    }
+    let (left, right) = (&pair[0], &pair[1]);
+
+    println!();
+    printer.print_columns(
+        "",
+        style(&left.name).yellow().bold(),
+        style(&right.name).yellow().bold(),
+    );
+    printer.print_columns(
+        "",
+        style(format!("#{:03}", left.pokedex_number)).dim(),
+        style(format!("#{:03}", right.pokedex_number)).dim(),
+    );
+    println!();
+
+    printer.print_columns("Type", types(left), types(right));
+    let (left_abilities, right_abilities) = (abilities(left), abilities(right));
+    for i in 0..left_abilities.len().max(right_abilities.len()) {
+        let cell = |abilities: &[String]| match abilities.get(i) {
+            Some(ability) => ability.clone(),
+            None => String::new(),
+        };
+        printer.print_columns(
+            if i == 0 { "Abilities" } else { "" },
+            cell(&left_abilities),
+            cell(&right_abilities),
+        );
+    }
+    println!();
+
+    printer.print_section_heading("Base stats");
+    for (label, stat) in [
+        ("HP", (|p: &Pokemon| p.hp) as fn(&Pokemon) -> u16),
+        ("Attack", |p| p.attack),
+        ("Defense", |p| p.defense),
+        ("Sp. Attack", |p| p.sp_attack),
+        ("Sp. Defense", |p| p.sp_defense),
+        ("Speed", |p| p.speed),
+        ("Total", |p| p.total_points),
+    ] {
+        let (left_value, right_value) = highlight(stat(left), stat(right));
+        printer.print_columns(label, left_value, right_value);
+    }
+    println!();
+
+    let catch_rate = |pokemon: &Pokemon| match pokemon.catch_rate {
+        Some(catch_rate) => style(catch_rate.to_string()).cyan(),
+        None => styled_empty_value(),
+    };
+    printer.print_columns("Catch Rate", catch_rate(left), catch_rate(right));
+    println!();
+}
@@ -0,0 +1,222 @@
+use std::io::{self, Write};
+
+use clap::{App, AppSettings, Arg, ArgMatches, Shell, SubCommand};
+
+use crate::pokedex;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("completions")
+        .about("Prints a shell completion script (e.g. pkmn completions bash > ~/.pkmn.bash)")
+        .setting(AppSettings::ArgRequiredElseHelp)
+        .arg(
+            Arg::with_name("shell")
+                .value_name("SHELL")
+                .possible_values(&["bash", "fish", "zsh"])
+                .required_unless("names"),
+        )
+        .arg(
+            Arg::with_name("names")
+                .long("names")
+                .help("Prints every Pokémon name the scripts complete")
+                .hidden(true),
+        )
+}
+
+/// Options that take a Pokémon.
+const POKEMON_OPTIONS: [&str; 5] = ["-s", "--search", "--id", "--pokemon", "--around"];
+
+/// Subcommands whose positional arguments are Pokémon.
+const POKEMON_SUBCOMMANDS: [&str; 14] = [
+    "compare",
+    "core-gaps",
+    "go cp",
+    "go raid",
+    "hunt start",
+    "moves",
+    "open",
+    "share",
+    "show",
+    "stats",
+    "suggest-evs",
+    "tcg",
+    "team",
+    "tera",
+];
+
+/// The name each of [`POKEMON_SUBCOMMANDS`] is typed as, e.g. `cp` for `go cp`.
+fn last_words() -> impl Iterator<Item = &'static str> {
+    POKEMON_SUBCOMMANDS
+        .iter()
+        .filter_map(|path| path.split(' ').next_back())
+}
+
+/// Every name `-s` accepts as-is, one per form, in Pokédex order.
+fn names() -> Vec<String> {
+    pokedex::entries()
+        .unwrap_or_default()
+        .iter()
+        .map(|pokemon| pokemon.sprite_name_slug())
+        .collect()
+}
+
+/// Completes Pokémon names after the options and subcommands that take one,
+/// and leaves everything else to the function clap generates.
+fn bash_names(name: &str) -> String {
+    format!(
+        r#"
+_{name}_with_names() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    local names="$({name} completions --names)"
+    case "${{prev}}" in
+        {words})
+            COMPREPLY=( $(compgen -W "${{names}}" -- "${{cur}}") )
+            return 0
+            ;;
+    esac
+    # Every positional argument of these is a Pokémon
+    case "${{COMP_WORDS[1]}}" in
+        compare|core-gaps|team)
+            if [[ "${{cur}}" != -* ]]; then
+                COMPREPLY=( $(compgen -W "${{names}}" -- "${{cur}}") )
+                return 0
+            fi
+            ;;
+    esac
+    _{name} "$@"
+}}
+
+complete -F _{name}_with_names -o bashdefault -o default {name}
+"#,
+        name = name,
+        words = POKEMON_OPTIONS
+            .iter()
+            .copied()
+            .chain(last_words())
+            .collect::<Vec<&str>>()
+            .join("|"),
+    )
+}
+
+fn fish_names(name: &str) -> String {
+    let names = format!("-x -a \"({} completions --names)\"", name);
+    let mut script = format!(
+        "\ncomplete -c {name} -n \"__fish_use_subcommand\" -s s -l search {names}\n\
+         complete -c {name} -n \"__fish_use_subcommand\" -l id {names}\n\
+         complete -c {name} -n \"__fish_seen_subcommand_from calc\" -l pokemon {names}\n\
+         complete -c {name} -n \"__fish_seen_subcommand_from cores\" -l around {names}\n",
+        name = name,
+        names = names
+    );
+    for subcommand in last_words() {
+        script.push_str(&format!(
+            "complete -c {} -n \"__fish_seen_subcommand_from {}\" {}\n",
+            name, subcommand, names
+        ));
+    }
+    script
+}
+
+/// Swaps `_{name}_names` in as the action of every Pokémon argument in the
+/// script clap generates, which completes files or nothing.
+fn zsh_names(name: &str, script: &str) -> String {
+    let action = format!("_{}_names", name);
+    let script = script
+        .lines()
+        .map(|line| {
+            // Specs start with the option, after any options it conflicts with
+            // and a `*` if it can be repeated
+            let is_option = POKEMON_OPTIONS.iter().any(|option| {
+                ["+[", "=["].iter().any(|value| {
+                    let spec = format!("{}{}", option, value);
+                    ["'", "'*", ")", ")*"]
+                        .iter()
+                        .any(|start| line.contains(&format!("{}{}", start, spec)))
+                })
+            });
+            // Positionals with help read e.g. `':pokemon -- help:_files'`
+            let is_positional = line.ends_with(":_files' \\")
+                && ["pokemon", "first", "second"].iter().any(|name| {
+                    line.starts_with(&format!("':{}:", name))
+                        || line.starts_with(&format!("':{} -- ", name))
+                });
+            match (is_option, is_positional) {
+                (true, _) => line.replacen("]' ", &format!("]:pokemon:{}' ", action), 1),
+                (_, true) => line.replace(":_files'", &format!(":{}'", action)),
+                _ => line.to_string(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    format!(
+        "{}\n\n(( $+functions[{action}] )) ||\n{action}() {{\n    \
+         local names; names=(${{(f)\"$({name} completions --names)\"}})\n    \
+         _describe -t pokemon 'Pokémon' names\n}}\n",
+        script,
+        action = action,
+        name = name
+    )
+}
+
+fn script(shell: &str) -> String {
+    let name = env!("CARGO_PKG_NAME");
+    let for_shell = match shell {
+        "fish" => Shell::Fish,
+        "zsh" => Shell::Zsh,
+        _ => Shell::Bash,
+    };
+
+    let mut buffer = Vec::new();
+    crate::cli().gen_completions_to(name, for_shell, &mut buffer);
+    let generated = String::from_utf8_lossy(&buffer).into_owned();
+
+    match for_shell {
+        Shell::Fish => generated + fish_names(name).as_str(),
+        Shell::Zsh => zsh_names(name, &generated),
+        _ => generated + bash_names(name).as_str(),
+    }
+}
+
+pub fn run(matches: &ArgMatches) {
+    let output = match matches.value_of("shell") {
+        _ if matches.is_present("names") => names().join("\n") + "\n",
+        Some(shell) => script(shell),
+        None => return,
+    };
+    if let Err(err) = io::stdout().write_all(output.as_bytes()) {
+        warn!("couldn't print completions: {}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pokemon_subcommands_exist() {
+        for path in POKEMON_SUBCOMMANDS {
+            let args = ["pkmn"]
+                .into_iter()
+                .chain(path.split(' '))
+                .chain(["--help"]);
+            let err = crate::cli().get_matches_from_safe(args).unwrap_err();
+            assert_eq!(err.kind, clap::ErrorKind::HelpDisplayed, "{}", path);
+        }
+    }
+
+    #[test]
+    fn completes_names() {
+        let names = names();
+        assert!(names.contains(&String::from("bulbasaur")));
+        assert!(names.contains(&String::from("charizard-mega-x")));
+
+        assert!(script("bash").contains("-s|--search|--id|--pokemon|--around|compare|"));
+        assert!(script("fish").contains("__fish_seen_subcommand_from raid\" -x"));
+        let zsh = script("zsh");
+        assert!(zsh.contains("'*-s+[]:pokemon:_pkmn_names' "));
+        assert!(zsh.contains("'(-s --search)*--id=[]:pokemon:_pkmn_names' "));
+        assert!(zsh.contains("':second:_pkmn_names' "));
+        assert!(zsh.contains("_pkmn_names() {"));
+    }
+}
@@ -0,0 +1,89 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::pokedex::{self, all_pokemon, Pokemon};
+use crate::print::Printer;
+use crate::team;
+use crate::types::Type;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("core-gaps")
+        .about("Lists typings an attacking core can't hit super effectively with STAB attacks")
+        .arg(
+            Arg::with_name("pokemon")
+                .value_name("POKEMON")
+                .required(true)
+                .multiple(true),
+        )
+}
+
+fn typing_name(typing: &[Type]) -> String {
+    typing
+        .iter()
+        .map(Type::name)
+        .collect::<Vec<&str>>()
+        .join(" | ")
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    let core = matches
+        .values_of("pokemon")
+        .unwrap_or_default()
+        .filter_map(|query| pokedex::search_by_name(query, 1).into_iter().next())
+        .map(|result| result.pokemon)
+        .collect::<Vec<Pokemon>>();
+    let core = core.iter().collect::<Vec<&Pokemon>>();
+
+    let pokedex = match all_pokemon() {
+        Ok(pokedex) => pokedex,
+        Err(err) => return printer.print_failure(&err.to_string()),
+    };
+    let gaps = team::core_gaps(&core, &team::typings(&pokedex));
+
+    printer.print_section_heading("Core");
+    for pokemon in &core {
+        printer.print_info(
+            &pokemon.name,
+            style(typing_name(&pokemon.types())).magenta(),
+        );
+    }
+    println!();
+
+    if gaps.is_empty() {
+        printer.print_center(style("No gaps: every typing is hit super effectively").green());
+        println!();
+        return;
+    }
+
+    printer.print_section_heading(&format!("Gaps ({})", gaps.len()));
+    for typing in &gaps {
+        let example = pokedex.iter().find(|pokemon| {
+            let mut types = pokemon.types();
+            types.sort();
+            types == *typing
+        });
+        printer.print_info(
+            typing_name(typing),
+            match example {
+                Some(pokemon) => style(format!("e.g. {}", pokemon.name)).dim().to_string(),
+                None => String::new(),
+            },
+        );
+    }
+    println!();
+
+    printer.print_section_heading("Add next");
+    for (attacker, covered) in team::gap_fillers(&gaps).into_iter().take(3) {
+        printer.print_info(
+            style(attacker).magenta(),
+            format!(
+                "{} {}",
+                style(format!("covers {} of {}", covered, gaps.len())).cyan(),
+                style("gaps").dim()
+            ),
+        );
+    }
+    println!();
+}
@@ -0,0 +1,139 @@
+use clap::{value_t, App, Arg, ArgMatches, SubCommand};
+use console::{pad_str, style, Alignment};
+
+use crate::pokedex::{self, all_pokemon};
+use crate::print::Printer;
+use crate::team::{self, DefensiveCore};
+use crate::types::Type;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("cores")
+        .about("Finds defensive cores whose resistances cover every attacking type")
+        .arg(
+            Arg::with_name("around")
+                .long("around")
+                .value_name("POKEMON")
+                .help("The Pokémon to build the core around")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("size")
+                .long("size")
+                .help("Number of Pokémon in the core")
+                .possible_values(&["2", "3"])
+                .default_value("3"),
+        )
+        .arg(
+            Arg::with_name("allow-legendaries")
+                .long("allow-legendaries")
+                .help("Considers Legendary and Mythical Pokémon"),
+        )
+        .arg(
+            Arg::with_name("count")
+                .long("count")
+                .help("Number of cores to print")
+                .takes_value(true)
+                .default_value("3"),
+        )
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    let query = matches.value_of("around").unwrap_or_default();
+    let anchor = match pokedex::search_by_name(query, 1).into_iter().next() {
+        Some(result) => result.pokemon,
+        None => return printer.print_failure(&format!("Couldn't find \"{}\"", query)),
+    };
+    let size = value_t!(matches, "size", usize).unwrap_or_else(|err| err.exit());
+    let count = value_t!(matches, "count", usize).unwrap_or_else(|err| err.exit());
+
+    let pokedex = match all_pokemon() {
+        Ok(pokedex) => pokedex,
+        Err(err) => return printer.print_failure(&err.to_string()),
+    };
+    let cores = team::defensive_cores(
+        &anchor,
+        &pokedex,
+        size,
+        matches.is_present("allow-legendaries"),
+        count,
+    );
+
+    if cores.is_empty() {
+        return printer.print_failure(&format!(
+            "No core of {} around {} resists every type",
+            size, anchor.name
+        ));
+    }
+
+    for (i, core) in cores.iter().enumerate() {
+        let names = core
+            .members
+            .iter()
+            .map(|pokemon| pokemon.name.as_str())
+            .collect::<Vec<&str>>()
+            .join(" + ");
+        printer.print_section_heading(&format!("{}. {}", i + 1, names));
+        printer.print_info(
+            "Redundancy",
+            format!(
+                "{} {}",
+                style(core.redundancy).cyan(),
+                style("types resisted more than once").dim()
+            ),
+        );
+        printer.print_info("Total BST", style(core.total).cyan());
+        println!();
+        print_matrix(core);
+        println!();
+    }
+}
+
+fn multiplier(effectiveness: f32) -> String {
+    let text = match effectiveness {
+        0.0 => String::from("0×"),
+        0.25 => String::from("¼×"),
+        0.5 => String::from("½×"),
+        e => format!("{}×", e),
+    };
+    let text = pad_str(&text, 4, Alignment::Center, None).to_string();
+    match effectiveness {
+        e if e < 1.0 => style(text).green().to_string(),
+        e if e > 1.0 => style(text).red().to_string(),
+        _ => style(text).dim().to_string(),
+    }
+}
+
+/// Each member's damage multiplier from every attacking type, one column per
+/// member.
+fn print_matrix(core: &DefensiveCore) {
+    const LABEL_WIDTH: usize = 10;
+    const COLUMN_WIDTH: usize = 14;
+
+    let header = core
+        .members
+        .iter()
+        .map(|pokemon| {
+            pad_str(&pokemon.name, COLUMN_WIDTH, Alignment::Center, Some("…")).to_string()
+        })
+        .collect::<String>();
+    println!("{}{}", " ".repeat(LABEL_WIDTH), style(header).bold());
+
+    for attacker in Type::ALL {
+        let cells = core
+            .members
+            .iter()
+            .map(|pokemon| {
+                let cell = multiplier(attacker.effectiveness_against(&pokemon.types()));
+                pad_str(&cell, COLUMN_WIDTH, Alignment::Center, None).to_string()
+            })
+            .collect::<String>();
+        println!(
+            "{}{}",
+            pad_str(attacker.name(), LABEL_WIDTH, Alignment::Left, None),
+            cells
+        );
+    }
+}
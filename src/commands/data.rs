@@ -0,0 +1,405 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use console::style;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use tokio::sync::Semaphore;
+
+use crate::commands::random;
+use crate::dataset::{self, Change, Snapshot};
+use crate::error::PkmnError;
+use crate::pokedex::{self, Pokemon};
+use crate::print::Printer;
+use crate::sprite::{SpriteStyle, CHECK_TIMEOUT};
+
+/// URLs checked at once by `audit-urls`, to go easy on the hosts.
+const CONCURRENT_CHECKS: usize = 8;
+
+/// Most requests `audit-urls` starts a second.
+const MAX_RATE: usize = 1000;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("data")
+        .about("Reports on the embedded datasets")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("info")
+                .about("Version, row counts and coverage of the data, and changes since last run"),
+        )
+        .subcommand(
+            SubCommand::with_name("audit-urls")
+                .about("Checks that sprite and artwork URLs still exist, to catch moved assets")
+                .arg(
+                    Arg::with_name("sample")
+                        .long("sample")
+                        .value_name("COUNT")
+                        .help("Pokémon to check, picked at random")
+                        .default_value("50")
+                        .validator(validate_count),
+                )
+                .arg(
+                    Arg::with_name("all")
+                        .long("all")
+                        .help("Checks every Pokémon instead of a sample"),
+                )
+                .arg(
+                    Arg::with_name("seed")
+                        .long("seed")
+                        .help("Picks the same sample for the same seed")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("style")
+                        .long("style")
+                        .help("Only these sprites; all of them by default")
+                        .possible_values(&SpriteStyle::NAMES)
+                        .multiple(true)
+                        .use_delimiter(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("rate")
+                        .long("rate")
+                        .value_name("PER_SECOND")
+                        .help("Requests started per second, at most")
+                        .default_value("10")
+                        .validator(validate_rate),
+                ),
+        )
+}
+
+fn validate_count(value: String) -> Result<(), String> {
+    match value.parse::<usize>() {
+        Ok(count) if count > 0 => Ok(()),
+        _ => Err(String::from("Expected a positive number")),
+    }
+}
+
+fn validate_rate(value: String) -> Result<(), String> {
+    match value.parse::<usize>() {
+        Ok(rate) if (1..=MAX_RATE).contains(&rate) => Ok(()),
+        _ => Err(format!("Expected a rate from 1 to {}", MAX_RATE)),
+    }
+}
+
+pub async fn run(matches: &ArgMatches<'_>) {
+    let printer = Printer::new();
+
+    match matches.subcommand() {
+        ("info", Some(_)) => print_info(&printer),
+        ("audit-urls", Some(matches)) => audit_urls(&printer, matches).await,
+        _ => unreachable!("data requires a subcommand"),
+    }
+}
+
+fn print_info(printer: &Printer) {
+    let snapshot = Snapshot::current();
+
+    printer.print_section_heading("Embedded Data");
+    printer.print_info("Version", style(&snapshot.version).cyan());
+    printer.print_info("pkmn", style(&snapshot.pkmn_version).cyan());
+    printer.print_info(
+        "Pokédex",
+        match (pokedex::replaced_version(), dataset::local_pokedex()) {
+            (Some(_), Some((path, _))) => style(format!("Downloaded, {}", path.display())).cyan(),
+            _ => style(String::from("Embedded")).cyan(),
+        },
+    );
+
+    let mut generations = BTreeMap::<u8, usize>::new();
+    for pokemon in pokedex::entries().unwrap_or_default() {
+        *generations.entry(pokemon.generation).or_default() += 1;
+    }
+    if let (Some(first), Some(last)) = (generations.keys().next(), generations.keys().last()) {
+        printer.print_info("Generations", style(format!("{}–{}", first, last)).cyan());
+    }
+    for (generation, count) in &generations {
+        printer.print_info(
+            format!("Gen {}", generation),
+            format!("{} {}", style(count).cyan(), style("entries").dim()),
+        );
+    }
+    println!();
+
+    printer.print_section_heading("Datasets");
+    for dataset in dataset::embedded() {
+        let summary = &snapshot.datasets[dataset.name];
+        printer.print_info(
+            dataset.name,
+            format!(
+                "{} {}",
+                style(format!("{} rows", summary.rows)).cyan(),
+                style(&summary.version[..8]).dim()
+            ),
+        );
+    }
+    println!();
+
+    printer.print_section_heading("Since Last Run");
+    match Snapshot::load() {
+        None => printer.print_info("Changes", style("First run, nothing to compare").dim()),
+        Some(previous) if previous.version == snapshot.version => printer.print_info(
+            "Changes",
+            style(format!("None since pkmn {}", previous.pkmn_version)).dim(),
+        ),
+        Some(previous) => {
+            printer.print_info(
+                "Previous",
+                format!(
+                    "{} {}",
+                    style(&previous.version).cyan(),
+                    style(format!("(pkmn {})", previous.pkmn_version)).dim()
+                ),
+            );
+            for (name, change) in snapshot.changes_since(&previous) {
+                let description = match change {
+                    Change::Added { rows } => format!("added, {} rows", rows),
+                    Change::Removed { rows } => format!("removed, had {} rows", rows),
+                    Change::Changed {
+                        previous_rows,
+                        rows,
+                    } => format!("updated, {:+} rows", rows as i64 - previous_rows as i64),
+                };
+                printer.print_info(name, style(description).cyan());
+            }
+        }
+    }
+
+    if let Err(err) = snapshot.save() {
+        warn!("couldn't save the dataset snapshot: {}", err);
+    }
+}
+
+/// `count` Pokémon picked at random, the same ones for the same `seed`, or
+/// every Pokémon without a `count`.
+fn sample(count: Option<usize>, seed: Option<&str>) -> Result<Vec<Pokemon>, PkmnError> {
+    let mut all = pokedex::all_pokemon()?;
+    let count = match count {
+        Some(count) => count,
+        None => return Ok(all),
+    };
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(random::seed_from(seed)),
+        None => StdRng::from_entropy(),
+    };
+    all.shuffle(&mut rng);
+    all.truncate(count);
+    all.sort_by_key(|pokemon| pokemon.pokedex_number);
+    Ok(all)
+}
+
+/// A URL built from one of the templates, and what its host answered.
+struct Check {
+    style: SpriteStyle,
+    name: String,
+    url: String,
+    /// HTTP status, if the host answered.
+    status: Option<u16>,
+    error: Option<String>,
+}
+
+impl Check {
+    fn is_ok(&self) -> bool {
+        matches!(self.status, Some(200..=299))
+    }
+}
+
+/// Sends a HEAD request to each URL, at most `rate` started a second and
+/// [`CONCURRENT_CHECKS`] at once.
+async fn send_checks(checks: &mut [Check], rate: usize) {
+    let client = reqwest::Client::builder()
+        .timeout(CHECK_TIMEOUT)
+        .build()
+        .unwrap_or_default();
+    let permits = Arc::new(Semaphore::new(CONCURRENT_CHECKS));
+    let mut ticks = tokio::time::interval(Duration::from_secs(1) / rate as u32);
+
+    let mut requests = Vec::new();
+    for check in checks.iter() {
+        ticks.tick().await;
+        let permit = permits
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("the semaphore is never closed");
+        let request = client.head(&check.url).send();
+        requests.push(tokio::spawn(async move {
+            let res = request.await;
+            drop(permit);
+            res
+        }));
+    }
+
+    for (check, request) in checks.iter_mut().zip(requests) {
+        match request.await {
+            Ok(Ok(res)) => check.status = Some(res.status().as_u16()),
+            Ok(Err(err)) => check.error = Some(err.to_string()),
+            Err(err) => check.error = Some(err.to_string()),
+        }
+        info!("audit {}: {:?} {:?}", check.url, check.status, check.error);
+    }
+}
+
+/// A template is broken when its host answers yet none of its URLs exist,
+/// e.g. after the host moves its assets.
+fn is_broken(checks: &[&Check]) -> bool {
+    !checks.is_empty()
+        && checks.iter().all(|check| !check.is_ok())
+        && checks.iter().any(|check| check.status.is_some())
+}
+
+async fn audit_urls(printer: &Printer, matches: &ArgMatches<'_>) {
+    let count = match matches.is_present("all") {
+        true => None,
+        false => matches
+            .value_of("sample")
+            .and_then(|count| count.parse().ok()),
+    };
+    let pokemon = match sample(count, matches.value_of("seed")) {
+        Ok(pokemon) => pokemon,
+        Err(err) => return printer.print_failure(&err.to_string()),
+    };
+    let styles = match matches.values_of("style") {
+        Some(names) => names.filter_map(SpriteStyle::from_name).collect(),
+        None => SpriteStyle::NAMES
+            .iter()
+            .filter_map(|name| SpriteStyle::from_name(name))
+            .collect::<Vec<SpriteStyle>>(),
+    };
+    let rate = matches
+        .value_of("rate")
+        .and_then(|rate| rate.parse().ok())
+        .unwrap_or(10);
+
+    let mut checks = styles
+        .iter()
+        .flat_map(|style| {
+            pokemon.iter().filter_map(move |pokemon| {
+                Some(Check {
+                    style: *style,
+                    name: pokemon.name.clone(),
+                    url: style.url(pokemon)?,
+                    status: None,
+                    error: None,
+                })
+            })
+        })
+        .collect::<Vec<Check>>();
+    send_checks(&mut checks, rate).await;
+
+    printer.print_section_heading("URL Audit");
+    printer.print_info(
+        "Checked",
+        format!(
+            "{} {}",
+            style(format!("{} URLs", checks.len())).cyan(),
+            style(format!("for {} Pokémon", pokemon.len())).dim()
+        ),
+    );
+    let mut broken_templates = Vec::new();
+    for sprite_style in &styles {
+        let checks = checks
+            .iter()
+            .filter(|check| check.style == *sprite_style)
+            .collect::<Vec<&Check>>();
+        let ok = checks.iter().filter(|check| check.is_ok()).count();
+        let summary = format!("{}/{} found", ok, checks.len());
+        printer.print_info(
+            sprite_style.name(),
+            match ok == checks.len() {
+                true => style(summary).green(),
+                false => style(summary).red(),
+            },
+        );
+        if is_broken(&checks) {
+            broken_templates.push(sprite_style.name());
+        }
+    }
+    println!();
+
+    let failed = checks
+        .iter()
+        .filter(|check| !check.is_ok())
+        .collect::<Vec<&Check>>();
+    if !failed.is_empty() {
+        printer.print_section_heading("Missing");
+        for check in failed {
+            let reason = match check.status {
+                Some(status) => status.to_string(),
+                None => String::from("unreachable"),
+            };
+            printer.print_info(
+                format!("{} ({})", check.name, check.style.name()),
+                format!("{} {}", style(&check.url).dim(), style(reason).red()),
+            );
+        }
+        println!();
+    }
+
+    if !checks.is_empty() && checks.iter().all(|check| check.status.is_none()) {
+        printer.print_failure(
+            "No host answered, check the connection (RUST_LOG=info shows the errors)",
+        );
+    } else if !broken_templates.is_empty() {
+        printer.print_failure(&format!(
+            "Broken templates: {}, the host may have moved its assets",
+            broken_templates.join(", ")
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples() {
+        let first = sample(Some(20), Some("audit")).unwrap();
+        assert_eq!(first.len(), 20);
+        let names = |sample: &[Pokemon]| {
+            sample
+                .iter()
+                .map(|pokemon| pokemon.name.clone())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(
+            names(&first),
+            names(&sample(Some(20), Some("audit")).unwrap())
+        );
+        assert_eq!(
+            sample(None, None).unwrap().len(),
+            pokedex::entries().unwrap().len()
+        );
+    }
+
+    #[test]
+    fn bounds_rate() {
+        assert!(validate_rate(String::from("10")).is_ok());
+        assert!(validate_rate(String::from("1000")).is_ok());
+        assert!(validate_rate(String::from("0")).is_err());
+        assert!(validate_rate(String::from("5000000000")).is_err());
+    }
+
+    #[test]
+    fn broken_templates() {
+        let check = |status: Option<u16>| Check {
+            style: SpriteStyle::Artwork,
+            name: String::from("Bulbasaur"),
+            url: String::from("https://example.com/1.png"),
+            status,
+            error: None,
+        };
+        let (found, missing, offline) = (check(Some(200)), check(Some(404)), check(None));
+
+        assert!(is_broken(&[&missing, &missing]));
+        assert!(is_broken(&[&missing, &offline]));
+        assert!(!is_broken(&[&found, &missing]));
+        // Unreachable isn't broken, e.g. when offline
+        assert!(!is_broken(&[&offline]));
+        assert!(!is_broken(&[]));
+    }
+}
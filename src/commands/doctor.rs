@@ -0,0 +1,73 @@
+use std::env;
+
+use clap::{App, ArgMatches, SubCommand};
+use console::style;
+
+use crate::print::{styled_empty_value, Printer};
+use crate::sprite_cache::SpriteCache;
+use crate::terminal::{ColorSupport, ENV_VARS};
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("doctor")
+        .about("Reports what the terminal supports and how output will be rendered")
+}
+
+fn yes_no(value: bool) -> &'static str {
+    match value {
+        true => "Yes",
+        false => "No",
+    }
+}
+
+pub fn run(_matches: &ArgMatches) {
+    let printer = Printer::new();
+    let capabilities = printer.capabilities;
+
+    printer.print_section_heading("Terminal");
+    printer.print_info(
+        "Output",
+        style(match capabilities.is_term {
+            true => "Terminal",
+            false => "Pipe or file",
+        })
+        .cyan(),
+    );
+    printer.print_info("Width", style(capabilities.width).cyan());
+    printer.print_info("Colors", style(capabilities.colors).cyan());
+    printer.print_info("Unicode", style(yes_no(capabilities.unicode)).cyan());
+    println!();
+
+    printer.print_section_heading("Renderers");
+    printer.print_info("Sprites", style(capabilities.images).cyan());
+    printer.print_info(
+        "Text",
+        style(match capabilities.colors {
+            ColorSupport::None => "Plain",
+            _ => "Styled",
+        })
+        .cyan(),
+    );
+    printer.print_info(
+        "Pager",
+        style(env::var("PAGER").unwrap_or_else(|_| String::from("less -R"))).cyan(),
+    );
+    printer.print_info(
+        "Sprite Cache",
+        match SpriteCache::open() {
+            Some(cache) => style(cache.dir().display().to_string()).cyan(),
+            None => styled_empty_value(),
+        },
+    );
+    println!();
+
+    printer.print_section_heading("Environment");
+    for name in ENV_VARS {
+        printer.print_info(
+            name,
+            match env::var(name) {
+                Ok(value) => style(value).cyan(),
+                Err(_) => styled_empty_value(),
+            },
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use crate::commands::learn::validate_profile;
+use crate::config::Config;
+use crate::hunt::Hunts;
+use crate::learn::Deck;
+use crate::pokedex;
+use crate::print::Printer;
+use crate::table::Table;
+use crate::tracker::{self, Format, TRACKERS};
+
+/// Exported with `export pokedex` besides the trackers, with a column per
+/// field including derived and computed ones.
+const POKEDEX: &str = "pokedex";
+
+const PARQUET: &str = "parquet";
+
+pub fn subcommand() -> App<'static, 'static> {
+    let format = Arg::with_name("format")
+        .long("format")
+        .help("Defaults to the --out file's extension, or CSV")
+        .possible_values(&Format::NAMES)
+        .takes_value(true);
+    #[cfg(feature = "parquet")]
+    let format = format.possible_value(PARQUET);
+
+    SubCommand::with_name("export")
+        .about(
+            "Exports a tracker's history (hunts or a learning profile) or the whole Pokédex as \
+             CSV or JSON",
+        )
+        .arg(
+            Arg::with_name("tracker")
+                .value_name("TRACKER")
+                .possible_values(&TRACKERS)
+                .possible_value(POKEDEX)
+                .required(true),
+        )
+        .arg(format)
+        .arg(
+            Arg::with_name("out")
+                .long("out")
+                .value_name("FILE")
+                .help("Writes to a file instead of printing")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("profile")
+                .long("profile")
+                .help("The learning profile to export")
+                .takes_value(true)
+                .default_value("default")
+                .validator(validate_profile),
+        )
+}
+
+/// Whether to write Parquet, from `--format` or the `--out` file's extension.
+fn is_parquet(matches: &ArgMatches, out: Option<&Path>) -> bool {
+    match matches.value_of("format") {
+        Some(format) => format == PARQUET,
+        None => out
+            .and_then(Path::extension)
+            .is_some_and(|ext| ext == PARQUET),
+    }
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+    let out = matches.value_of("out").map(Path::new);
+    let format = match matches.value_of("format").and_then(Format::from_name) {
+        Some(format) => format,
+        None => out.and_then(Format::from_path).unwrap_or(Format::Csv),
+    };
+    let parquet = is_parquet(matches, out);
+    #[cfg(not(feature = "parquet"))]
+    if parquet {
+        return printer.print_failure("pkmn was built without the parquet feature");
+    }
+    #[cfg(feature = "parquet")]
+    if parquet && matches.value_of("tracker") != Some(POKEDEX) {
+        return printer.print_failure("Only the Pokédex can be exported as Parquet");
+    }
+
+    let writer: Box<dyn io::Write + Send> = match out {
+        Some(path) => match File::create(path) {
+            Ok(file) => Box::new(file),
+            Err(err) => {
+                return printer.print_failure(&format!(
+                    "Couldn't write {}: {}",
+                    path.display(),
+                    err
+                ))
+            }
+        },
+        None => Box::new(io::stdout()),
+    };
+    let result = match matches.value_of("tracker") {
+        Some(POKEDEX) => {
+            match pokedex::entries()
+                .and_then(|entries| Table::pokedex(entries, &Config::load().fields))
+            {
+                Ok(table) => match format {
+                    #[cfg(feature = "parquet")]
+                    _ if parquet => table.write_parquet(writer),
+                    Format::Csv => table.write_csv(writer),
+                    Format::Json => table.write_json(writer),
+                },
+                Err(err) => Err(err.into()),
+            }
+        }
+        Some("learn") => {
+            let profile = matches.value_of("profile").unwrap_or("default");
+            tracker::write(&Deck::load(profile).records(), format, writer)
+        }
+        _ => tracker::write(&Hunts::load().records(), format, writer),
+    };
+    if let Err(err) = result {
+        printer.print_failure(&format!("Couldn't export: {}", err));
+    }
+}
@@ -0,0 +1,63 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::field::{self, CATEGORIES};
+use crate::print::Printer;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("field")
+        .about("Summarizes the effects of a weather or terrain")
+        .arg(
+            Arg::with_name("field")
+                .value_name("WEATHER OR TERRAIN")
+                .help("e.g. rain, sun, sandstorm, hail or electric-terrain")
+                .required(true),
+        )
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    let name = matches.value_of("field").unwrap_or_default();
+    let effects = field::effects_of(name);
+    let first = match effects.first() {
+        Some(first) => first,
+        None => {
+            return printer.print_failure(&format!(
+                "Unknown weather or terrain \"{}\" (try {})",
+                name,
+                field::field_names().join(", ")
+            ))
+        }
+    };
+
+    let title = first
+        .field
+        .split('-')
+        .map(|word| {
+            let mut word = word.to_string();
+            word[..1].make_ascii_uppercase();
+            word
+        })
+        .collect::<Vec<String>>()
+        .join(" ");
+    printer.print_center(style(title).yellow());
+    printer.print_center(style(&first.kind).dim());
+
+    for (category, heading) in CATEGORIES {
+        let lines = effects
+            .iter()
+            .filter(|effect| effect.category == category)
+            .collect::<Vec<_>>();
+        if lines.is_empty() {
+            continue;
+        }
+
+        println!();
+        printer.print_section_heading(heading);
+        for effect in lines {
+            printer.print_center(style(&effect.effect).cyan());
+        }
+    }
+    println!();
+}
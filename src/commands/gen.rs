@@ -0,0 +1,112 @@
+use clap::{value_t, App, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::config::Config;
+use crate::download_image;
+use crate::generation::{region_name, GenerationSummary};
+use crate::print::Printer;
+use crate::sprite;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("gen")
+        .about("Summarizes the Pokémon introduced in a generation")
+        .arg(
+            Arg::with_name("generation")
+                .value_name("GENERATION")
+                .help("Generation number, from 1")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("no-image")
+                .long("no-image")
+                .help("Skips the region image"),
+        )
+}
+
+async fn print_region_image(summary: &GenerationSummary, printer: &Printer) {
+    let url = match Config::load().region_image_url {
+        Some(template) => template
+            .replace("{region}", &summary.region.to_lowercase())
+            .replace("{generation}", &summary.generation.to_string()),
+        None => return info!("no region_image_url configured"),
+    };
+
+    match download_image(&url).await {
+        Err(err) if sprite::is_network_error(err.as_ref()) => {
+            info!("skipping region image, network unreachable: {}", err)
+        }
+        Err(err) => printer.print_failure(&format!("Image: {}", err)),
+        Ok(image) => {
+            if printer.print_image(&image, 68).is_err() {
+                warn!("image failed to print");
+            }
+            println!();
+        }
+    }
+}
+
+pub async fn run(matches: &ArgMatches<'_>) {
+    let printer = Printer::new();
+    let generation = value_t!(matches, "generation", u8).unwrap_or_else(|err| err.exit());
+
+    let summary = match (GenerationSummary::new(generation), region_name(generation)) {
+        (Some(summary), _) => summary,
+        (None, Some(region)) => {
+            return printer.print_failure(&format!(
+                "The Pokédex has no Pokémon from generation {} ({}) yet, pkmn update-data \
+                 downloads a newer one",
+                generation, region
+            ))
+        }
+        (None, None) => return printer.print_failure(&format!("No generation {}", generation)),
+    };
+
+    if !matches.is_present("no-image") {
+        print_region_image(&summary, &printer).await;
+    }
+
+    printer.print_center(style(format!("Generation {}", summary.generation)).yellow());
+    printer.print_center(style(format!("{} region", summary.region)).green());
+    println!();
+
+    printer.print_section_heading("Pokédex");
+    printer.print_info(
+        "National №",
+        style(format!("{}–{}", summary.first_number, summary.last_number)).yellow(),
+    );
+    printer.print_info("Species", style(summary.species).cyan());
+    printer.print_info(
+        "Entries",
+        format!(
+            "{} {}",
+            style(summary.entries).cyan(),
+            style("(including forms)").dim()
+        ),
+    );
+    printer.print_info(
+        "Highest BST",
+        format!(
+            "{} {}",
+            style(&summary.strongest.name).cyan(),
+            style(format!("({})", summary.strongest.total_points)).dim()
+        ),
+    );
+    println!();
+
+    printer.print_section_heading("Types");
+    for (pokemon_type, count) in &summary.types {
+        printer.print_info(pokemon_type, style(count).cyan());
+    }
+    println!();
+
+    printer.print_section_heading("Legendary & Mythical");
+    match summary.legendaries.is_empty() {
+        true => printer.print_info("", crate::print::styled_empty_value()),
+        false => {
+            for name in &summary.legendaries {
+                printer.print_info("", style(name).cyan());
+            }
+        }
+    }
+    println!();
+}
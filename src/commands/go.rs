@@ -0,0 +1,113 @@
+use clap::{value_t, App, AppSettings, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::go::{cp_multiplier, GoStats, Ivs, RaidTier};
+use crate::pokedex::{self, Pokemon};
+use crate::print::Printer;
+
+fn validate_level(value: String) -> Result<(), String> {
+    match value.parse::<f64>() {
+        Ok(level) if cp_multiplier(level).is_some() => Ok(()),
+        _ => Err(String::from("Levels range from 1 to 51, in steps of 0.5")),
+    }
+}
+
+fn pokemon_arg() -> Arg<'static, 'static> {
+    Arg::with_name("pokemon")
+        .value_name("POKEMON")
+        .required(true)
+}
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("go")
+        .about("Pokémon GO stats and CP")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("cp")
+                .about("CP and HP at a level, with the given IVs")
+                .arg(pokemon_arg())
+                .arg(
+                    Arg::with_name("level")
+                        .long("level")
+                        .takes_value(true)
+                        .default_value("40")
+                        .validator(validate_level),
+                )
+                .arg(
+                    Arg::with_name("ivs")
+                        .long("ivs")
+                        .value_name("ATK/DEF/STA")
+                        .takes_value(true)
+                        .default_value("15/15/15"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("raid")
+                .about("Raid boss CP per tier, and the CP range when caught")
+                .arg(pokemon_arg()),
+        )
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    let (name, matches) = match matches.subcommand() {
+        (name, Some(matches)) => (name, matches),
+        _ => unreachable!("go requires a subcommand"),
+    };
+
+    let query = matches.value_of("pokemon").unwrap_or_default();
+    let pokemon = match pokedex::search_by_name(query, 1).into_iter().next() {
+        Some(result) => result.pokemon,
+        None => return printer.print_failure(&format!("Couldn't find \"{}\"", query)),
+    };
+
+    match name {
+        "cp" => print_cp(matches, &pokemon, &printer),
+        "raid" => print_raid(&pokemon, &printer),
+        _ => unreachable!("unknown go subcommand"),
+    }
+}
+
+pub fn print_stats(stats: &GoStats, printer: &Printer) {
+    printer.print_info("Attack", style(stats.attack).cyan());
+    printer.print_info("Defense", style(stats.defense).cyan());
+    printer.print_info("Stamina", style(stats.stamina).cyan());
+}
+
+fn print_cp(matches: &ArgMatches, pokemon: &Pokemon, printer: &Printer) {
+    let level = value_t!(matches, "level", f64).unwrap_or_else(|err| err.exit());
+    let ivs = match Ivs::parse(matches.value_of("ivs").unwrap_or_default()) {
+        Ok(ivs) => ivs,
+        Err(err) => return printer.print_failure(&err),
+    };
+    let stats = GoStats::new(pokemon);
+
+    printer.print_section_heading(&format!("{} in Pokémon GO", pokemon.name));
+    print_stats(&stats, printer);
+    println!();
+
+    printer.print_info("Level", style(level).cyan());
+    printer.print_info(
+        "IVs",
+        style(format!("{}/{}/{}", ivs.attack, ivs.defense, ivs.stamina)).cyan(),
+    );
+    printer.print_info("CP", style(stats.cp(ivs, level)).yellow().bold());
+    printer.print_info("HP", style(stats.hp(ivs, level)).cyan());
+}
+
+fn print_raid(pokemon: &Pokemon, printer: &Printer) {
+    let stats = GoStats::new(pokemon);
+
+    printer.print_section_heading(&format!("{} Raids", pokemon.name));
+    for tier in RaidTier::ALL {
+        printer.print_info(tier.name(), style(stats.raid_boss_cp(tier)).cyan());
+    }
+    println!();
+
+    printer.print_section_heading("Caught CP");
+    for (label, boosted) in [("Normal", false), ("Weather Boosted", true)] {
+        let (min, max) = stats.raid_catch_cp(boosted);
+        printer.print_info(label, style(format!("{}–{}", min, max)).cyan());
+    }
+}
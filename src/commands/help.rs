@@ -0,0 +1,65 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::fuzzy;
+use crate::help::{self, TOPICS};
+use crate::print::Printer;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("help")
+        .about("Explains a command or a topic (query-language, calculators, data-sources)")
+        .arg(
+            Arg::with_name("topic")
+                .value_name("TOPIC")
+                .help("A help topic or the name of a command"),
+        )
+}
+
+fn print_topics() {
+    println!();
+    println!("{}", style("TOPICS:").bold());
+    for topic in TOPICS {
+        println!("    {:<18}{}", topic.name, topic.summary);
+    }
+    println!();
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    let name = match matches.value_of("topic") {
+        Some(name) => name,
+        None => {
+            let _ = crate::cli().print_help();
+            println!();
+            return print_topics();
+        }
+    };
+
+    if let Some(topic) = help::find_topic(name) {
+        return help::print_topic(topic, &printer);
+    }
+
+    if let Some(command) = super::all()
+        .into_iter()
+        .find(|command| command.get_name() == name)
+    {
+        let mut command = command.bin_name(format!("{} {}", env!("CARGO_PKG_NAME"), name));
+        let _ = command.print_help();
+        return println!();
+    }
+
+    let names = TOPICS
+        .iter()
+        .map(|topic| topic.name.to_string())
+        .chain(
+            super::all()
+                .iter()
+                .map(|command| command.get_name().to_string()),
+        )
+        .collect::<Vec<String>>();
+    printer.print_failure(&match fuzzy::did_you_mean(&names, name) {
+        Some(suggestion) => format!("No help for \"{}\", did you mean \"{}\"?", name, suggestion),
+        None => format!("No help for \"{}\"", name),
+    });
+}
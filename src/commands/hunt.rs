@@ -0,0 +1,352 @@
+use chrono::Utc;
+use clap::{value_t, App, AppSettings, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::config::Config;
+#[cfg(all(feature = "hotkeys", target_os = "linux"))]
+use crate::hotkeys;
+use crate::hunt::{self, Hunt, Hunts, Overlay, DEFAULT_ODDS};
+use crate::pokedex;
+use crate::print::Printer;
+use crate::webhook::{self, Event};
+
+/// Parses odds given as `4096` or `1/4096`.
+pub fn parse_odds(value: &str) -> Option<u32> {
+    let denominator = value.trim().strip_prefix("1/").unwrap_or(value.trim());
+    denominator.parse().ok().filter(|odds| *odds > 0)
+}
+
+/// Validates a whole number above zero, e.g. of seconds or attempts.
+fn validate_positive(value: String) -> Result<(), String> {
+    match value.parse::<u32>() {
+        Ok(number) if number > 0 => Ok(()),
+        _ => Err(String::from("Expected a whole number above zero")),
+    }
+}
+
+fn validate_odds(value: String) -> Result<(), String> {
+    match parse_odds(&value) {
+        Some(_) => Ok(()),
+        None => Err(String::from("Odds are given as e.g. 4096 or 1/4096")),
+    }
+}
+
+#[cfg(all(feature = "hotkeys", target_os = "linux"))]
+fn listen_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("listen")
+        .about(
+            "Adds an attempt to the active hunt on a global hotkey, without the terminal focused",
+        )
+        .arg(
+            Arg::with_name("key")
+                .long("key")
+                .help("A function key, Pause, ScrollLock, Insert or a Linux key code")
+                .takes_value(true)
+                .default_value("F13")
+                .validator(|value| match hotkeys::key_code(&value) {
+                    Some(_) => Ok(()),
+                    None => Err(format!("Unknown key \"{}\"", value)),
+                }),
+        )
+        .arg(
+            Arg::with_name("notify-every")
+                .long("notify-every")
+                .value_name("N")
+                .help("Shows a desktop notification every N attempts")
+                .takes_value(true)
+                .validator(validate_positive),
+        )
+}
+
+pub fn subcommand() -> App<'static, 'static> {
+    let hunt = SubCommand::with_name("hunt")
+        .about("Counts attempts at a shiny hunt")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("start")
+                .about("Starts or resumes counting a hunt, which becomes the active one")
+                .arg(
+                    Arg::with_name("pokemon")
+                        .value_name("POKEMON")
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("odds")
+                        .long("odds")
+                        .help("Shiny odds per attempt, e.g. 1/1365 with the Shiny Charm")
+                        .takes_value(true)
+                        .validator(validate_odds),
+                )
+                .arg(
+                    Arg::with_name("count")
+                        .long("count")
+                        .help("Attempts made before counting with pkmn")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("add")
+                .about("Adds attempts to the active hunt")
+                .arg(
+                    Arg::with_name("attempts")
+                        .value_name("N")
+                        .default_value("1")
+                        .validator(validate_positive),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("status")
+                .about("Prints the active hunt's progress")
+                .arg(
+                    Arg::with_name("line")
+                        .long("line")
+                        .help("Prints a single plain line, e.g. for a status bar or stream"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("overlay")
+                .about("Keeps a file showing the active hunt up to date, e.g. for an OBS browser source")
+                .arg(
+                    Arg::with_name("out")
+                        .long("out")
+                        .value_name("FILE")
+                        .help("Writes an HTML page, or JSON if the name ends in .json")
+                        .takes_value(true)
+                        .required_unless("stop"),
+                )
+                .arg(
+                    Arg::with_name("refresh")
+                        .long("refresh")
+                        .value_name("SECONDS")
+                        .help("How often the HTML page reloads")
+                        .takes_value(true)
+                        .default_value("2")
+                        .validator(validate_positive),
+                )
+                .arg(
+                    Arg::with_name("stop")
+                        .long("stop")
+                        .help("Stops updating the overlay")
+                        .conflicts_with("out"),
+                ),
+        );
+    #[cfg(all(feature = "hotkeys", target_os = "linux"))]
+    let hunt = hunt.subcommand(listen_subcommand());
+    hunt
+}
+
+fn print_status(hunt: &Hunt, printer: &Printer) {
+    printer.print_section_heading(&hunt.pokemon);
+    printer.print_info("Odds", style(format!("1/{}", hunt.odds)).cyan());
+    printer.print_info(
+        "Attempts",
+        format!(
+            "{} {}",
+            style(hunt.count).cyan(),
+            style(format!("({:.1}% chance so far)", hunt.chance() * 100.0)).dim()
+        ),
+    );
+    printer.print_info(
+        "Started",
+        style(
+            hunt.started
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M"),
+        )
+        .cyan(),
+    );
+
+    let per_attempt = match hunt.time_per_attempt(None) {
+        Some(per_attempt) => per_attempt,
+        None => return,
+    };
+    printer.print_info(
+        "Per attempt",
+        style(hunt::format_duration(per_attempt)).cyan(),
+    );
+    for (chance, left) in hunt.projections(per_attempt) {
+        printer.print_info(
+            format!("{:.0}% chance in", chance * 100.0),
+            style(hunt::format_duration(left)).yellow(),
+        );
+    }
+}
+
+/// Adds an attempt each time the hotkey is pressed, until interrupted.
+#[cfg(all(feature = "hotkeys", target_os = "linux"))]
+async fn listen(matches: &ArgMatches<'_>, printer: &Printer) {
+    let key = matches.value_of("key").unwrap_or_default();
+    let code = hotkeys::key_code(key).expect("keys are validated");
+    let every = matches
+        .value_of("notify-every")
+        .and_then(|value| value.parse::<u32>().ok());
+    if Hunts::load().active().is_none() {
+        return printer.print_failure("No active hunt, start one with pkmn hunt start");
+    }
+
+    let presses = match hotkeys::listen(code) {
+        Ok(presses) => presses,
+        Err(err) => return printer.print_failure(&format!("Couldn't listen for {}: {}", key, err)),
+    };
+    printer.print_center(style(format!("Press {} to count an attempt, Ctrl+C stops", key)).dim());
+    for () in presses {
+        // Loaded each time, so counts from other commands meanwhile aren't lost
+        let mut hunts = Hunts::load();
+        let hunt = match hunts.active_mut() {
+            Some(hunt) => hunt,
+            None => break,
+        };
+        let before = hunt.count;
+        hunt.add(1, Utc::now());
+        let line = hunt.stats_line(None);
+        if every
+            .filter(|every| *every > 0 && hunt.count % every == 0)
+            .is_some()
+        {
+            hotkeys::notify(&format!("{} attempts", hunt.count), &line);
+        }
+        println!("{}", line);
+        notify_milestones(hunt, before, printer).await;
+        if let Err(err) = hunts.save() {
+            warn!("couldn't save the hunt: {}", err);
+        }
+    }
+}
+
+/// Posts the `hunt_milestone` webhooks for each threshold `hunt` passed since
+/// its count was `before`, warning about those that fail.
+pub async fn notify_milestones(hunt: &Hunt, before: u32, printer: &Printer) {
+    let milestones = hunt.milestones_since(before);
+    if milestones.is_empty() {
+        return;
+    }
+    let config = Config::load().webhooks;
+    for chance in milestones {
+        let event = Event::HuntMilestone {
+            pokemon: hunt.pokemon.clone(),
+            chance,
+            attempts: hunt.count,
+            odds: hunt.odds,
+        };
+        for failure in webhook::notify(&config, &event).await {
+            printer.print_warning(&failure);
+        }
+    }
+}
+
+pub async fn run(matches: &ArgMatches<'_>) {
+    let printer = Printer::new();
+    #[cfg(all(feature = "hotkeys", target_os = "linux"))]
+    if let ("listen", Some(matches)) = matches.subcommand() {
+        return listen(matches, &printer).await;
+    }
+    let mut hunts = Hunts::load();
+    // The count before attempts were added, to tell which milestones passed
+    let mut before = None;
+
+    match matches.subcommand() {
+        ("start", Some(matches)) => {
+            let query = matches.value_of("pokemon").unwrap_or_default();
+            let pokemon = match pokedex::search_by_name(query, 1).into_iter().next() {
+                Some(result) => result.pokemon,
+                None => return printer.print_failure("Couldn't find any matches"),
+            };
+            let slug = pokemon.sprite_name_slug();
+            let hunt = hunts
+                .hunts
+                .entry(slug.clone())
+                .or_insert_with(|| Hunt::new(&pokemon.name, DEFAULT_ODDS, 0, Utc::now()));
+            if let Some(odds) = matches.value_of("odds").and_then(parse_odds) {
+                hunt.odds = odds;
+            }
+            if matches.is_present("count") {
+                hunt.count = value_t!(matches, "count", u32).unwrap_or_else(|err| err.exit());
+            }
+            hunts.active = Some(slug);
+        }
+        ("add", Some(matches)) => {
+            let attempts = value_t!(matches, "attempts", u32).unwrap_or_else(|err| err.exit());
+            match hunts.active_mut() {
+                Some(hunt) => {
+                    before = Some(hunt.count);
+                    hunt.add(attempts, Utc::now());
+                }
+                None => {
+                    return printer.print_failure("No active hunt, start one with pkmn hunt start")
+                }
+            }
+        }
+        ("overlay", Some(matches)) if matches.is_present("stop") => {
+            hunts.overlay = None;
+            if let Err(err) = hunts.save() {
+                return printer.print_failure(&format!("Couldn't save the hunt: {}", err));
+            }
+            return printer.print_center(style("The overlay is no longer updated").dim());
+        }
+        ("overlay", Some(matches)) => {
+            let out = matches.value_of("out").unwrap_or_default();
+            // Kept absolute, so commands run from elsewhere update the same file
+            let path = match std::env::current_dir() {
+                Ok(dir) => dir.join(out),
+                Err(_) => out.into(),
+            };
+            let refresh = value_t!(matches, "refresh", u32).unwrap_or_else(|err| err.exit());
+            hunts.overlay = Some(Overlay { path, refresh });
+        }
+        _ => {}
+    }
+
+    let hunt = match hunts.active() {
+        Some(hunt) => hunt,
+        None => return printer.print_failure("No active hunt, start one with pkmn hunt start"),
+    };
+    match matches.subcommand() {
+        ("status", Some(matches)) if matches.is_present("line") => {
+            return println!("{}", hunt.stats_line(None))
+        }
+        ("overlay", _) => {}
+        _ => print_status(hunt, &printer),
+    }
+
+    if matches.subcommand_name() != Some("status") {
+        if let Err(err) = hunts.save() {
+            printer.print_failure(&format!("Couldn't save the hunt: {}", err));
+        }
+    }
+    if let Some(before) = before {
+        notify_milestones(hunt, before, &printer).await;
+    }
+
+    if let (Some("overlay"), Some(overlay)) = (matches.subcommand_name(), &hunts.overlay) {
+        match hunts.write_overlay() {
+            Ok(()) => {
+                let hint = match overlay.is_json() {
+                    true => "",
+                    false => "(add it to OBS as a browser source using a local file)",
+                };
+                printer.print_info(
+                    "Overlay",
+                    format!(
+                        "{} {}",
+                        style(overlay.path.display()).cyan(),
+                        style(hint).dim()
+                    ),
+                )
+            }
+            Err(err) => printer.print_failure(&format!("Couldn't write the overlay: {}", err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn odds() {
+        assert_eq!(parse_odds("4096"), Some(4096));
+        assert_eq!(parse_odds("1/1365"), Some(1365));
+        assert_eq!(parse_odds("1/0"), None);
+        assert_eq!(parse_odds("2/4096"), None);
+    }
+}
@@ -0,0 +1,79 @@
+use std::fs::File;
+use std::path::Path;
+
+use chrono::Utc;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::commands::learn::validate_profile;
+use crate::hunt::Hunts;
+use crate::learn::Deck;
+use crate::print::Printer;
+use crate::tracker::{self, Format, TRACKERS};
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("import")
+        .about("Imports a tracker's history from CSV or JSON, e.g. as written by pkmn export")
+        .arg(
+            Arg::with_name("tracker")
+                .value_name("TRACKER")
+                .possible_values(&TRACKERS)
+                .required(true),
+        )
+        .arg(Arg::with_name("file").value_name("FILE").required(true))
+        .arg(
+            Arg::with_name("format")
+                .long("format")
+                .help("Defaults to the file's extension")
+                .possible_values(&Format::NAMES)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("profile")
+                .long("profile")
+                .help("The learning profile to import into")
+                .takes_value(true)
+                .default_value("default")
+                .validator(validate_profile),
+        )
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+    if let Err(err) = import(matches, &printer) {
+        printer.print_failure(&err);
+    }
+}
+
+fn import(matches: &ArgMatches, printer: &Printer) -> Result<(), String> {
+    let path = Path::new(matches.value_of("file").unwrap_or_default());
+    let format = matches
+        .value_of("format")
+        .and_then(Format::from_name)
+        .or_else(|| Format::from_path(path))
+        .ok_or("Couldn't tell the format from the file's extension, give it with --format")?;
+    let file =
+        File::open(path).map_err(|err| format!("Couldn't read {}: {}", path.display(), err))?;
+    let invalid = |err| format!("Couldn't import {}: {}", path.display(), err);
+
+    let (count, what) = match matches.value_of("tracker") {
+        Some("learn") => {
+            let profile = matches.value_of("profile").unwrap_or("default");
+            let mut deck = Deck::load(profile);
+            let count = deck.import(tracker::read(file, format).map_err(invalid)?)?;
+            deck.save(profile)
+                .map_err(|err| format!("Couldn't save the profile: {}", err))?;
+            (count, "reviews")
+        }
+        _ => {
+            let mut hunts = Hunts::load();
+            let count = hunts.import(tracker::read(file, format).map_err(invalid)?, Utc::now())?;
+            hunts
+                .save()
+                .map_err(|err| format!("Couldn't save the hunts: {}", err))?;
+            (count, "hunts")
+        }
+    };
+    printer.print_center(format!("Imported {} {}", style(count).green(), what));
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use std::io::{self, BufRead, Write};
+
+use chrono::{Local, NaiveDate};
+use clap::{value_t, App, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::commands;
+use crate::learn::{Card, Deck, Fact};
+use crate::pokedex;
+use crate::print::Printer;
+
+const FACTS: [&str; 3] = ["typing", "weaknesses", "speed"];
+
+pub fn validate_profile(value: String) -> Result<(), String> {
+    match !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    {
+        true => Ok(()),
+        false => Err(String::from(
+            "Profile names are made of letters, numbers, - and _",
+        )),
+    }
+}
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("learn")
+        .about("Quizzes typings, weaknesses and base Speed, scheduled by spaced repetition")
+        .arg(
+            Arg::with_name("profile")
+                .long("profile")
+                .help("Whose reviews to keep, for several players or goals on one machine")
+                .takes_value(true)
+                .default_value("default")
+                .validator(validate_profile),
+        )
+        .arg(
+            Arg::with_name("facts")
+                .long("facts")
+                .help("Facts to be quizzed on, separated by commas")
+                .possible_values(&FACTS)
+                .takes_value(true)
+                .use_delimiter(true)
+                .default_value("typing,weaknesses,speed"),
+        )
+        .arg(
+            Arg::with_name("type")
+                .long("type")
+                .value_name("TYPES")
+                .help("Only Pokémon of this type, or both of two types (e.g. fire,flying)")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("gen")
+                .long("gen")
+                .value_name("GENERATIONS")
+                .help("Only Pokémon introduced in this generation, or range (e.g. 1-3)")
+                .takes_value(true),
+        )
+        .arg(commands::where_arg())
+        .arg(
+            Arg::with_name("new")
+                .long("new")
+                .help("Most cards never seen before to add to today's reviews")
+                .takes_value(true)
+                .default_value("10"),
+        )
+        .arg(
+            Arg::with_name("stats")
+                .long("stats")
+                .help("Prints how many cards are learned and due instead of reviewing"),
+        )
+}
+
+fn print_stats(deck: &Deck, cards: &[Card], today: NaiveDate, printer: &Printer) {
+    let reviews = cards
+        .iter()
+        .filter_map(|card| deck.cards.get(&card.id()))
+        .collect::<Vec<_>>();
+    let due = reviews.iter().filter(|review| review.due <= today).count();
+    let learned = reviews
+        .iter()
+        .filter(|review| review.repetitions > 0)
+        .count();
+
+    printer.print_info("Cards", style(cards.len()).cyan());
+    printer.print_info("Seen", style(reviews.len()).cyan());
+    printer.print_info("Learned", style(learned).green());
+    printer.print_info("Due today", style(due).yellow());
+    if let Some(next) = reviews.iter().map(|review| review.due).min() {
+        printer.print_info("Next review", style(next).cyan());
+    }
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    let filter = match commands::filter(matches) {
+        Ok(filter) => filter,
+        Err(err) => return printer.print_failure(&err),
+    };
+    let pokemon = pokedex::filter(&filter);
+    let facts = matches
+        .values_of("facts")
+        .unwrap_or_default()
+        .filter_map(Fact::from_name)
+        .collect::<Vec<Fact>>();
+    let new = value_t!(matches, "new", usize).unwrap_or_else(|e| e.exit());
+
+    let profile = matches.value_of("profile").unwrap_or("default");
+    let mut deck = Deck::load(profile);
+    let today = Local::now().naive_local().date();
+    let cards = Card::all(&pokemon, &facts);
+
+    if matches.is_present("stats") {
+        return print_stats(&deck, &cards, today, &printer);
+    }
+
+    let next_due = deck.next_due(&cards);
+    let session = deck.session(cards, today, new);
+    if session.is_empty() {
+        printer.print_center(style("Nothing to review today").green());
+        if let Some(next) = next_due {
+            printer.print_center(format!("Next review on {}", style(next).cyan()));
+        }
+        return;
+    }
+
+    printer.print_center(format!(
+        "{} to review, type {} to stop",
+        style(format!("{} cards", session.len())).yellow(),
+        style("q").cyan()
+    ));
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut reviewed = 0;
+    for card in &session {
+        println!();
+        print!("{} ", style(card.question()).bold());
+        let _ = io::stdout().flush();
+
+        let response = match lines.next() {
+            Some(Ok(line)) if line.trim() != "q" => line,
+            _ => break,
+        };
+        let quality = card.grade(&response);
+        let due = deck.answer(card, quality, today);
+        reviewed += 1;
+
+        let verdict = match quality {
+            5 => style("Correct").green(),
+            3 => style("Close").yellow(),
+            _ => style("Not quite").red(),
+        };
+        println!(
+            "{}: {} {}",
+            verdict,
+            style(card.answer()).cyan(),
+            style(format!("(next on {})", due)).dim()
+        );
+
+        // Saved as it goes, so stopping early keeps the answers so far
+        if let Err(err) = deck.save(profile) {
+            warn!("couldn't save learning profile \"{}\": {}", profile, err);
+        }
+    }
+
+    println!();
+    printer.print_center(format!(
+        "Reviewed {} of {} cards",
+        style(reviewed).green(),
+        session.len()
+    ));
+}
@@ -0,0 +1,419 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BTreeMap;
+
+use clap::{value_t, App, Arg, ArgMatches, SubCommand};
+use console::{pad_str, style, Alignment};
+
+use crate::calc::stats::Stat;
+use crate::classification::{all_classifications, COLORS, HABITATS, SHAPES};
+use crate::commands;
+use crate::computed;
+use crate::config::Config;
+use crate::fuzzy;
+use crate::pokedex::{self, Pokemon};
+use crate::print::Printer;
+use crate::size::{SizeClass, SizeStats};
+use crate::trick_room::{self, Format};
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("list")
+        .about("Lists Pokémon matching filters")
+        .arg(
+            Arg::with_name("type")
+                .long("type")
+                .value_name("TYPES")
+                .help("Only Pokémon of this type, or both of two types (e.g. fire,flying)")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("gen")
+                .long("gen")
+                .value_name("GENERATIONS")
+                .help("Only Pokémon introduced in this generation, or range (e.g. 1-3)")
+                .takes_value(true),
+        )
+        .arg(commands::where_arg())
+        .arg(
+            Arg::with_name("size-class")
+                .long("size-class")
+                .help("Only Pokémon of this size, relative to the whole Pokédex")
+                .possible_values(&SizeClass::NAMES)
+                .case_insensitive(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("color")
+                .long("color")
+                .help("Only Gen 1 Pokémon of this Pokédex color")
+                .possible_values(&COLORS)
+                .case_insensitive(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("shape")
+                .long("shape")
+                .help("Only Gen 1 Pokémon of this body shape, where it's known")
+                .possible_values(&SHAPES)
+                .case_insensitive(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("habitat")
+                .long("habitat")
+                .help("Only Gen 1 Pokémon found in this habitat")
+                .possible_values(&HABITATS)
+                .case_insensitive(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("trick-room").long("trick-room").help(
+                "Ranks by Trick Room viability: low minimum Speed with strong offense and bulk",
+            ),
+        )
+        .arg(
+            Arg::with_name("format")
+                .long("format")
+                .help("Format whose level minimum Speed is calculated at; defaults to vgc")
+                .possible_values(&Format::NAMES)
+                .takes_value(true)
+                .requires("trick-room"),
+        )
+        .arg(
+            Arg::with_name("sort")
+                .long("sort")
+                .help(
+                    "Orders by Pokédex number (the default), name, or highest base stat total, \
+                     stat (e.g. sp-attack) or computed field of the config first",
+                )
+                .takes_value(true)
+                .conflicts_with("trick-room"),
+        )
+        .arg(
+            Arg::with_name("limit")
+                .long("limit")
+                .help("Number of Pokémon to show")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("offset")
+                .long("offset")
+                .help("Number of Pokémon to skip, for paging with --limit")
+                .takes_value(true)
+                .default_value("0"),
+        )
+}
+
+const SORT_KEYS: [&str; 9] = [
+    "number",
+    "name",
+    "total",
+    "hp",
+    "attack",
+    "defense",
+    "sp-attack",
+    "sp-defense",
+    "speed",
+];
+
+/// Sorts by a [`SORT_KEYS`] key or one of the config's computed `fields`,
+/// highest first, with Pokémon it can't be computed for last. Ties keep their
+/// Pokédex order.
+fn sort(
+    pokemon: &mut [&Pokemon],
+    key: &str,
+    fields: &BTreeMap<String, String>,
+) -> Result<(), String> {
+    if let (false, Some(expression)) = (SORT_KEYS.contains(&key), fields.get(key)) {
+        let value = |pokemon: &Pokemon| {
+            computed::evaluate(pokemon, expression)
+                .ok()?
+                .as_number()
+                .ok()
+        };
+        let mut keyed = pokemon
+            .iter()
+            .map(|pokemon| (value(pokemon), *pokemon))
+            .collect::<Vec<(Option<f64>, &Pokemon)>>();
+        keyed.sort_by(|(a, _), (b, _)| match (a, b) {
+            (Some(a), Some(b)) => b.total_cmp(a),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        });
+        for (slot, (_, sorted)) in pokemon.iter_mut().zip(keyed) {
+            *slot = sorted;
+        }
+        return Ok(());
+    }
+
+    let stat = match key {
+        "number" => {
+            pokemon.sort_by_key(|pokemon| pokemon.pokedex_number);
+            return Ok(());
+        }
+        "name" => {
+            pokemon.sort_by(|a, b| a.name.cmp(&b.name));
+            return Ok(());
+        }
+        "total" => {
+            pokemon.sort_by_key(|pokemon| Reverse(pokemon.total_points));
+            return Ok(());
+        }
+        "hp" => Stat::Hp,
+        "attack" => Stat::Attack,
+        "defense" => Stat::Defense,
+        "sp-attack" => Stat::SpAttack,
+        "sp-defense" => Stat::SpDefense,
+        "speed" => Stat::Speed,
+        _ => {
+            let keys = SORT_KEYS
+                .iter()
+                .map(|key| key.to_string())
+                .chain(fields.keys().cloned())
+                .collect::<Vec<String>>();
+            return Err(match fuzzy::did_you_mean(&keys, key) {
+                Some(suggestion) => format!(
+                    "Unknown sort key \"{}\", did you mean \"{}\"?",
+                    key, suggestion
+                ),
+                None => format!(
+                    "Unknown sort key \"{}\" (expected one of: {})",
+                    key,
+                    keys.join(", ")
+                ),
+            });
+        }
+    };
+    pokemon.sort_by_key(|pokemon| Reverse(stat.base(pokemon)));
+    Ok(())
+}
+
+/// The page of `items` after skipping `offset`, with `limit` items at most.
+fn page<T>(items: Vec<T>, offset: usize, limit: Option<usize>) -> Vec<T> {
+    items
+        .into_iter()
+        .skip(offset)
+        .take(limit.unwrap_or(usize::MAX))
+        .collect()
+}
+
+fn print_page_footer(shown: usize, offset: usize, total: usize, printer: &Printer) {
+    if shown == total {
+        return;
+    }
+    println!();
+    let range = match shown {
+        0 => String::from("none"),
+        _ => format!("{}–{}", offset + 1, offset + shown),
+    };
+    printer.print_center(style(format!("Showing {} of {}", range, total)).dim());
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+    let offset = value_t!(matches, "offset", usize).unwrap_or_else(|err| err.exit());
+    let limit = matches
+        .value_of("limit")
+        .map(|_| value_t!(matches, "limit", usize).unwrap_or_else(|err| err.exit()));
+    let size_stats = SizeStats::pokedex();
+
+    let filter = match commands::filter(matches) {
+        Ok(filter) => filter,
+        Err(err) => return printer.print_failure(&err),
+    };
+    let pokemon = pokedex::filter(&filter);
+
+    let size_class = matches
+        .value_of("size-class")
+        .and_then(SizeClass::from_name);
+
+    let color = matches.value_of("color").map(str::to_lowercase);
+    let shape = matches.value_of("shape").map(str::to_lowercase);
+    let habitat = matches.value_of("habitat").map(str::to_lowercase);
+    let classifications = match all_classifications() {
+        Ok(classifications) => classifications,
+        Err(err) => return printer.print_failure(&err.to_string()),
+    };
+    if (color.is_some() || shape.is_some() || habitat.is_some())
+        && filter.generations != Some(1..=1)
+    {
+        printer.print_warning("Colors, shapes and habitats are only bundled for Gen 1 species");
+    }
+    if shape.is_some() {
+        let unknown = classifications
+            .iter()
+            .filter(|classification| classification.shape.is_none())
+            .count();
+        printer.print_warning(&format!(
+            "{} Gen 1 species have no recorded shape and never match --shape",
+            unknown
+        ));
+    }
+    let classification = |pokemon: &Pokemon| {
+        classifications
+            .iter()
+            .find(|classification| classification.pokedex_number == pokemon.pokedex_number)
+    };
+
+    let mut results = pokemon
+        .iter()
+        .filter(|pokemon| match size_class {
+            Some(size_class) => size_stats.size_class(pokemon) == Some(size_class),
+            None => true,
+        })
+        .filter(|pokemon| match &color {
+            Some(color) => classification(pokemon).is_some_and(|c| c.color == *color),
+            None => true,
+        })
+        .filter(|pokemon| match &shape {
+            Some(shape) => classification(pokemon).is_some_and(|c| c.shape.as_ref() == Some(shape)),
+            None => true,
+        })
+        .filter(|pokemon| match &habitat {
+            Some(habitat) => {
+                classification(pokemon).is_some_and(|c| c.habitat.as_ref() == Some(habitat))
+            }
+            None => true,
+        })
+        .collect::<Vec<&Pokemon>>();
+
+    if results.is_empty() {
+        return printer.print_failure("No Pokémon match");
+    }
+
+    if matches.is_present("trick-room") {
+        let format = matches
+            .value_of("format")
+            .and_then(Format::from_name)
+            .unwrap_or(Format::Vgc);
+        return print_trick_room(results, format, offset, limit, &printer);
+    }
+
+    let key = matches.value_of("sort").unwrap_or("number");
+    if let Err(err) = sort(&mut results, key, &Config::load().fields) {
+        return printer.print_failure(&err);
+    }
+    let total = results.len();
+    let results = page(results, offset, limit);
+    let shown = results.len();
+
+    for pokemon in results {
+        let size = match (pokemon.height_m, pokemon.weight_kg) {
+            (Some(height), Some(weight)) => format!("{} m, {} kg", height, weight),
+            (Some(height), None) => format!("{} m", height),
+            (None, Some(weight)) => format!("{} kg", weight),
+            (None, None) => String::from("-"),
+        };
+        let types = match &*pokemon.type_2 {
+            "" => pokemon.type_1.to_string(),
+            type_2 => format!("{}/{}", pokemon.type_1, type_2),
+        };
+        printer.print_info(
+            format!(
+                "{} {}",
+                style(format!("#{:03}", pokemon.pokedex_number)).dim(),
+                pokemon.name
+            ),
+            format!(
+                "{} {} {}",
+                style(pad_str(&types, 17, Alignment::Left, None)).magenta(),
+                style(pad_str(
+                    &pokemon.total_points.to_string(),
+                    3,
+                    Alignment::Right,
+                    None
+                ))
+                .cyan(),
+                style(size).dim()
+            ),
+        );
+    }
+    print_page_footer(shown, offset, total, &printer);
+}
+
+fn print_trick_room(
+    pokemon: Vec<&Pokemon>,
+    format: Format,
+    offset: usize,
+    limit: Option<usize>,
+    printer: &Printer,
+) {
+    printer.print_section_heading(&format!("Trick Room viability at level {}", format.level()));
+    let ranked = trick_room::rank(pokemon, format);
+    let total = ranked.len();
+    let ranked = page(ranked, offset, limit);
+    let shown = ranked.len();
+    for (pokemon, viability) in ranked {
+        printer.print_info(
+            format!(
+                "{} {}",
+                style(format!("#{:03}", pokemon.pokedex_number)).dim(),
+                pokemon.name
+            ),
+            format!(
+                "{} {}  {}",
+                style(format!("{:.0}", viability.score)).cyan(),
+                style("score").dim(),
+                style(format!(
+                    "{} min Speed, {} offense, {:.0} bulk",
+                    viability.min_speed, viability.offense, viability.bulk
+                ))
+                .dim()
+            ),
+        );
+    }
+    print_page_footer(shown, offset, total, printer);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::Filter;
+
+    fn names(pokemon: &[&Pokemon]) -> Vec<String> {
+        pokemon.iter().map(|pokemon| pokemon.name.clone()).collect()
+    }
+
+    #[test]
+    fn sorts_highest_stats_first() {
+        let filter = Filter::parse(None, Some("1"), None, &BTreeMap::new()).unwrap();
+        let kanto = pokedex::filter(&filter);
+        let mut pokemon = kanto.iter().collect::<Vec<&Pokemon>>();
+
+        let fields = BTreeMap::new();
+        sort(&mut pokemon, "speed", &fields).unwrap();
+        assert_eq!(names(&pokemon[..2]), vec!["Electrode", "Jolteon"]);
+        sort(&mut pokemon, "name", &fields).unwrap();
+        assert_eq!(names(&pokemon[..1]), vec!["Abra"]);
+        sort(&mut pokemon, "number", &fields).unwrap();
+        assert_eq!(names(&pokemon[..1]), vec!["Bulbasaur"]);
+        assert!(sort(&mut pokemon, "sped", &fields)
+            .unwrap_err()
+            .contains("did you mean \"speed\""));
+    }
+
+    #[test]
+    fn sorts_by_computed_fields() {
+        let filter = Filter::parse(None, Some("1"), None, &BTreeMap::new()).unwrap();
+        let kanto = pokedex::filter(&filter);
+        let mut pokemon = kanto.iter().collect::<Vec<&Pokemon>>();
+
+        let fields = BTreeMap::from([
+            (String::from("bulk"), String::from("hp * defense")),
+            (String::from("broken"), String::from("hp * nothing")),
+        ]);
+        sort(&mut pokemon, "bulk", &fields).unwrap();
+        assert_eq!(names(&pokemon[..2]), vec!["Rhydon", "Slowbro"]);
+        // Fields that can't be computed keep the order
+        sort(&mut pokemon, "number", &fields).unwrap();
+        sort(&mut pokemon, "broken", &fields).unwrap();
+        assert_eq!(names(&pokemon[..1]), vec!["Bulbasaur"]);
+    }
+
+    #[test]
+    fn pages() {
+        assert_eq!(page(vec![1, 2, 3, 4, 5], 1, Some(2)), vec![2, 3]);
+        assert_eq!(page(vec![1, 2, 3], 2, None), vec![3]);
+        assert!(page(vec![1, 2, 3], 5, Some(2)).is_empty());
+    }
+}
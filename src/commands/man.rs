@@ -0,0 +1,38 @@
+use clap::{App, ArgMatches, SubCommand};
+
+use crate::help;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("man")
+        .about("Prints a man page in roff format (e.g. pkmn man | man -l -)")
+}
+
+fn help_text(app: &App) -> String {
+    let mut buffer = Vec::new();
+    if let Err(err) = app.write_help(&mut buffer) {
+        warn!("couldn't render help: {}", err);
+    }
+    String::from_utf8_lossy(&buffer).into_owned()
+}
+
+pub fn run(_matches: &ArgMatches) {
+    let name = env!("CARGO_PKG_NAME");
+    let commands = super::all()
+        .into_iter()
+        .map(|command| {
+            let command_name = command.get_name().to_string();
+            let command = command.bin_name(format!("{} {}", name, command_name));
+            (command_name, help_text(&command))
+        })
+        .collect::<Vec<(String, String)>>();
+
+    print!(
+        "{}",
+        help::man_page(
+            name,
+            env!("CARGO_PKG_VERSION"),
+            &help_text(&crate::cli()),
+            &commands
+        )
+    );
+}
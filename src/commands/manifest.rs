@@ -0,0 +1,182 @@
+use std::fs::File;
+use std::io::{self, Write};
+use std::sync::Arc;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use console::style;
+use serde::Serialize;
+use tokio::sync::Semaphore;
+
+use crate::pokedex::{self, Filter};
+use crate::print::Printer;
+use crate::provenance::{fingerprint, pokedex_version};
+use crate::sprite::SpriteStyle;
+
+/// Sprites downloaded at once for checksums, to go easy on the host.
+const CONCURRENT_DOWNLOADS: usize = 8;
+
+fn validate_filter(value: String) -> Result<(), String> {
+    Filter::parse_query(&value).map(|_| ())
+}
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("manifest")
+        .about("Writes the sprite URL and checksum of every Pokémon as JSON, for mirroring sprites")
+        .arg(
+            Arg::with_name("filter")
+                .long("filter")
+                .value_name("QUERY")
+                .help("Only these Pokémon, e.g. 'gen:<=3' or 'gen:1-2 type:fire'")
+                .takes_value(true)
+                .validator(validate_filter),
+        )
+        .arg(
+            Arg::with_name("style")
+                .long("style")
+                .help("Which sprites to link to; gen5 and artwork only have base forms")
+                .possible_values(&SpriteStyle::NAMES)
+                .takes_value(true)
+                .default_value("default"),
+        )
+        .arg(
+            Arg::with_name("out")
+                .long("out")
+                .value_name("FILE")
+                .help("Writes to a file instead of printing")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("no-checksums")
+                .long("no-checksums")
+                .help("Lists URLs without downloading the sprites to checksum them"),
+        )
+}
+
+#[derive(Serialize)]
+struct Manifest {
+    style: &'static str,
+    /// Version of the Pokédex the names and slugs come from.
+    data_version: String,
+    /// How checksums are calculated, if there are any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    checksum: Option<&'static str>,
+    sprites: Vec<Entry>,
+}
+
+#[derive(Serialize)]
+struct Entry {
+    name: String,
+    slug: String,
+    number: u16,
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    checksum: Option<String>,
+    /// Why the sprite couldn't be downloaded for a checksum.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+async fn fetch(client: reqwest::Client, url: String) -> Result<Vec<u8>, String> {
+    let res = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|err| err.to_string())?;
+    if !res.status().is_success() {
+        return Err(res.status().to_string());
+    }
+    let bytes = res.bytes().await.map_err(|err| err.to_string())?;
+    Ok(bytes.to_vec())
+}
+
+/// Downloads every entry's sprite and fills in its checksum, or its error.
+async fn add_checksums(entries: &mut [Entry]) {
+    let client = reqwest::Client::new();
+    let permits = Arc::new(Semaphore::new(CONCURRENT_DOWNLOADS));
+    let downloads = entries
+        .iter()
+        .map(|entry| {
+            let (client, url, permits) = (client.clone(), entry.url.clone(), permits.clone());
+            tokio::spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                fetch(client, url).await
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for (entry, download) in entries.iter_mut().zip(downloads) {
+        match download
+            .await
+            .map_err(|err| err.to_string())
+            .and_then(|result| result)
+        {
+            Ok(bytes) => entry.checksum = Some(fingerprint(&bytes)),
+            Err(err) => {
+                warn!("couldn't download {}: {}", entry.url, err);
+                entry.error = Some(err);
+            }
+        }
+    }
+}
+
+pub async fn run(matches: &ArgMatches<'_>) {
+    let printer = Printer::new();
+    let filter = Filter::parse_query(matches.value_of("filter").unwrap_or_default())
+        .expect("filters are validated");
+    let sprite_style = SpriteStyle::from_name(matches.value_of("style").unwrap_or_default())
+        .expect("clap only allows known styles");
+
+    let mut entries = pokedex::filter(&filter)
+        .into_iter()
+        .filter_map(|pokemon| {
+            Some(Entry {
+                url: sprite_style.url(&pokemon)?,
+                slug: pokemon.sprite_name_slug(),
+                number: pokemon.pokedex_number,
+                name: pokemon.name,
+                checksum: None,
+                error: None,
+            })
+        })
+        .collect::<Vec<Entry>>();
+    let checksums = !matches.is_present("no-checksums");
+    if checksums {
+        add_checksums(&mut entries).await;
+    }
+    let failed = entries.iter().filter(|entry| entry.error.is_some()).count();
+
+    let manifest = Manifest {
+        style: sprite_style.name(),
+        data_version: pokedex_version(),
+        checksum: Some("fnv1a-64").filter(|_| checksums),
+        sprites: entries,
+    };
+    let json = serde_json::to_string_pretty(&manifest).expect("manifests serialize to JSON");
+
+    let out = match matches.value_of("out") {
+        Some(out) => out,
+        None => {
+            if let Err(err) = writeln!(io::stdout(), "{}", json) {
+                warn!("couldn't print the manifest: {}", err);
+            }
+            return;
+        }
+    };
+    if let Err(err) = File::create(out).and_then(|mut file| writeln!(file, "{}", json)) {
+        return printer.print_failure(&format!("Couldn't write {}: {}", out, err));
+    }
+    printer.print_info(
+        "Manifest",
+        format!(
+            "{} {}",
+            style(format!("{} sprites", manifest.sprites.len())).cyan(),
+            style(format!("→ {}", out)).dim()
+        ),
+    );
+    if failed > 0 {
+        printer.print_info(
+            "Failed",
+            style(format!("{} downloads, marked with an error", failed)).red(),
+        );
+    }
+}
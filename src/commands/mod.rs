@@ -0,0 +1,111 @@
+use clap::{App, Arg, ArgMatches};
+
+use crate::config::Config;
+use crate::pokedex::Filter;
+
+pub mod ability;
+pub mod anniversary;
+pub mod backup;
+pub mod cache;
+pub mod calc;
+pub mod challenge;
+pub mod compare;
+pub mod completions;
+pub mod core_gaps;
+pub mod cores;
+pub mod data;
+pub mod doctor;
+pub mod export;
+pub mod field;
+pub mod gen;
+pub mod go;
+pub mod help;
+pub mod hunt;
+pub mod import;
+pub mod learn;
+pub mod list;
+pub mod man;
+pub mod manifest;
+pub mod mono;
+pub mod moves;
+pub mod nature;
+pub mod natures;
+pub mod open;
+pub mod random;
+pub mod search;
+pub mod share;
+pub mod show;
+pub mod stats;
+pub mod status;
+pub mod suggest_evs;
+pub mod tcg;
+pub mod team;
+pub mod tera;
+pub mod timer;
+pub mod update_data;
+
+/// Every subcommand, in the order they're listed in `--help`.
+pub fn all() -> Vec<App<'static, 'static>> {
+    vec![
+        ability::subcommand(),
+        anniversary::subcommand(),
+        backup::subcommand(),
+        cache::subcommand(),
+        calc::subcommand(),
+        challenge::subcommand(),
+        compare::subcommand(),
+        completions::subcommand(),
+        core_gaps::subcommand(),
+        cores::subcommand(),
+        data::subcommand(),
+        doctor::subcommand(),
+        export::subcommand(),
+        field::subcommand(),
+        gen::subcommand(),
+        go::subcommand(),
+        help::subcommand(),
+        hunt::subcommand(),
+        import::subcommand(),
+        learn::subcommand(),
+        list::subcommand(),
+        man::subcommand(),
+        manifest::subcommand(),
+        mono::subcommand(),
+        moves::subcommand(),
+        nature::subcommand(),
+        natures::subcommand(),
+        open::subcommand(),
+        random::subcommand(),
+        search::subcommand(),
+        share::subcommand(),
+        show::subcommand(),
+        stats::subcommand(),
+        status::subcommand(),
+        suggest_evs::subcommand(),
+        tcg::subcommand(),
+        team::subcommand(),
+        tera::subcommand(),
+        timer::subcommand(),
+        update_data::subcommand(),
+    ]
+}
+
+/// The `--where` option shared by every command that filters Pokémon.
+pub fn where_arg() -> Arg<'static, 'static> {
+    Arg::with_name("where")
+        .long("where")
+        .value_name("CONDITIONS")
+        .help("Only Pokémon whose base stats or computed fields meet these conditions (e.g. \"speed>=100 && total<600\")")
+        .takes_value(true)
+}
+
+/// The filter given by `--type`, `--gen` and `--where`, where conditions can
+/// use the config's computed fields.
+pub fn filter(matches: &ArgMatches) -> Result<Filter, String> {
+    Filter::parse(
+        matches.value_of("type"),
+        matches.value_of("gen"),
+        matches.value_of("where"),
+        &Config::load().fields,
+    )
+}
@@ -0,0 +1,97 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::games;
+use crate::generation::region_name;
+use crate::pokedex::{all_pokemon, Pokemon};
+use crate::print::Printer;
+use crate::types::Type;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("mono")
+        .about("Lists Pokémon of a type for planning a monotype run")
+        .arg(Arg::with_name("type").value_name("TYPE").required(true))
+        .arg(
+            Arg::with_name("game")
+                .long("game")
+                .help("Only Pokémon introduced by this game's generation, e.g. \"emerald\"")
+                .takes_value(true),
+        )
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    let type_name = matches.value_of("type").unwrap_or_default();
+    let pokemon_type = match Type::from_name(type_name) {
+        Some(pokemon_type) => pokemon_type,
+        None => return printer.print_failure(&format!("Unknown type \"{}\"", type_name)),
+    };
+
+    let game = match matches.value_of("game") {
+        Some(query) => match games::find_game(query) {
+            Some(game) => Some(game),
+            None => return printer.print_failure(&format!("Unknown game \"{}\"", query)),
+        },
+        None => None,
+    };
+    let max_generation = game.as_ref().map_or(u8::MAX, |game| game.generation);
+
+    // One entry per species and regional form, so e.g. Alolan Vulpix is
+    // listed alongside Vulpix but Megas aren't
+    let mut pokedex = match all_pokemon() {
+        Ok(pokedex) => pokedex,
+        Err(err) => return printer.print_failure(&err.to_string()),
+    };
+    pokedex.dedup_by(|pokemon, previous| {
+        (pokemon.pokedex_number, pokemon.regional_form())
+            == (previous.pokedex_number, previous.regional_form())
+    });
+    let candidates = pokedex
+        .iter()
+        .filter(|pokemon| pokemon.introduced_in() <= max_generation)
+        .filter(|pokemon| pokemon.types().contains(&pokemon_type))
+        .collect::<Vec<&Pokemon>>();
+
+    printer.print_center(style(format!("Monotype {}", pokemon_type)).magenta());
+    if let Some(game) = &game {
+        printer.print_center(style(&game.name).dim());
+    }
+
+    let mut generation = 0;
+    for pokemon in &candidates {
+        if pokemon.generation != generation {
+            generation = pokemon.generation;
+            println!();
+            printer.print_section_heading(&format!(
+                "Generation {} ({})",
+                generation,
+                region_name(generation).unwrap_or("Unknown")
+            ));
+        }
+        let other_type = pokemon
+            .types()
+            .into_iter()
+            .find(|other| *other != pokemon_type);
+        printer.print_info(
+            format!(
+                "{} {}",
+                style(format!("#{:03}", pokemon.pokedex_number)).dim(),
+                pokemon.name
+            ),
+            match other_type {
+                Some(other) => style(other.to_string()).magenta(),
+                None => style(String::from("-")).dim(),
+            },
+        );
+    }
+    println!();
+
+    printer.print_info("Total", style(candidates.len()).cyan());
+    if game.is_some() {
+        printer.print_center(
+            style("Availability isn't bundled: this lists every species that exists by then").dim(),
+        );
+    }
+    println!();
+}
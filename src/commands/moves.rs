@@ -0,0 +1,166 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::moves::{self, Category, Flag, Move};
+use crate::pokedex;
+use crate::print::{styled_empty_value, Printer};
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("moves")
+        .about("Lists the moves a Pokémon learns, or looks up a move")
+        .arg(
+            Arg::with_name("pokemon")
+                .value_name("POKEMON|MOVE")
+                .help("A Pokémon, or a move's name, e.g. flamethrower")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("flag")
+                .long("flag")
+                .help("Only moves with this flag; repeat to require several")
+                .possible_values(&Flag::NAMES)
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+        )
+        .arg(
+            Arg::with_name("category")
+                .long("category")
+                .possible_values(&Category::NAMES)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("method")
+                .long("method")
+                .help("Only moves learned this way")
+                .possible_values(&moves::METHODS)
+                .takes_value(true),
+        )
+}
+
+/// Prints a move on one line: type, category, power, accuracy, PP, priority
+/// and, if given, how it's learned.
+pub fn print_move(m: &Move, method: Option<&str>, printer: &Printer) {
+    let power = match m.power {
+        Some(power) => style(power.to_string()).cyan(),
+        None => styled_empty_value(),
+    };
+    let accuracy = match m.accuracy {
+        Some(accuracy) => style(format!("{}%", accuracy)).cyan(),
+        None => styled_empty_value(),
+    };
+    let priority = match m.priority {
+        0 => String::new(),
+        priority => style(format!(" {:+}", priority)).yellow().to_string(),
+    };
+    let method = match method {
+        Some(method) => format!(" {}", style(format!("({})", method)).dim()),
+        None => String::new(),
+    };
+    printer.print_info(
+        &m.name,
+        format!(
+            "{} {} {} {} {}{}{}",
+            style(&m.move_type).magenta(),
+            style(m.category.name()).dim(),
+            power,
+            accuracy,
+            style(format!("{}/{} PP", m.pp, m.max_pp())).cyan(),
+            priority,
+            method
+        ),
+    );
+}
+
+fn print_move_details(m: &Move, printer: &Printer) {
+    printer.print_section_heading(&m.name);
+    printer.print_info("Type", style(&m.move_type).magenta());
+    printer.print_info("Category", style(m.category.name()).cyan());
+    printer.print_info(
+        "Power",
+        match m.power {
+            Some(power) => style(power.to_string()).cyan(),
+            None => styled_empty_value(),
+        },
+    );
+    printer.print_info(
+        "Accuracy",
+        match m.accuracy {
+            Some(accuracy) => style(format!("{}%", accuracy)).cyan(),
+            None => style(String::from("Never misses")).cyan(),
+        },
+    );
+    printer.print_info(
+        "PP",
+        format!(
+            "{} {}",
+            style(m.pp).cyan(),
+            style(format!("({} with PP Ups)", m.max_pp())).dim()
+        ),
+    );
+    if m.priority != 0 {
+        printer.print_info("Priority", style(format!("{:+}", m.priority)).yellow());
+    }
+    let flags = m.flags();
+    if !flags.is_empty() {
+        let names = flags.iter().map(Flag::name).collect::<Vec<&str>>();
+        printer.print_info("Flags", style(names.join(", ")).cyan());
+    }
+
+    let learners = moves::learned_by(m);
+    if learners.is_empty() {
+        return;
+    }
+    println!();
+    printer.print_section_heading("Learned By");
+    for (pokemon, method) in learners {
+        printer.print_info(pokemon, style(method).dim());
+    }
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    let query = matches.value_of("pokemon").unwrap_or_default();
+    if let Some(m) = moves::find_move(query) {
+        return print_move_details(&m, &printer);
+    }
+    let pokemon = match pokedex::search_by_name(query, 1).into_iter().next() {
+        Some(result) => result.pokemon,
+        None => return printer.print_failure("Couldn't find any matches"),
+    };
+
+    let flags = matches
+        .values_of("flag")
+        .unwrap_or_default()
+        .filter_map(Flag::from_name)
+        .collect::<Vec<Flag>>();
+    let category = matches.value_of("category").and_then(Category::from_name);
+    let method = matches.value_of("method");
+
+    let learnset = moves::learnset(&pokemon);
+    if learnset.is_empty() {
+        return printer.print_failure(&format!(
+            "No learnset bundled for {}; learnsets are only bundled for {}",
+            pokemon.name,
+            moves::learnset_pokemon().join(", ")
+        ));
+    }
+
+    let matching = learnset
+        .iter()
+        .filter(|(m, _)| flags.iter().all(|flag| m.has_flag(*flag)))
+        .filter(|(m, _)| category.is_none_or(|category| m.category == category))
+        .filter(|(_, learned)| method.is_none_or(|method| learned == method))
+        .collect::<Vec<_>>();
+
+    printer.print_section_heading(&format!("{} Moves", pokemon.name));
+    printer.print_center(style("Notable moves only, without level-up levels").dim());
+    if matching.is_empty() {
+        return printer.print_center(style("No moves match").dim());
+    }
+
+    for (m, method) in matching {
+        print_move(m, Some(method), &printer);
+    }
+}
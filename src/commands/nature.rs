@@ -0,0 +1,57 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::calc::stats::{Nature, NATURES};
+use crate::fuzzy;
+use crate::print::Printer;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("nature")
+        .about("Shows the stats a nature raises and lowers, and the berry flavors it likes")
+        .arg(
+            Arg::with_name("nature")
+                .value_name("NATURE")
+                .help("e.g. adamant")
+                .required(true),
+        )
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    let query = matches.value_of("nature").unwrap_or_default();
+    let nature = match Nature::from_name(query) {
+        Some(nature) => nature,
+        None => {
+            let names = NATURES.iter().map(|nature| nature.name);
+            return printer.print_failure(&match fuzzy::did_you_mean(names, query) {
+                Some(suggestion) => format!(
+                    "Unknown nature \"{}\", did you mean \"{}\"?",
+                    query, suggestion
+                ),
+                None => format!("Unknown nature \"{}\"", query),
+            });
+        }
+    };
+
+    printer.print_center(style(nature.name).yellow());
+    println!();
+    match (nature.increased, nature.decreased) {
+        (Some(increased), Some(decreased)) => {
+            printer.print_info(
+                "Raises",
+                style(format!("{} +10%", increased.name())).green(),
+            );
+            printer.print_info("Lowers", style(format!("{} −10%", decreased.name())).red());
+        }
+        _ => printer.print_paragraph(
+            "A neutral nature: no stat is raised or lowered, and no berry flavor is \
+             preferred.",
+            |line| style(line).dim(),
+        ),
+    }
+    if let (Some(liked), Some(disliked)) = (nature.liked_flavor(), nature.disliked_flavor()) {
+        printer.print_info("Likes", style(liked).cyan());
+        printer.print_info("Dislikes", style(disliked).cyan());
+    }
+}
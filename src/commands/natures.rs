@@ -0,0 +1,47 @@
+use clap::{App, ArgMatches, SubCommand};
+use console::{pad_str, style, Alignment};
+
+use crate::calc::stats::{Stat, NATURES};
+use crate::print::Printer;
+
+/// Width of each column, enough for `Sp. Defense` and a gap.
+const COLUMN_WIDTH: usize = 13;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("natures")
+        .about("Lists every nature with the stats it raises and lowers and its berry flavors")
+}
+
+fn column(value: Option<&str>) -> String {
+    pad_str(value.unwrap_or("-"), COLUMN_WIDTH, Alignment::Left, None).to_string()
+}
+
+pub fn run(_matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    // Rows have the same width, so centering each one keeps the columns
+    // aligned
+    printer.print_center(
+        style(format!(
+            "{}{}{}{}{:<width$}",
+            column(Some("Nature")),
+            column(Some("Raises")),
+            column(Some("Lowers")),
+            column(Some("Likes")),
+            "Dislikes",
+            width = COLUMN_WIDTH
+        ))
+        .bold(),
+    );
+    for nature in NATURES.iter() {
+        let name = |stat: Option<Stat>| stat.map(|stat| stat.name());
+        printer.print_center(format!(
+            "{}{}{}{}{}",
+            column(Some(nature.name)),
+            style(column(name(nature.increased))).green(),
+            style(column(name(nature.decreased))).red(),
+            style(column(nature.liked_flavor())).cyan(),
+            style(column(nature.disliked_flavor())).cyan()
+        ));
+    }
+}
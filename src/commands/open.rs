@@ -0,0 +1,109 @@
+use std::env;
+use std::io;
+use std::process::{Command, Stdio};
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::config::Config;
+use crate::fuzzy;
+use crate::links;
+use crate::pokedex;
+use crate::print::Printer;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("open")
+        .about("Opens a Pokémon's page on another site in the browser")
+        .arg(
+            Arg::with_name("pokemon")
+                .value_name("POKEMON")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("site")
+                .long("site")
+                .help(
+                    "bulbapedia, pokeapi, pokemondb, serebii, smogon, or a site from the \
+                     config's [links] table",
+                )
+                .takes_value(true)
+                .default_value("pokemondb"),
+        )
+        .arg(
+            Arg::with_name("print")
+                .long("print")
+                .help("Prints the URL instead of opening it"),
+        )
+}
+
+/// Opens `url` with `$BROWSER` if it's set, or the platform's opener.
+fn open_in_browser(url: &str) -> io::Result<()> {
+    let mut command = match env::var("BROWSER") {
+        Ok(browser) if !browser.trim().is_empty() => Command::new(browser.trim()),
+        _ if cfg!(target_os = "macos") => Command::new("open"),
+        // Not `cmd /C start`, which would split the URL at `&`.
+        _ if cfg!(windows) => {
+            let mut command = Command::new("rundll32");
+            command.arg("url.dll,FileProtocolHandler");
+            command
+        }
+        _ => Command::new("xdg-open"),
+    };
+
+    let status = command
+        .arg(url)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+    match status.success() {
+        true => Ok(()),
+        false => Err(io::Error::other(format!("browser exited with {}", status))),
+    }
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    let query = matches.value_of("pokemon").unwrap_or_default();
+    let pokemon = match pokedex::search_by_name(query, 1).into_iter().next() {
+        Some(result) => result.pokemon,
+        None => return printer.print_failure("Couldn't find any matches"),
+    };
+
+    let site = matches
+        .value_of("site")
+        .unwrap_or("pokemondb")
+        .to_lowercase();
+    let links = links::links(&pokemon, &Config::load().links);
+    let url = match links.get(&site) {
+        Some(url) => url,
+        None => {
+            return printer.print_failure(&match fuzzy::did_you_mean(links.keys(), &site) {
+                Some(suggestion) => {
+                    format!(
+                        "Unknown site \"{}\", did you mean \"{}\"?",
+                        site, suggestion
+                    )
+                }
+                None => format!("Unknown site \"{}\"", site),
+            })
+        }
+    };
+
+    if matches.is_present("print") {
+        return println!("{}", url);
+    }
+
+    match open_in_browser(url) {
+        Ok(()) => printer.print_center(format!(
+            "Opened {} on {}",
+            style(&pokemon.name).yellow(),
+            style(&site).cyan()
+        )),
+        Err(err) => {
+            info!("couldn't open a browser: {}", err);
+            printer.print_failure("Couldn't open a browser, the page is at:");
+            printer.print_center(url);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::commands;
+use crate::pokedex::{self, Pokemon};
+use crate::print::Printer;
+use crate::provenance::fingerprint;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("random")
+        .about("Prints a random Pokémon")
+        .arg(
+            Arg::with_name("type")
+                .long("type")
+                .value_name("TYPES")
+                .help("Only Pokémon of this type, or both of two types (e.g. fire,flying)")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("gen")
+                .long("gen")
+                .value_name("GENERATIONS")
+                .help("Only Pokémon introduced in this generation, or range (e.g. 1-3)")
+                .takes_value(true),
+        )
+        .arg(commands::where_arg())
+        .arg(
+            Arg::with_name("seed")
+                .long("seed")
+                .help("Picks the same Pokémon for the same seed, e.g. today's date")
+                .takes_value(true),
+        )
+}
+
+/// The Pokémon to print, whose card is printed like any other lookup so the
+/// card options still apply.
+pub fn pick(matches: &ArgMatches) -> Option<Pokemon> {
+    let printer = Printer::new();
+
+    let filter = match commands::filter(matches) {
+        Ok(filter) => filter,
+        Err(err) => {
+            printer.print_failure(&err);
+            return None;
+        }
+    };
+
+    let mut rng = match matches.value_of("seed") {
+        Some(seed) => StdRng::seed_from_u64(seed_from(seed)),
+        None => StdRng::from_entropy(),
+    };
+
+    let pokemon = pokedex::filter(&filter).choose(&mut rng).cloned();
+    if pokemon.is_none() {
+        printer.print_failure("No Pokémon match");
+    }
+    pokemon
+}
+
+/// A numeric seed as is, or any other text (e.g. `2024-05-01`) hashed, so the
+/// same text always picks the same Pokémon.
+pub fn seed_from(seed: &str) -> u64 {
+    seed.parse().unwrap_or_else(|_| {
+        u64::from_str_radix(&fingerprint(seed.as_bytes()), 16).expect("fingerprints are hex")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_pokemon() {
+        let matches = subcommand().get_matches_from(vec!["random", "--seed", "2024-05-01"]);
+        let first = pick(&matches).unwrap();
+        let second = pick(&matches).unwrap();
+        assert_eq!(first.name, second.name);
+    }
+
+    #[test]
+    fn respects_filters() {
+        let matches =
+            subcommand().get_matches_from(vec!["random", "--type", "ghost", "--gen", "1"]);
+        for _ in 0..10 {
+            let pokemon = pick(&matches).unwrap();
+            assert!(["Gastly", "Haunter", "Gengar"].contains(&pokemon.name.as_str()));
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use clap::{value_t, App, Arg, ArgGroup, ArgMatches, SubCommand};
+use console::style;
+use serde::Serialize;
+
+use crate::abilities;
+use crate::etymology;
+use crate::fuzzy::Match;
+use crate::pokedex::{self, all_pokemon};
+use crate::print::Printer;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("search")
+        .about("Searches the Pokédex")
+        .arg(
+            Arg::with_name("name")
+                .value_name("QUERY")
+                .help("Pokémon whose name is closest to this, best first"),
+        )
+        .arg(
+            Arg::with_name("ability-effect")
+                .long("ability-effect")
+                .help("Abilities whose description matches this text, with their holders")
+                .value_name("TEXT")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("etymology")
+                .long("etymology")
+                .help("Pokémon whose name origin or design notes match this text")
+                .value_name("TEXT")
+                .takes_value(true),
+        )
+        .group(
+            ArgGroup::with_name("query")
+                .args(&["name", "ability-effect", "etymology"])
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("limit")
+                .long("limit")
+                .help("Number of matches to show, by default 3 abilities or 10 Pokémon")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .visible_alias("format")
+                .help("Prints the matches for the terminal, or as JSON with their scores")
+                .possible_values(&["terminal", "json"])
+                .default_value("terminal"),
+        )
+}
+
+/// A match as printed in JSON: its rank from 1, the matched value's fields,
+/// and its score, where a similarity of 1 is an exact match.
+#[derive(Serialize)]
+struct Candidate<T: Serialize> {
+    rank: usize,
+    #[serde(flatten)]
+    value: T,
+    similarity: f64,
+    distance: usize,
+}
+
+#[derive(Serialize)]
+struct PokemonCandidate {
+    name: String,
+    number: u16,
+    slug: String,
+}
+
+fn print_json<T: Serialize>(results: Vec<Match<T>>, printer: &Printer) {
+    let candidates = results
+        .into_iter()
+        .enumerate()
+        .map(|(index, Match { value, score })| Candidate {
+            rank: index + 1,
+            value,
+            similarity: score.similarity,
+            distance: score.distance,
+        })
+        .collect::<Vec<_>>();
+    match serde_json::to_string_pretty(&candidates) {
+        Ok(json) => println!("{}", json),
+        Err(err) => printer.print_failure(&format!("Couldn't serialize matches: {}", err)),
+    }
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+    let limit = |default: usize| match matches.value_of("limit") {
+        Some(_) => value_t!(matches, "limit", usize).unwrap_or_else(|err| err.exit()),
+        None => default,
+    };
+
+    let json = matches.value_of("output") == Some("json");
+
+    if let Some(query) = matches.value_of("name") {
+        let results = pokedex::search_by_name(query, limit(10))
+            .into_iter()
+            .map(|result| Match {
+                value: PokemonCandidate {
+                    slug: result.pokemon.sprite_name_slug(),
+                    number: result.pokemon.pokedex_number,
+                    name: result.pokemon.name,
+                },
+                score: result.score,
+            })
+            .collect::<Vec<_>>();
+        return match json {
+            true => print_json(results, &printer),
+            false => print_names(results, &printer),
+        };
+    }
+
+    match matches.value_of("etymology") {
+        Some(query) if json => print_json(etymology::search(query, limit(10)), &printer),
+        Some(query) => print_etymologies(query, limit(10), &printer),
+        None => {
+            let query = matches.value_of("ability-effect").unwrap_or_default();
+            match json {
+                true => print_json(abilities::search_by_effect(query, limit(3)), &printer),
+                false => print_abilities(query, limit(3), &printer),
+            }
+        }
+    }
+}
+
+fn print_names(results: Vec<Match<PokemonCandidate>>, printer: &Printer) {
+    if results.is_empty() {
+        return printer.print_failure("Couldn't find any matches");
+    }
+
+    printer.print_section_heading("Pokémon");
+    for result in results {
+        printer.print_info(
+            &result.value.name,
+            format!(
+                "{} {}",
+                style(format!("#{}", result.value.number)).cyan(),
+                style(format!("{:.0}% similar", result.score.similarity * 100.0)).dim()
+            ),
+        );
+    }
+}
+
+fn print_etymologies(query: &str, limit: usize, printer: &Printer) {
+    let results = etymology::search(query, limit);
+    if results.is_empty() {
+        return printer.print_failure("Couldn't find any matches");
+    }
+
+    printer.print_section_heading("Etymology");
+    for result in results {
+        let etymology = result.value;
+        printer.print_info(&etymology.name, style(&etymology.origin).cyan());
+        if !etymology.notes.is_empty() {
+            printer.print_info("", style(&etymology.notes).dim());
+        }
+    }
+}
+
+fn print_abilities(query: &str, limit: usize, printer: &Printer) {
+    let results = abilities::search_by_effect(query, limit);
+    if results.is_empty() {
+        return printer.print_failure("Couldn't find any matches");
+    }
+
+    let pokedex = match all_pokemon() {
+        Ok(pokedex) => pokedex,
+        Err(err) => return printer.print_failure(&err.to_string()),
+    };
+    for result in results {
+        super::ability::print_ability(&result.value, &pokedex, printer);
+        println!();
+    }
+}
@@ -0,0 +1,58 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::config::Config;
+use crate::markdown;
+use crate::pokedex;
+use crate::print::Printer;
+use crate::share;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("share")
+        .about("Uploads a Pokémon's card as Markdown and prints its URL")
+        .arg(
+            Arg::with_name("pokemon")
+                .value_name("POKEMON")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("gist")
+                .long("gist")
+                .help("Uploads a secret GitHub gist instead of using the paste endpoint"),
+        )
+}
+
+pub async fn run(matches: &ArgMatches<'_>) {
+    let printer = Printer::new();
+
+    let query = matches.value_of("pokemon").unwrap_or_default();
+    let pokemon = match pokedex::search_by_name(query, 1).into_iter().next() {
+        Some(result) => result.pokemon,
+        None => return printer.print_failure("Couldn't find any matches"),
+    };
+
+    let card = markdown::render_card(&pokemon);
+    let config = Config::load().share;
+
+    let url = match (matches.is_present("gist"), &config.endpoint) {
+        (true, _) => match config.github_token() {
+            Some(token) => {
+                let filename = format!("{}.md", pokemon.sprite_name_slug());
+                share::upload_gist(&token, &filename, &card).await
+            }
+            None => {
+                return printer
+                    .print_failure("Set share.github_token or GITHUB_TOKEN to upload gists")
+            }
+        },
+        (false, Some(endpoint)) => share::upload_paste(endpoint, card).await,
+        (false, None) => {
+            return printer.print_failure("Set share.endpoint in the config to share cards")
+        }
+    };
+
+    match url {
+        Ok(url) => println!("{}", style(url).cyan()),
+        Err(err) => printer.print_failure(&format!("Upload failed: {}", err)),
+    }
+}
@@ -0,0 +1,30 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use crate::pokedex::{self, Filter, Pokemon};
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("show")
+        .about("Prints the cards of several Pokémon, in order")
+        .arg(
+            Arg::with_name("pokemon")
+                .value_name("POKEMON")
+                .help("Slugs, Pokédex numbers or names to search for")
+                .multiple(true)
+                .required(true),
+        )
+}
+
+/// The Pokémon to print, each looked up by slug or number and otherwise
+/// searched for by name. Like `random`, the cards are printed like any
+/// other lookup, so the card options still apply.
+pub fn pick(matches: &ArgMatches) -> Vec<Pokemon> {
+    matches
+        .values_of("pokemon")
+        .into_iter()
+        .flatten()
+        .filter_map(|query| {
+            pokedex::find_by_id(query)
+                .or_else(|| crate::lookup_pokemon_by_name(query, &Filter::default()))
+        })
+        .collect()
+}
@@ -0,0 +1,151 @@
+use clap::{value_t, App, Arg, ArgMatches, SubCommand};
+use console::{pad_str, style, Alignment};
+
+use crate::calc::stats::{self, Nature, Stat, Stats, MAX_IV, MAX_STAT_EVS, MAX_TOTAL_EVS};
+use crate::commands::calc;
+use crate::pokedex;
+use crate::print::Printer;
+
+fn validate_level(value: String) -> Result<(), String> {
+    match value.parse::<u16>() {
+        Ok(level) if (1..=100).contains(&level) => Ok(()),
+        _ => Err(String::from("Levels range from 1 to 100")),
+    }
+}
+
+fn validate_nature(value: String) -> Result<(), String> {
+    match Nature::from_name(&value) {
+        Some(_) => Ok(()),
+        None => Err(format!("Unknown nature \"{}\"", value)),
+    }
+}
+
+fn validate_ivs(value: String) -> Result<(), String> {
+    Stats::parse(&value, MAX_IV).map(|_| ())
+}
+
+fn validate_evs(value: String) -> Result<(), String> {
+    match Stats::parse(&value, MAX_STAT_EVS)?.total() {
+        total if total > MAX_TOTAL_EVS => Err(format!(
+            "{} EVs in total, but a Pokémon can have at most {}",
+            total, MAX_TOTAL_EVS
+        )),
+        _ => Ok(()),
+    }
+}
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("stats")
+        .about("Calculates a Pokémon's stats in the games from its IVs, EVs, level and nature")
+        .arg(
+            Arg::with_name("pokemon")
+                .value_name("POKEMON")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("level")
+                .long("level")
+                .takes_value(true)
+                .default_value("100")
+                .validator(validate_level),
+        )
+        .arg(
+            Arg::with_name("nature")
+                .long("nature")
+                .takes_value(true)
+                .default_value("serious")
+                .validator(validate_nature),
+        )
+        .arg(
+            Arg::with_name("evs")
+                .long("evs")
+                .value_name("HP/ATK/DEF/SPA/SPD/SPE")
+                .help("EVs in each stat, e.g. 0/252/0/0/4/252, or one number for all of them")
+                .takes_value(true)
+                .default_value("0")
+                .validator(validate_evs),
+        )
+        .arg(
+            Arg::with_name("ivs")
+                .long("ivs")
+                .value_name("HP/ATK/DEF/SPA/SPD/SPE")
+                .help("IVs in each stat, or one number for all of them")
+                .takes_value(true)
+                .default_value("31")
+                .validator(validate_ivs),
+        )
+        .arg(calc::explain_arg())
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    let query = matches.value_of("pokemon").unwrap_or_default();
+    let pokemon = match pokedex::search_by_name(query, 1).into_iter().next() {
+        Some(result) => result.pokemon,
+        None => return printer.print_failure("Couldn't find any matches"),
+    };
+    let level = value_t!(matches, "level", u16).unwrap_or_else(|err| err.exit());
+    let nature = Nature::from_name(matches.value_of("nature").unwrap_or_default())
+        .expect("natures are validated");
+    let evs = Stats::parse(matches.value_of("evs").unwrap_or_default(), MAX_STAT_EVS)
+        .expect("EVs are validated");
+    let ivs = Stats::parse(matches.value_of("ivs").unwrap_or_default(), MAX_IV)
+        .expect("IVs are validated");
+    let values = stats::stats(&pokemon, &ivs, &evs, level, &nature);
+
+    printer.print_section_heading(&format!("{} Stats", pokemon.name));
+    printer.print_info("Nature", style(nature.name).cyan());
+    printer.print_info("Level", style(level).cyan());
+    println!();
+
+    if matches.is_present("explain") {
+        for stat in Stat::ALL {
+            let (_, steps) = stats::explain_stat(
+                stat,
+                stat.base(&pokemon),
+                ivs.get(stat),
+                evs.get(stat),
+                level,
+                &nature,
+            );
+            printer.print_section_heading(stat.name());
+            calc::print_steps(&steps, &printer);
+            println!();
+        }
+        printer.print_info("Total", style(values.total()).yellow());
+        return;
+    }
+
+    for stat in Stat::ALL {
+        let inputs = format!(
+            "Base {} · {} IV · {} EVs",
+            stat.base(&pokemon),
+            ivs.get(stat),
+            evs.get(stat)
+        );
+        let effect = if nature.increased == Some(stat) {
+            style("+").green()
+        } else if nature.decreased == Some(stat) {
+            style("−").red()
+        } else {
+            style(" ")
+        };
+        printer.print_info(
+            stat.name(),
+            format!(
+                "{}{}  {}",
+                style(pad_str(
+                    &values.get(stat).to_string(),
+                    4,
+                    Alignment::Left,
+                    None
+                ))
+                .yellow(),
+                effect,
+                style(inputs).dim()
+            ),
+        );
+    }
+    printer.print_info("Total", style(values.total()).yellow());
+}
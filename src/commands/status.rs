@@ -0,0 +1,100 @@
+use clap::{value_t, App, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::generation::latest_generation;
+use crate::print::Printer;
+use crate::status;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("status")
+        .about("Explains a status condition, with its damage over time")
+        .arg(
+            Arg::with_name("status")
+                .value_name("STATUS")
+                .help("burn, poison, toxic or paralysis")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("gen")
+                .long("gen")
+                .help("Generation whose mechanics to use; defaults to the latest")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("hp")
+                .long("hp")
+                .help("Max HP, for the damage taken each turn")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("turns")
+                .long("turns")
+                .takes_value(true)
+                .default_value("5"),
+        )
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    let name = matches.value_of("status").unwrap_or_default();
+    let generation = match matches.value_of("gen") {
+        Some(_) => value_t!(matches, "gen", u8).unwrap_or_else(|err| err.exit()),
+        None => latest_generation(),
+    };
+
+    let mechanics = match status::mechanics(name, generation) {
+        Some(mechanics) => mechanics,
+        None => {
+            return printer.print_failure(&format!(
+                "No \"{}\" in generation {} (try {})",
+                name,
+                generation,
+                status::status_names().join(", ")
+            ))
+        }
+    };
+
+    let mut heading = mechanics.status.clone();
+    heading[..1].make_ascii_uppercase();
+    printer.print_section_heading(&format!("{} (Gen {})", heading, generation));
+    printer.print_info(
+        "Damage",
+        match (mechanics.damage_denominator, mechanics.escalating) {
+            (None, _) => style(String::from("None")).cyan(),
+            (Some(denominator), false) => {
+                style(format!("1/{} max HP per turn", denominator)).cyan()
+            }
+            (Some(denominator), true) => {
+                style(format!("n/{} max HP on turn n", denominator)).cyan()
+            }
+        },
+    );
+    printer.print_paragraph(&mechanics.notes, |line| style(line).dim());
+    println!();
+
+    if matches.value_of("hp").is_none() || mechanics.damage_denominator.is_none() {
+        return;
+    }
+
+    let max_hp = value_t!(matches, "hp", u16).unwrap_or_else(|err| err.exit());
+    let turns = value_t!(matches, "turns", u16).unwrap_or_else(|err| err.exit());
+
+    printer.print_section_heading(&format!("{} HP", max_hp));
+    let mut total: u16 = 0;
+    for (turn, damage) in mechanics.damage_per_turn(max_hp, turns).iter().enumerate() {
+        total += damage;
+        printer.print_info(
+            format!("Turn {}", turn + 1),
+            format!(
+                "{} {}",
+                style(format!("-{}", damage)).cyan(),
+                style(format!("({} total, {} left)", total, max_hp - total)).dim()
+            ),
+        );
+    }
+    if total == max_hp {
+        printer.print_center(style("Fainted").red());
+    }
+    println!();
+}
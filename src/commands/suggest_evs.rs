@@ -0,0 +1,176 @@
+use clap::{value_t, App, Arg, ArgMatches, SubCommand};
+use console::{pad_str, style, Alignment};
+
+use crate::calc::evs::{self, Goals, HpBenchmark, Role};
+use crate::calc::stats::{self, Nature, Stat, Stats, MAX_IV, MAX_STAT_EVS};
+use crate::pokedex::{self, Pokemon};
+use crate::print::Printer;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("suggest-evs")
+        .about("Suggests an EV spread for a role, hitting Speed and HP benchmarks")
+        .arg(
+            Arg::with_name("pokemon")
+                .value_name("POKEMON")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("role")
+                .long("role")
+                .takes_value(true)
+                .required(true)
+                .possible_values(&Role::NAMES),
+        )
+        .arg(
+            Arg::with_name("nature")
+                .long("nature")
+                .takes_value(true)
+                .default_value("serious"),
+        )
+        .arg(
+            Arg::with_name("level")
+                .long("level")
+                .takes_value(true)
+                .default_value("100"),
+        )
+        .arg(
+            Arg::with_name("outspeed")
+                .long("outspeed")
+                .value_name("[NATURE] POKEMON")
+                .help(
+                    "A Pokémon to outspeed, e.g. \"adamant excadrill\", assumed to have \
+                     max Speed EVs and IVs",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("hp")
+                .long("hp")
+                .value_name("leftovers, substitute or a minimum HP")
+                .takes_value(true),
+        )
+}
+
+fn find(query: &str) -> Result<Pokemon, String> {
+    match pokedex::search_by_name(query, 1).into_iter().next() {
+        Some(result) => Ok(result.pokemon),
+        None => Err(format!("Couldn't find \"{}\"", query)),
+    }
+}
+
+fn parse_nature(name: &str) -> Result<Nature, String> {
+    Nature::from_name(name).ok_or_else(|| format!("Unknown nature \"{}\"", name))
+}
+
+/// The Pokémon and nature of an `--outspeed` value, whose first word is a
+/// nature if it names one.
+fn parse_target(value: &str) -> Result<(Nature, Pokemon), String> {
+    let value = value.trim();
+    match value.split_once(' ') {
+        Some((first, rest)) => match Nature::from_name(first) {
+            Some(nature) => Ok((nature, find(rest)?)),
+            None => Ok((parse_nature("serious")?, find(value)?)),
+        },
+        None => Ok((parse_nature("serious")?, find(value)?)),
+    }
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+    if let Err(err) = print_spread(matches, &printer) {
+        printer.print_failure(&err);
+    }
+}
+
+fn print_spread(matches: &ArgMatches, printer: &Printer) -> Result<(), String> {
+    let pokemon = find(matches.value_of("pokemon").unwrap_or_default())?;
+    let nature = parse_nature(matches.value_of("nature").unwrap_or_default())?;
+    let level = value_t!(matches, "level", u16).unwrap_or_else(|err| err.exit());
+    if !(1..=100).contains(&level) {
+        return Err(String::from("Levels range from 1 to 100"));
+    }
+    let role = Role::from_name(matches.value_of("role").unwrap_or_default())
+        .expect("clap only allows known roles");
+
+    let hp = match matches.value_of("hp") {
+        Some(value) => Some(
+            HpBenchmark::from_name(value)
+                .ok_or_else(|| format!("Unknown HP benchmark \"{}\"", value))?,
+        ),
+        None => None,
+    };
+
+    let target = match matches.value_of("outspeed") {
+        Some(value) => {
+            let (target_nature, target) = parse_target(value)?;
+            let speed = stats::stat(
+                Stat::Speed,
+                target.speed,
+                MAX_IV,
+                MAX_STAT_EVS,
+                level,
+                &target_nature,
+            );
+            Some((target_nature, target, speed))
+        }
+        None => None,
+    };
+
+    let goals = Goals {
+        level,
+        nature,
+        role,
+        outspeed: target.as_ref().map(|(_, _, speed)| *speed),
+        hp,
+    };
+    let evs = evs::suggest(&pokemon, &goals).map_err(|err| err.to_string())?;
+    let values = stats::stats(&pokemon, &Stats::uniform(MAX_IV), &evs, level, &nature);
+
+    printer.print_section_heading(&format!("{} EV Spread", pokemon.name));
+    printer.print_info("Nature", style(nature.name).cyan());
+    printer.print_info("Level", style(level).cyan());
+    println!();
+
+    for stat in Stat::ALL {
+        let spent = match evs.get(stat) {
+            0 => style(String::from("–")).dim(),
+            spent => style(format!("{} EVs", spent)).cyan(),
+        };
+        printer.print_info(
+            stat.name(),
+            format!(
+                "{}  {}",
+                pad_str(&spent.to_string(), 8, Alignment::Left, None),
+                style(values.get(stat)).yellow()
+            ),
+        );
+    }
+    printer.print_info("Total", style(format!("{} EVs", evs.total())).cyan());
+
+    if target.is_some() || hp.is_some() {
+        println!();
+    }
+    if let Some((target_nature, target, speed)) = target {
+        printer.print_info(
+            "Outspeeds",
+            format!(
+                "{} {} {}",
+                style(target_nature.name).cyan(),
+                style(&target.name).cyan(),
+                style(format!("({} Speed)", speed)).dim()
+            ),
+        );
+    }
+    if let Some(benchmark) = hp {
+        printer.print_info(
+            "HP Benchmark",
+            format!(
+                "{} {}",
+                style(benchmark).cyan(),
+                style(format!("({} HP)", values.get(Stat::Hp))).dim()
+            ),
+        );
+    }
+
+    Ok(())
+}
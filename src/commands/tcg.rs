@@ -0,0 +1,98 @@
+use clap::{value_t, App, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::download_image;
+use crate::pokedex;
+use crate::print::{styled_empty_value, Printer};
+use crate::sprite;
+use crate::tcg::{self, printing_name};
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("tcg")
+        .about("Lists a Pokémon's trading cards, with their rarity and market price")
+        .arg(
+            Arg::with_name("pokemon")
+                .value_name("POKEMON")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("count")
+                .long("count")
+                .takes_value(true)
+                .default_value("20"),
+        )
+        .arg(
+            Arg::with_name("image")
+                .long("image")
+                .help("Shows the newest card"),
+        )
+        .arg(
+            Arg::with_name("refresh")
+                .long("refresh")
+                .help("Ignores cached cards and prices"),
+        )
+}
+
+pub async fn run(matches: &ArgMatches<'_>) {
+    let printer = Printer::new();
+    let count = value_t!(matches, "count", usize).unwrap_or_else(|err| err.exit());
+
+    let query = matches.value_of("pokemon").unwrap_or_default();
+    let pokemon = match pokedex::search_by_name(query, 1).into_iter().next() {
+        Some(result) => result.pokemon,
+        None => return printer.print_failure("Couldn't find any matches"),
+    };
+
+    let cards = match tcg::cards(&pokemon, matches.is_present("refresh")).await {
+        Ok(cards) => cards,
+        Err(err) if sprite::is_network_error(err.as_ref()) => {
+            return printer.print_failure("Couldn't reach the Pokémon TCG API")
+        }
+        Err(err) => return printer.print_failure(&format!("TCG: {}", err)),
+    };
+    if cards.is_empty() {
+        return printer.print_failure(&format!("No cards of {}", pokemon.name));
+    }
+
+    if matches.is_present("image") {
+        match download_image(&cards[0].images.large).await {
+            Err(err) => printer.print_failure(&format!("Image: {}", err)),
+            Ok(image) => {
+                if printer.print_image(&image, 40).is_err() {
+                    warn!("image failed to print");
+                }
+                println!();
+            }
+        }
+    }
+
+    printer.print_section_heading(&format!("{} Cards", pokemon.name));
+    for card in cards.iter().take(count) {
+        let rarity = match &card.rarity {
+            Some(rarity) => style(rarity.clone()).cyan(),
+            None => styled_empty_value(),
+        };
+        let price = match card.market_price() {
+            Some((printing, price)) => format!(
+                "{} {}",
+                style(format!("${:.2}", price)).yellow(),
+                style(format!("({})", printing_name(printing))).dim()
+            ),
+            None => styled_empty_value().to_string(),
+        };
+        printer.print_info(
+            &card.set.name,
+            format!(
+                "{} {} {}",
+                style(format!("#{}", card.number)).dim(),
+                rarity,
+                price
+            ),
+        );
+    }
+
+    if cards.len() > count {
+        println!();
+        printer.print_center(style(format!("and {} more", cards.len() - count)).dim());
+    }
+}
@@ -0,0 +1,355 @@
+use clap::{value_t, App, AppSettings, Arg, ArgMatches, SubCommand};
+use console::{pad_str, style, Alignment, StyledObject};
+
+use crate::moves;
+use crate::pokedex::{self, all_pokemon, Pokemon};
+use crate::print::Printer;
+use crate::team::{self, Requirement, Role, SavedTeam, TeamConstraints};
+use crate::types::Type;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("team")
+        .about("Team building tools; given Pokémon, shows their combined type matchups")
+        .setting(AppSettings::ArgRequiredElseHelp)
+        .arg(
+            Arg::with_name("pokemon")
+                .value_name("POKEMON")
+                .help("Members of a team to analyze, e.g. charizard gyarados venusaur")
+                .multiple(true),
+        )
+        .subcommand(
+            SubCommand::with_name("optimize")
+                .about("Searches for six-member teams meeting the given constraints")
+                .arg(
+                    Arg::with_name("budget")
+                        .long("budget")
+                        .value_name("BST")
+                        .help("Maximum combined base stat total")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("require")
+                        .long("require")
+                        .value_name("KIND:VALUE")
+                        .help("Requires a member matching e.g. type:water or pokemon:pikachu")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1),
+                )
+                .arg(
+                    Arg::with_name("cover-all-types")
+                        .long("cover-all-types")
+                        .help("Requires STAB attacks hitting every type super effectively"),
+                )
+                .arg(
+                    Arg::with_name("allow-legendaries")
+                        .long("allow-legendaries")
+                        .help("Considers Legendary and Mythical Pokémon"),
+                )
+                .arg(
+                    Arg::with_name("count")
+                        .long("count")
+                        .help("Number of teams to print")
+                        .takes_value(true)
+                        .default_value("3"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("roles")
+                .about("Reports support roles a saved team's movesets are missing")
+                .arg(
+                    Arg::with_name("team")
+                        .value_name("TEAM")
+                        .help("Name of a team in <config dir>/teams, or a path to a team file")
+                        .required(true),
+                ),
+        )
+}
+
+pub fn run(matches: &ArgMatches) {
+    match matches.subcommand() {
+        ("optimize", Some(matches)) => run_optimize(matches),
+        ("roles", Some(matches)) => run_roles(matches),
+        _ => run_analysis(matches),
+    }
+}
+
+/// Every type's first three letters, as column headings.
+fn print_type_header(name_width: usize) {
+    let header = Type::ALL
+        .iter()
+        .map(|pokemon_type| format!("{:<3}", &pokemon_type.name()[..3]))
+        .collect::<Vec<String>>()
+        .join("");
+    println!(
+        "{:name_width$}{}",
+        "",
+        style(header).dim(),
+        name_width = name_width
+    );
+}
+
+/// How much damage each attacking type deals to each member, with how many
+/// members are weak to and resist it.
+pub fn print_defense_matrix(team: &[&Pokemon]) {
+    let name_width = 16;
+
+    print_type_header(name_width);
+
+    for pokemon in team {
+        let types = pokemon.types();
+        let cells = Type::ALL
+            .iter()
+            .map(|attacker| {
+                let multiplier = attacker.effectiveness_against(&types);
+                let cell = match multiplier {
+                    m if m >= 4.0 => style("4").red().bold(),
+                    m if m > 1.0 => style("2").red(),
+                    0.0 => style("0").green().bold(),
+                    m if m <= 0.25 => style("¼").green().bold(),
+                    m if m < 1.0 => style("½").green(),
+                    _ => style("·").dim(),
+                };
+                format!("{}  ", cell)
+            })
+            .collect::<String>();
+        println!(
+            "{}{}",
+            pad_str(&pokemon.name, name_width, Alignment::Left, Some("…")),
+            cells
+        );
+    }
+
+    let matchups = team::defensive_matchups(team);
+    let count_row = |label: &str, cell: &dyn Fn(&team::Matchup) -> StyledObject<String>| {
+        let cells = matchups
+            .iter()
+            .map(|matchup| format!("{}  ", cell(matchup)))
+            .collect::<String>();
+        println!(
+            "{}{}",
+            pad_str(label, name_width, Alignment::Left, None),
+            cells
+        );
+    };
+    count_row("Weak", &|matchup| match matchup.weak {
+        0 => style(String::from("·")).dim(),
+        weak if matchup.is_shared_weakness() => style(weak.to_string()).red().bold(),
+        weak => style(weak.to_string()).red(),
+    });
+    count_row("Resist", &|matchup| match matchup.resist {
+        0 => style(String::from("·")).dim(),
+        resist => style(resist.to_string()).green(),
+    });
+}
+
+/// Members' STAB effectiveness against each type, with the team's coverage.
+pub fn print_coverage_matrix(team: &[&Pokemon]) {
+    let name_width = 16;
+
+    print_type_header(name_width);
+
+    for pokemon in team {
+        let cells = Type::ALL
+            .iter()
+            .map(|defender| {
+                let cell = match team::stab_effectiveness(pokemon, *defender) {
+                    e if e > 1.0 => style("+").green(),
+                    0.0 => style("0").red(),
+                    e if e < 1.0 => style("-").red(),
+                    _ => style("·").dim(),
+                };
+                format!("{}  ", cell)
+            })
+            .collect::<String>();
+        println!(
+            "{}{}",
+            pad_str(&pokemon.name, name_width, Alignment::Left, Some("…")),
+            cells
+        );
+    }
+
+    let coverage = team::stab_coverage(team);
+    let cells = Type::ALL
+        .iter()
+        .map(|defender| match coverage.contains(defender) {
+            true => format!("{}  ", style("✓").green().bold()),
+            false => format!("{}  ", style("✗").red().bold()),
+        })
+        .collect::<String>();
+    println!(
+        "{}{}",
+        pad_str("Coverage", name_width, Alignment::Left, None),
+        cells
+    );
+}
+
+fn run_optimize(matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    let budget = matches
+        .value_of("budget")
+        .map(|_| value_t!(matches, "budget", u32).unwrap_or_else(|err| err.exit()));
+    let count = value_t!(matches, "count", usize).unwrap_or_else(|err| err.exit());
+
+    let requirements = match matches
+        .values_of("require")
+        .map(|values| {
+            values
+                .map(Requirement::parse)
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()
+    {
+        Ok(requirements) => requirements.unwrap_or_default(),
+        Err(err) => return printer.print_failure(&err),
+    };
+
+    let constraints = TeamConstraints {
+        size: 6,
+        budget,
+        requirements,
+        cover_all_types: matches.is_present("cover-all-types"),
+        allow_legendaries: matches.is_present("allow-legendaries"),
+    };
+
+    let pokedex = match all_pokemon() {
+        Ok(pokedex) => pokedex,
+        Err(err) => return printer.print_failure(&err.to_string()),
+    };
+    let teams = team::optimize(&pokedex, &constraints, count);
+
+    if teams.is_empty() {
+        return printer.print_failure("Couldn't find a team meeting the constraints");
+    }
+
+    for (i, team) in teams.iter().enumerate() {
+        let total: u32 = team
+            .iter()
+            .map(|pokemon| u32::from(pokemon.total_points))
+            .sum();
+
+        printer.print_section_heading(&format!("Team #{}", i + 1));
+        printer.print_info("Total BST", style(total).cyan().bold());
+        for pokemon in team {
+            printer.print_info(
+                &pokemon.name,
+                format!(
+                    "{} {}",
+                    style(
+                        pokemon
+                            .types()
+                            .iter()
+                            .map(Type::name)
+                            .collect::<Vec<&str>>()
+                            .join(" | ")
+                    )
+                    .magenta(),
+                    style(format!("(BST {})", pokemon.total_points)).dim()
+                ),
+            );
+        }
+        println!();
+        print_coverage_matrix(team);
+        println!();
+    }
+}
+
+fn run_roles(matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    let team = match SavedTeam::load(matches.value_of("team").unwrap_or_default()) {
+        Ok(team) => team,
+        Err(err) => return printer.print_failure(&err),
+    };
+
+    for member in &team.members {
+        for unknown in member
+            .moves
+            .iter()
+            .filter(|name| moves::find_move(name).is_none())
+        {
+            warn!(
+                "{}'s move \"{}\" isn't in the moves dataset",
+                member.pokemon, unknown
+            );
+        }
+    }
+
+    printer.print_section_heading("Roles");
+    for role in Role::ALL {
+        let providers = role.providers(&team);
+        let value = match providers.is_empty() {
+            true => style(String::from("Missing")).red(),
+            false => style(
+                providers
+                    .iter()
+                    .map(|(pokemon, m)| format!("{} ({})", pokemon, m))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+            )
+            .cyan(),
+        };
+        printer.print_info(role.name(), value);
+    }
+    println!();
+}
+
+fn run_analysis(matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    let mut members = Vec::new();
+    for query in matches.values_of("pokemon").unwrap_or_default() {
+        match pokedex::search_by_name(query, 1).into_iter().next() {
+            Some(result) => members.push(result.pokemon),
+            None => return printer.print_failure(&format!("Couldn't find \"{}\"", query)),
+        }
+    }
+    let members = members.iter().collect::<Vec<&Pokemon>>();
+
+    printer.print_section_heading("Team");
+    for pokemon in &members {
+        printer.print_info(
+            &pokemon.name,
+            style(
+                pokemon
+                    .types()
+                    .iter()
+                    .map(Type::name)
+                    .collect::<Vec<&str>>()
+                    .join(" | "),
+            )
+            .magenta(),
+        );
+    }
+    println!();
+
+    printer.print_section_heading("Defenses");
+    print_defense_matrix(&members);
+    println!();
+
+    let shared = team::defensive_matchups(&members)
+        .into_iter()
+        .filter(team::Matchup::is_shared_weakness)
+        .collect::<Vec<team::Matchup>>();
+    if shared.is_empty() {
+        printer.print_center(style("No shared weaknesses").green());
+    } else {
+        printer.print_section_heading(&format!("Shared weaknesses ({})", shared.len()));
+        for matchup in &shared {
+            printer.print_info(
+                matchup.attacker,
+                format!(
+                    "{} {}",
+                    style(format!("{} of {} weak", matchup.weak, members.len())).red(),
+                    style(format!("({} resist)", matchup.resist)).dim()
+                ),
+            );
+        }
+    }
+    println!();
+
+    printer.print_section_heading("STAB coverage");
+    print_coverage_matrix(&members);
+    println!();
+}
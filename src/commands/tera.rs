@@ -0,0 +1,142 @@
+use clap::{App, Arg, ArgGroup, ArgMatches, SubCommand};
+use console::style;
+
+use crate::pokedex::{self, Pokemon};
+use crate::print::{styled_empty_value, Printer};
+use crate::team::SavedTeam;
+use crate::tera::{self, TeraOption};
+use crate::types::Type;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("tera")
+        .about("Plans a Pokémon's Tera type")
+        .arg(
+            Arg::with_name("pokemon")
+                .value_name("POKEMON")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("suggest")
+                .long("suggest")
+                .help("Ranks Tera types as defensive pivots and offensive boosts"),
+        )
+        .arg(
+            Arg::with_name("type")
+                .long("type")
+                .help("Shows how a single Tera type changes the Pokémon's matchups")
+                .takes_value(true),
+        )
+        .group(
+            ArgGroup::with_name("mode")
+                .args(&["suggest", "type"])
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("team")
+                .long("team")
+                .help("Saved team whose shared weaknesses the Tera type should cover")
+                .takes_value(true),
+        )
+}
+
+fn type_list(types: &[Type]) -> String {
+    match types.is_empty() {
+        true => styled_empty_value().to_string(),
+        false => types
+            .iter()
+            .map(|t| style(t.name()).magenta().to_string())
+            .collect::<Vec<String>>()
+            .join(", "),
+    }
+}
+
+fn print_option(printer: &Printer, option: &TeraOption) {
+    printer.print_section_heading(&format!("Tera {}", option.tera_type));
+    printer.print_info("Weak to", type_list(&option.weaknesses));
+    printer.print_info("Resists", type_list(&option.resistances));
+    printer.print_info("New coverage", type_list(&option.new_coverage));
+    if option.stacks_stab {
+        printer.print_info("STAB", style("2×").cyan());
+    }
+    println!();
+}
+
+pub fn run(matches: &ArgMatches) {
+    let printer = Printer::new();
+
+    let query = matches.value_of("pokemon").unwrap_or_default();
+    let pokemon = match pokedex::search_by_name(query, 1).into_iter().next() {
+        Some(result) => result.pokemon,
+        None => return printer.print_failure("Couldn't find any matches"),
+    };
+
+    let team = match matches.value_of("team") {
+        Some(name) => match SavedTeam::load(name) {
+            Ok(team) => team
+                .members
+                .iter()
+                .filter_map(|member| pokedex::search_by_name(&member.pokemon, 1).pop())
+                .map(|result| result.pokemon)
+                .filter(|member| member.name != pokemon.name)
+                .collect(),
+            Err(err) => return printer.print_failure(&err),
+        },
+        None => Vec::new(),
+    };
+    let team = team.iter().collect::<Vec<&Pokemon>>();
+
+    let options = tera::options(&pokemon, &team);
+    printer.print_center(style(&pokemon.name).yellow());
+    println!();
+
+    if let Some(name) = matches.value_of("type") {
+        let tera_type = match Type::from_name(name) {
+            Some(tera_type) => tera_type,
+            None => return printer.print_failure(&format!("Unknown type \"{}\"", name)),
+        };
+        if let Some(option) = options.iter().find(|option| option.tera_type == tera_type) {
+            print_option(&printer, option);
+        }
+        return;
+    }
+
+    printer.print_section_heading("Defensive");
+    for option in options.iter().take(5) {
+        printer.print_info(
+            option.tera_type,
+            format!(
+                "{} {}",
+                style(format!("{:+}", option.defensive_score)).cyan(),
+                style(format!(
+                    "({} weaknesses, {} resistances)",
+                    option.weaknesses.len(),
+                    option.resistances.len()
+                ))
+                .dim()
+            ),
+        );
+    }
+    println!();
+
+    let mut offensive = options.iter().collect::<Vec<&TeraOption>>();
+    offensive.sort_by(|a, b| {
+        b.stacks_stab
+            .cmp(&a.stacks_stab)
+            .then(b.new_coverage.len().cmp(&a.new_coverage.len()))
+    });
+    printer.print_section_heading("Offensive");
+    for option in offensive.iter().take(5) {
+        let gain = match option.stacks_stab {
+            true => style(format!(
+                "STAB 1.5× → {}×",
+                tera::stab_multiplier(&pokemon, option.tera_type, option.tera_type)
+            ))
+            .cyan(),
+            false => style(format!("+{} types hit", option.new_coverage.len())).cyan(),
+        };
+        printer.print_info(option.tera_type.name(), gain);
+    }
+    println!();
+
+    print_option(&printer, &options[0]);
+}
@@ -0,0 +1,141 @@
+use std::io::{self, BufRead};
+
+use chrono::{Duration, Utc};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use console::{style, Key, Term};
+
+use crate::commands;
+use crate::hunt::{self, Hunts};
+use crate::print::Printer;
+
+fn validate_duration(value: String) -> Result<(), String> {
+    hunt::parse_duration(&value).map(|_| ())
+}
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("timer")
+        .about("Times soft resets or encounters of the active hunt, with a live stats line")
+        .arg(
+            Arg::with_name("per-attempt")
+                .long("per-attempt")
+                .value_name("DURATION")
+                .help("Expected time per attempt (e.g. 42s), used until attempts are timed")
+                .takes_value(true)
+                .validator(validate_duration),
+        )
+}
+
+enum Action {
+    Attempt,
+    Undo,
+    Quit,
+    Ignore,
+}
+
+fn handle_key(key: &Key) -> Action {
+    match key {
+        Key::Enter | Key::Char(' ') | Key::Char('+') => Action::Attempt,
+        Key::Backspace | Key::Char('u') | Key::Char('-') => Action::Undo,
+        Key::Escape | Key::Char('q') => Action::Quit,
+        _ => Action::Ignore,
+    }
+}
+
+fn handle_line(line: &str) -> Action {
+    match line.trim() {
+        "" | "+" => Action::Attempt,
+        "u" | "-" => Action::Undo,
+        "q" => Action::Quit,
+        _ => Action::Ignore,
+    }
+}
+
+/// Reads actions from keys on a terminal, or from lines when piped.
+fn next_action(term: &Term, lines: &mut impl Iterator<Item = io::Result<String>>) -> Action {
+    match term.is_term() {
+        true => match term.read_key() {
+            Ok(key) => handle_key(&key),
+            Err(_) => Action::Quit,
+        },
+        false => match lines.next() {
+            Some(Ok(line)) => handle_line(&line),
+            _ => Action::Quit,
+        },
+    }
+}
+
+pub async fn run(matches: &ArgMatches<'_>) {
+    let printer = Printer::new();
+    let per_attempt = matches
+        .value_of("per-attempt")
+        .and_then(|value| hunt::parse_duration(value).ok());
+
+    let mut hunts = Hunts::load();
+    let before = match hunts.active() {
+        Some(hunt) => hunt.count,
+        None => return printer.print_failure("No active hunt, start one with pkmn hunt start"),
+    };
+
+    let term = Term::stdout();
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let started = Utc::now();
+    let mut session = 0i32;
+
+    printer
+        .print_center(style("Enter or space counts an attempt, u takes one back, q stops").dim());
+    loop {
+        if let Some(hunt) = hunts.active() {
+            let elapsed = hunt::format_duration(Utc::now() - started);
+            let line = format!(
+                "{} {}",
+                hunt.stats_line(per_attempt),
+                style(format!("· {} this session in {}", session, elapsed)).dim()
+            );
+            match term.is_term() {
+                true => {
+                    let _ = term.clear_line();
+                    let _ = term.write_str(&line);
+                }
+                false => println!("{}", line),
+            }
+        }
+
+        let hunt = match hunts.active_mut() {
+            Some(hunt) => hunt,
+            None => break,
+        };
+        match next_action(&term, &mut lines) {
+            Action::Attempt => {
+                hunt.add(1, Utc::now());
+                session += 1;
+            }
+            Action::Undo if session > 0 => {
+                hunt.undo();
+                session -= 1;
+            }
+            Action::Undo | Action::Ignore => continue,
+            Action::Quit => break,
+        }
+
+        // Saved as it goes, so the count survives the terminal closing
+        if let Err(err) = hunts.save() {
+            warn!("couldn't save the hunt: {}", err);
+        }
+    }
+
+    if term.is_term() {
+        let _ = term.write_line("");
+    }
+    if let Some(hunt) = hunts.active() {
+        commands::hunt::notify_milestones(hunt, before, &printer).await;
+    }
+    let elapsed = Utc::now() - started;
+    if session > 0 && elapsed > Duration::zero() {
+        printer.print_center(format!(
+            "{} attempts in {}",
+            style(session).green(),
+            hunt::format_duration(elapsed)
+        ));
+    }
+}
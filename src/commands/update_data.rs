@@ -0,0 +1,97 @@
+use std::fs;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::dataset::{self, Format};
+use crate::pokedex;
+use crate::print::Printer;
+
+/// The Pokédex on pkmn's main branch, which gets new Pokémon before they're
+/// released in a new version.
+const DEFAULT_URL: &str =
+    "https://raw.githubusercontent.com/VinceMalone/pkmn/main/data/pokedex.csv";
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("update-data")
+        .about("Downloads a newer Pokédex, used instead of the embedded one")
+        .arg(
+            Arg::with_name("url")
+                .long("url")
+                .value_name("URL")
+                .help(
+                    "A Pokédex as CSV or JSON with the embedded one's columns, by default the \
+                     one on pkmn's main branch",
+                )
+                .conflicts_with("reset"),
+        )
+        .arg(
+            Arg::with_name("reset")
+                .long("reset")
+                .help("Removes the downloaded Pokédex, going back to the embedded one"),
+        )
+}
+
+pub async fn run(matches: &ArgMatches<'_>) {
+    let printer = Printer::new();
+    let dir = match dataset::data_dir() {
+        Some(dir) => dir,
+        None => return printer.print_failure("No data directory on this system"),
+    };
+
+    if matches.is_present("reset") {
+        return match dataset::local_pokedex() {
+            Some((path, _)) => match fs::remove_file(&path) {
+                Ok(()) => printer.print_info("Pokédex", style("Embedded").cyan()),
+                Err(err) => {
+                    printer.print_failure(&format!("Couldn't remove {}: {}", path.display(), err))
+                }
+            },
+            None => printer.print_info("Pokédex", style("Embedded, nothing to remove").dim()),
+        };
+    }
+
+    let url = matches.value_of("url").unwrap_or(DEFAULT_URL);
+    info!("downloading the Pokédex from \"{}\"", url);
+    let bytes = match crate::download(url).await {
+        Ok(bytes) => bytes,
+        Err(err) => return printer.print_failure(&format!("Couldn't download {}: {}", url, err)),
+    };
+    let format = Format::detect(url, &bytes);
+    let entries = match dataset::parse_pokedex(&bytes, format) {
+        Ok(entries) => entries,
+        Err(err) => {
+            return printer.print_failure(&format!("The download isn't a Pokédex: {}", err))
+        }
+    };
+
+    let path = dir.join(format!("pokedex.{}", format.extension()));
+    let temporary = path.with_extension("download");
+    let written = fs::create_dir_all(&dir)
+        .and_then(|_| fs::write(&temporary, &bytes))
+        .and_then(|_| fs::rename(&temporary, &path));
+    if let Err(err) = written {
+        return printer.print_failure(&format!("Couldn't save {}: {}", path.display(), err));
+    }
+    // Only one downloaded Pokédex is used, so drop one in the other format
+    for other in Format::ALL.iter().filter(|other| **other != format) {
+        let _ = fs::remove_file(dir.join(format!("pokedex.{}", other.extension())));
+    }
+
+    let generations = entries.iter().map(|pokemon| pokemon.generation);
+    printer.print_info(
+        "Pokédex",
+        format!(
+            "{} {}",
+            style(format!("{} entries", entries.len())).cyan(),
+            style(format!(
+                "(was {}), generations {}–{}",
+                pokedex::entries().map_or(0, <[_]>::len),
+                generations.clone().min().unwrap_or_default(),
+                generations.max().unwrap_or_default()
+            ))
+            .dim()
+        ),
+    );
+    printer.print_info("Saved", style(path.display()).cyan());
+}
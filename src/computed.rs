@@ -0,0 +1,94 @@
+use evalexpr::{
+    eval_with_context, ContextWithMutableVariables, EvalexprError, HashMapContext, Value,
+};
+
+use crate::pokedex::Pokemon;
+
+/// Variables available to computed-field expressions. Optional values that
+/// are missing for a Pokémon are left undefined.
+fn context(pokemon: &Pokemon) -> Result<HashMapContext, EvalexprError> {
+    let mut context = HashMapContext::new();
+
+    let ints = [
+        ("pokedex_number", Some(pokemon.pokedex_number)),
+        ("generation", Some(pokemon.generation.into())),
+        ("total_points", Some(pokemon.total_points)),
+        ("hp", Some(pokemon.hp)),
+        ("attack", Some(pokemon.attack)),
+        ("defense", Some(pokemon.defense)),
+        ("sp_attack", Some(pokemon.sp_attack)),
+        ("sp_defense", Some(pokemon.sp_defense)),
+        ("speed", Some(pokemon.speed)),
+        ("catch_rate", pokemon.catch_rate),
+        ("base_friendship", pokemon.base_friendship),
+        ("base_experience", pokemon.base_experience),
+        ("egg_cycles", pokemon.egg_cycles),
+    ];
+    for (name, value) in ints {
+        if let Some(value) = value {
+            context.set_value(name.into(), Value::Int(value.into()))?;
+        }
+    }
+
+    let floats = [
+        ("height_m", pokemon.height_m),
+        ("weight_kg", pokemon.weight_kg),
+        ("percentage_male", pokemon.percentage_male),
+    ];
+    for (name, value) in floats {
+        if let Some(value) = value {
+            context.set_value(name.into(), Value::Float(value.into()))?;
+        }
+    }
+
+    Ok(context)
+}
+
+/// Evaluates `expression` (e.g. `hp * defense`) against a Pokémon's numeric fields.
+pub fn evaluate(pokemon: &Pokemon, expression: &str) -> Result<Value, EvalexprError> {
+    eval_with_context(expression, &context(pokemon)?)
+}
+
+/// Evaluates each named expression, skipping (and logging) any that fail.
+pub fn evaluate_all<'a, I>(pokemon: &Pokemon, fields: I) -> Vec<(String, Value)>
+where
+    I: IntoIterator<Item = (&'a String, &'a String)>,
+{
+    fields
+        .into_iter()
+        .filter_map(|(name, expression)| match evaluate(pokemon, expression) {
+            Ok(value) => Some((name.clone(), value)),
+            Err(err) => {
+                log::info!("computed field \"{}\" skipped: {}", name, err);
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::search_by_name;
+
+    fn garchomp() -> Pokemon {
+        search_by_name("garchomp", 1).remove(0).pokemon
+    }
+
+    #[test]
+    fn integer_expression() {
+        let value = evaluate(&garchomp(), "hp * defense").unwrap();
+        assert_eq!(value, Value::Int(10260));
+    }
+
+    #[test]
+    fn float_expression() {
+        let value = evaluate(&garchomp(), "weight_kg / 5").unwrap();
+        assert_eq!(value, Value::Float(19.0));
+    }
+
+    #[test]
+    fn unknown_variable() {
+        assert!(evaluate(&garchomp(), "hp * bulk").is_err());
+    }
+}
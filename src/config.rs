@@ -0,0 +1,104 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::cache::CacheConfig;
+use crate::card::Theme;
+use crate::share::ShareConfig;
+use crate::terminal::TerminalConfig;
+use crate::webhook::WebhookConfig;
+
+/// Directory holding user configuration, e.g. `~/.config/pkmn` on Linux.
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")))
+}
+
+/// User settings read from `<config dir>/config.toml`.
+///
+/// ```toml
+/// sections = ["stats", "breeding"]
+/// region_image_url = "https://example.com/maps/{region}.png"
+/// footprint_image_url = "https://example.com/footprints/{number}.png"
+///
+/// [share]
+/// endpoint = "https://paste.example.com/"
+///
+/// [webhooks]
+/// hunt_milestone = ["https://example.com/hooks/pkmn"]
+///
+/// [cache]
+/// max_size = "200MB"
+/// max_unused_days = 90
+///
+/// [terminal]
+/// colors = "256"
+/// sprite_renderer = "blocks"
+///
+/// [fields]
+/// physical_bulk = "hp * defense"
+/// special_bulk = "hp * sp_defense"
+///
+/// [links]
+/// wiki = "https://wiki.example.com/{species}"
+/// serebii = ""
+/// ```
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Card sections to print by default, in order. Empty means the default
+    /// card.
+    pub sections: Vec<String>,
+    /// Image shown atop `pkmn gen`, with `{region}` (e.g. `kanto`) and
+    /// `{generation}` placeholders.
+    pub region_image_url: Option<String>,
+    /// Footprint shown at the bottom of Pokémon cards, with `{number}` (the
+    /// National Pokédex number) and `{slug}` placeholders.
+    pub footprint_image_url: Option<String>,
+    /// Computed fields, keyed by name, defined as expressions over the
+    /// Pokémon's numeric fields.
+    pub fields: BTreeMap<String, String>,
+    /// Link templates for JSON output and `pkmn open`, keyed by site, adding to or replacing
+    /// the built-in ones (see [`crate::links::fill`]).
+    pub links: BTreeMap<String, String>,
+    pub share: ShareConfig,
+    pub webhooks: WebhookConfig,
+    pub cache: CacheConfig,
+    /// Terminal capabilities to use instead of detecting them.
+    pub terminal: TerminalConfig,
+    /// Look of exported card images.
+    pub theme: Theme,
+}
+
+impl Config {
+    /// Where the config is read from, i.e. `<config dir>/config.toml`.
+    pub fn path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("config.toml"))
+    }
+
+    pub fn load() -> Self {
+        let path = match Config::path() {
+            Some(path) => path,
+            None => return Config::default(),
+        };
+
+        if !path.exists() {
+            return Config::default();
+        }
+
+        match Config::read(&path) {
+            Ok(config) => config,
+            Err(err) => {
+                warn!("failed to read config \"{}\": {}", path.display(), err);
+                Config::default()
+            }
+        }
+    }
+
+    fn read(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let contents = fs::read_to_string(path)?;
+        Ok(toml::from_str(&contents)?)
+    }
+}
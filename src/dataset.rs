@@ -0,0 +1,357 @@
+//! The datasets compiled into the binary, and what changed in them since the
+//! user last looked, for `pkmn data`.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::config_dir;
+use crate::error;
+use crate::pokedex::{self, Pokemon};
+use crate::provenance::fingerprint;
+
+pub struct Dataset {
+    pub name: &'static str,
+    pub bytes: &'static [u8],
+}
+
+impl Dataset {
+    /// Number of records, not counting the header.
+    pub fn rows(&self) -> usize {
+        csv::Reader::from_reader(self.bytes).records().count()
+    }
+
+    pub fn version(&self) -> String {
+        fingerprint(self.bytes)
+    }
+}
+
+pub fn embedded() -> Vec<Dataset> {
+    vec![
+        Dataset {
+            name: "pokedex.csv",
+            bytes: crate::pokedex::POKEDEX_CSV,
+        },
+        Dataset {
+            name: "abilities.csv",
+            bytes: crate::abilities::ABILITIES_CSV,
+        },
+        Dataset {
+            name: "anime.csv",
+            bytes: crate::anime::ANIME_CSV,
+        },
+        Dataset {
+            name: "classifications.csv",
+            bytes: crate::classification::CLASSIFICATIONS_CSV,
+        },
+        Dataset {
+            name: "etymology.csv",
+            bytes: crate::etymology::ETYMOLOGY_CSV,
+        },
+        Dataset {
+            name: "evolution_hints.csv",
+            bytes: crate::evolution::EVOLUTION_HINTS_CSV,
+        },
+        Dataset {
+            name: "evolutions.csv",
+            bytes: crate::evolution::EVOLUTIONS_CSV,
+        },
+        Dataset {
+            name: "field_effects.csv",
+            bytes: crate::field::FIELD_EFFECTS_CSV,
+        },
+        Dataset {
+            name: "flavor_text.csv",
+            bytes: crate::flavor::FLAVOR_TEXT_CSV,
+        },
+        Dataset {
+            name: "games.csv",
+            bytes: crate::games::GAMES_CSV,
+        },
+        Dataset {
+            name: "incense_babies.csv",
+            bytes: crate::breeding::INCENSE_BABIES_CSV,
+        },
+        Dataset {
+            name: "items.csv",
+            bytes: crate::items::ITEMS_CSV,
+        },
+        Dataset {
+            name: "learnsets.csv",
+            bytes: crate::moves::LEARNSETS_CSV,
+        },
+        Dataset {
+            name: "moves.csv",
+            bytes: crate::moves::MOVES_CSV,
+        },
+        Dataset {
+            name: "statuses.csv",
+            bytes: crate::status::STATUSES_CSV,
+        },
+    ]
+}
+
+/// Directory of datasets downloaded by `pkmn update-data`, e.g.
+/// `~/.local/share/pkmn` on Linux.
+pub fn data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")))
+}
+
+/// How a downloaded Pokédex is written: CSV with the embedded one's columns,
+/// or a JSON array of entries with the same fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+impl Format {
+    pub const ALL: [Format; 2] = [Format::Csv, Format::Json];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Json => "json",
+        }
+    }
+
+    /// The format of a file or URL by its extension, or of its contents.
+    pub fn detect(name: &str, bytes: &[u8]) -> Self {
+        let name = name.split(['?', '#']).next().unwrap_or_default();
+        if name.ends_with(".json") {
+            return Format::Json;
+        }
+        if name.ends_with(".csv") {
+            return Format::Csv;
+        }
+        match bytes.iter().find(|byte| !byte.is_ascii_whitespace()) {
+            Some(b'[') => Format::Json,
+            _ => Format::Csv,
+        }
+    }
+}
+
+/// Every entry of a Pokédex in `format`, failing on the first that doesn't
+/// fit.
+pub fn parse_pokedex(bytes: &[u8], format: Format) -> Result<Vec<Pokemon>, Box<dyn Error>> {
+    let entries: Vec<Pokemon> = match format {
+        Format::Csv => error::read_csv("pokedex.csv", bytes)?,
+        Format::Json => serde_json::from_slice(bytes)?,
+    };
+    if entries.is_empty() {
+        return Err("the Pokédex has no entries".into());
+    }
+    Ok(entries)
+}
+
+/// The Pokédex downloaded by `pkmn update-data`, if there is one.
+pub fn local_pokedex() -> Option<(PathBuf, Format)> {
+    let dir = data_dir()?;
+    Format::ALL
+        .iter()
+        .map(|format| (dir.join(format!("pokedex.{}", format.extension())), *format))
+        .find(|(path, _)| path.is_file())
+}
+
+/// Uses the downloaded Pokédex instead of the embedded one, if there is one
+/// that reads; call it before anything looks up a Pokémon.
+pub fn use_local_pokedex() {
+    let (path, format) = match local_pokedex() {
+        Some(local) => local,
+        None => return,
+    };
+    let read = |path: &PathBuf| -> Result<(Vec<Pokemon>, String), Box<dyn Error>> {
+        let bytes = fs::read(path)?;
+        Ok((parse_pokedex(&bytes, format)?, fingerprint(&bytes)))
+    };
+    match read(&path) {
+        Ok((entries, version)) => {
+            info!("using the Pokédex in \"{}\"", path.display());
+            if pokedex::replace_entries(entries, version).is_err() {
+                warn!("the embedded Pokédex was already in use");
+            }
+        }
+        Err(err) => warn!(
+            "ignoring the Pokédex in \"{}\", using the embedded one: {}",
+            path.display(),
+            err
+        ),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DatasetSummary {
+    pub rows: usize,
+    pub version: String,
+}
+
+/// The embedded data as of a given run, saved to compare against after
+/// upgrading pkmn.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Snapshot {
+    /// Fingerprint of every dataset together.
+    pub version: String,
+    pub pkmn_version: String,
+    pub datasets: BTreeMap<String, DatasetSummary>,
+}
+
+/// How a dataset differs from the previous snapshot.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Change {
+    Added { rows: usize },
+    Removed { rows: usize },
+    Changed { previous_rows: usize, rows: usize },
+}
+
+fn snapshot_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("dataset.toml"))
+}
+
+impl Snapshot {
+    pub fn current() -> Self {
+        let datasets = embedded()
+            .iter()
+            .map(|dataset| {
+                let summary = DatasetSummary {
+                    rows: dataset.rows(),
+                    version: dataset.version(),
+                };
+                (dataset.name.to_string(), summary)
+            })
+            .collect::<BTreeMap<String, DatasetSummary>>();
+        let versions = datasets
+            .values()
+            .map(|summary| summary.version.as_str())
+            .collect::<String>();
+
+        Snapshot {
+            version: fingerprint(versions.as_bytes()),
+            pkmn_version: env!("CARGO_PKG_VERSION").to_string(),
+            datasets,
+        }
+    }
+
+    /// The snapshot saved by the last `pkmn data info`, if any.
+    pub fn load() -> Option<Self> {
+        let text = fs::read_to_string(snapshot_path()?).ok()?;
+        match toml::from_str(&text) {
+            Ok(snapshot) => Some(snapshot),
+            Err(err) => {
+                warn!("ignoring unreadable dataset snapshot: {}", err);
+                None
+            }
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let path = snapshot_path().ok_or("no config directory")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Datasets that differ from `previous`, by name.
+    pub fn changes_since(&self, previous: &Snapshot) -> Vec<(String, Change)> {
+        let mut changes = Vec::new();
+
+        for (name, summary) in &self.datasets {
+            match previous.datasets.get(name) {
+                None => changes.push((name.clone(), Change::Added { rows: summary.rows })),
+                Some(old) if old.version != summary.version => changes.push((
+                    name.clone(),
+                    Change::Changed {
+                        previous_rows: old.rows,
+                        rows: summary.rows,
+                    },
+                )),
+                Some(_) => {}
+            }
+        }
+        for (name, old) in &previous.datasets {
+            if !self.datasets.contains_key(name) {
+                changes.push((name.clone(), Change::Removed { rows: old.rows }));
+            }
+        }
+
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_rows_without_header() {
+        let dataset = Dataset {
+            name: "test.csv",
+            bytes: b"a,b\n1,2\n3,4\n",
+        };
+        assert_eq!(dataset.rows(), 2);
+    }
+
+    #[test]
+    fn reads_pokedex_formats() {
+        let entries = parse_pokedex(pokedex::POKEDEX_CSV, Format::Csv).unwrap();
+        assert_eq!(entries.len(), pokedex::entries().unwrap().len());
+
+        let json = serde_json::to_vec(&entries[..3]).unwrap();
+        assert_eq!(Format::detect("pokedex", &json), Format::Json);
+        let from_json = parse_pokedex(&json, Format::Json).unwrap();
+        assert_eq!(from_json[2].name, entries[2].name);
+
+        assert_eq!(
+            Format::detect("https://example.com/pokedex.csv?v=2", b"["),
+            Format::Csv
+        );
+        assert!(parse_pokedex(b"[]", Format::Json).is_err());
+        assert!(parse_pokedex(b"name\nPikachu\n", Format::Csv).is_err());
+    }
+
+    #[test]
+    fn unchanged_snapshot() {
+        let snapshot = Snapshot::current();
+        assert_eq!(snapshot.datasets.len(), embedded().len());
+        assert!(snapshot.changes_since(&Snapshot::current()).is_empty());
+    }
+
+    #[test]
+    fn changes() {
+        let current = Snapshot::current();
+        let mut previous = Snapshot::current();
+        previous.datasets.remove("moves.csv");
+        previous.datasets.insert(
+            String::from("legacy.csv"),
+            DatasetSummary {
+                rows: 3,
+                version: String::from("0"),
+            },
+        );
+        previous.datasets.get_mut("pokedex.csv").unwrap().version = String::from("0");
+        let pokedex_rows = current.datasets["pokedex.csv"].rows;
+        let moves_rows = current.datasets["moves.csv"].rows;
+
+        assert_eq!(
+            current.changes_since(&previous),
+            vec![
+                (
+                    String::from("moves.csv"),
+                    Change::Added { rows: moves_rows }
+                ),
+                (
+                    String::from("pokedex.csv"),
+                    Change::Changed {
+                        previous_rows: pokedex_rows,
+                        rows: pokedex_rows
+                    }
+                ),
+                (String::from("legacy.csv"), Change::Removed { rows: 3 }),
+            ]
+        );
+    }
+}
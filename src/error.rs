@@ -0,0 +1,85 @@
+//! Errors from reading the bundled datasets and decoding images.
+
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+
+use crate::{abilities, anime, breeding, classification, etymology, evolution, field, flavor};
+use crate::{games, items, moves, pokedex, status};
+
+#[derive(Debug, Error)]
+pub enum PkmnError {
+    /// A row of a bundled dataset that doesn't fit its columns.
+    #[error("row {row} of {dataset} is invalid: {source}")]
+    Dataset {
+        dataset: &'static str,
+        row: usize,
+        #[source]
+        source: csv::Error,
+    },
+    /// An image that couldn't be decoded, e.g. a corrupt download.
+    #[error("couldn't decode the image: {0}")]
+    Image(#[from] image::ImageError),
+}
+
+/// Reads every row of a bundled CSV dataset, failing on the first one that
+/// doesn't fit `T`.
+pub fn read_csv<T: DeserializeOwned>(
+    dataset: &'static str,
+    bytes: &[u8],
+) -> Result<Vec<T>, PkmnError> {
+    let mut csv_reader = csv::Reader::from_reader(bytes);
+    csv_reader
+        .deserialize()
+        .enumerate()
+        .map(|(index, result)| {
+            result.map_err(|source| PkmnError::Dataset {
+                dataset,
+                // Row 1 is the header
+                row: index + 2,
+                source,
+            })
+        })
+        .collect()
+}
+
+/// Reads every bundled dataset. Each dataset's accessor (e.g.
+/// [`pokedex::entries`]) returns the error too, while lookups by name treat a
+/// dataset that doesn't read as having no rows.
+pub fn check_datasets() -> Result<(), PkmnError> {
+    abilities::all_abilities()?;
+    anime::all_trivia()?;
+    breeding::load()?;
+    classification::all_classifications()?;
+    etymology::all_etymologies()?;
+    evolution::load_hints()?;
+    evolution::load_evolutions()?;
+    field::load()?;
+    flavor::all_flavor_text()?;
+    games::all_games()?;
+    items::all_items()?;
+    moves::all_moves()?;
+    moves::load_learnsets()?;
+    pokedex::load()?;
+    status::load()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_datasets_read() {
+        check_datasets().unwrap();
+    }
+
+    #[test]
+    fn reports_bad_rows() {
+        let bytes = b"name,description\nSturdy,Holds on\nFilter\n";
+        let err = read_csv::<(String, String)>("abilities.csv", bytes).unwrap_err();
+        assert!(matches!(err, PkmnError::Dataset { row: 3, .. }));
+        assert!(err
+            .to_string()
+            .starts_with("row 3 of abilities.csv is invalid: "));
+    }
+}
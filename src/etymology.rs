@@ -0,0 +1,92 @@
+//! Where Pokémon names come from, e.g. Charizard from "char + lizard", with
+//! notes on the myths and folklore behind some designs.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{self, PkmnError};
+use crate::fuzzy::{self, Match, MatchScore};
+use crate::pokedex::{self, Pokemon};
+
+pub static ETYMOLOGY_CSV: &[u8] = include_bytes!("../data/etymology.csv");
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct Etymology {
+    pub name: String,
+    /// The words the English name is made of.
+    pub origin: String,
+    /// What the design draws on, if anything notable. Empty otherwise.
+    pub notes: String,
+}
+
+impl Etymology {
+    /// Origin and notes together, as searched by [`search`].
+    fn text(&self) -> String {
+        format!("{} {}", self.origin, self.notes)
+    }
+}
+
+/// Every bundled etymology, in Pokédex order. Mostly covers Generation 1.
+pub fn all_etymologies() -> Result<Vec<Etymology>, PkmnError> {
+    error::read_csv("etymology.csv", ETYMOLOGY_CSV)
+}
+
+/// The etymology of the Pokémon's species, which its forms share.
+pub fn etymology_for(pokemon: &Pokemon) -> Option<Etymology> {
+    let species = pokedex::species_of(pokemon)?;
+    all_etymologies()
+        .unwrap_or_default()
+        .into_iter()
+        .find(|etymology| etymology.name == species.name)
+}
+
+/// Etymologies whose origin or notes contain a phrase closest to `query`,
+/// best first, e.g. "japanese folklore".
+pub fn search(query: &str, limit: usize) -> Vec<Match<Etymology>> {
+    let mut results = all_etymologies()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|etymology| Match {
+            score: fuzzy::phrase_score(&etymology.text(), query),
+            value: etymology,
+        })
+        .collect::<Vec<Match<Etymology>>>();
+    // Ties stay in Pokédex order
+    results.sort_by(|a, b| MatchScore::compare(&a.score, &b.score));
+    results.truncate(limit);
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::find_by_id;
+
+    #[test]
+    fn every_entry_is_a_pokemon() {
+        for etymology in all_etymologies().unwrap() {
+            assert!(
+                pokedex::entries()
+                    .unwrap()
+                    .iter()
+                    .any(|pokemon| pokemon.name == etymology.name),
+                "{} isn't in the Pokédex",
+                etymology.name
+            );
+        }
+    }
+
+    #[test]
+    fn forms_share_etymology() {
+        let etymology = etymology_for(&find_by_id("charizard-mega-y").unwrap()).unwrap();
+        assert_eq!(etymology.origin, "char + lizard");
+    }
+
+    #[test]
+    fn searches_notes() {
+        let names = search("japanese folklore", 3)
+            .into_iter()
+            .map(|result| result.value.name)
+            .collect::<Vec<String>>();
+        assert_eq!(names, vec!["Vulpix", "Ninetales", "Ho-oh"]);
+    }
+}
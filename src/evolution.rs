@@ -0,0 +1,237 @@
+use serde::Deserialize;
+
+use crate::error::{self, PkmnError};
+use crate::pokedex::Pokemon;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvolutionTrigger {
+    /// Evolves when traded.
+    Trade,
+    /// Evolves when traded while holding an item.
+    TradeHeld,
+    /// Evolves when levelled up while holding an item.
+    LevelHeld,
+}
+
+/// An evolution that's easy to miss because it needs a trade or a held item.
+#[derive(Deserialize)]
+pub struct EvolutionHint {
+    pub name: String,
+    pub evolves_into: String,
+    pub trigger: EvolutionTrigger,
+    pub item: String,
+    /// How to evolve it otherwise, in some games.
+    pub alternative: String,
+}
+
+impl EvolutionHint {
+    pub fn message(&self) -> String {
+        let method = match self.trigger {
+            EvolutionTrigger::Trade => String::from("when traded"),
+            EvolutionTrigger::TradeHeld => format!("when traded holding {}", self.item),
+            EvolutionTrigger::LevelHeld => format!("when levelled up holding {}", self.item),
+        };
+
+        format!("Evolves into {} {}", self.evolves_into, method)
+    }
+}
+
+pub static EVOLUTION_HINTS_CSV: &[u8] = include_bytes!("../data/evolution_hints.csv");
+
+pub(crate) fn load_hints() -> Result<Vec<EvolutionHint>, PkmnError> {
+    error::read_csv("evolution_hints.csv", EVOLUTION_HINTS_CSV)
+}
+
+/// Trade and held-item evolutions of a Pokémon, including those of its
+/// cosmetic forms (e.g. "Pumpkaboo Small Size").
+pub fn hints_for(pokemon: &Pokemon) -> Vec<EvolutionHint> {
+    load_hints()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|hint| {
+            pokemon.name == hint.name || pokemon.name.starts_with(&format!("{} ", hint.name))
+        })
+        .collect()
+}
+
+#[derive(Deserialize)]
+pub(crate) struct Evolution {
+    name: String,
+    evolves_into: String,
+    method: String,
+}
+
+pub static EVOLUTIONS_CSV: &[u8] = include_bytes!("../data/evolutions.csv");
+
+/// A Pokémon in an evolution line, and how the previous one evolves into it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Stage {
+    pub name: String,
+    pub method: Option<String>,
+}
+
+pub(crate) fn load_evolutions() -> Result<Vec<Evolution>, PkmnError> {
+    error::read_csv("evolutions.csv", EVOLUTIONS_CSV)
+}
+
+fn all_evolutions() -> Vec<Evolution> {
+    load_evolutions().unwrap_or_default()
+}
+
+/// Lines from `name` to each of its final evolutions.
+fn lines_from(name: &str, method: Option<String>, evolutions: &[Evolution]) -> Vec<Vec<Stage>> {
+    let stage = Stage {
+        name: name.to_string(),
+        method,
+    };
+    let next = evolutions
+        .iter()
+        .filter(|evolution| evolution.name == name)
+        .collect::<Vec<&Evolution>>();
+    if next.is_empty() {
+        return vec![vec![stage]];
+    }
+
+    let mut lines = Vec::new();
+    for evolution in next {
+        for line in lines_from(
+            &evolution.evolves_into,
+            Some(evolution.method.clone()),
+            evolutions,
+        ) {
+            let mut full = vec![Stage {
+                name: stage.name.clone(),
+                method: stage.method.clone(),
+            }];
+            full.extend(line);
+            lines.push(full);
+        }
+    }
+    lines
+}
+
+/// The evolution lines the Pokémon is part of, from the first stage of its
+/// family to each final one. Empty if it doesn't evolve.
+pub fn lines_for(pokemon: &Pokemon) -> Vec<Vec<Stage>> {
+    let evolutions = all_evolutions();
+    let in_family = evolutions
+        .iter()
+        .any(|evolution| evolution.name == pokemon.name || evolution.evolves_into == pokemon.name);
+    if !in_family {
+        return Vec::new();
+    }
+
+    let mut first = pokemon.name.as_str();
+    while let Some(evolution) = evolutions
+        .iter()
+        .find(|evolution| evolution.evolves_into == first)
+    {
+        first = &evolution.name;
+    }
+
+    lines_from(first, None, &evolutions)
+        .into_iter()
+        .filter(|line| line.iter().any(|stage| stage.name == pokemon.name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::{self, find_by_id};
+
+    fn messages(id: &str) -> Vec<String> {
+        hints_for(&find_by_id(id).unwrap())
+            .iter()
+            .map(EvolutionHint::message)
+            .collect()
+    }
+
+    #[test]
+    fn trade_held_item() {
+        assert_eq!(
+            messages("onix"),
+            vec!["Evolves into Steelix when traded holding Metal Coat"]
+        );
+    }
+
+    #[test]
+    fn branching_evolutions() {
+        assert_eq!(messages("clamperl").len(), 2);
+    }
+
+    #[test]
+    fn forms() {
+        assert_eq!(messages("pumpkaboo-small-size").len(), 1);
+        assert!(messages("slowpoke-galar").is_empty());
+    }
+
+    #[test]
+    fn no_hints() {
+        assert!(messages("pikachu").is_empty());
+    }
+
+    fn lines(id: &str) -> Vec<String> {
+        lines_for(&find_by_id(id).unwrap())
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|stage| match &stage.method {
+                        Some(method) => format!("{} ({})", stage.name, method),
+                        None => stage.name.clone(),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(" → ")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn full_line_from_any_stage() {
+        let charizard = vec![String::from(
+            "Charmander → Charmeleon (Lv 16) → Charizard (Lv 36)",
+        )];
+        assert_eq!(lines("charmander"), charizard);
+        assert_eq!(lines("charizard"), charizard);
+    }
+
+    #[test]
+    fn branches_through_the_pokemon() {
+        assert_eq!(lines("gloom").len(), 2);
+        assert_eq!(lines("eevee").len(), 8);
+        assert_eq!(
+            lines("umbreon"),
+            vec![String::from("Eevee → Umbreon (Friendship, night)")]
+        );
+    }
+
+    #[test]
+    fn coverage() {
+        assert!(lines("tauros").is_empty());
+        assert!(lines("magnezone")[0].starts_with("Magnemite"));
+        assert_eq!(
+            lines("bibarel"),
+            vec![String::from("Bidoof → Bibarel (Lv 15)")]
+        );
+        assert_eq!(lines("kirlia").len(), 2);
+        assert!(lines("obstagoon")[0].starts_with("Galarian Zigzagoon"));
+        assert!(lines("dragapult")[0].starts_with("Dreepy"));
+    }
+
+    #[test]
+    fn every_name_is_in_the_pokedex() {
+        for evolution in all_evolutions() {
+            for name in [&evolution.name, &evolution.evolves_into] {
+                assert!(
+                    pokedex::entries()
+                        .unwrap()
+                        .iter()
+                        .any(|pokemon| pokemon.name == *name),
+                    "unknown Pokémon {}",
+                    name
+                );
+            }
+        }
+    }
+}
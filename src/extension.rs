@@ -0,0 +1,185 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config;
+use crate::pokedex::Pokemon;
+
+/// A spin-off dataset (e.g. Pokémon Sleep styles or TCG card counts) mounted
+/// as a card section from `<config dir>/extensions/<name>.csv`.
+///
+/// The first column identifies the Pokémon, by name or National Pokédex
+/// number. Every other column becomes a row of the section, labelled with its
+/// header:
+///
+/// ```csv
+/// pokemon,Sleep Type,Specialty
+/// Pikachu,Snoozing,Berries
+/// 25,Snoozing,Berries
+/// ```
+pub struct Extension {
+    /// Section name for `--sections`: the file name without `.csv`.
+    pub name: String,
+    pub heading: String,
+    labels: Vec<String>,
+    records: Vec<(String, Vec<String>)>,
+}
+
+/// `sleep_styles` → `Sleep Styles`
+fn heading_for(name: &str) -> String {
+    name.split(['_', '-'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+impl Extension {
+    /// Every extension in the config directory, by file name.
+    pub fn load_all() -> Vec<Extension> {
+        let dir = match config::config_dir() {
+            Some(dir) => dir.join("extensions"),
+            None => return Vec::new(),
+        };
+
+        match Extension::load_dir(&dir) {
+            Ok(extensions) => extensions,
+            Err(err) => {
+                info!("no extensions loaded from \"{}\": {}", dir.display(), err);
+                Vec::new()
+            }
+        }
+    }
+
+    fn load_dir(dir: &Path) -> Result<Vec<Extension>, Box<dyn Error>> {
+        let mut paths = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "csv"))
+            .collect::<Vec<PathBuf>>();
+        paths.sort();
+
+        let mut extensions = Vec::new();
+        for path in paths {
+            let name = match path.file_stem().and_then(|stem| stem.to_str()) {
+                Some(name) => name.to_lowercase(),
+                None => continue,
+            };
+            match fs::read(&path)
+                .map_err(Box::<dyn Error>::from)
+                .and_then(|bytes| Extension::parse(&name, &bytes))
+            {
+                Ok(extension) => {
+                    info!("loaded extension \"{}\"", path.display());
+                    extensions.push(extension);
+                }
+                Err(err) => warn!("extension \"{}\" failed to load: {}", path.display(), err),
+            }
+        }
+
+        Ok(extensions)
+    }
+
+    pub fn parse(name: &str, bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let mut csv_reader = csv::Reader::from_reader(bytes);
+        let labels = csv_reader
+            .headers()?
+            .iter()
+            .skip(1)
+            .map(String::from)
+            .collect::<Vec<String>>();
+        if labels.is_empty() {
+            return Err("expected a Pokémon column and at least one data column".into());
+        }
+
+        let mut records = Vec::new();
+        for record in csv_reader.records() {
+            let record = record?;
+            let mut values = record.iter().map(|value| value.trim().to_string());
+            let key = values.next().unwrap_or_default().to_lowercase();
+            records.push((key, values.collect()));
+        }
+
+        Ok(Extension {
+            name: name.to_string(),
+            heading: heading_for(name),
+            labels,
+            records,
+        })
+    }
+
+    /// The section's rows for `pokemon`, leaving out empty values. Empty if
+    /// the dataset doesn't list it.
+    pub fn rows_for(&self, pokemon: &Pokemon) -> Vec<(String, String)> {
+        let name = pokemon.name.to_lowercase();
+        let number = pokemon.pokedex_number.to_string();
+        // An entry by name wins over one by number, which also matches forms
+        let record = self
+            .records
+            .iter()
+            .find(|(key, _)| *key == name)
+            .or_else(|| self.records.iter().find(|(key, _)| *key == number));
+
+        match record {
+            Some((_, values)) => self
+                .labels
+                .iter()
+                .zip(values)
+                .filter(|(_, value)| !value.is_empty())
+                .map(|(label, value)| (label.clone(), value.clone()))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::find_by_id;
+
+    const SLEEP: &[u8] = b"pokemon,Sleep Type,Specialty\n\
+        Pikachu,Snoozing,Berries\n\
+        6,Snoozing,\n\
+        Mega Charizard X,Dozing,Skills\n";
+
+    #[test]
+    fn headings() {
+        assert_eq!(heading_for("sleep_styles"), "Sleep Styles");
+        assert_eq!(heading_for("mystery-dungeon"), "Mystery Dungeon");
+    }
+
+    #[test]
+    fn rows_by_name_or_number() {
+        let extension = Extension::parse("sleep", SLEEP).unwrap();
+        assert_eq!(extension.heading, "Sleep");
+        assert_eq!(
+            extension.rows_for(&find_by_id("pikachu").unwrap()),
+            vec![
+                (String::from("Sleep Type"), String::from("Snoozing")),
+                (String::from("Specialty"), String::from("Berries")),
+            ]
+        );
+        assert_eq!(
+            extension.rows_for(&find_by_id("charizard").unwrap()),
+            vec![(String::from("Sleep Type"), String::from("Snoozing"))]
+        );
+        assert_eq!(
+            extension.rows_for(&find_by_id("charizard-mega-x").unwrap())[0].1,
+            "Dozing"
+        );
+        assert!(extension
+            .rows_for(&find_by_id("bulbasaur").unwrap())
+            .is_empty());
+    }
+
+    #[test]
+    fn needs_data_columns() {
+        assert!(Extension::parse("empty", b"pokemon\nPikachu\n").is_err());
+    }
+}
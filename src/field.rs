@@ -0,0 +1,112 @@
+use serde::Deserialize;
+
+use crate::error::{self, PkmnError};
+
+/// One effect of a weather or terrain, as of Generation 8.
+#[derive(Deserialize)]
+pub struct FieldEffect {
+    pub field: String,
+    /// `weather` or `terrain`.
+    pub kind: String,
+    /// `damage`, `accuracy`, `ability`, `item` or `other`.
+    pub category: String,
+    pub effect: String,
+}
+
+/// Categories in the order they're shown, with their headings.
+pub const CATEGORIES: [(&str, &str); 5] = [
+    ("damage", "Damage"),
+    ("accuracy", "Accuracy"),
+    ("ability", "Abilities"),
+    ("item", "Items"),
+    ("other", "Other"),
+];
+
+pub static FIELD_EFFECTS_CSV: &[u8] = include_bytes!("../data/field_effects.csv");
+
+pub(crate) fn load() -> Result<Vec<FieldEffect>, PkmnError> {
+    error::read_csv("field_effects.csv", FIELD_EFFECTS_CSV)
+}
+
+fn all_effects() -> Vec<FieldEffect> {
+    load().unwrap_or_default()
+}
+
+/// Names of the weathers and terrains with effects, e.g. `electric-terrain`.
+pub fn field_names() -> Vec<String> {
+    let mut names = all_effects()
+        .into_iter()
+        .map(|effect| effect.field)
+        .collect::<Vec<String>>();
+    names.dedup();
+    names
+}
+
+/// Resolves common aliases, e.g. "harsh sunlight" or "electric".
+pub fn canonical_name(name: &str) -> Option<String> {
+    let name = name.trim().to_lowercase().replace(' ', "-");
+    let name = match name.as_str() {
+        "harsh-sunlight" | "sunlight" | "sunny-day" => String::from("sun"),
+        "rain-dance" | "heavy-rain" => String::from("rain"),
+        "sand" => String::from("sandstorm"),
+        "snow" => String::from("hail"),
+        _ => name,
+    };
+
+    let names = field_names();
+    if names.contains(&name) {
+        return Some(name);
+    }
+    let terrain = format!("{}-terrain", name);
+    names.into_iter().find(|field| *field == terrain)
+}
+
+/// Effects of a weather or terrain, in dataset order.
+pub fn effects_of(name: &str) -> Vec<FieldEffect> {
+    let name = match canonical_name(name) {
+        Some(name) => name,
+        None => return Vec::new(),
+    };
+    all_effects()
+        .into_iter()
+        .filter(|effect| effect.field == name)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aliases() {
+        assert_eq!(canonical_name("Rain").as_deref(), Some("rain"));
+        assert_eq!(canonical_name("harsh sunlight").as_deref(), Some("sun"));
+        assert_eq!(
+            canonical_name("electric").as_deref(),
+            Some("electric-terrain")
+        );
+        assert_eq!(
+            canonical_name("Misty Terrain").as_deref(),
+            Some("misty-terrain")
+        );
+        assert_eq!(canonical_name("fog"), None);
+    }
+
+    #[test]
+    fn categories_are_known() {
+        for effect in all_effects() {
+            assert!(CATEGORIES
+                .iter()
+                .any(|(category, _)| *category == effect.category));
+            assert!(effect.kind == "weather" || effect.kind == "terrain");
+        }
+    }
+
+    #[test]
+    fn rain() {
+        let effects = effects_of("rain");
+        assert!(effects
+            .iter()
+            .any(|effect| effect.category == "accuracy" && effect.effect.contains("Thunder")));
+    }
+}
@@ -0,0 +1,124 @@
+//! Pokédex entries: the flavor text a game's Pokédex shows, e.g. Squirtle's
+//! "After birth, its back swells and hardens into a shell."
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{self, PkmnError};
+use crate::pokedex::{self, Pokemon};
+
+pub static FLAVOR_TEXT_CSV: &[u8] = include_bytes!("../data/flavor_text.csv");
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct FlavorText {
+    pub name: String,
+    /// The games showing the entry, e.g. `Red & Blue`.
+    pub version: String,
+    /// Language of the entry, as `--lang` takes it, e.g. `en`.
+    pub language: String,
+    pub text: String,
+}
+
+/// Every bundled entry, in Pokédex order. Only the English Red & Blue and
+/// Yellow entries of well-known Pokémon are covered.
+pub fn all_flavor_text() -> Result<Vec<FlavorText>, PkmnError> {
+    error::read_csv("flavor_text.csv", FLAVOR_TEXT_CSV)
+}
+
+/// Versions with bundled entries, oldest first.
+pub fn versions() -> Vec<String> {
+    let mut versions: Vec<String> = Vec::new();
+    for entry in all_flavor_text().unwrap_or_default() {
+        if !versions.contains(&entry.version) {
+            versions.push(entry.version);
+        }
+    }
+    versions
+}
+
+/// The version whose name contains `query` (case-insensitive), e.g. "blue"
+/// for Red & Blue.
+pub fn find_version(query: &str) -> Option<String> {
+    let needle = query.trim().to_lowercase();
+    versions()
+        .into_iter()
+        .find(|version| version.to_lowercase().contains(&needle))
+}
+
+/// The species' entry in `version`, which its forms share, or its entry in
+/// the newest version with one. Entries in `language` are preferred, then
+/// English ones.
+pub fn flavor_text_for(
+    pokemon: &Pokemon,
+    version: Option<&str>,
+    language: &str,
+) -> Option<FlavorText> {
+    let species = pokedex::species_of(pokemon)?;
+    let versions = versions();
+    let newest = |entry: &FlavorText| versions.iter().position(|v| *v == entry.version);
+    let entries = all_flavor_text()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|entry| entry.name == species.name)
+        .filter(|entry| version.is_none_or(|version| entry.version == version))
+        .collect::<Vec<FlavorText>>();
+    [language, "en"].into_iter().find_map(|language| {
+        entries
+            .iter()
+            .filter(|entry| entry.language == language)
+            .max_by_key(|entry| newest(entry))
+            .cloned()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::find_by_id;
+
+    #[test]
+    fn every_entry_is_a_pokemon() {
+        for entry in all_flavor_text().unwrap() {
+            assert!(
+                pokedex::entries()
+                    .unwrap()
+                    .iter()
+                    .any(|pokemon| pokemon.name == entry.name),
+                "{} isn't in the Pokédex",
+                entry.name
+            );
+        }
+        assert_eq!(versions(), vec!["Red & Blue", "Yellow"]);
+    }
+
+    #[test]
+    fn picks_versions() {
+        let squirtle = find_by_id("squirtle").unwrap();
+        assert_eq!(find_version("blue").as_deref(), Some("Red & Blue"));
+        assert_eq!(find_version("crystal"), None);
+
+        assert_eq!(
+            flavor_text_for(&squirtle, None, "en").unwrap().version,
+            "Yellow"
+        );
+        assert!(flavor_text_for(&squirtle, Some("Red & Blue"), "en")
+            .unwrap()
+            .text
+            .starts_with("After birth"));
+
+        let mega = find_by_id("charizard-mega-x").unwrap();
+        assert_eq!(
+            flavor_text_for(&mega, None, "en").unwrap().name,
+            "Charizard"
+        );
+        assert!(flavor_text_for(&mega, Some("Yellow"), "en").is_none());
+        assert!(flavor_text_for(&find_by_id("rattata").unwrap(), None, "en").is_none());
+    }
+
+    #[test]
+    fn falls_back_to_english() {
+        let squirtle = find_by_id("squirtle").unwrap();
+        let entry = flavor_text_for(&squirtle, None, "ja").unwrap();
+        assert_eq!(entry.language, "en");
+        assert_eq!(entry.version, "Yellow");
+    }
+}
@@ -0,0 +1,290 @@
+use std::cmp::Ordering;
+
+pub struct MatchScore {
+    pub distance: usize,
+    pub similarity: f64,
+}
+
+impl MatchScore {
+    pub fn new(value: &str, query: &str) -> Self {
+        MatchScore {
+            distance: strsim::levenshtein(value, query),
+            similarity: strsim::jaro_winkler(value, query),
+        }
+    }
+
+    /// Scores `value` only if it could rank ahead of `threshold`, the worst
+    /// score currently kept, skipping the expensive Levenshtein distance
+    /// wherever possible.
+    fn new_within(value: &str, query: &str, threshold: &MatchScore) -> Option<Self> {
+        if max_jaro_winkler(value, query) < threshold.similarity {
+            return None;
+        }
+
+        let similarity = strsim::jaro_winkler(value, query);
+
+        if similarity < threshold.similarity {
+            return None;
+        }
+
+        let distance = if similarity > threshold.similarity {
+            strsim::levenshtein(value, query)
+        } else {
+            // Ties on similarity only rank ahead with a strictly smaller distance
+            bounded_levenshtein(value, query, threshold.distance.checked_sub(1)?)?
+        };
+
+        Some(MatchScore {
+            distance,
+            similarity,
+        })
+    }
+
+    pub fn compare(a: &MatchScore, b: &MatchScore) -> Ordering {
+        b.similarity
+            .partial_cmp(&a.similarity)
+            .unwrap()
+            .then(a.distance.cmp(&b.distance))
+    }
+}
+
+/// Scores `query` against the run of words in `text` that matches it best,
+/// for searching prose (e.g. descriptions) for a phrase rather than comparing
+/// whole values.
+pub fn phrase_score(text: &str, query: &str) -> MatchScore {
+    let text = text.to_lowercase();
+    let query = query.to_lowercase();
+    let words = text
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| c.is_ascii_punctuation() && c != '\''))
+        .collect::<Vec<&str>>();
+    let query_words = query.split_whitespace().count().max(1);
+
+    words
+        .windows(query_words.min(words.len()).max(1))
+        .map(|window| MatchScore::new(&window.join(" "), &query))
+        .min_by(MatchScore::compare)
+        .unwrap_or_else(|| MatchScore::new(&text, &query))
+}
+
+/// Upper bound of the Jaro-Winkler similarity of two strings, from their
+/// lengths and common prefix alone.
+fn max_jaro_winkler(a: &str, b: &str) -> f64 {
+    let a_len = a.chars().count();
+    let b_len = b.chars().count();
+
+    if a_len == 0 || b_len == 0 {
+        return if a_len == b_len { 1.0 } else { 0.0 };
+    }
+
+    let matches = a_len.min(b_len) as f64;
+    let jaro = (matches / a_len as f64 + matches / b_len as f64 + 1.0) / 3.0;
+    let prefix = a.chars().zip(b.chars()).take_while(|(a, b)| a == b).count();
+
+    // strsim doesn't cap the prefix at 4 characters, so neither can this.
+    (jaro + (prefix as f64 * 0.1 * (1.0 - jaro))).min(1.0)
+}
+
+/// Levenshtein distance, or `None` as soon as it's known to exceed `max`.
+fn bounded_levenshtein(a: &str, b: &str, max: usize) -> Option<usize> {
+    let a = a.chars().collect::<Vec<char>>();
+    let b = b.chars().collect::<Vec<char>>();
+
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+
+    let mut row = (0..=b.len()).collect::<Vec<usize>>();
+
+    for (i, a_char) in a.iter().enumerate() {
+        let mut previous_diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous_diagonal + usize::from(a_char != b_char);
+            previous_diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+
+        if row.iter().min().unwrap() > &max {
+            return None;
+        }
+    }
+
+    Some(row[b.len()]).filter(|distance| *distance <= max)
+}
+
+/// A value from a fuzzy search, with its score against the query.
+pub struct Match<T> {
+    pub value: T,
+    pub score: MatchScore,
+}
+
+/// Ranks `values` by how closely their key matches `query` (case-insensitive),
+/// keeping the best `limit` matches. Equal scores keep their original order.
+pub fn search<T, I, K>(values: I, key: K, query: &str, limit: usize) -> Vec<Match<T>>
+where
+    I: IntoIterator<Item = T>,
+    K: Fn(&T) -> String,
+{
+    let query = query.to_lowercase();
+    let mut results: Vec<Match<T>> = Vec::with_capacity(limit + 1);
+
+    if limit == 0 {
+        return results;
+    }
+
+    for value in values {
+        let key = key(&value).to_lowercase();
+        let score = match results.last() {
+            Some(worst) if results.len() == limit => {
+                match MatchScore::new_within(&key, &query, &worst.score) {
+                    Some(score) => score,
+                    None => continue,
+                }
+            }
+            _ => MatchScore::new(&key, &query),
+        };
+
+        let index = results.partition_point(|result| {
+            MatchScore::compare(&result.score, &score) != Ordering::Greater
+        });
+        results.insert(index, Match { value, score });
+        results.truncate(limit);
+    }
+
+    results
+}
+
+/// Minimum similarity for a value to be offered as a "did you mean" suggestion.
+const SUGGESTION_SIMILARITY: f64 = 0.8;
+
+/// The closest of `candidates` to `query`, if it's close enough to be a likely
+/// typo, e.g. for "did you mean …?" hints after an exact lookup fails.
+pub fn did_you_mean<I, S>(candidates: I, query: &str) -> Option<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    search(
+        candidates
+            .into_iter()
+            .map(|candidate| candidate.as_ref().to_string()),
+        String::clone,
+        query,
+        1,
+    )
+    .into_iter()
+    .find(|result| result.score.similarity >= SUGGESTION_SIMILARITY)
+    .map(|result| result.value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_strings() {
+        let moves = ["Thunderbolt", "Thunder", "Thunder Punch", "Flamethrower"];
+        let results = search(moves, |name| name.to_string(), "thunderbolt", 2);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].value, "Thunderbolt");
+        assert_eq!(results[0].score.distance, 0);
+    }
+
+    #[test]
+    fn search_zero_limit() {
+        assert!(search(["Adamant"], |name| name.to_string(), "adamant", 0).is_empty());
+    }
+
+    #[test]
+    fn did_you_mean_close() {
+        let abilities = ["Levitate", "Intimidate", "Limber"];
+        assert_eq!(
+            did_you_mean(abilities, "intimidat"),
+            Some("Intimidate".to_string())
+        );
+    }
+
+    #[test]
+    fn did_you_mean_far() {
+        assert_eq!(did_you_mean(["Levitate", "Intimidate"], "xyz"), None);
+    }
+
+    #[test]
+    fn phrase_within_text() {
+        let text = "Ignores the target's stat changes when attacking or being attacked.";
+        assert_eq!(
+            phrase_score(text, "ignores the target's stat changes").distance,
+            0
+        );
+        assert_eq!(phrase_score(text, "being attacked").distance, 0);
+        assert!(phrase_score(text, "ignore targets stat change").similarity > 0.9);
+    }
+
+    #[test]
+    fn bounded_levenshtein_within_max() {
+        assert_eq!(bounded_levenshtein("charzad", "charizard", 2), Some(2));
+        assert_eq!(bounded_levenshtein("charizard", "charizard", 0), Some(0));
+    }
+
+    #[test]
+    fn bounded_levenshtein_exceeds_max() {
+        assert_eq!(bounded_levenshtein("charzad", "charizard", 1), None);
+        assert_eq!(bounded_levenshtein("char", "charizard", 4), None);
+    }
+
+    #[test]
+    fn max_jaro_winkler_is_upper_bound() {
+        for (a, b) in [
+            ("char", "charizard"),
+            ("x", "xatu"),
+            ("mew", "mewtwo"),
+            ("garchom", "garchomp"),
+            ("abc", "xyz"),
+        ] {
+            assert!(max_jaro_winkler(a, b) >= strsim::jaro_winkler(a, b));
+        }
+    }
+
+    #[test]
+    fn match_compare_equal() {
+        let a = MatchScore {
+            distance: 1,
+            similarity: 1.0,
+        };
+        let b = MatchScore {
+            distance: 1,
+            similarity: 1.0,
+        };
+        assert_eq!(MatchScore::compare(&a, &b), Ordering::Equal);
+    }
+
+    #[test]
+    fn match_compare_similarity_is_highest_priority() {
+        let a = MatchScore {
+            distance: 1,
+            similarity: 0.75,
+        };
+        let b = MatchScore {
+            distance: 2,
+            similarity: 0.25,
+        };
+        assert_eq!(MatchScore::compare(&a, &b), Ordering::Less);
+        assert_eq!(MatchScore::compare(&b, &a), Ordering::Greater);
+    }
+
+    #[test]
+    fn match_compare_distance_is_tie_breaker() {
+        let a = MatchScore {
+            distance: 2,
+            similarity: 0.5,
+        };
+        let b = MatchScore {
+            distance: 1,
+            similarity: 0.5,
+        };
+        assert_eq!(MatchScore::compare(&b, &a), Ordering::Less);
+        assert_eq!(MatchScore::compare(&a, &b), Ordering::Greater);
+    }
+}
@@ -0,0 +1,135 @@
+use chrono::{Datelike, NaiveDate};
+use serde::Deserialize;
+
+use crate::error::{self, PkmnError};
+use crate::fuzzy;
+
+#[derive(Deserialize)]
+pub struct Game {
+    pub name: String,
+    pub generation: u8,
+    /// First release, which is the Japanese one for older games.
+    pub release_date: NaiveDate,
+    /// Whether the game debuted its generation's new Pokémon.
+    pub introduced_generation: bool,
+}
+
+impl Game {
+    /// Complete years since release as of `date`.
+    pub fn age_on(&self, date: NaiveDate) -> i32 {
+        let years = date.year() - self.release_date.year();
+        match (date.month(), date.day()) < (self.release_date.month(), self.release_date.day()) {
+            true => years - 1,
+            false => years,
+        }
+    }
+
+    fn is_anniversary(&self, date: NaiveDate) -> bool {
+        self.release_date.month() == date.month()
+            && self.release_date.day() == date.day()
+            && self.release_date.year() < date.year()
+    }
+
+    /// Next anniversary strictly after `date`.
+    fn next_anniversary(&self, date: NaiveDate) -> Option<NaiveDate> {
+        (date.year()..=date.year() + 4)
+            .filter_map(|year| self.release_date.with_year(year))
+            .find(|anniversary| {
+                *anniversary > date && anniversary.year() > self.release_date.year()
+            })
+    }
+}
+
+pub static GAMES_CSV: &[u8] = include_bytes!("../data/games.csv");
+
+/// Every bundled game, oldest first.
+pub fn all_games() -> Result<Vec<Game>, PkmnError> {
+    error::read_csv("games.csv", GAMES_CSV)
+}
+
+/// The earliest game whose name contains `query` (case-insensitive), e.g.
+/// "emerald", falling back to the closest name.
+pub fn find_game(query: &str) -> Option<Game> {
+    let games = all_games().unwrap_or_default();
+    let needle = query.trim().to_lowercase();
+    if let Some(index) = games
+        .iter()
+        .position(|game| game.name.to_lowercase().contains(&needle))
+    {
+        return games.into_iter().nth(index);
+    }
+    fuzzy::search(games, |game| game.name.clone(), query, 1)
+        .into_iter()
+        .next()
+        .map(|result| result.value)
+}
+
+/// Games released on this day (month and day) in an earlier year.
+pub fn anniversaries_on(date: NaiveDate) -> Vec<Game> {
+    all_games()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|game| game.is_anniversary(date))
+        .collect()
+}
+
+/// The soonest anniversary after `date`, with the games sharing it.
+pub fn next_anniversaries(date: NaiveDate) -> Option<(NaiveDate, Vec<Game>)> {
+    let next = all_games()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|game| game.next_anniversary(date))
+        .min()?;
+    Some((next, anniversaries_on(next)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn shared_anniversary() {
+        let names = anniversaries_on(date(2022, 11, 21))
+            .into_iter()
+            .map(|game| game.name)
+            .collect::<Vec<String>>();
+        assert_eq!(
+            names,
+            vec![
+                "Pokémon Gold & Silver",
+                "Pokémon Ruby & Sapphire",
+                "Pokémon Omega Ruby & Alpha Sapphire"
+            ]
+        );
+    }
+
+    #[test]
+    fn no_anniversary_on_release_day() {
+        assert!(anniversaries_on(date(1996, 2, 27)).is_empty());
+    }
+
+    #[test]
+    fn age() {
+        let red_green = all_games().unwrap().remove(0);
+        assert_eq!(red_green.age_on(date(2021, 2, 27)), 25);
+        assert_eq!(red_green.age_on(date(2021, 2, 26)), 24);
+    }
+
+    #[test]
+    fn find() {
+        assert_eq!(find_game("emerald").unwrap().generation, 3);
+        assert_eq!(find_game("Ruby").unwrap().name, "Pokémon Ruby & Sapphire");
+        assert_eq!(find_game("plattinum").unwrap().name, "Pokémon Platinum");
+    }
+
+    #[test]
+    fn next() {
+        let (next, games) = next_anniversaries(date(2021, 12, 31)).unwrap();
+        assert_eq!(next, date(2022, 1, 29));
+        assert_eq!(games[0].name, "Pokémon FireRed & LeafGreen");
+    }
+}
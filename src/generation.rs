@@ -0,0 +1,130 @@
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+
+use crate::pokedex::{entries, Pokemon, PokemonStatus};
+
+const REGIONS: [&str; 9] = [
+    "Kanto", "Johto", "Hoenn", "Sinnoh", "Unova", "Kalos", "Alola", "Galar", "Paldea",
+];
+
+/// The newest generation, of those with a region or with Pokémon in the
+/// Pokédex, e.g. a downloaded one (see `pkmn update-data`).
+pub fn latest_generation() -> u8 {
+    entries()
+        .unwrap_or_default()
+        .iter()
+        .map(|pokemon| pokemon.generation)
+        .chain([REGIONS.len() as u8])
+        .max()
+        .unwrap_or_default()
+}
+
+pub fn region_name(generation: u8) -> Option<&'static str> {
+    REGIONS
+        .get(usize::from(generation).checked_sub(1)?)
+        .copied()
+}
+
+/// Overview of the Pokémon introduced in a generation.
+pub struct GenerationSummary {
+    pub generation: u8,
+    pub region: &'static str,
+    /// Distinct species, i.e. National Pokédex numbers.
+    pub species: usize,
+    /// Entries including Mega Evolutions, regional variants and other forms.
+    pub entries: usize,
+    pub first_number: u16,
+    pub last_number: u16,
+    /// Types by the number of entries having them, most common first.
+    pub types: Vec<(String, usize)>,
+    pub strongest: Pokemon,
+    pub legendaries: Vec<String>,
+}
+
+impl GenerationSummary {
+    pub fn new(generation: u8) -> Option<Self> {
+        let region = region_name(generation)?;
+        let pokemon = entries()
+            .unwrap_or_default()
+            .iter()
+            .filter(|pokemon| pokemon.generation == generation)
+            .cloned()
+            .collect::<Vec<Pokemon>>();
+
+        let numbers = pokemon
+            .iter()
+            .map(|pokemon| pokemon.pokedex_number)
+            .collect::<BTreeSet<u16>>();
+
+        let mut types = Vec::<(String, usize)>::new();
+        for pokemon_type in pokemon
+            .iter()
+            .flat_map(|pokemon| [&pokemon.type_1, &pokemon.type_2])
+            .filter(|pokemon_type| !pokemon_type.is_empty())
+        {
+            match types
+                .iter_mut()
+                .find(|(name, _)| name.as_str() == &**pokemon_type)
+            {
+                Some((_, count)) => *count += 1,
+                None => types.push((pokemon_type.to_string(), 1)),
+            }
+        }
+        types.sort_by(|(a_name, a_count), (b_name, b_count)| {
+            b_count.cmp(a_count).then(a_name.cmp(b_name))
+        });
+
+        let legendaries = pokemon
+            .iter()
+            .filter(|pokemon| !matches!(pokemon.status, PokemonStatus::Normal))
+            .map(|pokemon| pokemon.name.clone())
+            .collect();
+
+        let strongest = pokemon
+            .iter()
+            .min_by_key(|pokemon| Reverse(pokemon.total_points))?
+            .clone();
+
+        Some(GenerationSummary {
+            generation,
+            region,
+            species: numbers.len(),
+            entries: pokemon.len(),
+            first_number: *numbers.iter().next()?,
+            last_number: *numbers.iter().next_back()?,
+            types,
+            strongest,
+            legendaries,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regions() {
+        assert_eq!(region_name(1), Some("Kanto"));
+        assert_eq!(region_name(9), Some("Paldea"));
+        assert_eq!(region_name(0), None);
+        assert_eq!(region_name(10), None);
+        assert_eq!(latest_generation(), 9);
+    }
+
+    #[test]
+    fn kanto() {
+        let summary = GenerationSummary::new(1).unwrap();
+        assert_eq!(summary.region, "Kanto");
+        assert_eq!(summary.species, 151);
+        assert_eq!(summary.first_number, 1);
+        assert_eq!(summary.last_number, 151);
+        assert_eq!(summary.types[0].0, "Poison");
+        assert!(summary.legendaries.contains(&String::from("Mewtwo")));
+    }
+
+    #[test]
+    fn unknown_generation() {
+        assert!(GenerationSummary::new(10).is_none());
+    }
+}
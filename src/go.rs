@@ -0,0 +1,231 @@
+//! Pokémon GO stats and CP, derived from the main series base stats the way
+//! GO does for newly released species.
+//!
+//! Fast and charged move pools aren't covered: they need GO's own move data,
+//! which isn't bundled, so they're a separate request from these stats.
+
+use serde::Serialize;
+
+use crate::pokedex::Pokemon;
+
+/// CP multipliers for levels 1 to 51. Half levels are interpolated.
+const CP_MULTIPLIERS: [f64; 51] = [
+    0.094, 0.16639787, 0.21573247, 0.25572005, 0.29024988, 0.3210876, 0.34921268, 0.37523559,
+    0.39956728, 0.42250001, 0.44310755, 0.46279839, 0.48168495, 0.49985844, 0.51739395, 0.53435433,
+    0.55079269, 0.56675452, 0.58227891, 0.59740001, 0.61215729, 0.62656713, 0.64065295, 0.65443563,
+    0.667934, 0.68116492, 0.69414365, 0.70688421, 0.71939909, 0.7317, 0.73776948, 0.74378943,
+    0.74976104, 0.75568551, 0.76156384, 0.76739717, 0.7731865, 0.77893275, 0.78463697, 0.79030001,
+    0.79530001, 0.8003, 0.8053, 0.81029999, 0.81529999, 0.82029999, 0.82529999, 0.83029999,
+    0.83529999, 0.84029999, 0.84529999,
+];
+
+pub const MAX_LEVEL: f64 = 51.0;
+
+/// The CP multiplier at `level`, from 1 to 51 in steps of 0.5.
+pub fn cp_multiplier(level: f64) -> Option<f64> {
+    if !(1.0..=MAX_LEVEL).contains(&level) || level.fract() % 0.5 != 0.0 {
+        return None;
+    }
+    let index = level as usize - 1;
+    if level.fract() == 0.0 {
+        return Some(CP_MULTIPLIERS[index]);
+    }
+    let (below, above) = (CP_MULTIPLIERS[index], CP_MULTIPLIERS[index + 1]);
+    Some(((below * below + above * above) / 2.0).sqrt())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct GoStats {
+    pub attack: u16,
+    pub defense: u16,
+    pub stamina: u16,
+}
+
+/// Individual values, each 0–15.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ivs {
+    pub attack: u16,
+    pub defense: u16,
+    pub stamina: u16,
+}
+
+impl Ivs {
+    pub const PERFECT: Ivs = Ivs {
+        attack: 15,
+        defense: 15,
+        stamina: 15,
+    };
+
+    /// Parses `attack/defense/stamina`, e.g. `15/15/15`.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let ivs = value
+            .split('/')
+            .map(|iv| match iv.trim().parse::<u16>() {
+                Ok(iv) if iv <= 15 => Ok(iv),
+                _ => Err(format!("IVs range from 0 to 15, got \"{}\"", iv.trim())),
+            })
+            .collect::<Result<Vec<u16>, String>>()?;
+        match ivs[..] {
+            [attack, defense, stamina] => Ok(Ivs {
+                attack,
+                defense,
+                stamina,
+            }),
+            _ => Err(String::from(
+                "Expected attack, defense and stamina IVs, e.g. 15/15/15",
+            )),
+        }
+    }
+}
+
+/// Species whose max CP would pass this are nerfed by 9%.
+const NERF_THRESHOLD: u32 = 4000;
+
+impl GoStats {
+    pub fn new(pokemon: &Pokemon) -> Self {
+        let ordered = |a: u16, b: u16| (f64::from(a.max(b)), f64::from(a.min(b)));
+        let speed_modifier = 1.0 + (f64::from(pokemon.speed) - 75.0) / 500.0;
+
+        let (attack_high, attack_low) = ordered(pokemon.attack, pokemon.sp_attack);
+        let attack = (2.0 * (7.0 / 8.0 * attack_high + 1.0 / 8.0 * attack_low)).round();
+        let (defense_high, defense_low) = ordered(pokemon.defense, pokemon.sp_defense);
+        let defense = (2.0 * (5.0 / 8.0 * defense_high + 3.0 / 8.0 * defense_low)).round();
+        let stats = GoStats {
+            attack: (attack * speed_modifier).round() as u16,
+            defense: (defense * speed_modifier).round() as u16,
+            stamina: (f64::from(pokemon.hp) * 1.75 + 50.0).floor() as u16,
+        };
+
+        if stats.cp(Ivs::PERFECT, 40.0) <= NERF_THRESHOLD {
+            return stats;
+        }
+        let nerf = |stat: u16| (f64::from(stat) * 0.91).round() as u16;
+        GoStats {
+            attack: nerf(stats.attack),
+            defense: nerf(stats.defense),
+            stamina: nerf(stats.stamina),
+        }
+    }
+
+    /// CP at `level`, which must be a valid level.
+    pub fn cp(&self, ivs: Ivs, level: f64) -> u32 {
+        let multiplier = cp_multiplier(level).expect("invalid level");
+        let attack = f64::from(self.attack + ivs.attack);
+        let defense = f64::from(self.defense + ivs.defense);
+        let stamina = f64::from(self.stamina + ivs.stamina);
+        let cp = attack * defense.sqrt() * stamina.sqrt() * multiplier * multiplier / 10.0;
+        (cp.floor() as u32).max(10)
+    }
+
+    pub fn hp(&self, ivs: Ivs, level: f64) -> u32 {
+        let multiplier = cp_multiplier(level).expect("invalid level");
+        let hp = f64::from(self.stamina + ivs.stamina) * multiplier;
+        (hp.floor() as u32).max(10)
+    }
+
+    /// CP of the raid boss, which has perfect IVs and the tier's stamina.
+    pub fn raid_boss_cp(&self, tier: RaidTier) -> u32 {
+        let attack = f64::from(self.attack + 15);
+        let defense = f64::from(self.defense + 15);
+        let cp = attack * defense.sqrt() * f64::from(tier.boss_stamina()).sqrt() / 10.0;
+        cp.floor() as u32
+    }
+
+    /// Lowest and highest CP when caught after a raid: level 20 (25 when
+    /// weather boosted), with IVs of at least 10.
+    pub fn raid_catch_cp(&self, weather_boosted: bool) -> (u32, u32) {
+        let level = if weather_boosted { 25.0 } else { 20.0 };
+        let minimum = Ivs {
+            attack: 10,
+            defense: 10,
+            stamina: 10,
+        };
+        (self.cp(minimum, level), self.cp(Ivs::PERFECT, level))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RaidTier {
+    One,
+    Three,
+    Five,
+    Mega,
+}
+
+impl RaidTier {
+    pub const ALL: [RaidTier; 4] = [
+        RaidTier::One,
+        RaidTier::Three,
+        RaidTier::Five,
+        RaidTier::Mega,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RaidTier::One => "Tier 1",
+            RaidTier::Three => "Tier 3",
+            RaidTier::Five => "Tier 5",
+            RaidTier::Mega => "Mega",
+        }
+    }
+
+    fn boss_stamina(&self) -> u32 {
+        match self {
+            RaidTier::One => 600,
+            RaidTier::Three => 3600,
+            RaidTier::Five => 15000,
+            RaidTier::Mega => 9000,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::find_by_id;
+
+    #[test]
+    fn stats_match_the_game() {
+        let pikachu = GoStats::new(&find_by_id("pikachu").unwrap());
+        assert_eq!(
+            pikachu,
+            GoStats {
+                attack: 112,
+                defense: 96,
+                stamina: 111
+            }
+        );
+        assert_eq!(pikachu.cp(Ivs::PERFECT, 40.0), 938);
+    }
+
+    #[test]
+    fn nerfs_the_strongest() {
+        let mewtwo = GoStats::new(&find_by_id("mewtwo").unwrap());
+        assert_eq!(
+            mewtwo,
+            GoStats {
+                attack: 300,
+                defense: 182,
+                stamina: 214
+            }
+        );
+        assert_eq!(mewtwo.cp(Ivs::PERFECT, 40.0), 4178);
+        assert_eq!(mewtwo.raid_catch_cp(false), (2294, 2387));
+    }
+
+    #[test]
+    fn half_levels() {
+        assert_eq!(cp_multiplier(40.0), Some(0.79030001));
+        let multiplier = cp_multiplier(39.5).unwrap();
+        assert!(multiplier > 0.78463697 && multiplier < 0.79030001);
+        assert_eq!(cp_multiplier(39.25), None);
+        assert_eq!(cp_multiplier(52.0), None);
+    }
+
+    #[test]
+    fn parses_ivs() {
+        assert_eq!(Ivs::parse("15/15/15"), Ok(Ivs::PERFECT));
+        assert!(Ivs::parse("16/15/15").is_err());
+        assert!(Ivs::parse("15/15").is_err());
+    }
+}
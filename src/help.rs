@@ -0,0 +1,562 @@
+use crate::print::Printer;
+use console::style;
+
+/// A block of text within a help topic section.
+pub enum Block {
+    Paragraph(&'static str),
+    /// A term and its description, e.g. an option or a variable.
+    Definition(&'static str, &'static str),
+    /// A command line, printed verbatim.
+    Example(&'static str),
+}
+
+pub struct Section {
+    pub heading: &'static str,
+    pub blocks: &'static [Block],
+}
+
+/// Built-in documentation for `pkmn help <topic>` and the man page.
+pub struct Topic {
+    pub name: &'static str,
+    pub summary: &'static str,
+    pub sections: &'static [Section],
+}
+
+use Block::{Definition, Example, Paragraph};
+
+pub static TOPICS: &[Topic] = &[
+    Topic {
+        name: "query-language",
+        summary: "How Pokémon are looked up, filtered and computed on",
+        sections: &[
+            Section {
+                heading: "Lookups",
+                blocks: &[
+                    Paragraph(
+                        "`--search` matches names fuzzily, so typos and partial names still find \
+                         the closest Pokémon. `--id` is exact and takes either a Pokédex number or \
+                         a slug, which also selects alternate forms.",
+                    ),
+                    Example("pkmn -s charzard"),
+                    Example("pkmn --id charizard-mega-x"),
+                    Example("pkmn --id 6"),
+                    Paragraph(
+                        "`--form` picks a form of the Pokémon found, e.g. a regional variant, Mega \
+                         Evolution or Forme; the card's Other Forms section lists each one's \
+                         name for it.",
+                    ),
+                    Example("pkmn -s vulpix --form alolan"),
+                    Paragraph(
+                        "`-s` and `--id` can be repeated to print several cards in one run, or \
+                         `pkmn show` takes slugs, numbers or names; with `--format json` they're \
+                         printed as one array.",
+                    ),
+                    Example("pkmn -s eevee -s vaporeon -s jolteon"),
+                    Example("pkmn --format json show eevee 134 jolteon"),
+                    Paragraph(
+                        "`--show-jp`, or `--lang ja`, adds the Japanese name under the English \
+                         one, with its Hepburn rōmaji and the trademarked romanization, since \
+                         many resources index Pokémon by their Japanese names.",
+                    ),
+                    Example("pkmn -s charizard --show-jp"),
+                    Paragraph(
+                        "`pkmn search <query>` lists the closest names instead of picking one, \
+                         and `--format json` adds their similarity and edit distance, e.g. for \
+                         bots offering the candidates.",
+                    ),
+                    Example("pkmn search charzard --limit 5 --format json"),
+                    Paragraph(
+                        "`pkmn completions <shell>` prints a bash, zsh or fish script that also \
+                         completes Pokémon names, e.g. `pkmn -s bulb<TAB>`.",
+                    ),
+                    Example("pkmn completions bash > ~/.local/share/bash-completion/completions/pkmn"),
+                    Paragraph(
+                        "Output has no colors when it's piped or written to a file, when the \
+                         NO_COLOR environment variable is set, or with `--no-color`. When \
+                         detection gets the terminal wrong, `colors` and `sprite_renderer` under \
+                         [terminal] in the config override it, and `--sprite-renderer` overrides \
+                         both; `pkmn doctor` shows the result.",
+                    ),
+                    Example("pkmn -s pikachu --no-color"),
+                    Paragraph(
+                        "`--format markdown` prints the card as GitHub-flavored Markdown, with \
+                         tables for its data and stats and the sprite as an image link, to paste \
+                         into wikis and Discord.",
+                    ),
+                    Example("pkmn -s pikachu --format markdown"),
+                ],
+            },
+            Section {
+                heading: "Filters",
+                blocks: &[
+                    Paragraph(
+                        "`pkmn list` combines filters, each narrowing the results further. \
+                         Values are case-insensitive.",
+                    ),
+                    Definition("--type", "One type, or two separated by a comma"),
+                    Definition(
+                        "--gen",
+                        "Generation introduced in, or a range like 1-3; forms count from their own",
+                    ),
+                    Definition(
+                        "--where",
+                        "Base stat conditions joined by &&, e.g. \"speed>=100 && total<600\"; \
+                         stats are hp, attack, defense, sp-attack, sp-defense, speed, total or a \
+                         computed field under [fields] in the config",
+                    ),
+                    Definition("--size-class", "Relative size, from tiny to huge"),
+                    Definition("--color", "Pokédex color, e.g. red; Gen 1 species only"),
+                    Definition(
+                        "--shape",
+                        "Pokédex body shape, e.g. quadruped; only recorded for some Gen 1 species",
+                    ),
+                    Definition("--habitat", "Gen 1 habitat, e.g. mountain"),
+                    Definition(
+                        "--trick-room",
+                        "Ranks the results for Trick Room teams, with --format vgc or singles",
+                    ),
+                    Definition(
+                        "--sort",
+                        "number, name, total, a stat like speed, or a computed field under \
+                         [fields] in the config; all but number and name sort highest first",
+                    ),
+                    Definition("--limit, --offset", "Pages through long listings"),
+                    Example("pkmn list --color blue --habitat sea"),
+                    Example("pkmn list --gen 3 --type ghost --sort total --limit 10"),
+                    Example("pkmn list --where 'speed>=100 && attack>120' --sort speed"),
+                    Paragraph(
+                        "`--type`, `--gen` and `--where` also narrow `--search`, and \
+                         `--ability`, which lists the Pokémon that can have an ability, regular \
+                         or hidden.",
+                    ),
+                    Example("pkmn -s rattata --gen 1"),
+                    Example("pkmn --ability levitate --type ghost"),
+                ],
+            },
+            Section {
+                heading: "Computed fields",
+                blocks: &[
+                    Paragraph(
+                        "The `[fields]` table of the config file maps names to arithmetic \
+                         expressions, which are evaluated for every card. Missing values are \
+                         left undefined, so an expression using them is skipped.",
+                    ),
+                    Definition(
+                        "Integers",
+                        "pokedex_number, generation, total_points, hp, attack, defense, \
+                         sp_attack, sp_defense, speed, catch_rate, base_friendship, \
+                         base_experience, egg_cycles",
+                    ),
+                    Definition("Floats", "height_m, weight_kg, percentage_male"),
+                    Example("bulk = \"hp * defense\""),
+                ],
+            },
+        ],
+    },
+    Topic {
+        name: "calculators",
+        summary: "Game mechanics calculators under `pkmn calc` and friends",
+        sections: &[
+            Section {
+                heading: "pkmn calc",
+                blocks: &[
+                    Definition(
+                        "gen1-dv",
+                        "HP DV, shininess, Hidden Power and gender from Gen 1–2 DVs",
+                    ),
+                    Definition(
+                        "gen1-catch",
+                        "Chance of catching a Pokémon in Gen 1, per ball",
+                    ),
+                    Definition(
+                        "hit",
+                        "Chance of a move hitting, with accuracy and evasion stages",
+                    ),
+                    Definition("pp", "A move's PP after PP Ups"),
+                    Example("pkmn calc hit --move thunder --evasion-stage 1"),
+                ],
+            },
+            Section {
+                heading: "Related commands",
+                blocks: &[
+                    Definition(
+                        "status",
+                        "Damage dealt by burn, poison and toxic per generation",
+                    ),
+                    Definition("tera", "Defensive and offensive effect of Terastallizing"),
+                    Definition(
+                        "go",
+                        "Pokémon GO CP at a level and IVs, and raid boss CPs; --go adds GO stats to the card",
+                    ),
+                    Definition("core-gaps", "Types a core of Pokémon can't handle"),
+                    Definition(
+                        "cores",
+                        "Defensive cores around a Pokémon that resist every attacking type",
+                    ),
+                    Definition(
+                        "team",
+                        "How many of a team are weak to and resist each type, flagging shared \
+                         weaknesses; team optimize and team roles build and check whole teams",
+                    ),
+                    Definition(
+                        "stats",
+                        "Stats in the games at a --level, --nature, --evs and --ivs; --explain \
+                         shows each step of the formula",
+                    ),
+                    Definition(
+                        "nature",
+                        "The stats a nature raises and lowers and the berry flavors it likes \
+                         and dislikes; natures lists all 25",
+                    ),
+                    Definition(
+                        "suggest-evs",
+                        "An EV spread for a role that outspeeds a threat or hits an HP benchmark",
+                    ),
+                    Definition(
+                        "learn",
+                        "Flashcards of typings, weaknesses and base Speed, reviewed on an SM-2 \
+                         schedule kept per --profile",
+                    ),
+                    Definition(
+                        "hunt",
+                        "Counts shiny hunt attempts, with the chance of a shiny so far and the \
+                         time left to 50%, 75% and 90%. hunt overlay keeps an HTML or JSON file \
+                         of the active hunt up to date for OBS, and hunt listen (built with the \
+                         hotkeys feature, on Linux) counts attempts on a global hotkey. URLs in \
+                         hunt_milestone under [webhooks] in the config get a JSON POST as the hunt \
+                         passes each of those odds",
+                    ),
+                    Definition(
+                        "export",
+                        "A tracker's history (hunts, or a learning --profile) as CSV or JSON; \
+                         import reads it back, or a spreadsheet with the same columns. export \
+                         pokedex writes every Pokémon with derived and computed fields as typed \
+                         columns, also as Parquet when built with the parquet feature",
+                    ),
+                    Definition(
+                        "backup",
+                        "backup create writes the config, hunts, teams, learning profiles and \
+                         caches to a .tar.zst (--no-sprites leaves out cached sprites), and \
+                         backup restore puts them back",
+                    ),
+                    Definition(
+                        "cache",
+                        "cache gc removes cached sprites and TCG cards unused for --max-age \
+                         days, then the least recently used until the cache fits in --max-size \
+                         (e.g. 200MB); --dry-run only reports them. max_size and max_unused_days \
+                         under [cache] in the config set the defaults, and are applied whenever \
+                         something new is cached",
+                    ),
+                    Definition(
+                        "timer",
+                        "Counts attempts of the active hunt a key press at a time, with a live \
+                         stats line",
+                    ),
+                    Definition(
+                        "challenge",
+                        "This week's challenge run, the same for everyone using the same --seed",
+                    ),
+                    Paragraph(
+                        "Calculators use the mechanics of the latest generation unless a \
+                         generation is given, and round the way the games do.",
+                    ),
+                ],
+            },
+        ],
+    },
+    Topic {
+        name: "data-sources",
+        summary: "Where the data behind pkmn comes from",
+        sections: &[
+            Section {
+                heading: "Embedded datasets",
+                blocks: &[
+                    Paragraph(
+                        "Everything except images is compiled into the binary, so lookups work \
+                         offline.",
+                    ),
+                    Paragraph(
+                        "A few fields are missing for newer forms, e.g. catch rates of Galarian \
+                         forms. `--online` fills them in from PokéAPI, keeping the bundled value \
+                         of every other field; `--provenance` marks the ones fetched.",
+                    ),
+                    Example("pkmn --id meowth-galar --online --provenance"),
+                    Paragraph(
+                        "`pkmn update-data` downloads a newer Pokédex, by default the one on \
+                         pkmn's main branch, and uses it instead of the embedded one until \
+                         `--reset`. `--url` takes any CSV with the same columns, or a JSON array \
+                         of entries with the same fields.",
+                    ),
+                    Example("pkmn update-data --url https://example.com/pokedex.json"),
+                    Definition(
+                        "pokedex",
+                        "Species, forms, stats, training and breeding data",
+                    ),
+                    Definition(
+                        "abilities",
+                        "Ability descriptions, looked up by `pkmn ability <name>` and shown next \
+                         to a card's abilities",
+                    ),
+                    Definition(
+                        "anime",
+                        "Anime debuts, notable trainers and voice actors, for well-known Pokémon",
+                    ),
+                    Definition(
+                        "moves",
+                        "Move data, looked up by `pkmn moves <move>`; learnsets cover eight \
+                         popular Pokémon, list notable moves rather than every move, have no \
+                         level-up levels, and are added to the card by `--move-method`",
+                    ),
+                    Definition(
+                        "classifications",
+                        "Color, shape and habitat, for Gen 1 species only; shapes are missing \
+                         for many of them",
+                    ),
+                    Definition(
+                        "etymology",
+                        "Name origins and folklore notes, mostly for Gen 1; searched by `pkmn search --etymology`",
+                    ),
+                    Definition("games", "Main series games and their generations"),
+                    Definition("statuses", "Status condition mechanics per generation"),
+                    Definition("field_effects", "Weather, terrain and room effects"),
+                    Definition("evolutions", "Evolution lines and methods"),
+                    Definition("evolution_hints", "Trade and held-item evolutions"),
+                    Definition(
+                        "flavor_text",
+                        "Red & Blue and Yellow Pokédex entries for a few well-known Pokémon, picked \
+                         by `--flavor-version`",
+                    ),
+                    Paragraph(
+                        "`pkmn data info` reports each dataset's version and row count, and what \
+                         changed since it was last run.",
+                    ),
+                    Paragraph(
+                        "`pkmn data audit-urls` sends HEAD requests for the sprite and artwork \
+                         URLs of a --sample of Pokémon (or --all), at most --rate a second, and \
+                         reports the missing ones and any template whose host finds none of them.",
+                    ),
+                ],
+            },
+            Section {
+                heading: "Images",
+                blocks: &[
+                    Paragraph(
+                        "Sprites are downloaded when a card is printed. Region images and \
+                         footprints are opt-in: set `region_image_url` or `footprint_image_url` \
+                         in the config file to a URL template.",
+                    ),
+                    Example("footprint_image_url = \"https://example.com/{number}.png\""),
+                    Paragraph(
+                        "`pkmn manifest` writes every sprite URL as JSON, with the name and slug \
+                         each belongs to and a checksum (64-bit FNV-1a) of the image, so site \
+                         generators and bots can mirror sprites without reimplementing slugs. \
+                         `--filter` takes the same filters as `pkmn list` written as one value.",
+                    ),
+                    Example("pkmn manifest --filter 'gen:<=3' --style gen5 --out manifest.json"),
+                ],
+            },
+            Section {
+                heading: "Trading cards",
+                blocks: &[Paragraph(
+                    "`pkmn tcg` lists cards from the Pokémon TCG API, with TCGplayer market \
+                     prices. Responses are cached for a day, and used for longer when the API \
+                     can't be reached.",
+                )],
+            },
+            Section {
+                heading: "Extensions",
+                blocks: &[
+                    Paragraph(
+                        "Spin-off data, like Pokémon Sleep styles or TCG card counts, can be \
+                         mounted as a card section by adding a CSV file to the `extensions` \
+                         folder of the config directory. The file name is the section's name \
+                         for `--sections`.",
+                    ),
+                    Paragraph(
+                        "The first column is a Pokémon's name or National Pokédex number. Every \
+                         other column is printed as a row, labelled with its header.",
+                    ),
+                    Example("pokemon,Sleep Type,Specialty"),
+                ],
+            },
+            Section {
+                heading: "Links",
+                blocks: &[
+                    Paragraph(
+                        "JSON output links each Pokémon on Bulbapedia, PokéAPI, PokémonDB, \
+                         Serebii and Smogon, and `pkmn open` opens one of them in the browser. \
+                         The `[links]` table of the config file adds sites or replaces these URL \
+                         templates, and an empty template removes a site.",
+                    ),
+                    Definition(
+                        "Placeholders",
+                        "{number}, {padded_number}, {slug}, {species_slug}, {species}",
+                    ),
+                    Example("wiki = \"https://wiki.example.com/{species}\""),
+                    Example("pkmn open garchomp --site smogon"),
+                ],
+            },
+        ],
+    },
+];
+
+pub fn find_topic(name: &str) -> Option<&'static Topic> {
+    let name = name.trim().to_lowercase();
+    TOPICS.iter().find(|topic| topic.name == name)
+}
+
+/// Splits `text` into lines no longer than `width`, breaking between words.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+pub fn print_topic(topic: &Topic, printer: &Printer) {
+    let width = usize::from(printer.width);
+
+    println!();
+    println!("{}", style(topic.name).yellow().bold());
+    println!("{}", style(topic.summary).dim());
+    for section in topic.sections {
+        println!();
+        println!("{}", style(section.heading).bold());
+        for block in section.blocks {
+            match block {
+                Paragraph(text) => {
+                    for line in wrap(text, width - 2) {
+                        println!("  {}", line);
+                    }
+                }
+                Definition(term, description) => {
+                    println!("  {}", style(term).cyan());
+                    for line in wrap(description, width - 6) {
+                        println!("      {}", line);
+                    }
+                }
+                Example(command) => println!("    {}", style(command).dim()),
+            }
+        }
+    }
+    println!();
+}
+
+/// Escapes text for roff, so dashes, backslashes and leading dots survive.
+fn roff_escape(text: &str) -> String {
+    let text = text.replace('\\', "\\e").replace('-', "\\-");
+    if text.starts_with('.') || text.starts_with('\'') {
+        format!("\\&{}", text)
+    } else {
+        text
+    }
+}
+
+fn roff_verbatim(text: &str) -> String {
+    let lines = text
+        .lines()
+        .map(roff_escape)
+        .collect::<Vec<String>>()
+        .join("\n");
+    format!(".nf\n{}\n.fi\n", lines)
+}
+
+/// Renders a man page from the top-level help, each command's help (name and
+/// `--help` output) and the help topics.
+pub fn man_page(name: &str, version: &str, options: &str, commands: &[(String, String)]) -> String {
+    let mut page = String::new();
+
+    page += &format!(
+        ".TH {} 1 \"\" \"{} {}\" \"User Commands\"\n",
+        name.to_uppercase(),
+        name,
+        version
+    );
+    page += &format!(".SH NAME\n{} \\- Pokédex in the terminal\n", name);
+    page += &format!(
+        ".SH SYNOPSIS\n.B {}\n[\\fIOPTIONS\\fR] [\\fICOMMAND\\fR]\n",
+        name
+    );
+    page += ".SH OPTIONS\n";
+    page += &roff_verbatim(options);
+
+    page += ".SH COMMANDS\n";
+    for (command, help) in commands {
+        page += &format!(".SS {}\n", roff_escape(command));
+        page += &roff_verbatim(help);
+    }
+
+    for topic in TOPICS {
+        page += &format!(".SH {}\n", roff_escape(&topic.name.to_uppercase()));
+        page += &format!("{}\n", roff_escape(topic.summary));
+        for section in topic.sections {
+            page += &format!(".SS {}\n", roff_escape(section.heading));
+            for block in section.blocks {
+                page += &match block {
+                    Paragraph(text) => format!(".PP\n{}\n", roff_escape(text)),
+                    Definition(term, description) => format!(
+                        ".TP\n.B {}\n{}\n",
+                        roff_escape(term),
+                        roff_escape(description)
+                    ),
+                    Example(command) => format!(".PP\n{}", roff_verbatim(command)),
+                };
+            }
+        }
+    }
+
+    page
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_topics_case_insensitively() {
+        assert_eq!(find_topic("Calculators").unwrap().name, "calculators");
+        assert!(find_topic("calculator").is_none());
+    }
+
+    #[test]
+    fn wraps_between_words() {
+        assert_eq!(
+            wrap("the quick brown fox jumps", 10),
+            vec!["the quick", "brown fox", "jumps"]
+        );
+    }
+
+    #[test]
+    fn escapes_roff() {
+        assert_eq!(roff_escape("--id"), "\\-\\-id");
+        assert_eq!(roff_escape(".hidden"), "\\&.hidden");
+        assert_eq!(roff_escape("a\\b"), "a\\eb");
+    }
+
+    #[test]
+    fn man_page_has_commands_and_topics() {
+        let page = man_page(
+            "pkmn",
+            "1.0.0",
+            "USAGE:\n    pkmn [OPTIONS]",
+            &[(
+                String::from("compare"),
+                String::from("USAGE:\n    pkmn compare"),
+            )],
+        );
+        assert!(page.starts_with(".TH PKMN 1"));
+        assert!(page.contains(".SS compare\n.nf\nUSAGE:\n    pkmn compare\n.fi\n"));
+        assert!(page.contains(".SH QUERY\\-LANGUAGE\n"));
+    }
+}
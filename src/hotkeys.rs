@@ -0,0 +1,157 @@
+//! Global hotkeys, read from the keyboards' event devices in `/dev/input` so
+//! they work without the terminal focused, under X11 and Wayland alike.
+//! Reading the devices needs permission, usually by being in the `input`
+//! group. Only built on Linux; elsewhere `hunt listen` doesn't exist.
+
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+/// `EV_KEY`, the type of key events.
+const KEY_EVENT: u16 = 1;
+/// `EV_REP`, autorepeat, which keyboards support and mice and buttons don't.
+const REPEAT_EVENT: usize = 20;
+/// A key event's value when the key goes down, rather than up or repeating.
+const KEY_DOWN: i32 = 1;
+
+/// Size of `struct input_event`: a `timeval`, then the type, code and value.
+const EVENT_SIZE: usize = 2 * std::mem::size_of::<usize>() + 8;
+
+/// The Linux key code of a key named e.g. `F13`, or given as a number.
+pub fn key_code(name: &str) -> Option<u16> {
+    let name = name.trim().to_lowercase();
+    if let Ok(code) = name.parse() {
+        return Some(code);
+    }
+    match name.as_str() {
+        "pause" => Some(119),
+        "scrolllock" => Some(70),
+        "insert" => Some(110),
+        _ => match name.strip_prefix('f')?.parse::<u16>().ok()? {
+            number @ 1..=10 => Some(58 + number),
+            11 => Some(87),
+            12 => Some(88),
+            number @ 13..=24 => Some(170 + number),
+            _ => None,
+        },
+    }
+}
+
+/// The type, code and value of an event read from a device.
+fn parse_event(bytes: &[u8; EVENT_SIZE]) -> (u16, u16, i32) {
+    let at = EVENT_SIZE - 8;
+    (
+        u16::from_ne_bytes([bytes[at], bytes[at + 1]]),
+        u16::from_ne_bytes([bytes[at + 2], bytes[at + 3]]),
+        i32::from_ne_bytes([bytes[at + 4], bytes[at + 5], bytes[at + 6], bytes[at + 7]]),
+    )
+}
+
+/// Whether bit `bit` is set in a capability bitmap from sysfs: hex words of
+/// `usize` bits, most significant first.
+fn has_bit(bitmap: &str, bit: usize) -> bool {
+    let word_bits = 8 * std::mem::size_of::<usize>();
+    let words = bitmap.split_whitespace().rev().collect::<Vec<&str>>();
+    words
+        .get(bit / word_bits)
+        .and_then(|word| usize::from_str_radix(word, 16).ok())
+        .is_some_and(|word| word & (1 << (bit % word_bits)) != 0)
+}
+
+/// Whether the event device `name` (e.g. `event3`) is a keyboard that can
+/// send the key with `code`, by its capabilities in sysfs.
+fn is_keyboard(name: &str, code: u16) -> bool {
+    let capabilities = format!("/sys/class/input/{}/device/capabilities", name);
+    let read = |capability: &str| fs::read_to_string(format!("{}/{}", capabilities, capability));
+    match (read("ev"), read("key")) {
+        (Ok(events), Ok(keys)) => {
+            has_bit(&events, KEY_EVENT.into())
+                && has_bit(&events, REPEAT_EVENT)
+                && has_bit(&keys, code.into())
+        }
+        _ => false,
+    }
+}
+
+/// Sends on the returned channel each time the key with `code` is pressed on
+/// any keyboard that can be read.
+pub fn listen(code: u16) -> io::Result<Receiver<()>> {
+    let devices = fs::read_dir("/dev/input")
+        .map_err(|err| io::Error::new(err.kind(), format!("/dev/input: {}", err)))?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            name.starts_with("event") && is_keyboard(&name, code)
+        })
+        .filter_map(|entry| File::open(entry.path()).ok())
+        .collect::<Vec<File>>();
+    if devices.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "no readable keyboards with that key in /dev/input, e.g. add yourself to the \
+             input group",
+        ));
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    for mut device in devices {
+        let sender = sender.clone();
+        thread::spawn(move || {
+            let mut bytes = [0; EVENT_SIZE];
+            while device.read_exact(&mut bytes).is_ok() {
+                if parse_event(&bytes) == (KEY_EVENT, code, KEY_DOWN) && sender.send(()).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    Ok(receiver)
+}
+
+/// Shows a desktop notification with `notify-send`.
+pub fn notify(summary: &str, body: &str) {
+    let status = Command::new("notify-send")
+        .args(["--app-name", env!("CARGO_PKG_NAME"), summary, body])
+        .status();
+    if let Err(err) = status {
+        warn!("couldn't show a notification: {}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_codes() {
+        assert_eq!(key_code("F1"), Some(59));
+        assert_eq!(key_code("f12"), Some(88));
+        assert_eq!(key_code("F13"), Some(183));
+        assert_eq!(key_code("F24"), Some(194));
+        assert_eq!(key_code("Pause"), Some(119));
+        assert_eq!(key_code("183"), Some(183));
+        assert_eq!(key_code("F25"), None);
+        assert_eq!(key_code("space bar"), None);
+    }
+
+    #[test]
+    fn capability_bits() {
+        assert!(has_bit("120013", KEY_EVENT.into()));
+        assert!(has_bit("120013", REPEAT_EVENT));
+        assert!(!has_bit("17", REPEAT_EVENT));
+        assert!(has_bit("1 0", 8 * std::mem::size_of::<usize>()));
+        assert!(!has_bit("1 0", 0));
+        assert!(!has_bit("", 0));
+    }
+
+    #[test]
+    fn parses_events() {
+        let mut bytes = [0; EVENT_SIZE];
+        bytes[EVENT_SIZE - 8..EVENT_SIZE - 6].copy_from_slice(&KEY_EVENT.to_ne_bytes());
+        bytes[EVENT_SIZE - 6..EVENT_SIZE - 4].copy_from_slice(&183u16.to_ne_bytes());
+        bytes[EVENT_SIZE - 4..].copy_from_slice(&KEY_DOWN.to_ne_bytes());
+        assert_eq!(parse_event(&bytes), (KEY_EVENT, 183, KEY_DOWN));
+    }
+}
@@ -0,0 +1,681 @@
+//! Shiny hunts: a counter of attempts (encounters, soft resets, eggs) per
+//! target, with when each was made, kept in `<config dir>/hunts.toml`.
+//!
+//! An overlay file can be kept up to date with the active hunt, for an OBS
+//! browser source: it's rewritten whenever the hunts are saved.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::card::escape_xml;
+use crate::config::config_dir;
+use crate::pokedex;
+
+/// Full odds of a shiny from Generation 6 onwards.
+pub const DEFAULT_ODDS: u32 = 4096;
+
+/// Chances of having found the shiny that projections are made for.
+pub const THRESHOLDS: [f64; 3] = [0.5, 0.75, 0.9];
+
+/// Gaps between attempts longer than this are breaks, left out of the rate.
+const BREAK_MINUTES: i64 = 10;
+
+/// Attempts the rate is averaged over, so it follows changes of pace.
+const RATE_WINDOW: usize = 20;
+
+/// Longest duration parsed, well beyond any one attempt.
+const MAX_DURATION_SECONDS: f64 = 24.0 * 3600.0;
+
+/// Parses a duration like `42s`, `1m30s`, `2h` or a number of seconds, up to
+/// a day.
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let invalid = || format!("Invalid duration \"{}\", expected e.g. 42s or 1m30s", value);
+    let too_long = || format!("Duration \"{}\" is longer than a day", value);
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<f64>() {
+        return match seconds {
+            seconds if seconds > MAX_DURATION_SECONDS => Err(too_long()),
+            seconds if seconds > 0.0 => {
+                Ok(Duration::milliseconds((seconds * 1000.0).round() as i64))
+            }
+            _ => Err(invalid()),
+        };
+    }
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in value.chars() {
+        match c {
+            '0'..='9' | '.' => number.push(c),
+            'h' | 'm' | 's' => {
+                let amount = number.parse::<f64>().map_err(|_| invalid())?;
+                let seconds = match c {
+                    'h' => amount * 3600.0,
+                    'm' => amount * 60.0,
+                    _ => amount,
+                };
+                if seconds > MAX_DURATION_SECONDS {
+                    return Err(too_long());
+                }
+                total += Duration::milliseconds((seconds * 1000.0).round() as i64);
+                number.clear();
+            }
+            _ => return Err(invalid()),
+        }
+    }
+    match number.is_empty() && total > Duration::zero() {
+        true if total > Duration::seconds(MAX_DURATION_SECONDS as i64) => Err(too_long()),
+        true => Ok(total),
+        false => Err(invalid()),
+    }
+}
+
+/// e.g. `4h 12m`, or `38s` under a minute.
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.num_seconds().max(0);
+    match (seconds / 3600, seconds / 60 % 60) {
+        (0, 0) => format!("{}s", seconds),
+        (0, minutes) => format!("{}m {}s", minutes, seconds % 60),
+        (hours, minutes) => format!("{}h {}m", hours, minutes),
+    }
+}
+
+/// Chance of having found at least one shiny after `attempts` at `1/odds`.
+pub fn chance_after(attempts: u32, odds: u32) -> f64 {
+    1.0 - (1.0 - 1.0 / f64::from(odds)).powf(f64::from(attempts))
+}
+
+/// Attempts until the chance of having found the shiny reaches `chance`.
+pub fn attempts_for(chance: f64, odds: u32) -> u32 {
+    ((1.0 - chance).ln() / (1.0 - 1.0 / f64::from(odds)).ln()).ceil() as u32
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Hunt {
+    /// Name of the target, e.g. `Charmander`.
+    pub pokemon: String,
+    /// The shiny odds are `1/odds` per attempt.
+    pub odds: u32,
+    pub count: u32,
+    pub started: DateTime<Utc>,
+    /// When attempts were counted by pkmn, once however many were added at a
+    /// time. A hunt can start from an earlier count, so there may be fewer
+    /// of these than `count`.
+    #[serde(default)]
+    pub attempts: Vec<DateTime<Utc>>,
+}
+
+impl Hunt {
+    pub fn new(pokemon: &str, odds: u32, count: u32, now: DateTime<Utc>) -> Self {
+        Hunt {
+            pokemon: pokemon.to_string(),
+            odds,
+            count,
+            started: now,
+            attempts: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, attempts: u32, now: DateTime<Utc>) {
+        self.count = self.count.saturating_add(attempts);
+        self.attempts.push(now);
+    }
+
+    /// Takes back the last attempt, e.g. one counted by mistake.
+    pub fn undo(&mut self) {
+        if self.count > 0 {
+            self.count -= 1;
+            self.attempts.pop();
+        }
+    }
+
+    pub fn chance(&self) -> f64 {
+        chance_after(self.count, self.odds)
+    }
+
+    /// Average time per attempt over the last attempts, leaving out breaks,
+    /// or `fallback` until there are two to go by.
+    pub fn time_per_attempt(&self, fallback: Option<Duration>) -> Option<Duration> {
+        let start = self.attempts.len().saturating_sub(RATE_WINDOW + 1);
+        let gaps = self.attempts[start..]
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .filter(|gap| *gap > Duration::zero() && *gap <= Duration::minutes(BREAK_MINUTES))
+            .collect::<Vec<Duration>>();
+        match gaps.len() {
+            0 => fallback,
+            len => Some(gaps.iter().fold(Duration::zero(), |sum, gap| sum + *gap) / len as i32),
+        }
+    }
+
+    /// Time left until each of [`THRESHOLDS`] is reached at `per_attempt`,
+    /// with thresholds already passed, or too far off to time, left out.
+    pub fn projections(&self, per_attempt: Duration) -> Vec<(f64, Duration)> {
+        THRESHOLDS
+            .iter()
+            .filter_map(|chance| {
+                let remaining = attempts_for(*chance, self.odds).checked_sub(self.count)?;
+                let left = per_attempt.checked_mul(i32::try_from(remaining).ok()?)?;
+                Some((*chance, left))
+            })
+            .filter(|(_, left)| *left > Duration::zero())
+            .collect()
+    }
+
+    /// Which of [`THRESHOLDS`] were reached since the count was `before`.
+    pub fn milestones_since(&self, before: u32) -> Vec<f64> {
+        THRESHOLDS
+            .iter()
+            .copied()
+            .filter(|chance| {
+                let attempts = attempts_for(*chance, self.odds);
+                attempts > before && attempts <= self.count
+            })
+            .collect()
+    }
+
+    /// One line summing the hunt up, e.g. for a stream overlay:
+    /// `Charmander 1/4096 · 312 attempts (7.3%) · 85.7/h · 50% in 4h 12m`.
+    pub fn stats_line(&self, fallback: Option<Duration>) -> String {
+        let mut parts = vec![
+            format!("{} 1/{}", self.pokemon, self.odds),
+            format!("{} attempts ({:.1}%)", self.count, self.chance() * 100.0),
+        ];
+        if let Some(per_attempt) = self.time_per_attempt(fallback) {
+            let per_hour = 3600.0 / (per_attempt.num_milliseconds() as f64 / 1000.0);
+            parts.push(format!("{:.1}/h", per_hour));
+            parts.extend(self.projections(per_attempt).into_iter().take(1).map(
+                |(chance, left)| format!("{:.0}% in {}", chance * 100.0, format_duration(left)),
+            ));
+        }
+        parts.join(" · ")
+    }
+}
+
+/// A file showing the active hunt, written as JSON if its name ends in
+/// `.json` and as an HTML page otherwise.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Overlay {
+    pub path: PathBuf,
+    /// Seconds between reloads of the HTML page.
+    pub refresh: u32,
+}
+
+/// What the overlay shows, as written to JSON overlays.
+#[derive(Serialize)]
+struct OverlayData<'a> {
+    pokemon: &'a str,
+    sprite: Option<String>,
+    count: u32,
+    odds: u32,
+    chance: f64,
+}
+
+impl Overlay {
+    pub fn is_json(&self) -> bool {
+        self.path.extension().and_then(|ext| ext.to_str()) == Some("json")
+    }
+
+    /// The overlay's contents for `hunt`, whose sprite is at `sprite`.
+    pub fn render(&self, hunt: &Hunt, sprite: Option<String>) -> Result<String, Box<dyn Error>> {
+        if self.is_json() {
+            let data = OverlayData {
+                pokemon: &hunt.pokemon,
+                sprite,
+                count: hunt.count,
+                odds: hunt.odds,
+                chance: hunt.chance(),
+            };
+            return Ok(serde_json::to_string_pretty(&data)?);
+        }
+
+        let image = match sprite {
+            Some(url) => format!(
+                "<img src=\"{}\" alt=\"{}\">\n",
+                escape_xml(&url),
+                escape_xml(&hunt.pokemon)
+            ),
+            None => String::new(),
+        };
+        Ok(format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta http-equiv="refresh" content="{refresh}">
+<title>{pokemon}</title>
+<style>
+body {{ margin: 0; background: transparent; color: #fff; font: bold 32px sans-serif; text-shadow: 0 0 4px #000; display: flex; align-items: center; gap: 12px; }}
+img {{ width: 96px; height: 96px; }}
+.odds {{ font-size: 20px; opacity: 0.8; }}
+</style>
+</head>
+<body>
+{image}<div>
+<div class="count">{count}</div>
+<div class="odds">1/{odds} · {chance:.1}%</div>
+</div>
+</body>
+</html>
+"#,
+            refresh = self.refresh,
+            pokemon = escape_xml(&hunt.pokemon),
+            image = image,
+            count = hunt.count,
+            odds = hunt.odds,
+            chance = hunt.chance() * 100.0,
+        ))
+    }
+
+    /// Writes the overlay through a temporary file, so a browser source
+    /// reloading meanwhile never sees it half written.
+    fn write(&self, contents: &str) -> Result<(), Box<dyn Error>> {
+        let temporary = self.path.with_extension("tmp");
+        fs::write(&temporary, contents)?;
+        fs::rename(&temporary, &self.path)?;
+        Ok(())
+    }
+}
+
+/// A hunt as exported and imported, one per row. Only `pokemon` and `count`
+/// are needed to import one, e.g. from a spreadsheet.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct HuntRecord {
+    /// Key of the hunt, found from `pokemon` if left empty.
+    #[serde(default)]
+    pub slug: String,
+    pub pokemon: String,
+    /// Full odds if left empty.
+    #[serde(default)]
+    pub odds: Option<u32>,
+    pub count: u32,
+    /// When the hunt started, as RFC 3339, e.g. `2024-05-06T18:30:00Z`.
+    #[serde(default)]
+    pub started: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub active: bool,
+    /// When each counted attempt was made, as RFC 3339 times separated by
+    /// spaces.
+    #[serde(default)]
+    pub attempts: String,
+}
+
+/// Every hunt, by the target's slug, and which one is being counted.
+#[derive(Default, Deserialize, Serialize)]
+pub struct Hunts {
+    pub active: Option<String>,
+    #[serde(default)]
+    pub hunts: BTreeMap<String, Hunt>,
+    pub overlay: Option<Overlay>,
+}
+
+fn hunts_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("hunts.toml"))
+}
+
+impl Hunts {
+    pub fn load() -> Self {
+        let text = match hunts_path().map(fs::read_to_string) {
+            Some(Ok(text)) => text,
+            _ => return Hunts::default(),
+        };
+        match toml::from_str::<Hunts>(&text) {
+            Ok(hunts) => hunts.upgrade_slugs(),
+            Err(err) => {
+                warn!("ignoring unreadable hunts: {}", err);
+                Hunts::default()
+            }
+        }
+    }
+
+    /// Moves hunts saved under slugs older versions gave regional variants,
+    /// e.g. `alolan-vulpix`, to their current slugs.
+    fn upgrade_slugs(self) -> Self {
+        let current = |slug: String| match pokedex::find_by_id(&slug) {
+            Some(pokemon) => pokemon.sprite_name_slug(),
+            None => slug,
+        };
+        Hunts {
+            active: self.active.map(current),
+            hunts: self
+                .hunts
+                .into_iter()
+                .map(|(slug, hunt)| (current(slug), hunt))
+                .collect(),
+            overlay: self.overlay,
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let path = hunts_path().ok_or("no config directory")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, toml::to_string(self)?)?;
+
+        if let Err(err) = self.write_overlay() {
+            if let Some(overlay) = &self.overlay {
+                warn!(
+                    "couldn't update the overlay at {}: {}",
+                    overlay.path.display(),
+                    err
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Rewrites the overlay, if there is one, with the active hunt.
+    pub fn write_overlay(&self) -> Result<(), Box<dyn Error>> {
+        let (overlay, slug, hunt) = match (&self.overlay, &self.active, self.active()) {
+            (Some(overlay), Some(slug), Some(hunt)) => (overlay, slug, hunt),
+            _ => return Ok(()),
+        };
+        let sprite = pokedex::find_by_id(slug).map(|pokemon| pokemon.sprite_url());
+        overlay.write(&overlay.render(hunt, sprite)?)
+    }
+
+    /// Every hunt as a record, in order of slug.
+    pub fn records(&self) -> Vec<HuntRecord> {
+        self.hunts
+            .iter()
+            .map(|(slug, hunt)| HuntRecord {
+                slug: slug.clone(),
+                pokemon: hunt.pokemon.clone(),
+                odds: Some(hunt.odds),
+                count: hunt.count,
+                started: Some(hunt.started),
+                active: self.active.as_ref() == Some(slug),
+                attempts: hunt
+                    .attempts
+                    .iter()
+                    .map(|attempt| attempt.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+                    .collect::<Vec<String>>()
+                    .join(" "),
+            })
+            .collect()
+    }
+
+    /// Adds the hunts in `records`, replacing those with the same slug. If
+    /// any is marked active it becomes the active hunt. Either every record
+    /// is imported or, on the first invalid one, none are.
+    pub fn import(
+        &mut self,
+        records: Vec<HuntRecord>,
+        now: DateTime<Utc>,
+    ) -> Result<usize, String> {
+        let mut imported = Vec::new();
+        for record in records {
+            let slug = match record.slug.trim() {
+                "" => match pokedex::search_by_name(&record.pokemon, 1)
+                    .into_iter()
+                    .next()
+                {
+                    Some(result) => result.pokemon.sprite_name_slug(),
+                    None => return Err(format!("Couldn't find \"{}\"", record.pokemon)),
+                },
+                slug => slug.to_string(),
+            };
+            let odds = record.odds.unwrap_or(DEFAULT_ODDS);
+            if odds == 0 {
+                return Err(format!("The odds of {} can't be 1/0", record.pokemon));
+            }
+            let attempts = record
+                .attempts
+                .split_whitespace()
+                .map(|time| {
+                    DateTime::parse_from_rfc3339(time)
+                        .map(|time| time.with_timezone(&Utc))
+                        .map_err(|err| format!("Invalid attempt time \"{}\": {}", time, err))
+                })
+                .collect::<Result<Vec<DateTime<Utc>>, String>>()?;
+            let started = record
+                .started
+                .or_else(|| attempts.first().copied())
+                .unwrap_or(now);
+            let hunt = Hunt {
+                pokemon: record.pokemon,
+                odds,
+                count: record.count.max(attempts.len() as u32),
+                started,
+                attempts,
+            };
+            imported.push((slug, hunt, record.active));
+        }
+
+        let count = imported.len();
+        for (slug, hunt, active) in imported {
+            if active {
+                self.active = Some(slug.clone());
+            }
+            self.hunts.insert(slug, hunt);
+        }
+        Ok(count)
+    }
+
+    pub fn active(&self) -> Option<&Hunt> {
+        self.hunts.get(self.active.as_ref()?)
+    }
+
+    pub fn active_mut(&mut self) -> Option<&mut Hunt> {
+        self.hunts.get_mut(self.active.as_ref()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(1_700_000_000 + seconds, 0).unwrap()
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("42s"), Ok(Duration::seconds(42)));
+        assert_eq!(parse_duration("1m30s"), Ok(Duration::seconds(90)));
+        assert_eq!(parse_duration("2h"), Ok(Duration::hours(2)));
+        assert_eq!(parse_duration("12.5"), Ok(Duration::milliseconds(12500)));
+        assert!(parse_duration("42").is_ok());
+        assert!(parse_duration("0s").is_err());
+        assert!(parse_duration("42x").is_err());
+        assert!(parse_duration("1m30").is_err());
+        assert!(parse_duration("24h").is_ok());
+        assert!(parse_duration("1e15").is_err());
+        assert!(parse_duration("25h").is_err());
+        assert!(parse_duration("999999999999999999h").is_err());
+        assert!(parse_duration("23h59m2m").is_err());
+
+        assert_eq!(format_duration(Duration::seconds(38)), "38s");
+        assert_eq!(format_duration(Duration::seconds(150)), "2m 30s");
+        assert_eq!(format_duration(Duration::minutes(252)), "4h 12m");
+    }
+
+    #[test]
+    fn odds() {
+        assert_eq!(attempts_for(0.5, 4096), 2839);
+        assert!((chance_after(2839, 4096) - 0.5).abs() < 0.001);
+        assert_eq!(chance_after(0, 4096), 0.0);
+    }
+
+    #[test]
+    fn rate_skips_breaks() {
+        let mut hunt = Hunt::new("Charmander", 4096, 100, at(0));
+        assert_eq!(hunt.time_per_attempt(None), None);
+        assert_eq!(
+            hunt.time_per_attempt(Some(Duration::seconds(42))),
+            Some(Duration::seconds(42))
+        );
+
+        for seconds in [0, 40, 80, 3600, 3650] {
+            hunt.add(1, at(seconds));
+        }
+        assert_eq!(hunt.count, 105);
+        assert_eq!(
+            hunt.time_per_attempt(None),
+            Some(Duration::seconds(130) / 3)
+        );
+
+        hunt.undo();
+        assert_eq!((hunt.count, hunt.attempts.len()), (104, 4));
+    }
+
+    #[test]
+    fn projects_thresholds() {
+        let hunt = Hunt::new("Charmander", 4096, 2839, at(0));
+        let projections = hunt.projections(Duration::seconds(10));
+        assert_eq!(projections.len(), 2);
+        assert_eq!(projections[0].0, 0.75);
+        assert_eq!(
+            projections[0].1,
+            Duration::seconds(10 * i64::from(attempts_for(0.75, 4096) - 2839))
+        );
+
+        let line = Hunt::new("Charmander", 4096, 0, at(0)).stats_line(Some(Duration::seconds(36)));
+        assert_eq!(
+            line,
+            "Charmander 1/4096 · 0 attempts (0.0%) · 100.0/h · 50% in 28h 23m"
+        );
+    }
+
+    #[test]
+    fn milestones() {
+        let mut hunt = Hunt::new("Charmander", 4096, 2838, at(0));
+        assert!(hunt.milestones_since(0).is_empty());
+        hunt.add(1, at(0));
+        assert_eq!(hunt.milestones_since(2838), vec![0.5]);
+        assert!(hunt.milestones_since(2839).is_empty());
+        hunt.add(10_000, at(0));
+        assert_eq!(hunt.milestones_since(2839), vec![0.75, 0.9]);
+    }
+
+    #[test]
+    fn huge_counts() {
+        let mut hunt = Hunt::new("Charmander", 4096, u32::MAX - 1, at(0));
+        hunt.add(u32::MAX, at(0));
+        assert_eq!((hunt.count, hunt.attempts.len()), (u32::MAX, 1));
+        assert!(hunt.milestones_since(u32::MAX).is_empty());
+
+        let hunt = Hunt::new("Charmander", u32::MAX, 0, at(0));
+        assert!(hunt.projections(Duration::hours(24)).is_empty());
+        hunt.stats_line(Some(Duration::hours(24)));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut hunts = Hunts::default();
+        let mut hunt = Hunt::new("Charmander", 4096, 0, at(0));
+        hunt.add(2, at(30));
+        hunts.hunts.insert(String::from("charmander"), hunt);
+        hunts.active = Some(String::from("charmander"));
+
+        let text = toml::to_string(&hunts).unwrap();
+        let loaded: Hunts = toml::from_str(&text).unwrap();
+        assert_eq!(loaded.active(), hunts.active());
+
+        hunts.overlay = Some(Overlay {
+            path: PathBuf::from("/tmp/overlay.html"),
+            refresh: 2,
+        });
+        let text = toml::to_string(&hunts).unwrap();
+        let loaded: Hunts = toml::from_str(&text).unwrap();
+        assert_eq!(loaded.overlay, hunts.overlay);
+    }
+
+    #[test]
+    fn exports_and_imports() {
+        let mut hunts = Hunts::default();
+        let mut hunt = Hunt::new("Charmander", 1365, 10, at(0));
+        hunt.add(2, at(30));
+        hunts.hunts.insert(String::from("charmander"), hunt);
+        hunts.active = Some(String::from("charmander"));
+
+        let records = hunts.records();
+        assert_eq!(records[0].attempts.split(' ').count(), 1);
+        assert!(records[0].active);
+
+        let mut imported = Hunts::default();
+        assert_eq!(imported.import(records, at(60)), Ok(1));
+        assert_eq!(imported.active(), hunts.active());
+
+        // As typed into a spreadsheet
+        let records = vec![HuntRecord {
+            slug: String::new(),
+            pokemon: String::from("ralts"),
+            odds: None,
+            count: 312,
+            started: None,
+            active: false,
+            attempts: String::new(),
+        }];
+        assert_eq!(imported.import(records.clone(), at(60)), Ok(1));
+        assert_eq!(imported.hunts["ralts"].started, at(60));
+        assert_eq!(imported.active.as_deref(), Some("charmander"));
+
+        let mut invalid = records;
+        invalid[0].attempts = String::from("yesterday");
+        assert!(imported.import(invalid, at(60)).is_err());
+    }
+
+    #[test]
+    fn renders_overlays() {
+        let mut hunt = Hunt::new("Farfetch'd & co", 4096, 0, at(0));
+        hunt.add(312, at(30));
+        let sprite = Some(String::from("https://example.com/farfetchd.png"));
+
+        let html = Overlay {
+            path: PathBuf::from("overlay.html"),
+            refresh: 2,
+        }
+        .render(&hunt, sprite.clone())
+        .unwrap();
+        assert!(html.contains("<meta http-equiv=\"refresh\" content=\"2\">"));
+        assert!(html.contains("<img src=\"https://example.com/farfetchd.png\""));
+        assert!(html.contains("Farfetch'd &amp; co"));
+        assert!(html.contains(">312<"));
+        assert!(html.contains("1/4096 · 7.3%"));
+
+        let json = Overlay {
+            path: PathBuf::from("overlay.json"),
+            refresh: 2,
+        }
+        .render(&hunt, sprite)
+        .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["count"], 312);
+        assert_eq!(value["odds"], 4096);
+        assert_eq!(value["sprite"], "https://example.com/farfetchd.png");
+    }
+
+    #[test]
+    fn upgrades_regional_slugs() {
+        let text = r#"
+            active = "alolan-vulpix"
+
+            [hunts.alolan-vulpix]
+            pokemon = "Alolan Vulpix"
+            odds = 4096
+            count = 57
+            started = "2024-05-06T18:30:00Z"
+
+            [hunts.pikachu]
+            pokemon = "Pikachu"
+            odds = 4096
+            count = 3
+            started = "2024-05-06T18:30:00Z"
+        "#;
+        let hunts = toml::from_str::<Hunts>(text).unwrap().upgrade_slugs();
+        assert_eq!(hunts.active.as_deref(), Some("vulpix-alola"));
+        assert_eq!(
+            hunts.hunts.keys().collect::<Vec<&String>>(),
+            vec!["pikachu", "vulpix-alola"]
+        );
+        assert_eq!(hunts.hunts["vulpix-alola"].count, 57);
+    }
+}
@@ -0,0 +1,54 @@
+//! Strings repeated across many rows, e.g. type, ability and egg group
+//! names, stored once and shared. Over a thousand Pokémon share a few dozen
+//! such values, so each is allocated once instead of once per row.
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use serde::{Deserialize, Deserializer};
+
+static STRINGS: OnceLock<Mutex<HashSet<Arc<str>>>> = OnceLock::new();
+
+/// The shared copy of `value`, added on first use.
+pub fn intern(value: &str) -> Arc<str> {
+    let mut strings = STRINGS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    match strings.get(value) {
+        Some(interned) => Arc::clone(interned),
+        None => {
+            let interned: Arc<str> = Arc::from(value);
+            strings.insert(Arc::clone(&interned));
+            interned
+        }
+    }
+}
+
+/// Deserializes a string with [`intern`], for
+/// `#[serde(deserialize_with = "intern::deserialize")]`.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Arc<str>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    Ok(intern(&value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::find_by_id;
+
+    #[test]
+    fn shares_values() {
+        assert!(Arc::ptr_eq(&intern("Grass"), &intern("Grass")));
+        assert!(!Arc::ptr_eq(&intern("Grass"), &intern("Poison")));
+
+        let bulbasaur = find_by_id("bulbasaur").unwrap();
+        let oddish = find_by_id("oddish").unwrap();
+        assert!(Arc::ptr_eq(&bulbasaur.type_1, &oddish.type_1));
+        // The Grass type and the Grass Egg Group are the same string
+        assert!(Arc::ptr_eq(&bulbasaur.type_1, &oddish.egg_type_1));
+    }
+}
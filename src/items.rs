@@ -0,0 +1,168 @@
+use serde::Deserialize;
+
+use crate::abilities;
+use crate::error::{self, PkmnError};
+use crate::pokedex::Pokemon;
+use crate::types::Type;
+
+#[derive(Clone, Deserialize)]
+pub struct Item {
+    pub name: String,
+    /// The type a resist berry weakens, empty for other items.
+    resists: String,
+    pub description: String,
+}
+
+impl Item {
+    pub fn resists(&self) -> Option<Type> {
+        Type::from_name(&self.resists)
+    }
+}
+
+pub static ITEMS_CSV: &[u8] = include_bytes!("../data/items.csv");
+
+/// Every bundled item.
+pub fn all_items() -> Result<Vec<Item>, PkmnError> {
+    error::read_csv("items.csv", ITEMS_CSV)
+}
+
+/// Finds an item by name, ignoring case and treating hyphens as spaces.
+pub fn find_item(name: &str) -> Option<Item> {
+    let slug = |name: &str| name.trim().to_lowercase().replace('-', " ");
+    let name = slug(name);
+    all_items()
+        .unwrap_or_default()
+        .into_iter()
+        .find(|item| slug(&item.name) == name)
+}
+
+/// The berry that weakens a hit of `attacker`'s type.
+pub fn resist_berry(attacker: Type) -> Option<Item> {
+    all_items()
+        .unwrap_or_default()
+        .into_iter()
+        .find(|item| item.resists() == Some(attacker))
+}
+
+/// Abilities that change how a Pokémon takes super-effective hits.
+const WEAKNESS_ABILITIES: [&str; 7] = [
+    "Filter",
+    "Multiscale",
+    "Prism Armor",
+    "Shadow Shield",
+    "Solid Rock",
+    "Sturdy",
+    "Wonder Guard",
+];
+
+/// An item or ability worth knowing about given the types that hit a Pokémon
+/// for 2× or more.
+pub struct Hint {
+    pub name: String,
+    pub types: Vec<Type>,
+    pub description: String,
+}
+
+/// Items and the Pokémon's own abilities that play into its weaknesses:
+/// Focus Sash and resist berries against 4× hits, Weakness Policy against 2×
+/// hits, and abilities like Sturdy or Filter against both.
+pub fn weakness_hints(pokemon: &Pokemon) -> Vec<Hint> {
+    let defenses = pokemon.type_defenses();
+    let weak_to = |at_least: f32, below: f32| {
+        defenses
+            .iter()
+            .filter(|(_, multiplier)| (at_least..below).contains(multiplier))
+            .map(|(attacker, _)| *attacker)
+            .collect::<Vec<Type>>()
+    };
+    let quadruple = weak_to(4.0, f32::INFINITY);
+    let double = weak_to(2.0, 4.0);
+    let weaknesses = weak_to(2.0, f32::INFINITY);
+
+    let mut hints = Vec::new();
+    let mut item_hint = |item: Option<Item>, types: &[Type]| {
+        if let (Some(item), false) = (item, types.is_empty()) {
+            hints.push(Hint {
+                name: item.name,
+                types: types.to_vec(),
+                description: item.description,
+            });
+        }
+    };
+    item_hint(find_item("Focus Sash"), &quadruple);
+    for attacker in &quadruple {
+        item_hint(resist_berry(*attacker), &[*attacker]);
+    }
+    item_hint(find_item("Weakness Policy"), &double);
+
+    if !weaknesses.is_empty() {
+        let mut names = vec![
+            &pokemon.ability_1,
+            &pokemon.ability_2,
+            &pokemon.ability_hidden,
+        ];
+        names.dedup();
+        hints.extend(
+            names
+                .into_iter()
+                .filter(|name| WEAKNESS_ABILITIES.contains(&name.as_ref()))
+                .filter_map(|name| abilities::find_ability(name))
+                .map(|ability| Hint {
+                    name: ability.name,
+                    types: weaknesses.clone(),
+                    description: ability.description,
+                }),
+        );
+    }
+    hints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex;
+
+    fn hints(name: &str) -> Vec<(String, Vec<Type>)> {
+        let pokemon = pokedex::search_by_name(name, 1).remove(0).pokemon;
+        weakness_hints(&pokemon)
+            .into_iter()
+            .map(|hint| (hint.name, hint.types))
+            .collect()
+    }
+
+    #[test]
+    fn finds_items() {
+        for attacker in Type::ALL {
+            assert!(resist_berry(attacker).is_some(), "{}", attacker.name());
+        }
+        assert_eq!(resist_berry(Type::Ice).unwrap().name, "Yache Berry");
+        assert!(find_item("weakness-policy").is_some());
+        assert!(find_item("Leftovers").is_none());
+    }
+
+    #[test]
+    fn hints_at_weaknesses() {
+        assert_eq!(
+            hints("Garchomp"),
+            vec![
+                (String::from("Focus Sash"), vec![Type::Ice]),
+                (String::from("Yache Berry"), vec![Type::Ice]),
+                (
+                    String::from("Weakness Policy"),
+                    vec![Type::Dragon, Type::Fairy]
+                ),
+            ]
+        );
+
+        let dragonite = hints("Dragonite");
+        assert_eq!(dragonite.last().unwrap().0, "Multiscale");
+        assert_eq!(
+            dragonite.last().unwrap().1,
+            vec![Type::Ice, Type::Rock, Type::Dragon, Type::Fairy]
+        );
+
+        assert!(hints("Magnezone")
+            .iter()
+            .any(|(name, types)| name == "Sturdy" && types.contains(&Type::Ground)));
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+
+use evalexpr::Value;
+use serde::Serialize;
+
+use crate::anime::Trivia;
+use crate::etymology::Etymology;
+use crate::flavor::{self, FlavorText};
+use crate::go::GoStats;
+use crate::names::JapaneseName;
+use crate::pokedex::{self, EggCycleStats, Pokemon};
+use crate::provenance::{Provenance, Source};
+use crate::sprite::SpriteCheck;
+
+/// A Pokémon as printed by `--format json`: every dataset field, plus the
+/// values the card derives from them.
+#[derive(Serialize)]
+pub struct PokemonJson<'a> {
+    #[serde(flatten)]
+    pub pokemon: &'a Pokemon,
+    pub slug: String,
+    pub sprite_url: String,
+    /// Name of the form within its species, e.g. `Alolan`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub form: Option<String>,
+    pub egg_cycle_steps: Option<EggCycleStats>,
+    /// Computed fields from the config file.
+    pub fields: BTreeMap<String, serde_json::Value>,
+    /// URLs of the Pokémon on other sites, keyed by site.
+    pub links: BTreeMap<String, String>,
+    /// Which sprite URLs exist, if they were checked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sprite: Option<SpriteCheck>,
+    /// Pokémon GO stats, with `--go`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub go: Option<GoStats>,
+    /// Pokédex entry, from `--flavor-version` or the newest bundled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flavor_text: Option<FlavorText>,
+    /// Anime trivia, with `--trivia`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trivia: Option<Trivia>,
+    /// Name origin, with `--trivia`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub etymology: Option<Etymology>,
+    /// Japanese name and its romanizations, with `--show-jp`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub japanese: Option<JapaneseName>,
+    /// Pokédex fields filled in from PokéAPI with `--online`.
+    #[serde(skip)]
+    pub online_fields: Vec<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Provenance>,
+}
+
+fn json_value(value: Value) -> serde_json::Value {
+    match value {
+        Value::Int(int) => int.into(),
+        Value::Float(float) => float.into(),
+        Value::Boolean(boolean) => boolean.into(),
+        Value::String(string) => string.into(),
+        Value::Empty => serde_json::Value::Null,
+        value => value.to_string().into(),
+    }
+}
+
+impl<'a> PokemonJson<'a> {
+    pub fn new(pokemon: &'a Pokemon, fields: Vec<(String, Value)>) -> Self {
+        PokemonJson {
+            pokemon,
+            slug: pokemon.sprite_name_slug(),
+            sprite_url: pokemon.sprite_url(),
+            form: pokedex::form_name(pokemon),
+            egg_cycle_steps: pokemon.egg_cycle_stats(),
+            fields: fields
+                .into_iter()
+                .map(|(name, value)| (name, json_value(value)))
+                .collect(),
+            links: BTreeMap::new(),
+            sprite: None,
+            go: None,
+            flavor_text: flavor::flavor_text_for(pokemon, None, "en"),
+            trivia: None,
+            etymology: None,
+            japanese: None,
+            online_fields: Vec::new(),
+            provenance: None,
+        }
+    }
+
+    /// Annotates the JSON with the source of each field.
+    pub fn with_provenance(mut self) -> Self {
+        let derived = [
+            "slug",
+            "sprite_url",
+            "form",
+            "egg_cycle_steps",
+            "links",
+            "sprite",
+            "go",
+            "japanese",
+        ]
+        .iter()
+        .map(|name| (name.to_string(), Source::Derived));
+        let embedded = [
+            ("flavor_text", "flavor_text.csv"),
+            ("trivia", "anime.csv"),
+            ("etymology", "etymology.csv"),
+        ]
+        .iter()
+        .map(|(name, dataset)| (name.to_string(), Source::Embedded(dataset)));
+        let config = self
+            .fields
+            .keys()
+            .map(|name| (format!("fields.{}", name), Source::Config));
+        let online = self
+            .online_fields
+            .iter()
+            .map(|name| (name.to_string(), Source::Online("pokeapi")));
+        let fields = Provenance::pokedex_fields(self.pokemon)
+            .into_iter()
+            .chain(derived)
+            .chain(embedded)
+            .chain(config)
+            .chain(online)
+            .collect::<Vec<(String, Source)>>();
+        self.provenance = Some(Provenance::new(fields));
+        self
+    }
+
+    pub fn to_string_pretty(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Pokémon should serialize to JSON")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex;
+
+    #[test]
+    fn includes_dataset_and_derived_fields() {
+        let pikachu = pokedex::find_by_id("pikachu").unwrap();
+        let json = PokemonJson::new(&pikachu, vec![(String::from("bulk"), Value::Int(1600))]);
+        let value = serde_json::to_value(&json).unwrap();
+
+        assert_eq!(value["name"], "Pikachu");
+        assert_eq!(value["type_1"], "Electric");
+        assert_eq!(value["slug"], "pikachu");
+        assert!(value["sprite_url"]
+            .as_str()
+            .unwrap()
+            .ends_with("/pikachu.png"));
+        assert_eq!(value["egg_cycle_steps"]["cycles"], 10);
+        assert_eq!(value["egg_cycle_steps"]["max_steps"], 2570);
+        assert_eq!(value["fields"]["bulk"], 1600);
+        assert!(value.get("provenance").is_none());
+    }
+
+    #[test]
+    fn annotates_provenance() {
+        let pikachu = pokedex::find_by_id("pikachu").unwrap();
+        let mut json = PokemonJson::new(&pikachu, vec![(String::from("bulk"), Value::Int(1600))]);
+        json.online_fields = vec!["catch_rate"];
+        let value = serde_json::to_value(json.with_provenance()).unwrap();
+
+        let fields = &value["provenance"]["fields"];
+        assert_eq!(fields["name"], "embedded:pokedex.csv");
+        assert_eq!(fields["catch_rate"], "online:pokeapi");
+        assert_eq!(fields["sprite_url"], "derived");
+        assert_eq!(fields["fields.bulk"], "config");
+        assert_eq!(
+            value["provenance"]["data_version"].as_str().unwrap().len(),
+            16
+        );
+    }
+}
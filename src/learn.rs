@@ -0,0 +1,478 @@
+//! Spaced-repetition review of facts worth knowing by heart in battle:
+//! typings, weaknesses and base Speed. Reviews are scheduled with SM-2, the
+//! algorithm behind SuperMemo and Anki, and kept per profile in
+//! `<config dir>/learn/<profile>.toml`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::config::config_dir;
+use crate::pokedex::{self, Pokemon};
+use crate::types::Type;
+
+/// Ease of a card that's never been reviewed, and the lowest it can drop to.
+const INITIAL_EASE: f32 = 2.5;
+const MIN_EASE: f32 = 1.3;
+
+/// How far off a base Speed answer can be and still count as close. Most
+/// speed tiers that matter are further apart than this.
+const CLOSE_SPEED: u16 = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fact {
+    Typing,
+    Weaknesses,
+    Speed,
+}
+
+impl Fact {
+    pub const ALL: [Fact; 3] = [Fact::Typing, Fact::Weaknesses, Fact::Speed];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Fact::Typing => "typing",
+            Fact::Weaknesses => "weaknesses",
+            Fact::Speed => "speed",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Fact::ALL
+            .iter()
+            .find(|fact| fact.name().eq_ignore_ascii_case(name.trim()))
+            .copied()
+    }
+}
+
+/// Types in an answer, separated by commas, slashes or spaces.
+fn parse_types(answer: &str) -> Option<BTreeSet<Type>> {
+    answer
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|name| !name.is_empty())
+        .map(Type::from_name)
+        .collect()
+}
+
+fn type_names(types: &BTreeSet<Type>) -> String {
+    match types.is_empty() {
+        true => String::from("none"),
+        false => types
+            .iter()
+            .map(Type::name)
+            .collect::<Vec<&str>>()
+            .join(", "),
+    }
+}
+
+/// One fact about one Pokémon.
+pub struct Card<'a> {
+    pub fact: Fact,
+    pub pokemon: &'a Pokemon,
+}
+
+impl<'a> Card<'a> {
+    /// Every card for `facts` about `pokedex`, grouped by Pokémon.
+    pub fn all(pokedex: &'a [Pokemon], facts: &[Fact]) -> Vec<Self> {
+        pokedex
+            .iter()
+            .flat_map(|pokemon| {
+                facts.iter().map(move |fact| Card {
+                    fact: *fact,
+                    pokemon,
+                })
+            })
+            .collect()
+    }
+
+    /// Key of the card's review state, e.g. `typing:charizard-mega-x`.
+    pub fn id(&self) -> String {
+        format!("{}:{}", self.fact.name(), self.pokemon.sprite_name_slug())
+    }
+
+    pub fn question(&self) -> String {
+        match self.fact {
+            Fact::Typing => format!("What type is {}?", self.pokemon.name),
+            Fact::Weaknesses => format!("What is {} weak to?", self.pokemon.name),
+            Fact::Speed => format!("What is {}'s base Speed?", self.pokemon.name),
+        }
+    }
+
+    fn typing(&self) -> BTreeSet<Type> {
+        self.pokemon.types().into_iter().collect()
+    }
+
+    fn weaknesses(&self) -> BTreeSet<Type> {
+        self.pokemon
+            .type_defenses()
+            .into_iter()
+            .filter(|(_, multiplier)| *multiplier > 1.0)
+            .map(|(attacker, _)| attacker)
+            .collect()
+    }
+
+    pub fn answer(&self) -> String {
+        match self.fact {
+            Fact::Typing => type_names(&self.typing()),
+            Fact::Weaknesses => type_names(&self.weaknesses()),
+            Fact::Speed => self.pokemon.speed.to_string(),
+        }
+    }
+
+    /// SM-2 quality of `response`, from 0 (blank) to 5 (correct). Base Speed
+    /// answers that are close score 3, and pass.
+    pub fn grade(&self, response: &str) -> u8 {
+        let response = response.trim();
+        if response.is_empty() {
+            return 0;
+        }
+
+        let correct = match self.fact {
+            Fact::Typing => parse_types(response) == Some(self.typing()),
+            Fact::Weaknesses => match response.eq_ignore_ascii_case("none") {
+                true => self.weaknesses().is_empty(),
+                false => parse_types(response) == Some(self.weaknesses()),
+            },
+            Fact::Speed => match response.parse::<u16>() {
+                Ok(speed) if speed == self.pokemon.speed => true,
+                Ok(speed) if speed.abs_diff(self.pokemon.speed) <= CLOSE_SPEED => return 3,
+                _ => false,
+            },
+        };
+        match correct {
+            true => 5,
+            false => 1,
+        }
+    }
+}
+
+/// Review state of a card.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Review {
+    /// Correct answers in a row.
+    pub repetitions: u32,
+    /// Days until the card is due again.
+    pub interval: u32,
+    pub ease: f32,
+    pub due: NaiveDate,
+}
+
+impl Review {
+    pub fn new(today: NaiveDate) -> Self {
+        Review {
+            repetitions: 0,
+            interval: 0,
+            ease: INITIAL_EASE,
+            due: today,
+        }
+    }
+
+    /// Schedules the next review after an answer of `quality` (0–5). Answers
+    /// below 3 start the card over, to be seen again tomorrow.
+    pub fn answer(&mut self, quality: u8, today: NaiveDate) {
+        let quality = quality.min(5);
+        if quality >= 3 {
+            self.interval = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (self.interval as f32 * self.ease).round() as u32,
+            };
+            self.repetitions += 1;
+        } else {
+            self.repetitions = 0;
+            self.interval = 1;
+        }
+
+        let miss = f32::from(5 - quality);
+        self.ease = (self.ease + 0.1 - miss * (0.08 + miss * 0.02)).max(MIN_EASE);
+        self.due = today + Duration::days(i64::from(self.interval));
+    }
+}
+
+/// A card's review state as exported and imported, one per row.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ReviewRecord {
+    /// The card's ID, e.g. `typing:charizard-mega-x`.
+    pub card: String,
+    pub repetitions: u32,
+    pub interval: u32,
+    pub ease: f32,
+    pub due: NaiveDate,
+}
+
+/// A profile's review state, by card ID.
+#[derive(Default, Deserialize, Serialize)]
+pub struct Deck {
+    #[serde(default)]
+    pub cards: BTreeMap<String, Review>,
+}
+
+fn deck_path(profile: &str) -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("learn").join(format!("{}.toml", profile)))
+}
+
+impl Deck {
+    /// The profile's saved deck, or an empty one if it hasn't been saved yet.
+    pub fn load(profile: &str) -> Self {
+        let text = match deck_path(profile).map(fs::read_to_string) {
+            Some(Ok(text)) => text,
+            _ => return Deck::default(),
+        };
+        match toml::from_str::<Deck>(&text) {
+            Ok(deck) => deck.upgrade_ids(),
+            Err(err) => {
+                warn!(
+                    "ignoring unreadable learning profile \"{}\": {}",
+                    profile, err
+                );
+                Deck::default()
+            }
+        }
+    }
+
+    /// Moves reviews saved under slugs older versions gave regional variants,
+    /// e.g. `typing:alolan-vulpix`, to their current IDs.
+    fn upgrade_ids(self) -> Self {
+        let cards = self
+            .cards
+            .into_iter()
+            .map(|(id, review)| {
+                let current = id.split_once(':').and_then(|(fact, slug)| {
+                    let pokemon = pokedex::find_by_id(slug)?;
+                    Some(format!("{}:{}", fact, pokemon.sprite_name_slug()))
+                });
+                (current.unwrap_or(id), review)
+            })
+            .collect();
+        Deck { cards }
+    }
+
+    pub fn save(&self, profile: &str) -> Result<(), Box<dyn Error>> {
+        let path = deck_path(profile).ok_or("no config directory")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Cards to review today: those due, most overdue first, then up to
+    /// `new` cards that have never been reviewed, in the order given.
+    pub fn session<'a>(&self, cards: Vec<Card<'a>>, today: NaiveDate, new: usize) -> Vec<Card<'a>> {
+        let (seen, unseen): (Vec<Card>, Vec<Card>) = cards
+            .into_iter()
+            .partition(|card| self.cards.contains_key(&card.id()));
+
+        let mut due = seen
+            .into_iter()
+            .map(|card| (self.cards[&card.id()].due, card))
+            .filter(|(date, _)| *date <= today)
+            .collect::<Vec<(NaiveDate, Card)>>();
+        due.sort_by_key(|(date, _)| *date);
+
+        due.into_iter()
+            .map(|(_, card)| card)
+            .chain(unseen.into_iter().take(new))
+            .collect()
+    }
+
+    /// Records an answer to `card` and returns when it's due next.
+    pub fn answer(&mut self, card: &Card, quality: u8, today: NaiveDate) -> NaiveDate {
+        let review = self
+            .cards
+            .entry(card.id())
+            .or_insert_with(|| Review::new(today));
+        review.answer(quality, today);
+        review.due
+    }
+
+    /// Every reviewed card as a record, in order of ID.
+    pub fn records(&self) -> Vec<ReviewRecord> {
+        self.cards
+            .iter()
+            .map(|(card, review)| ReviewRecord {
+                card: card.clone(),
+                repetitions: review.repetitions,
+                interval: review.interval,
+                ease: review.ease,
+                due: review.due,
+            })
+            .collect()
+    }
+
+    /// Adds the reviews in `records`, replacing those of the same cards.
+    /// Either every record is imported or, on the first invalid one, none are.
+    pub fn import(&mut self, records: Vec<ReviewRecord>) -> Result<usize, String> {
+        for record in &records {
+            let valid = match record.card.split_once(':') {
+                Some((fact, slug)) => Fact::from_name(fact).is_some() && !slug.is_empty(),
+                None => false,
+            };
+            if !valid {
+                return Err(format!(
+                    "Invalid card \"{}\", expected e.g. typing:pikachu",
+                    record.card
+                ));
+            }
+        }
+
+        let count = records.len();
+        for record in records {
+            let review = Review {
+                repetitions: record.repetitions,
+                interval: record.interval,
+                ease: record.ease.max(MIN_EASE),
+                due: record.due,
+            };
+            self.cards.insert(record.card, review);
+        }
+        Ok(count)
+    }
+
+    /// Earliest date one of `cards` is due, if any have been reviewed. Cards
+    /// outside `cards`, e.g. left out by a filter, don't count.
+    pub fn next_due(&self, cards: &[Card]) -> Option<NaiveDate> {
+        cards
+            .iter()
+            .filter_map(|card| self.cards.get(&card.id()))
+            .map(|review| review.due)
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::find_by_id;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2022, 1, day).unwrap()
+    }
+
+    #[test]
+    fn grades_answers() {
+        let gyarados = find_by_id("gyarados").unwrap();
+        let typing = Card {
+            fact: Fact::Typing,
+            pokemon: &gyarados,
+        };
+        assert_eq!(typing.grade("flying/water"), 5);
+        assert_eq!(typing.grade("Water, Flying"), 5);
+        assert_eq!(typing.grade("water"), 1);
+        assert_eq!(typing.grade("  "), 0);
+
+        let weaknesses = Card {
+            fact: Fact::Weaknesses,
+            pokemon: &gyarados,
+        };
+        assert_eq!(weaknesses.answer(), "Electric, Rock");
+        assert_eq!(weaknesses.grade("rock electric"), 5);
+        assert_eq!(weaknesses.grade("none"), 1);
+
+        let speed = Card {
+            fact: Fact::Speed,
+            pokemon: &gyarados,
+        };
+        assert_eq!(speed.grade("81"), 5);
+        assert_eq!(speed.grade("85"), 3);
+        assert_eq!(speed.grade("100"), 1);
+        assert_eq!(speed.id(), "speed:gyarados");
+    }
+
+    #[test]
+    fn sm2_schedule() {
+        let mut review = Review::new(date(1));
+        review.answer(5, date(1));
+        assert_eq!((review.interval, review.due), (1, date(2)));
+        review.answer(5, date(2));
+        assert_eq!((review.interval, review.due), (6, date(8)));
+        review.answer(4, date(8));
+        assert_eq!(review.interval, 16);
+        assert!((review.ease - 2.7).abs() < 1e-5);
+
+        review.answer(1, date(25));
+        assert_eq!(
+            (review.repetitions, review.interval, review.due),
+            (0, 1, date(26))
+        );
+        assert!((review.ease - 2.16).abs() < 1e-5);
+
+        for _ in 0..10 {
+            review.answer(0, date(26));
+        }
+        assert_eq!(review.ease, MIN_EASE);
+    }
+
+    #[test]
+    fn sessions_review_due_cards_first() {
+        let pokedex = vec![
+            find_by_id("bulbasaur").unwrap(),
+            find_by_id("charmander").unwrap(),
+            find_by_id("squirtle").unwrap(),
+        ];
+        let mut deck = Deck::default();
+        let cards = Card::all(&pokedex, &[Fact::Speed]);
+        deck.answer(&cards[2], 1, date(1));
+        deck.answer(&cards[1], 5, date(1));
+        deck.answer(&cards[1], 5, date(2));
+
+        let ids = |session: Vec<Card>| session.iter().map(Card::id).collect::<Vec<String>>();
+        assert_eq!(
+            ids(deck.session(Card::all(&pokedex, &[Fact::Speed]), date(2), 10)),
+            vec!["speed:squirtle", "speed:bulbasaur"]
+        );
+        assert_eq!(
+            ids(deck.session(Card::all(&pokedex, &[Fact::Speed]), date(9), 0)),
+            vec!["speed:squirtle", "speed:charmander"]
+        );
+        let cards = Card::all(&pokedex, &[Fact::Speed]);
+        assert_eq!(deck.next_due(&cards), Some(date(2)));
+        assert_eq!(deck.next_due(&cards[2..]), Some(date(2)));
+        assert_eq!(deck.next_due(&cards[..1]), None);
+    }
+
+    #[test]
+    fn deck_round_trip() {
+        let mut deck = Deck::default();
+        let pikachu = find_by_id("pikachu").unwrap();
+        deck.answer(
+            &Card {
+                fact: Fact::Typing,
+                pokemon: &pikachu,
+            },
+            5,
+            date(1),
+        );
+
+        let text = toml::to_string(&deck).unwrap();
+        let loaded: Deck = toml::from_str(&text).unwrap();
+        assert_eq!(loaded.cards, deck.cards);
+
+        let mut imported = Deck::default();
+        assert_eq!(imported.import(deck.records()), Ok(1));
+        assert_eq!(imported.cards, deck.cards);
+
+        let mut invalid = deck.records();
+        invalid[0].card = String::from("pikachu");
+        assert!(imported.import(invalid).is_err());
+    }
+
+    #[test]
+    fn upgrades_regional_ids() {
+        let mut deck = Deck::default();
+        for id in ["typing:alolan-vulpix", "speed:pikachu", "typing:missingno"] {
+            deck.cards.insert(String::from(id), Review::new(date(1)));
+        }
+        assert_eq!(
+            deck.upgrade_ids()
+                .cards
+                .into_keys()
+                .collect::<Vec<String>>(),
+            vec!["speed:pikachu", "typing:missingno", "typing:vulpix-alola"]
+        );
+    }
+}
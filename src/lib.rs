@@ -0,0 +1,52 @@
+//! The Pokédex behind the `pkmn` command line tool, for embedding in bots,
+//! web services and other Rust projects.
+//!
+//! All data is compiled in, so nothing here touches the network or the file
+//! system.
+//!
+//! ```
+//! use pkmn::pokedex::{self, Filter};
+//!
+//! let pikachu = pokedex::find_by_id("pikachu").unwrap();
+//! assert_eq!(pikachu.pokedex_number, 25);
+//! assert_eq!(&*pikachu.type_1, "Electric");
+//!
+//! let best = pokedex::search_by_name("charmnder", 1).remove(0);
+//! assert_eq!(best.pokemon.name, "Charmander");
+//!
+//! let filter = Filter::parse(Some("fire,flying"), Some("1"), None, &Default::default()).unwrap();
+//! assert_eq!(pokedex::filter(&filter)[0].name, "Charizard");
+//! ```
+//!
+//! [`pokedex`] is the place to start: finding, searching and filtering
+//! Pokémon. The other modules cover type matchups, moves, abilities,
+//! evolutions and stat calculations.
+
+pub mod abilities;
+pub mod anime;
+pub mod breeding;
+pub mod calc;
+pub mod challenge;
+pub mod classification;
+pub mod columns;
+pub mod computed;
+pub mod error;
+pub mod etymology;
+pub mod evolution;
+pub mod field;
+pub mod flavor;
+pub mod fuzzy;
+pub mod games;
+pub mod generation;
+pub mod go;
+pub mod intern;
+pub mod items;
+pub mod moves;
+pub mod names;
+pub mod pokedex;
+pub mod provenance;
+pub mod size;
+pub mod status;
+pub mod tera;
+pub mod trick_room;
+pub mod types;
@@ -0,0 +1,105 @@
+//! Canonical URLs of a Pokémon on other sites, for `--output json` and
+//! `pkmn open`, built from URL templates so consumers don't have to know each
+//! site's structure.
+
+use std::collections::BTreeMap;
+
+use crate::pokedex::{self, Pokemon};
+
+/// Sites linked to unless the config's `[links]` table disables them.
+const DEFAULT_LINKS: [(&str, &str); 5] = [
+    (
+        "bulbapedia",
+        "https://bulbapedia.bulbagarden.net/wiki/{species}_(Pok%C3%A9mon)",
+    ),
+    (
+        "pokeapi",
+        "https://pokeapi.co/api/v2/pokemon-species/{number}/",
+    ),
+    ("pokemondb", "https://pokemondb.net/pokedex/{species_slug}"),
+    ("serebii", "https://www.serebii.net/pokemon/{species_slug}/"),
+    (
+        "smogon",
+        "https://www.smogon.com/dex/sv/pokemon/{species_slug}/",
+    ),
+];
+
+/// Fills in a link template's placeholders:
+///
+/// - `{number}`: National Pokédex number, e.g. `6`
+/// - `{padded_number}`: the number padded to three digits, e.g. `006`
+/// - `{slug}`: the form's slug, e.g. `charizard-mega-x`
+/// - `{species_slug}`: the species' slug, e.g. `charizard`
+/// - `{species}`: the species' name as a wiki title, e.g. `Mr._Mime`
+pub fn fill(template: &str, pokemon: &Pokemon) -> String {
+    let species = pokedex::species_of(pokemon).unwrap_or(pokemon);
+    template
+        .replace("{number}", &pokemon.pokedex_number.to_string())
+        .replace("{padded_number}", &format!("{:03}", pokemon.pokedex_number))
+        .replace("{slug}", &pokemon.sprite_name_slug())
+        .replace("{species_slug}", &species.sprite_name_slug())
+        .replace("{species}", &species.name.replace(' ', "_"))
+}
+
+/// Links by site name. `templates` from the config add sites or replace the
+/// defaults, and an empty template removes a site.
+pub fn links(pokemon: &Pokemon, templates: &BTreeMap<String, String>) -> BTreeMap<String, String> {
+    let mut all = DEFAULT_LINKS
+        .iter()
+        .map(|(site, template)| (site.to_string(), template.to_string()))
+        .collect::<BTreeMap<String, String>>();
+    all.extend(templates.clone());
+
+    all.into_iter()
+        .filter(|(_, template)| !template.is_empty())
+        .map(|(site, template)| {
+            let url = fill(&template, pokemon);
+            (site, url)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::find_by_id;
+
+    #[test]
+    fn default_links() {
+        let links = links(&find_by_id("mr-mime").unwrap(), &BTreeMap::new());
+        assert_eq!(
+            links["bulbapedia"],
+            "https://bulbapedia.bulbagarden.net/wiki/Mr._Mime_(Pok%C3%A9mon)"
+        );
+        assert_eq!(
+            links["pokeapi"],
+            "https://pokeapi.co/api/v2/pokemon-species/122/"
+        );
+        assert_eq!(links["pokemondb"], "https://pokemondb.net/pokedex/mr-mime");
+        assert_eq!(
+            links["smogon"],
+            "https://www.smogon.com/dex/sv/pokemon/mr-mime/"
+        );
+    }
+
+    #[test]
+    fn forms_link_to_their_species() {
+        let templates = BTreeMap::from([
+            (String::from("serebii"), String::new()),
+            (
+                String::from("sprites"),
+                String::from("https://example.com/{padded_number}/{slug}.png"),
+            ),
+        ]);
+        let links = links(&find_by_id("charizard-mega-x").unwrap(), &templates);
+        assert_eq!(
+            links["pokemondb"],
+            "https://pokemondb.net/pokedex/charizard"
+        );
+        assert_eq!(
+            links["sprites"],
+            "https://example.com/006/charizard-mega-x.png"
+        );
+        assert!(!links.contains_key("serebii"));
+    }
+}
@@ -2,17 +2,59 @@
 extern crate log;
 
 use std::error::Error;
+use std::sync::OnceLock;
 
-use clap::{App, Arg};
-use console::style;
+use clap::{App, AppSettings, Arg, ArgMatches, ErrorKind};
+use console::{measure_text_width, style, truncate_str};
 use image::load_from_memory;
 use num_format::{Locale, ToFormattedString};
+use pkmn::{
+    abilities, anime, breeding, calc, challenge, classification, columns, computed, error,
+    etymology, evolution, field, flavor, fuzzy, games, generation, go, intern, items, moves, names,
+    pokedex, provenance, size, status, tera, trick_room, types,
+};
 
-use pokedex::{PokeMatch, Pokemon, PokemonStatus};
+use config::Config;
+use error::PkmnError;
+use extension::Extension;
+use pokedex::{Filter, PokeMatch, Pokemon, PokemonStatus};
 use print::{styled_empty_value, Printer};
+use provenance::{Provenance, Source};
+use size::SizeStats;
+use sprite::SpriteError;
+use terminal::{Capabilities, ImageRenderer};
 
-mod pokedex;
+mod api;
+mod backup;
+mod cache;
+mod card;
+mod commands;
+mod config;
+mod dataset;
+mod extension;
+mod help;
+#[cfg(all(feature = "hotkeys", target_os = "linux"))]
+mod hotkeys;
+mod hunt;
+mod json;
+mod learn;
+mod links;
+mod markdown;
+mod pager;
+mod picker;
 mod print;
+#[cfg(feature = "scripting")]
+mod scripting;
+mod share;
+mod sprite;
+mod sprite_cache;
+mod suggest;
+mod table;
+mod tcg;
+mod team;
+mod terminal;
+mod tracker;
+mod webhook;
 
 mod join {
     use std::convert::identity;
@@ -56,24 +98,44 @@ fn optional_empty(value: &str) -> Option<&str> {
     Some(value)
 }
 
-async fn download_image(url: &str) -> Result<image::DynamicImage, Box<dyn Error>> {
+/// One HTTP client for the whole run, so looking up several Pokémon reuses
+/// its connections for their sprites.
+fn http_client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(reqwest::Client::new)
+}
+
+async fn download(url: &str) -> Result<Vec<u8>, Box<dyn Error>> {
     info!("downloading image from \"{}\"", url);
 
-    let res = reqwest::get(url).await?;
+    let res = http_client().get(url).send().await?;
 
     match res.status() {
-        status if status.is_success() => {
-            let bytes = res.bytes().await?;
-            let image = load_from_memory(&bytes).unwrap();
-            Ok(image)
-        }
+        status if status.is_success() => Ok(res.bytes().await?.to_vec()),
         status => Err(Box::<dyn Error>::from(status.to_string())),
     }
 }
 
+async fn download_image(url: &str) -> Result<image::DynamicImage, Box<dyn Error>> {
+    let bytes = download(url).await?;
+    let image = load_from_memory(&bytes).map_err(PkmnError::from)?;
+    Ok(image)
+}
+
 struct PokemonPrinter {
     pokemon: Pokemon,
     printer: Printer,
+    custom_fields: Vec<(String, String)>,
+    /// How moves in the moves section are learned, e.g. `level-up`.
+    move_method: String,
+    /// Version of the Pokédex entry to show, or the newest bundled.
+    flavor_version: Option<String>,
+    /// Language of the Pokédex entry, from `--lang`.
+    language: String,
+    /// Pokédex fields filled in from PokéAPI with `--online`.
+    online_fields: Vec<&'static str>,
+    #[cfg(feature = "scripting")]
+    scripts: scripting::Scripts,
 }
 
 impl PokemonPrinter {
@@ -123,33 +185,152 @@ impl PokemonPrinter {
         })
     }
 
-    async fn print_sprite(&self) {
-        let url = self.pokemon.sprite_url();
+    /// Prints the sprite, returning `false` if the network is unreachable.
+    async fn print_sprite(&self, refresh: bool) -> bool {
+        match sprite::load(&self.pokemon, refresh).await {
+            Err(SpriteError::NotFound(check)) => {
+                println!();
+                self.printer.print_center(
+                    style(format!(
+                        "No sprite for \"{}\" (tried {} URLs)",
+                        check.slug,
+                        check.attempts.len()
+                    ))
+                    .dim(),
+                );
+            }
+            Err(SpriteError::Offline(err)) => {
+                info!("skipping sprite, network unreachable: {}", err);
+                return false;
+            }
+            Err(SpriteError::Failed(err)) => self.printer.print_failure(&format!("Image: {}", err)),
+            Ok(image) => {
+                if self.printer.print_image(&image, 68).is_err() {
+                    warn!("image failed to print");
+                }
+            }
+        }
+        true
+    }
+
+    fn print_provenance(&self) {
+        let mut fields = Provenance::pokedex_fields(&self.pokemon);
+        if classification::classification_of(&self.pokemon).is_some() {
+            fields.extend(
+                ["color", "shape", "habitat"]
+                    .iter()
+                    .map(|name| (name.to_string(), Source::Embedded("classifications.csv"))),
+            );
+        }
+        fields.push((String::from("egg_cycle_steps"), Source::Derived));
+        fields.extend(
+            self.custom_fields
+                .iter()
+                .map(|(name, _)| (format!("fields.{}", name), Source::Config)),
+        );
+        fields.extend(
+            self.online_fields
+                .iter()
+                .map(|name| (name.to_string(), Source::Online("pokeapi"))),
+        );
+
+        println!();
+        self.printer
+            .print_center(style(Provenance::new(fields).summary()).dim());
+    }
+
+    async fn print_footprint(&self, template: &str) {
+        let url = template
+            .replace("{number}", &self.pokemon.pokedex_number.to_string())
+            .replace("{slug}", &self.pokemon.sprite_name_slug());
 
         match download_image(&url).await {
-            Err(err) => self.printer.print_failure(&format!("Image: {}", err)),
+            Err(err) if sprite::is_network_error(err.as_ref()) => {
+                info!("skipping footprint, network unreachable: {}", err)
+            }
+            Err(err) => self.printer.print_failure(&format!("Footprint: {}", err)),
             Ok(image) => {
-                if let Err(_) = self.printer.print_image(&image, 68) {
-                    warn!("image failed to print");
+                if self.printer.print_image(&image, 8).is_err() {
+                    warn!("footprint failed to print");
                 }
             }
         }
     }
 
-    fn print_header(&self) {
-        let PokemonPrinter { pokemon, printer } = self;
+    fn print_header(&self, japanese: bool) {
+        let PokemonPrinter {
+            pokemon, printer, ..
+        } = self;
 
         printer.print_center(style(&pokemon.name).yellow());
 
+        if let Some(name) = names::japanese_name(pokemon).filter(|_| japanese) {
+            printer.print_center(format!(
+                "{}  {}  {}",
+                name.katakana,
+                style(name.romaji).italic(),
+                style(format!("({}™)", name.trademark)).dim()
+            ));
+        }
+
         if let Some(status) = self.pokemon_status() {
             printer.print_center(style(status).green());
         }
 
         printer.print_center(format!("Generation {}", pokemon.generation));
+
+        for hint in evolution::hints_for(pokemon) {
+            printer.print_center(style(hint.message()).yellow().bold());
+            if !hint.alternative.is_empty() {
+                printer.print_center(style(format!("({})", hint.alternative)).yellow());
+            }
+        }
+    }
+
+    fn print_etymology(&self) {
+        let PokemonPrinter {
+            pokemon, printer, ..
+        } = self;
+
+        if let Some(etymology) = etymology::etymology_for(pokemon) {
+            printer.print_center(style(&etymology.origin).italic());
+            if !etymology.notes.is_empty() {
+                printer.print_paragraph(&etymology.notes, |line| style(line).dim());
+            }
+        }
+    }
+
+    fn print_flavor_section(&self) {
+        let PokemonPrinter {
+            pokemon,
+            printer,
+            flavor_version,
+            language,
+            ..
+        } = self;
+
+        let entry = match flavor::flavor_text_for(pokemon, flavor_version.as_deref(), language) {
+            Some(entry) => entry,
+            None => {
+                // Only missing when asked for, as most Pokémon have no entry bundled
+                if let Some(version) = flavor_version {
+                    println!();
+                    printer
+                        .print_center(style(format!("No {} Pokédex entry bundled", version)).dim());
+                }
+                return;
+            }
+        };
+        println!();
+        printer.print_section_heading(&format!("Pokédex Entry ({})", entry.version));
+        printer.print_paragraph(&entry.text, |line| style(line).italic());
     }
 
     fn print_pokedex_section(&self) {
-        let PokemonPrinter { pokemon, printer } = self;
+        let PokemonPrinter {
+            pokemon, printer, ..
+        } = self;
+        println!();
 
         printer.print_section_heading("Pokédex data");
 
@@ -159,19 +340,52 @@ impl PokemonPrinter {
 
         printer.print_info("Species", style(&pokemon.species).cyan());
 
+        let classification = classification::classification_of(pokemon);
         printer.print_info(
-            "Height",
-            match pokemon.height_m {
-                Some(val) => style(format!("{} m", val)).cyan(),
+            "Color",
+            match &classification {
+                Some(classification) => style(classification.color.clone()).cyan(),
+                None => styled_empty_value(),
+            },
+        );
+        printer.print_info(
+            "Shape",
+            match classification.as_ref().and_then(|c| c.shape.clone()) {
+                Some(shape) => style(shape).cyan(),
                 None => styled_empty_value(),
             },
         );
+        printer.print_info(
+            "Habitat",
+            match classification.and_then(|classification| classification.habitat) {
+                Some(habitat) => style(habitat).cyan(),
+                None => styled_empty_value(),
+            },
+        );
+
+        let size_stats = SizeStats::pokedex();
+
+        printer.print_info(
+            "Height",
+            match (pokemon.height_m, size_stats.height_percentile(pokemon)) {
+                (Some(val), Some(percentile)) => format!(
+                    "{} {}",
+                    style(format!("{} m", val)).cyan(),
+                    style(format!("(taller than {:.0}% of Pokémon)", percentile)).dim()
+                ),
+                _ => styled_empty_value().to_string(),
+            },
+        );
 
         printer.print_info(
             "Weight",
-            match pokemon.weight_kg {
-                Some(val) => style(format!("{} kg", val)).cyan(),
-                None => styled_empty_value(),
+            match (pokemon.weight_kg, size_stats.weight_percentile(pokemon)) {
+                (Some(val), Some(percentile)) => format!(
+                    "{} {}",
+                    style(format!("{} kg", val)).cyan(),
+                    style(format!("(heavier than {:.0}% of Pokémon)", percentile)).dim()
+                ),
+                _ => styled_empty_value().to_string(),
             },
         );
 
@@ -180,40 +394,76 @@ impl PokemonPrinter {
                 1 => "Ability",
                 _ => "Abilities",
             },
-            style(&pokemon.ability_1).cyan(),
+            self.ability_info(&pokemon.ability_1, None),
         );
 
         if !pokemon.ability_2.is_empty() {
-            printer.print_info("", style(&pokemon.ability_2).cyan());
+            printer.print_info("", self.ability_info(&pokemon.ability_2, None));
         }
 
         if !pokemon.ability_hidden.is_empty() {
             printer.print_info(
                 "",
-                format!(
-                    "{} {}",
-                    style(&pokemon.ability_hidden).cyan(),
-                    style("(hidden ability)").dim()
-                ),
+                self.ability_info(&pokemon.ability_hidden, Some("(hidden)")),
             );
         }
     }
 
+    /// An ability's name, then as much of its description as fits on the
+    /// line.
+    /// Descriptions cut shorter than this say too little to be worth it.
+    const MIN_DESCRIPTION_WIDTH: usize = 16;
+
+    fn ability_info(&self, name: &str, note: Option<&str>) -> String {
+        let mut info = style(name).cyan().to_string();
+        if let Some(note) = note {
+            info = format!("{} {}", info, style(note).dim());
+        }
+
+        let width = usize::from(self.printer.width - self.printer.width / 2 - 1);
+        let room = width.saturating_sub(measure_text_width(&info) + 2);
+        match abilities::find_ability(name) {
+            Some(ability) if room >= Self::MIN_DESCRIPTION_WIDTH => format!(
+                "{}  {}",
+                info,
+                style(truncate_str(&ability.description, room, "…")).dim()
+            ),
+            _ => info,
+        }
+    }
+
     fn print_stats_section(&self) {
-        let PokemonPrinter { pokemon, printer } = self;
+        let PokemonPrinter {
+            pokemon, printer, ..
+        } = self;
+        println!();
 
         printer.print_section_heading("Base Stats");
-        printer.print_info("HP", style(pokemon.hp).cyan());
-        printer.print_info("Attack", style(pokemon.attack).cyan());
-        printer.print_info("Defense", style(pokemon.defense).cyan());
-        printer.print_info("Sp. Attack", style(pokemon.sp_attack).cyan());
-        printer.print_info("Sp. Defense", style(pokemon.sp_defense).cyan());
-        printer.print_info("Speed", style(pokemon.speed).cyan());
-        printer.print_info("Total", style(pokemon.total_points).cyan().bold());
+        printer.print_stat("HP", pokemon.hp);
+        printer.print_stat("Attack", pokemon.attack);
+        printer.print_stat("Defense", pokemon.defense);
+        printer.print_stat("Sp. Attack", pokemon.sp_attack);
+        printer.print_stat("Sp. Defense", pokemon.sp_defense);
+        printer.print_stat("Speed", pokemon.speed);
+        printer.print_info(
+            "Total",
+            format!(
+                "{} {}",
+                style(pokemon.total_points).cyan().bold(),
+                style(format!(
+                    "(higher than {:.0}% of Pokémon)",
+                    columns::Columns::pokedex().total_percentile(pokemon)
+                ))
+                .dim()
+            ),
+        );
     }
 
     fn print_training_section(&self) {
-        let PokemonPrinter { pokemon, printer } = self;
+        let PokemonPrinter {
+            pokemon, printer, ..
+        } = self;
+        println!();
 
         printer.print_section_heading("Training");
 
@@ -250,8 +500,59 @@ impl PokemonPrinter {
         );
     }
 
+    fn print_defenses_section(&self) {
+        let PokemonPrinter {
+            pokemon, printer, ..
+        } = self;
+        println!();
+
+        printer.print_section_heading("Type Defenses");
+
+        let defenses = pokemon.type_defenses();
+        for (label, multiplier) in [
+            ("4× Damage", 4.0),
+            ("2× Damage", 2.0),
+            ("½× Damage", 0.5),
+            ("¼× Damage", 0.25),
+            ("No Effect", 0.0),
+        ] {
+            let types = defenses
+                .iter()
+                .filter(|(_, effectiveness)| *effectiveness == multiplier)
+                .map(|(attacker, _)| attacker.name())
+                .collect::<Vec<&str>>();
+            if !types.is_empty() {
+                printer.print_info(label, style(types.join(", ")).magenta());
+            }
+        }
+
+        let hints = items::weakness_hints(pokemon);
+        if !hints.is_empty() {
+            println!();
+        }
+        let width = usize::from(printer.width - printer.width / 2 - 1);
+        for hint in hints {
+            let types = hint
+                .types
+                .iter()
+                .map(|attacker| attacker.name())
+                .collect::<Vec<&str>>()
+                .join(", ");
+            let room = width.saturating_sub(measure_text_width(&types) + 2);
+            let mut info = style(types).magenta().to_string();
+            if room >= Self::MIN_DESCRIPTION_WIDTH {
+                let description = truncate_str(&hint.description, room, "…");
+                info = format!("{}  {}", info, style(description).dim());
+            }
+            printer.print_info(&hint.name, info);
+        }
+    }
+
     fn print_breeding_section(&self) {
-        let PokemonPrinter { printer, .. } = self;
+        let PokemonPrinter {
+            pokemon, printer, ..
+        } = self;
+        println!();
 
         printer.print_section_heading("Breeding");
 
@@ -278,30 +579,480 @@ impl PokemonPrinter {
                 None => styled_empty_value(),
             },
         );
+
+        if let Some(baby) = breeding::incense_requirement(pokemon) {
+            printer.print_info(
+                "Incense",
+                format!(
+                    "{} {}",
+                    style(&baby.incense).cyan(),
+                    style(format!("(held by {})", baby.parent)).dim()
+                ),
+            );
+        }
+
+        if let Some(baby) = breeding::incense_offspring(pokemon) {
+            printer.print_info(
+                "Offspring",
+                format!(
+                    "{} {}",
+                    style(&baby.baby).cyan(),
+                    style(format!("(holding {})", baby.incense)).dim()
+                ),
+            );
+            printer.print_info(
+                "",
+                format!(
+                    "{} {}",
+                    style(&baby.offspring_without_incense).cyan(),
+                    style("(without incense)").dim()
+                ),
+            );
+        }
+    }
+
+    fn print_names_section(&self) {
+        let PokemonPrinter {
+            pokemon, printer, ..
+        } = self;
+
+        println!();
+        printer.print_section_heading("Names");
+
+        printer.print_info("English", style(&pokemon.name).cyan());
+
+        printer.print_info(
+            "German",
+            match optional_empty(&pokemon.german_name) {
+                Some(name) => style(name.to_owned()).cyan(),
+                None => styled_empty_value(),
+            },
+        );
+
+        printer.print_info(
+            "Japanese",
+            match optional_empty(&pokemon.japanese_name) {
+                Some(name) => style(name.to_owned()).cyan(),
+                None => styled_empty_value(),
+            },
+        );
+    }
+
+    fn print_evolution_section(&self) {
+        let PokemonPrinter {
+            pokemon, printer, ..
+        } = self;
+
+        let lines = evolution::lines_for(pokemon);
+
+        println!();
+        printer.print_section_heading("Evolution");
+
+        if lines.is_empty() {
+            printer.print_center(style("Doesn't evolve").dim());
+        }
+        for line in lines {
+            let stages = line
+                .iter()
+                .map(|stage| {
+                    let name = match stage.name == pokemon.name {
+                        true => style(&stage.name).yellow().bold(),
+                        false => style(&stage.name).cyan(),
+                    };
+                    match &stage.method {
+                        Some(method) => {
+                            format!("{} {}", name, style(format!("({})", method)).dim())
+                        }
+                        None => name.to_string(),
+                    }
+                })
+                .collect::<Vec<String>>();
+            printer.print_center(stages.join(" → "));
+        }
+    }
+
+    fn print_go_section(&self) {
+        let PokemonPrinter {
+            pokemon, printer, ..
+        } = self;
+        println!();
+
+        let stats = go::GoStats::new(pokemon);
+        printer.print_section_heading("Pokémon GO");
+        commands::go::print_stats(&stats, printer);
+        for level in [40.0, 50.0] {
+            printer.print_info(
+                format!("Max CP (Lv {})", level),
+                style(stats.cp(go::Ivs::PERFECT, level)).cyan(),
+            );
+        }
+        let (min, max) = stats.raid_catch_cp(false);
+        printer.print_info("Raid Catch CP", style(format!("{}–{}", min, max)).cyan());
+    }
+
+    fn print_trivia_section(&self) {
+        let PokemonPrinter {
+            pokemon, printer, ..
+        } = self;
+        println!();
+        printer.print_section_heading("Trivia");
+
+        let trivia = match anime::trivia_for(pokemon) {
+            Some(trivia) => trivia,
+            None => return printer.print_center(style("No trivia bundled").dim()),
+        };
+        printer.print_info("Anime Debut", style(&trivia.debut).cyan());
+        if !trivia.notable_trainers.is_empty() {
+            printer.print_info(
+                "Notable Trainers",
+                style(trivia.notable_trainers.join(", ")).cyan(),
+            );
+        }
+        if let Some(voice_actor) = &trivia.voice_actor {
+            printer.print_info("Voice Actor", style(voice_actor).cyan());
+        }
+    }
+
+    fn print_moves_section(&self) {
+        let PokemonPrinter {
+            pokemon,
+            printer,
+            move_method,
+            ..
+        } = self;
+        println!();
+        printer.print_section_heading("Moves");
+
+        let learnset = moves::learnset(pokemon);
+        if learnset.is_empty() {
+            return printer.print_center(style("No learnset bundled").dim());
+        }
+        let learned = learnset
+            .iter()
+            .filter(|(_, method)| method == move_method)
+            .collect::<Vec<_>>();
+        if learned.is_empty() {
+            return printer.print_center(style(format!("No {} moves", move_method)).dim());
+        }
+        printer.print_center(style("Notable moves only, without level-up levels").dim());
+        for (m, _) in learned {
+            commands::moves::print_move(m, None, printer);
+        }
+    }
+
+    fn print_extension_section(&self, extension: &Extension) {
+        let PokemonPrinter {
+            pokemon, printer, ..
+        } = self;
+
+        let rows = extension.rows_for(pokemon);
+        if rows.is_empty() {
+            return;
+        }
+
+        println!();
+        printer.print_section_heading(&extension.heading);
+        for (label, value) in rows {
+            printer.print_info(label, style(value).cyan());
+        }
+    }
+
+    fn print_forms_section(&self) {
+        let PokemonPrinter {
+            pokemon, printer, ..
+        } = self;
+
+        let forms = pokedex::forms_of(pokemon);
+
+        if forms.is_empty() {
+            return;
+        }
+
+        println!();
+        printer.print_section_heading("Other Forms");
+
+        for form in forms {
+            let types = join::filter(vec![&form.type_1, &form.type_2], " | ", join::not_empty);
+            let flag = match pokedex::form_slug(&form) {
+                Some(slug) => format!(" --form {}", slug),
+                None => String::new(),
+            };
+            printer.print_info(
+                &form.name,
+                format!(
+                    "{} {}",
+                    style(types).magenta(),
+                    style(format!("(BST {}){}", form.total_points, flag)).dim()
+                ),
+            );
+        }
+    }
+
+    fn print_custom_fields_section(&self) {
+        let PokemonPrinter {
+            printer,
+            custom_fields,
+            ..
+        } = self;
+
+        if custom_fields.is_empty() {
+            return;
+        }
+
+        println!();
+        printer.print_section_heading("Custom Fields");
+
+        for (name, value) in custom_fields {
+            printer.print_info(name, style(value).cyan());
+        }
+    }
+
+    #[cfg(feature = "scripting")]
+    fn print_script_sections(&self) {
+        let PokemonPrinter {
+            pokemon,
+            printer,
+            scripts,
+            ..
+        } = self;
+
+        for section in scripts.sections(pokemon) {
+            println!();
+            printer.print_section_heading(&section.heading);
+            for (label, value) in section.rows {
+                printer.print_info(label, style(value).cyan());
+            }
+        }
     }
 }
 
-async fn print_pokemon(pokemon: Pokemon, printer: Printer) {
-    let poke_printer = PokemonPrinter { pokemon, printer };
+/// What a card section prints.
+enum SectionRenderer {
+    Builtin(fn(&PokemonPrinter)),
+    /// A spin-off dataset mounted from the extensions directory.
+    Extension(Extension),
+}
 
-    poke_printer.print_sprite().await;
-    println!();
-    poke_printer.print_header();
-    println!();
-    poke_printer.print_pokedex_section();
-    println!();
-    poke_printer.print_stats_section();
-    println!();
-    poke_printer.print_training_section();
+/// A named section of the Pokémon card that can be toggled with `--sections`.
+struct CardSection {
+    name: String,
+    render: SectionRenderer,
+    /// Whether the section is part of the regular card, rather than only
+    /// being printed by `--full` or when requested by name.
+    default: bool,
+}
+
+impl CardSection {
+    fn builtin(name: &str, render: fn(&PokemonPrinter), default: bool) -> Self {
+        CardSection {
+            name: name.to_string(),
+            render: SectionRenderer::Builtin(render),
+            default,
+        }
+    }
+
+    fn render(&self, printer: &PokemonPrinter) {
+        match &self.render {
+            SectionRenderer::Builtin(render) => render(printer),
+            SectionRenderer::Extension(extension) => printer.print_extension_section(extension),
+        }
+    }
+}
+
+fn card_sections() -> Vec<CardSection> {
+    let mut sections = vec![
+        CardSection::builtin("pokedex", PokemonPrinter::print_pokedex_section, true),
+        CardSection::builtin("flavor", PokemonPrinter::print_flavor_section, true),
+        CardSection::builtin("evolution", PokemonPrinter::print_evolution_section, true),
+        CardSection::builtin("stats", PokemonPrinter::print_stats_section, true),
+        CardSection::builtin("defenses", PokemonPrinter::print_defenses_section, true),
+        CardSection::builtin("training", PokemonPrinter::print_training_section, true),
+        CardSection::builtin("breeding", PokemonPrinter::print_breeding_section, true),
+        CardSection::builtin("names", PokemonPrinter::print_names_section, false),
+        CardSection::builtin("forms", PokemonPrinter::print_forms_section, true),
+        CardSection::builtin("go", PokemonPrinter::print_go_section, false),
+        CardSection::builtin("trivia", PokemonPrinter::print_trivia_section, false),
+        CardSection::builtin("moves", PokemonPrinter::print_moves_section, false),
+        CardSection::builtin("fields", PokemonPrinter::print_custom_fields_section, true),
+    ];
+
+    #[cfg(feature = "scripting")]
+    sections.push(CardSection::builtin(
+        "scripts",
+        PokemonPrinter::print_script_sections,
+        true,
+    ));
+
+    // Mounting a dataset is opting in, so its section is on by default
+    for extension in Extension::load_all() {
+        if sections
+            .iter()
+            .any(|section| section.name == extension.name)
+        {
+            warn!(
+                "ignoring extension \"{}\", a section has that name already",
+                extension.name
+            );
+            continue;
+        }
+        sections.push(CardSection {
+            name: extension.name.clone(),
+            render: SectionRenderer::Extension(extension),
+            default: true,
+        });
+    }
+
+    sections
+}
+
+/// Picks the card sections to print, in the given order. The default
+/// sections are printed when `names` is empty, or every section when `full`.
+/// `source` says where the names came from, for errors.
+fn select_card_sections(
+    names: &[String],
+    source: &str,
+    full: bool,
+) -> Result<Vec<CardSection>, String> {
+    let mut sections = card_sections();
+
+    if full {
+        return Ok(sections);
+    }
+
+    if names.is_empty() {
+        sections.retain(|section| section.default);
+        return Ok(sections);
+    }
+
+    let mut selected = Vec::new();
+    for name in names {
+        let name = name.trim().to_lowercase();
+        if selected
+            .iter()
+            .any(|section: &CardSection| section.name == name)
+        {
+            return Err(format!(
+                "Section \"{}\" is listed more than once in {}",
+                name, source
+            ));
+        }
+        match sections.iter().position(|section| section.name == name) {
+            Some(index) => selected.push(sections.remove(index)),
+            None => {
+                let names = card_sections()
+                    .iter()
+                    .map(|section| section.name.clone())
+                    .collect::<Vec<String>>();
+                return Err(match fuzzy::did_you_mean(&names, &name) {
+                    Some(suggestion) => format!(
+                        "Unknown section \"{}\", did you mean \"{}\"?",
+                        name, suggestion
+                    ),
+                    None => format!(
+                        "Unknown section \"{}\" (expected one of: {})",
+                        name,
+                        names.join(", ")
+                    ),
+                });
+            }
+        }
+    }
+    Ok(selected)
+}
+
+/// How a Pokémon's card is printed.
+struct CardOptions {
+    sections: Vec<CardSection>,
+    /// Ends the card with the sources of its data.
+    provenance: bool,
+    sprite: bool,
+    /// Downloads the sprite even if it's cached.
+    refresh_sprite: bool,
+    /// Adds where the name comes from under the header.
+    etymology: bool,
+    /// Adds the Japanese name and its romanizations to the header.
+    japanese: bool,
+    /// How moves in the moves section are learned.
+    move_method: String,
+    flavor_version: Option<String>,
+    /// Language of the Pokédex entry, from `--lang`.
+    language: String,
+    /// Pokédex fields filled in from PokéAPI with `--online`.
+    online_fields: Vec<&'static str>,
+}
+
+async fn print_pokemon(pokemon: Pokemon, printer: Printer, options: &CardOptions) {
+    let config = Config::load();
+
+    #[cfg(feature = "scripting")]
+    let scripts = scripting::Scripts::load();
+    #[cfg(feature = "scripting")]
+    let pokemon = scripts.process(pokemon);
+
+    let custom_fields = computed::evaluate_all(&pokemon, &config.fields)
+        .into_iter()
+        .map(|(name, value)| (name, value.to_string()));
+    #[cfg(feature = "scripting")]
+    let custom_fields = custom_fields.chain(scripts.fields(&pokemon));
+    let custom_fields = custom_fields.collect();
+
+    let poke_printer = PokemonPrinter {
+        pokemon,
+        printer,
+        custom_fields,
+        move_method: options.move_method.clone(),
+        flavor_version: options.flavor_version.clone(),
+        language: options.language.clone(),
+        online_fields: options.online_fields.clone(),
+        #[cfg(feature = "scripting")]
+        scripts,
+    };
+
+    let online = match options.sprite {
+        true => poke_printer.print_sprite(options.refresh_sprite).await,
+        false => true,
+    };
     println!();
-    poke_printer.print_breeding_section();
+    poke_printer.print_header(options.japanese);
+    if options.etymology {
+        poke_printer.print_etymology();
+    }
+    for section in &options.sections {
+        section.render(&poke_printer);
+    }
+    if let Some(template) = config.footprint_image_url.as_ref().filter(|_| online) {
+        poke_printer.print_footprint(template).await;
+    }
+    if options.provenance {
+        poke_printer.print_provenance();
+    }
     println!();
     println!();
 }
 
-async fn lookup_pokemon_by_name(query: &str) {
-    let printer = Printer { width: 80 };
-    let results = pokedex::search_by_name(&query, 5);
+fn lookup_pokemon_by_id(id: &str) -> Option<Pokemon> {
+    let pokemon = pokedex::find_by_id(id);
+
+    if pokemon.is_none() {
+        let slugs = pokedex::entries()
+            .unwrap_or_default()
+            .iter()
+            .map(Pokemon::sprite_name_slug)
+            .collect::<Vec<String>>();
+        Printer::new().print_failure(&match fuzzy::did_you_mean(&slugs, id) {
+            Some(slug) => format!("No Pokémon with id \"{}\", did you mean \"{}\"?", id, slug),
+            None => format!("No Pokémon with id \"{}\"", id),
+        });
+    }
+
+    pokemon
+}
+
+/// Search results to log and, when they're ambiguous, pick from.
+const SEARCH_CANDIDATES: usize = 8;
+
+fn lookup_pokemon_by_name(query: &str, filter: &Filter) -> Option<Pokemon> {
+    let mut results = pokedex::search_by_name_filtered(query, SEARCH_CANDIDATES, filter);
 
     for (i, PokeMatch { pokemon, score }) in results.iter().enumerate() {
         info!(
@@ -314,29 +1065,590 @@ async fn lookup_pokemon_by_name(query: &str) {
         );
     }
 
-    match results.first() {
-        None => printer.print_failure("Couldn't find any matches"),
-        Some(poke_match) => {
-            print_pokemon(poke_match.pokemon.clone(), printer).await;
+    let similarities = results
+        .iter()
+        .map(|result| result.score.similarity)
+        .collect::<Vec<f64>>();
+    let close = picker::close_matches(&similarities);
+    if close > 1 && console::user_attended() {
+        let names = results[..close]
+            .iter()
+            .map(|result| result.pokemon.name.clone())
+            .collect::<Vec<String>>();
+        let prompt = format!("Several Pokémon match \"{}\":", query);
+        return match picker::pick(&prompt, &names) {
+            Ok(Some(index)) => Some(results.swap_remove(index).pokemon),
+            Ok(None) => None,
+            Err(err) => {
+                warn!("picker failed: {}", err);
+                Some(results.swap_remove(0).pokemon)
+            }
+        };
+    }
+
+    match results.into_iter().next() {
+        None => {
+            Printer::new().print_failure("Couldn't find any matches");
+            None
         }
+        Some(poke_match) => Some(poke_match.pokemon),
     }
 }
 
-#[tokio::main]
-async fn main() {
-    pretty_env_logger::init();
+fn print_unknown_form(pokemon: &Pokemon, form: &str) {
+    let printer = Printer::new();
+    printer.print_failure(&format!("{} has no form \"{}\"", pokemon.name, form));
+    let forms = pokedex::forms_of(pokemon)
+        .iter()
+        .chain([pokemon])
+        .filter_map(pokedex::form_slug)
+        .collect::<Vec<String>>();
+    if !forms.is_empty() {
+        printer.print_info("Forms", style(forms.join(", ")).cyan());
+    }
+}
+
+fn validate_flavor_version(value: String) -> Result<(), String> {
+    match flavor::find_version(&value) {
+        Some(_) => Ok(()),
+        None => Err(format!(
+            "Pokédex entries are bundled for {}",
+            flavor::versions().join(" and ")
+        )),
+    }
+}
 
-    let matches = App::new(env!("CARGO_PKG_NAME"))
+/// The top-level command line, including every subcommand.
+fn cli() -> App<'static, 'static> {
+    App::new(env!("CARGO_PKG_NAME"))
         .version(env!("CARGO_PKG_VERSION"))
         .arg(
             Arg::with_name("search")
                 .short("s")
                 .long("search")
                 .value_name("Searches for a Pokèmon")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+        )
+        .arg(
+            Arg::with_name("id")
+                .long("id")
+                .value_name("Looks up a Pokèmon by slug (e.g. charizard-mega-x) or Pokédex number")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .conflicts_with("search"),
+        )
+        .arg(
+            Arg::with_name("ability")
+                .long("ability")
+                .value_name("ABILITY")
+                .help("Lists the Pokémon that can have this ability, regular or hidden")
+                .takes_value(true)
+                .conflicts_with_all(&["search", "id"]),
+        )
+        .arg(
+            Arg::with_name("type")
+                .long("type")
+                .value_name("TYPES")
+                .help("Only searches Pokémon of this type, or both of two types (e.g. fire,flying)")
+                .takes_value(true)
+                .conflicts_with("id"),
+        )
+        .arg(
+            Arg::with_name("gen")
+                .long("gen")
+                .value_name("GENERATIONS")
+                .help("Only searches Pokémon introduced in this generation, or range (e.g. 1-3)")
+                .takes_value(true)
+                .conflicts_with("id"),
+        )
+        .arg(commands::where_arg().conflicts_with("id"))
+        .arg(
+            Arg::with_name("sections")
+                .long("sections")
+                .value_name("Card sections to print, in order (e.g. stats,breeding)")
+                .takes_value(true)
+                .use_delimiter(true),
+        )
+        .arg(
+            Arg::with_name("full")
+                .long("full")
+                .help("Prints every section of the card, paged")
+                .conflicts_with("sections"),
+        )
+        .arg(
+            Arg::with_name("go")
+                .long("go")
+                .help("Adds Pokémon GO stats and CP to the card"),
+        )
+        .arg(
+            Arg::with_name("trivia")
+                .long("trivia")
+                .help("Adds anime trivia and the name's etymology to the card"),
+        )
+        .arg(
+            Arg::with_name("form")
+                .long("form")
+                .value_name("FORM")
+                .help("Looks up a form of the Pokémon instead, e.g. alolan, mega-x or therian")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("flavor-version")
+                .long("flavor-version")
+                .value_name("VERSION")
+                .help("Shows this game's Pokédex entry, e.g. red or yellow, instead of the newest")
+                .takes_value(true)
+                .validator(validate_flavor_version),
+        )
+        .arg(
+            Arg::with_name("show-jp").long("show-jp").help(
+                "Adds the Japanese name, its rōmaji and trademarked romanization to the card",
+            ),
+        )
+        .arg(
+            Arg::with_name("lang")
+                .long("lang")
+                .value_name("LANG")
+                .help(
+                    "Adds names in this language to the card, as --show-jp does for ja, and \
+                     prefers Pokédex entries in it over English ones",
+                )
+                .possible_values(&names::LANGUAGES)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("move-method")
+                .long("move-method")
+                .help("Adds the notable moves learned this way to the card, for Pokémon with a bundled learnset")
+                .possible_values(&moves::METHODS)
                 .takes_value(true),
         )
-        .get_matches();
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .visible_alias("format")
+                .help(
+                    "Prints the card for the terminal, as a standalone SVG image, as JSON or as \
+                     Markdown",
+                )
+                .possible_values(&["terminal", "svg", "json", "markdown"])
+                .default_value("terminal"),
+        )
+        .arg(
+            Arg::with_name("no-sprite")
+                .long("no-sprite")
+                .help("Skips the sprite, e.g. when offline")
+                .conflicts_with("refresh-sprite"),
+        )
+        .arg(
+            Arg::with_name("refresh-sprite")
+                .long("refresh-sprite")
+                .help("Downloads the sprite again instead of using the cached one"),
+        )
+        .arg(
+            Arg::with_name("sprite-renderer")
+                .long("sprite-renderer")
+                .help("How the sprite is drawn, detected from the terminal by default")
+                .possible_values(&ImageRenderer::NAMES)
+                .default_value("auto"),
+        )
+        .arg(
+            Arg::with_name("online")
+                .long("online")
+                .help("Fills in data missing from the bundled Pokédex from PokéAPI"),
+        )
+        .arg(
+            Arg::with_name("provenance")
+                .long("provenance")
+                .help("Notes where the data comes from, in JSON or below the card"),
+        )
+        .arg(
+            Arg::with_name("background")
+                .long("background")
+                .value_name("COLOR")
+                .help("Background of exported cards")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("accent")
+                .long("accent")
+                .value_name("COLOR")
+                .help("Accent of exported cards, instead of the Pokémon's type color")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("font")
+                .long("font")
+                .help("Font family of exported cards")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("transparent")
+                .long("transparent")
+                .help("Leaves out the background of exported cards"),
+        )
+        .arg(
+            Arg::with_name("no-color")
+                .long("no-color")
+                .help("Prints without colors, like setting NO_COLOR")
+                .global(true),
+        )
+        .setting(AppSettings::DisableHelpSubcommand)
+        .subcommands(commands::all())
+}
 
-    let search_query = matches.value_of("search").unwrap_or("");
-    lookup_pokemon_by_name(search_query).await;
+/// Whether a global flag is given, which clap only sets on the subcommand
+/// it's given after.
+fn is_present_anywhere(matches: &ArgMatches, name: &str) -> bool {
+    matches.is_present(name)
+        || matches
+            .subcommand()
+            .1
+            .is_some_and(|matches| is_present_anywhere(matches, name))
+}
+
+/// Parses the command line. An unknown command gets a suggestion of the
+/// nearest one, and an offer to look it up if it looks like a Pokémon's name.
+/// Turns colors off for `--no-color`, or NO_COLOR unless the config sets
+/// colors, as https://no-color.org asks. Returns the terminal config.
+fn apply_no_color(no_color_flag: bool) -> terminal::TerminalConfig {
+    let terminal_config = Config::load().terminal;
+    if no_color_flag || (terminal::no_color() && terminal_config.colors.is_none()) {
+        terminal::disable_colors();
+    }
+    terminal_config
+}
+
+fn parse_args() -> ArgMatches<'static> {
+    let err = match cli().get_matches_safe() {
+        Ok(matches) => return matches,
+        Err(err) => err,
+    };
+    let word = match (&err.kind, &err.info) {
+        (ErrorKind::InvalidSubcommand | ErrorKind::UnknownArgument, Some(info))
+            if !info[0].starts_with('-') =>
+        {
+            info[0].clone()
+        }
+        _ => err.exit(),
+    };
+
+    let args = std::env::args().collect::<Vec<String>>();
+    let index = match args.iter().position(|arg| *arg == word) {
+        Some(index) => index,
+        None => err.exit(),
+    };
+    // Only a word where a subcommand goes can be a mistyped command; a stray
+    // argument to a subcommand gets clap's own usage error.
+    match cli().get_matches_from_safe(&args[..index]) {
+        Ok(matches) if matches.subcommand_name().is_none() => {}
+        _ => err.exit(),
+    }
+    let rest = &args[index + 1..];
+    let suggestion = suggest::suggest(&word, rest);
+    // The arguments couldn't be parsed, so `--no-color` is looked for by hand
+    apply_no_color(args.iter().any(|arg| arg == "--no-color"));
+
+    let printer = Printer::new();
+    printer.print_failure(&match &suggestion.command {
+        Some(command) => format!(
+            "Unknown command \"{}\", did you mean \"{}\"?",
+            word, command
+        ),
+        None => format!("Unknown command \"{}\"", word),
+    });
+
+    if let Some(pokemon) = suggestion.pokemon {
+        let slug = pokemon.sprite_name_slug();
+        if !console::user_attended() {
+            printer.print_center(
+                style(format!(
+                    "To look up {}, run `pkmn --id {}`",
+                    pokemon.name, slug
+                ))
+                .dim(),
+            );
+        } else {
+            print!("Look up {} instead? [y/N] ", style(&pokemon.name).yellow());
+            let _ = std::io::Write::flush(&mut std::io::stdout());
+            let answer = console::Term::stdout().read_line().unwrap_or_default();
+            if answer.trim().eq_ignore_ascii_case("y") {
+                return cli().get_matches_from(vec![env!("CARGO_PKG_NAME"), "--id", &slug]);
+            }
+        }
+    }
+
+    std::process::exit(1);
+}
+
+#[tokio::main]
+async fn main() {
+    run().await;
+    if print::failed() {
+        std::process::exit(1);
+    }
+}
+
+async fn run() {
+    pretty_env_logger::init();
+    dataset::use_local_pokedex();
+
+    let matches = parse_args();
+    let no_color_flag = is_present_anywhere(&matches, "no-color");
+    let terminal_config = apply_no_color(no_color_flag);
+    terminal::set_overrides(terminal::Overrides::new(
+        &terminal_config,
+        no_color_flag,
+        matches.value_of("sprite-renderer"),
+    ));
+
+    if let Err(err) = error::check_datasets() {
+        Printer::new().print_failure(&format!("The bundled data is broken: {}", err));
+        std::process::exit(1);
+    }
+
+    match matches.subcommand() {
+        ("ability", Some(matches)) => return commands::ability::run(matches),
+        ("anniversary", Some(matches)) => return commands::anniversary::run(matches),
+        ("backup", Some(matches)) => return commands::backup::run(matches),
+        ("cache", Some(matches)) => return commands::cache::run(matches),
+        ("calc", Some(matches)) => return commands::calc::run(matches),
+        ("challenge", Some(matches)) => return commands::challenge::run(matches),
+        ("compare", Some(matches)) => return commands::compare::run(matches),
+        ("completions", Some(matches)) => return commands::completions::run(matches),
+        ("core-gaps", Some(matches)) => return commands::core_gaps::run(matches),
+        ("cores", Some(matches)) => return commands::cores::run(matches),
+        ("data", Some(matches)) => return commands::data::run(matches).await,
+        ("doctor", Some(matches)) => return commands::doctor::run(matches),
+        ("export", Some(matches)) => return commands::export::run(matches),
+        ("field", Some(matches)) => return commands::field::run(matches),
+        ("gen", Some(matches)) => return commands::gen::run(matches).await,
+        ("go", Some(matches)) => return commands::go::run(matches),
+        ("help", Some(matches)) => return commands::help::run(matches),
+        ("hunt", Some(matches)) => return commands::hunt::run(matches).await,
+        ("import", Some(matches)) => return commands::import::run(matches),
+        ("learn", Some(matches)) => return commands::learn::run(matches),
+        ("list", Some(matches)) => return commands::list::run(matches),
+        ("man", Some(matches)) => return commands::man::run(matches),
+        ("manifest", Some(matches)) => return commands::manifest::run(matches).await,
+        ("mono", Some(matches)) => return commands::mono::run(matches),
+        ("moves", Some(matches)) => return commands::moves::run(matches),
+        ("nature", Some(matches)) => return commands::nature::run(matches),
+        ("natures", Some(matches)) => return commands::natures::run(matches),
+        ("open", Some(matches)) => return commands::open::run(matches),
+        ("search", Some(matches)) => return commands::search::run(matches),
+        ("share", Some(matches)) => return commands::share::run(matches).await,
+        ("stats", Some(matches)) => return commands::stats::run(matches),
+        ("status", Some(matches)) => return commands::status::run(matches),
+        ("suggest-evs", Some(matches)) => return commands::suggest_evs::run(matches),
+        ("tcg", Some(matches)) => return commands::tcg::run(matches).await,
+        ("team", Some(matches)) => return commands::team::run(matches),
+        ("tera", Some(matches)) => return commands::tera::run(matches),
+        ("timer", Some(matches)) => return commands::timer::run(matches).await,
+        ("update-data", Some(matches)) => return commands::update_data::run(matches).await,
+        _ => {}
+    }
+
+    let full = matches.is_present("full");
+    if full && pager::page_output() {
+        return;
+    }
+
+    let (section_names, source) = match matches.values_of("sections") {
+        Some(values) => (values.map(String::from).collect(), "--sections".to_string()),
+        None => (
+            Config::load().sections,
+            match Config::path() {
+                Some(path) => format!("\"sections\" of {}", path.display()),
+                None => "the config".to_string(),
+            },
+        ),
+    };
+
+    let mut sections = match select_card_sections(&section_names, &source, full) {
+        Ok(sections) => sections,
+        Err(err) => return Printer::new().print_failure(&err),
+    };
+    // Flags adding an optional section to whichever sections were selected
+    for (flag, name) in [
+        ("go", "go"),
+        ("trivia", "trivia"),
+        ("move-method", "moves"),
+        ("flavor-version", "flavor"),
+    ] {
+        if matches.is_present(flag) && !sections.iter().any(|section| section.name == name) {
+            sections.extend(
+                card_sections()
+                    .into_iter()
+                    .filter(|section| section.name == name),
+            );
+        }
+    }
+
+    let filter = match commands::filter(&matches) {
+        Ok(filter) => filter,
+        Err(err) => return Printer::new().print_failure(&err),
+    };
+
+    if let Some(ability) = matches.value_of("ability") {
+        return commands::ability::lookup(ability, &pokedex::filter(&filter));
+    }
+
+    // How many Pokémon were asked for, which decides the output's shape
+    // whether or not each one is found
+    let requested = match (matches.subcommand(), matches.values_of("id")) {
+        (("random", Some(_)), _) => 1,
+        (("show", Some(matches)), _) => matches.values_of("pokemon").map_or(0, |ids| ids.len()),
+        (_, Some(ids)) => ids.len(),
+        (_, None) => matches
+            .values_of("search")
+            .map_or(1, |queries| queries.len()),
+    };
+    if requested > 1 && matches.value_of("output") == Some("svg") {
+        return Printer::new()
+            .print_failure("An SVG card shows one Pokémon, look them up one by one");
+    }
+
+    let lookups = match (matches.subcommand(), matches.values_of("id")) {
+        (("random", Some(matches)), _) => commands::random::pick(matches).into_iter().collect(),
+        (("show", Some(matches)), _) => commands::show::pick(matches),
+        (_, Some(ids)) => ids.filter_map(lookup_pokemon_by_id).collect(),
+        (_, None) => match matches.values_of("search") {
+            Some(queries) => queries
+                .filter_map(|query| lookup_pokemon_by_name(query, &filter))
+                .collect(),
+            None => lookup_pokemon_by_name("", &filter).into_iter().collect(),
+        },
+    };
+    let lookups = match matches.value_of("form") {
+        Some(form) => lookups
+            .into_iter()
+            .filter_map(|pokemon| match pokedex::find_form(&pokemon, form) {
+                Some(form) => Some(form),
+                None => {
+                    print_unknown_form(&pokemon, form);
+                    None
+                }
+            })
+            .collect(),
+        None => lookups,
+    };
+
+    let japanese = matches.is_present("show-jp") || matches.value_of("lang") == Some("ja");
+    let flavor_version = matches
+        .value_of("flavor-version")
+        .and_then(flavor::find_version);
+    let mut options = CardOptions {
+        sections,
+        provenance: matches.is_present("provenance"),
+        sprite: !matches.is_present("no-sprite"),
+        refresh_sprite: matches.is_present("refresh-sprite"),
+        etymology: matches.is_present("trivia"),
+        japanese,
+        move_method: matches
+            .value_of("move-method")
+            .unwrap_or("level-up")
+            .to_string(),
+        flavor_version,
+        language: matches.value_of("lang").unwrap_or("en").to_string(),
+        online_fields: Vec::new(),
+    };
+    let capabilities = Capabilities::detect();
+
+    let mut lookups = lookups;
+    let mut online_fields = Vec::new();
+    for pokemon in lookups.iter_mut() {
+        online_fields.push(match matches.is_present("online") {
+            true => match api::fill_missing(pokemon).await {
+                Ok(filled) => filled,
+                Err(err) => {
+                    warn!("couldn't fill in missing data from PokéAPI: {}", err);
+                    Vec::new()
+                }
+            },
+            false => Vec::new(),
+        });
+    }
+
+    let mut json = Vec::new();
+    for (i, (pokemon, online_fields)) in lookups.iter().zip(online_fields).enumerate() {
+        options.online_fields = online_fields;
+        match matches.value_of("output") {
+            Some("svg") => {
+                let mut theme = Config::load().theme;
+                if let Some(background) = matches.value_of("background") {
+                    theme.background = background.to_string();
+                }
+                if let Some(accent) = matches.value_of("accent") {
+                    theme.accent = Some(accent.to_string());
+                }
+                if let Some(font) = matches.value_of("font") {
+                    theme.font = font.to_string();
+                }
+                theme.transparent |= matches.is_present("transparent");
+                println!("{}", card::render_svg(pokemon, &theme));
+            }
+            Some("markdown") => {
+                if i > 0 {
+                    println!();
+                }
+                print!("{}", markdown::render_card(pokemon))
+            }
+            Some("json") => json.push(pokemon_json(pokemon, &matches, &options).await),
+            _ => {
+                let printer = Printer::with_capabilities(capabilities);
+                print_pokemon(pokemon.clone(), printer, &options).await
+            }
+        }
+    }
+
+    // Several Pokémon are printed as one array, even if only some were found,
+    // so the output stays valid JSON of the same shape
+    match json.as_slice() {
+        [json] if requested == 1 => println!("{}", json.to_string_pretty()),
+        _ if requested == 1 || matches.value_of("output") != Some("json") => {}
+        _ => println!(
+            "{}",
+            serde_json::to_string_pretty(&json).expect("Pokémon should serialize to JSON")
+        ),
+    }
+}
+
+async fn pokemon_json<'a>(
+    pokemon: &'a Pokemon,
+    matches: &ArgMatches<'_>,
+    options: &CardOptions,
+) -> json::PokemonJson<'a> {
+    let config = Config::load();
+    let fields = computed::evaluate_all(pokemon, &config.fields);
+    let mut json = json::PokemonJson::new(pokemon, fields);
+    json.links = links::links(pokemon, &config.links);
+    json.online_fields = options.online_fields.clone();
+    if options.provenance {
+        json = json.with_provenance();
+    }
+    if options.sprite {
+        json.sprite = Some(sprite::check(pokemon).await);
+    }
+    if matches.is_present("go") {
+        json.go = Some(go::GoStats::new(pokemon));
+    }
+    if options.etymology {
+        json.trivia = anime::trivia_for(pokemon);
+        json.etymology = etymology::etymology_for(pokemon);
+    }
+    if options.japanese {
+        json.japanese = names::japanese_name(pokemon);
+    }
+    if options.flavor_version.is_some() {
+        json.flavor_text = flavor::flavor_text_for(
+            pokemon,
+            options.flavor_version.as_deref(),
+            &options.language,
+        );
+    }
+    json
 }
@@ -0,0 +1,139 @@
+//! Pokémon cards as GitHub-flavored Markdown, for places that don't render
+//! ANSI colors.
+
+use std::fmt::Write;
+
+use crate::pokedex::{Pokemon, PokemonStatus};
+
+fn or_dash<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(|| String::from("-"), |value| value.to_string())
+}
+
+fn non_empty(values: &[&str]) -> Vec<String> {
+    values
+        .iter()
+        .filter(|value| !value.is_empty())
+        .map(|value| value.to_string())
+        .collect()
+}
+
+/// The card's main sections: header, Pokédex data, base stats, training and
+/// breeding.
+pub fn render_card(pokemon: &Pokemon) -> String {
+    let mut card = String::new();
+
+    // Writing to a String can't fail
+    let _ = writeln!(card, "# {}", pokemon.name);
+    let _ = writeln!(card);
+    let _ = writeln!(card, "![{}]({})", pokemon.name, pokemon.sprite_url());
+    let _ = writeln!(card);
+    let mut subtitle = format!(
+        "*{}* · Generation {} · #{:03}",
+        pokemon.species, pokemon.generation, pokemon.pokedex_number
+    );
+    if !matches!(pokemon.status, PokemonStatus::Normal) {
+        let _ = write!(subtitle, " · {} Pokémon", pokemon.status.display_name());
+    }
+    let _ = writeln!(card, "{}", subtitle);
+    let _ = writeln!(card);
+
+    let mut abilities = non_empty(&[&pokemon.ability_1, &pokemon.ability_2]);
+    if !pokemon.ability_hidden.is_empty() {
+        abilities.push(format!("{} (hidden)", pokemon.ability_hidden));
+    }
+
+    let _ = writeln!(card, "## Pokédex data");
+    let _ = writeln!(card);
+    let _ = writeln!(card, "| | |");
+    let _ = writeln!(card, "|---|---|");
+    for (label, value) in [
+        (
+            "Type",
+            non_empty(&[&pokemon.type_1, &pokemon.type_2]).join(" / "),
+        ),
+        (
+            "Height",
+            or_dash(pokemon.height_m.map(|h| format!("{} m", h))),
+        ),
+        (
+            "Weight",
+            or_dash(pokemon.weight_kg.map(|w| format!("{} kg", w))),
+        ),
+        ("Abilities", abilities.join(", ")),
+    ] {
+        let _ = writeln!(card, "| {} | {} |", label, value);
+    }
+    let _ = writeln!(card);
+
+    let _ = writeln!(card, "## Base stats");
+    let _ = writeln!(card);
+    let _ = writeln!(
+        card,
+        "| HP | Attack | Defense | Sp. Attack | Sp. Defense | Speed | Total |"
+    );
+    let _ = writeln!(card, "|---:|---:|---:|---:|---:|---:|---:|");
+    let _ = writeln!(
+        card,
+        "| {} | {} | {} | {} | {} | {} | **{}** |",
+        pokemon.hp,
+        pokemon.attack,
+        pokemon.defense,
+        pokemon.sp_attack,
+        pokemon.sp_defense,
+        pokemon.speed,
+        pokemon.total_points
+    );
+    let _ = writeln!(card);
+
+    let _ = writeln!(card, "## Training & breeding");
+    let _ = writeln!(card);
+    let _ = writeln!(card, "| | |");
+    let _ = writeln!(card, "|---|---|");
+    for (label, value) in [
+        ("Catch Rate", or_dash(pokemon.catch_rate)),
+        ("Base Friendship", or_dash(pokemon.base_friendship)),
+        (
+            "Growth Rate",
+            or_dash(Some(&pokemon.growth_rate).filter(|g| !g.is_empty())),
+        ),
+        (
+            "Egg Groups",
+            non_empty(&[&pokemon.egg_type_1, &pokemon.egg_type_2]).join(", "),
+        ),
+        (
+            "Gender",
+            or_dash(
+                pokemon
+                    .percentage_male
+                    .map(|male| format!("{}% male, {}% female", male, 100.0 - male)),
+            ),
+        ),
+        ("Egg Cycles", or_dash(pokemon.egg_cycles)),
+    ] {
+        let _ = writeln!(card, "| {} | {} |", label, value);
+    }
+
+    card
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::find_by_id;
+
+    #[test]
+    fn pikachu() {
+        let card = render_card(&find_by_id("pikachu").unwrap());
+        assert!(card.starts_with("# Pikachu\n"));
+        assert!(card.contains("![Pikachu](https://"));
+        assert!(card.contains("| Type | Electric |"));
+        assert!(card.contains("| 35 | 55 | 40 | 50 | 50 | 90 | **320** |"));
+        assert!(card.contains("Lightning Rod (hidden)"));
+    }
+
+    #[test]
+    fn legendary_subtitle() {
+        let card = render_card(&find_by_id("mewtwo").unwrap());
+        assert!(card.contains("· Legendary Pokémon"));
+    }
+}
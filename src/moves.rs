@@ -0,0 +1,301 @@
+use serde::Deserialize;
+
+use crate::error::{self, PkmnError};
+use crate::pokedex::{self, Pokemon};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub enum Category {
+    Physical,
+    Special,
+    Status,
+}
+
+impl Category {
+    pub const NAMES: [&'static str; 3] = ["physical", "special", "status"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "physical" => Some(Category::Physical),
+            "special" => Some(Category::Special),
+            "status" => Some(Category::Status),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Category::Physical => "Physical",
+            Category::Special => "Special",
+            Category::Status => "Status",
+        }
+    }
+}
+
+/// Move metadata that abilities and items key off, e.g. Iron Fist boosting
+/// punching moves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Flag {
+    Contact,
+    Sound,
+    Punch,
+    Bite,
+    Pulse,
+    /// Moves with increased priority, i.e. that usually go first.
+    Priority,
+    /// Entry hazards, e.g. Stealth Rock.
+    Hazard,
+    HazardRemoval,
+    /// Moves that let the team outspeed, e.g. Tailwind or Icy Wind.
+    SpeedControl,
+    /// Attacks or status moves that switch the user out, e.g. U-turn.
+    Pivot,
+}
+
+impl Flag {
+    pub const NAMES: [&'static str; 10] = [
+        "contact",
+        "sound",
+        "punch",
+        "bite",
+        "pulse",
+        "priority",
+        "hazard",
+        "hazard-removal",
+        "speed-control",
+        "pivot",
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "contact" => Some(Flag::Contact),
+            "sound" => Some(Flag::Sound),
+            "punch" => Some(Flag::Punch),
+            "bite" => Some(Flag::Bite),
+            "pulse" => Some(Flag::Pulse),
+            "priority" => Some(Flag::Priority),
+            "hazard" => Some(Flag::Hazard),
+            "hazard-removal" => Some(Flag::HazardRemoval),
+            "speed-control" => Some(Flag::SpeedControl),
+            "pivot" => Some(Flag::Pivot),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        Flag::NAMES[*self as usize]
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Move {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub move_type: String,
+    pub category: Category,
+    /// `None` for status moves and moves with variable power, e.g. Low Kick.
+    pub power: Option<u16>,
+    /// `None` for moves that never miss.
+    pub accuracy: Option<u8>,
+    pub pp: u8,
+    pub priority: i8,
+    /// Semicolon-separated flag names; see [`Move::has_flag`].
+    flags: String,
+}
+
+/// Most PP Ups (or one PP Max) a move can take.
+pub const MAX_PP_UPS: u8 = 3;
+
+impl Move {
+    /// PP after using `ups` PP Ups, each adding a fifth of the base PP,
+    /// rounded down. Moves with under 5 PP, e.g. Sketch, can't be raised.
+    pub fn pp_with_ups(&self, ups: u8) -> u8 {
+        self.pp + self.pp / 5 * ups.min(MAX_PP_UPS)
+    }
+
+    pub fn max_pp(&self) -> u8 {
+        self.pp_with_ups(MAX_PP_UPS)
+    }
+
+    pub fn has_flag(&self, flag: Flag) -> bool {
+        match flag {
+            Flag::Priority => self.priority > 0,
+            _ => self.flags.split(';').any(|name| name == flag.name()),
+        }
+    }
+
+    /// Every flag the move has, in the order of [`Flag::NAMES`].
+    pub fn flags(&self) -> Vec<Flag> {
+        Flag::NAMES
+            .iter()
+            .filter_map(|name| Flag::from_name(name))
+            .filter(|flag| self.has_flag(*flag))
+            .collect()
+    }
+}
+
+pub static MOVES_CSV: &[u8] = include_bytes!("../data/moves.csv");
+
+/// Every bundled move.
+pub fn all_moves() -> Result<Vec<Move>, PkmnError> {
+    error::read_csv("moves.csv", MOVES_CSV)
+}
+
+/// Finds a move by name, ignoring case and treating hyphens as spaces, e.g.
+/// "focus-blast".
+pub fn find_move(name: &str) -> Option<Move> {
+    let slug = |name: &str| name.trim().to_lowercase().replace('-', " ");
+    let name = slug(name);
+    all_moves()
+        .unwrap_or_default()
+        .into_iter()
+        .find(|m| slug(&m.name) == name)
+}
+
+/// Ways a Pokémon can learn a move, as named in learnsets.
+pub const METHODS: [&str; 3] = ["level-up", "tm", "egg"];
+
+/// How a Pokémon learns a move, e.g. `level-up` or `tm`.
+#[derive(Deserialize)]
+pub(crate) struct LearnsetEntry {
+    pokemon: String,
+    #[serde(rename = "move")]
+    move_name: String,
+    method: String,
+}
+
+pub static LEARNSETS_CSV: &[u8] = include_bytes!("../data/learnsets.csv");
+
+pub(crate) fn load_learnsets() -> Result<Vec<LearnsetEntry>, PkmnError> {
+    error::read_csv("learnsets.csv", LEARNSETS_CSV)
+}
+
+fn learnset_entries() -> Vec<LearnsetEntry> {
+    load_learnsets().unwrap_or_default()
+}
+
+/// Moves the Pokémon learns, with how it learns them. Forms without a
+/// learnset of their own share their base species' one. Learnsets are only
+/// bundled for the Pokémon in [`learnset_pokemon`], and list notable moves
+/// rather than every move, with no levels for level-up moves.
+pub fn learnset(pokemon: &Pokemon) -> Vec<(Move, String)> {
+    let entries = learnset_entries();
+    let mut name = pokemon.name.clone();
+    if !entries.iter().any(|entry| entry.pokemon == name) {
+        match pokedex::find_by_id(&pokemon.pokedex_number.to_string()) {
+            Some(base) => name = base.name,
+            None => return Vec::new(),
+        }
+    }
+
+    let moves = all_moves().unwrap_or_default();
+    entries
+        .into_iter()
+        .filter(|entry| entry.pokemon == name)
+        .filter_map(|entry| {
+            let learned = moves.iter().find(|m| m.name == entry.move_name)?;
+            Some((learned.clone(), entry.method))
+        })
+        .collect()
+}
+
+/// Pokémon with a bundled learnset, in the learnsets' order.
+pub fn learnset_pokemon() -> Vec<String> {
+    let mut names = Vec::<String>::new();
+    for entry in learnset_entries() {
+        if !names.contains(&entry.pokemon) {
+            names.push(entry.pokemon);
+        }
+    }
+    names
+}
+
+/// Pokémon with a bundled learnset that learn `learned`, with how they learn
+/// it, in the learnsets' order.
+pub fn learned_by(learned: &Move) -> Vec<(String, String)> {
+    learnset_entries()
+        .into_iter()
+        .filter(|entry| entry.move_name == learned.name)
+        .map(|entry| (entry.pokemon, entry.method))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags() {
+        let bullet_punch = find_move("bullet-punch").unwrap();
+        assert!(bullet_punch.has_flag(Flag::Punch));
+        assert!(bullet_punch.has_flag(Flag::Contact));
+        assert!(bullet_punch.has_flag(Flag::Priority));
+        assert!(!bullet_punch.has_flag(Flag::Sound));
+
+        let trick_room = find_move("Trick Room").unwrap();
+        assert!(!trick_room.has_flag(Flag::Priority));
+        assert!(trick_room.has_flag(Flag::SpeedControl));
+        assert_eq!(trick_room.category, Category::Status);
+    }
+
+    #[test]
+    fn pp_ups() {
+        let with_pp = |pp| Move {
+            pp,
+            ..find_move("Protect").unwrap()
+        };
+        assert_eq!(with_pp(5).max_pp(), 8);
+        assert_eq!(with_pp(10).pp_with_ups(1), 12);
+        assert_eq!(with_pp(15).max_pp(), 24);
+        assert_eq!(with_pp(35).pp_with_ups(2), 49);
+        assert_eq!(with_pp(40).max_pp(), 64);
+        assert_eq!(with_pp(1).max_pp(), 1);
+        assert_eq!(with_pp(10).pp_with_ups(7), 16);
+    }
+
+    #[test]
+    fn learnset_moves_exist() {
+        let moves = all_moves().unwrap();
+        for entry in learnset_entries() {
+            assert!(
+                moves.iter().any(|m| m.name == entry.move_name),
+                "{} learns unknown move {}",
+                entry.pokemon,
+                entry.move_name
+            );
+        }
+    }
+
+    #[test]
+    fn learnset_methods() {
+        for entry in learnset_entries() {
+            assert!(METHODS.contains(&entry.method.as_str()), "{}", entry.method);
+        }
+
+        let flamethrower = find_move("flamethrower").unwrap();
+        let learners = learned_by(&flamethrower);
+        assert!(learners.contains(&(String::from("Charizard"), String::from("level-up"))));
+        assert!(learners.contains(&(String::from("Garchomp"), String::from("tm"))));
+        assert_eq!(
+            find_move("U-turn").unwrap().flags(),
+            vec![Flag::Contact, Flag::Pivot]
+        );
+    }
+
+    #[test]
+    fn bundled_learnsets() {
+        let names = learnset_pokemon();
+        assert_eq!(names.len(), 8);
+        assert_eq!(names[0], "Lucario");
+        assert!(learnset(&pokedex::find_by_id("bulbasaur").unwrap()).is_empty());
+    }
+
+    #[test]
+    fn mega_shares_learnset() {
+        let lucario = pokedex::find_by_id("lucario").unwrap();
+        let mega = pokedex::forms_of(&lucario).remove(0);
+        assert_eq!(learnset(&mega).len(), learnset(&lucario).len());
+        assert!(learnset(&lucario)
+            .iter()
+            .any(|(m, method)| m.name == "Extreme Speed" && method == "level-up"));
+    }
+}
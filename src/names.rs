@@ -0,0 +1,312 @@
+//! Names in other languages, from the Pokédex's `japanese_name` column, which
+//! holds the katakana followed by the trademarked romanization, e.g.
+//! `ピカチュウ (Pikachu)`.
+
+use serde::Serialize;
+
+use crate::pokedex::Pokemon;
+
+/// Languages names can be shown in alongside English.
+pub const LANGUAGES: [&str; 2] = ["en", "ja"];
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct JapaneseName {
+    pub katakana: String,
+    /// Hepburn romanization, as resources that index by Japanese name use.
+    pub romaji: String,
+    /// The official romanization trademarked by Nintendo, e.g. "Lizardon".
+    pub trademark: String,
+}
+
+/// The Pokémon's Japanese name, if the Pokédex has one.
+pub fn japanese_name(pokemon: &Pokemon) -> Option<JapaneseName> {
+    let (katakana, trademark) = pokemon
+        .japanese_name
+        .trim()
+        .strip_suffix(')')?
+        .split_once(" (")?;
+    Some(JapaneseName {
+        katakana: katakana.to_string(),
+        romaji: romanize(katakana),
+        trademark: trademark.to_string(),
+    })
+}
+
+fn syllable(kana: char) -> Option<&'static str> {
+    let syllable = match kana {
+        'ア' => "a",
+        'イ' => "i",
+        'ウ' => "u",
+        'エ' => "e",
+        'オ' => "o",
+        'カ' => "ka",
+        'キ' => "ki",
+        'ク' => "ku",
+        'ケ' => "ke",
+        'コ' => "ko",
+        'ガ' => "ga",
+        'ギ' => "gi",
+        'グ' => "gu",
+        'ゲ' => "ge",
+        'ゴ' => "go",
+        'サ' => "sa",
+        'シ' => "shi",
+        'ス' => "su",
+        'セ' => "se",
+        'ソ' => "so",
+        'ザ' => "za",
+        'ジ' => "ji",
+        'ズ' => "zu",
+        'ゼ' => "ze",
+        'ゾ' => "zo",
+        'タ' => "ta",
+        'チ' => "chi",
+        'ツ' => "tsu",
+        'テ' => "te",
+        'ト' => "to",
+        'ダ' => "da",
+        'ヂ' => "ji",
+        'ヅ' => "zu",
+        'デ' => "de",
+        'ド' => "do",
+        'ナ' => "na",
+        'ニ' => "ni",
+        'ヌ' => "nu",
+        'ネ' => "ne",
+        'ノ' => "no",
+        'ハ' => "ha",
+        'ヒ' => "hi",
+        'フ' => "fu",
+        'ヘ' => "he",
+        'ホ' => "ho",
+        'バ' => "ba",
+        'ビ' => "bi",
+        'ブ' => "bu",
+        'ベ' => "be",
+        'ボ' => "bo",
+        'パ' => "pa",
+        'ピ' => "pi",
+        'プ' => "pu",
+        'ペ' => "pe",
+        'ポ' => "po",
+        'マ' => "ma",
+        'ミ' => "mi",
+        'ム' => "mu",
+        'メ' => "me",
+        'モ' => "mo",
+        'ヤ' => "ya",
+        'ユ' => "yu",
+        'ヨ' => "yo",
+        'ラ' => "ra",
+        'リ' => "ri",
+        'ル' => "ru",
+        'レ' => "re",
+        'ロ' => "ro",
+        'ワ' => "wa",
+        'ヲ' => "o",
+        'ヴ' => "vu",
+        _ => return None,
+    };
+    Some(syllable)
+}
+
+/// The vowel of a small kana that changes the one before it, e.g. the `ァ`
+/// in `ファ` (fa).
+fn small_vowel(kana: char) -> Option<char> {
+    match kana {
+        'ァ' => Some('a'),
+        'ィ' => Some('i'),
+        'ゥ' => Some('u'),
+        'ェ' => Some('e'),
+        'ォ' => Some('o'),
+        _ => None,
+    }
+}
+
+fn small_glide(kana: char) -> Option<&'static str> {
+    match kana {
+        'ャ' => Some("ya"),
+        'ュ' => Some("yu"),
+        'ョ' => Some("yo"),
+        _ => None,
+    }
+}
+
+/// Swaps the vowel ending `romaji` for its long form, e.g. for `ー`.
+fn lengthen(romaji: &mut String) {
+    let long = match romaji.chars().last() {
+        Some('a') => 'ā',
+        Some('i') => 'ī',
+        Some('u') => 'ū',
+        Some('e') => 'ē',
+        Some('o') => 'ō',
+        _ => return,
+    };
+    romaji.pop();
+    romaji.push(long);
+}
+
+/// Romanizes katakana with modified Hepburn, e.g. `フシギソウ` as
+/// "Fushigisō": long vowels take a macron, a small `ッ` doubles the next
+/// consonant and `ン` before a vowel is written "n'".
+pub fn romanize(katakana: &str) -> String {
+    let kana = katakana.chars().collect::<Vec<char>>();
+    let mut romaji = String::new();
+    let mut geminate = false;
+    let mut after_n = false;
+
+    for (index, &character) in kana.iter().enumerate() {
+        let next = kana.get(index + 1).copied();
+        let previous = romaji.chars().last();
+
+        if let Some(vowel) = small_vowel(character) {
+            match previous {
+                Some(last) if last == vowel => lengthen(&mut romaji),
+                Some('a' | 'i' | 'u' | 'e' | 'o') => {
+                    romaji.pop();
+                    romaji.push(vowel);
+                }
+                _ => romaji.push(vowel),
+            }
+            continue;
+        }
+        if let Some(glide) = small_glide(character) {
+            if romaji.ends_with("shi") || romaji.ends_with("chi") || romaji.ends_with("ji") {
+                romaji.pop();
+                romaji.push_str(&glide[1..]);
+            } else {
+                if romaji.ends_with('i') {
+                    romaji.pop();
+                }
+                romaji.push_str(glide);
+            }
+            continue;
+        }
+
+        let syllable = match character {
+            'ッ' => {
+                geminate = true;
+                continue;
+            }
+            'ー' => {
+                lengthen(&mut romaji);
+                continue;
+            }
+            'ン' => {
+                romaji.push('n');
+                after_n = true;
+                continue;
+            }
+            // A ウ before a small vowel is a w, as in ウォ (wo)
+            'ウ' if next.and_then(small_vowel).is_some() => "w",
+            'ウ' if matches!(previous, Some('o' | 'u')) => {
+                lengthen(&mut romaji);
+                continue;
+            }
+            'オ' if previous == Some('o') => {
+                lengthen(&mut romaji);
+                continue;
+            }
+            '：' => ": ",
+            '・' => " ",
+            _ => match syllable(character) {
+                Some(syllable) => syllable,
+                None => {
+                    // Full-width letters and digits, e.g. the ２ of ポリゴン２
+                    let ascii = u32::from(character)
+                        .checked_sub(0xFEE0)
+                        .and_then(char::from_u32)
+                        .filter(char::is_ascii_graphic);
+                    romaji.push(ascii.unwrap_or(character));
+                    geminate = false;
+                    after_n = false;
+                    continue;
+                }
+            },
+        };
+
+        if after_n && syllable.starts_with(['a', 'i', 'u', 'e', 'o', 'y']) {
+            romaji.push('\'');
+        }
+        if geminate {
+            match syllable.strip_prefix("ch") {
+                Some(_) => romaji.push('t'),
+                None => romaji.extend(syllable.chars().next()),
+            }
+        }
+        romaji.push_str(syllable);
+        geminate = false;
+        after_n = false;
+    }
+
+    romaji
+        .split(' ')
+        .map(|word| {
+            let mut characters = word.chars();
+            match characters.next() {
+                Some(first) => first.to_uppercase().chain(characters).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex;
+
+    #[test]
+    fn romanizes_katakana() {
+        for (katakana, romaji) in [
+            ("フシギダネ", "Fushigidane"),
+            ("フシギソウ", "Fushigisō"),
+            ("リザードン", "Rizādon"),
+            ("カメックス", "Kamekkusu"),
+            ("ピカチュウ", "Pikachū"),
+            ("ニャース", "Nyāsu"),
+            ("ケーシィ", "Kēshī"),
+            ("ウインディ", "Uindi"),
+            ("ナッシー", "Nasshī"),
+            ("アズマオウ", "Azumaō"),
+            ("オオタチ", "Ōtachi"),
+            ("ウォーグル", "Wōguru"),
+            ("ファイヤー", "Faiyā"),
+            ("シェイミ", "Sheimi"),
+            ("ミュウツー", "Myūtsū"),
+            ("ニドラン♀", "Nidoran♀"),
+            ("ポリゴン２", "Porigon2"),
+            ("タイプ：ヌル", "Taipu: Nuru"),
+            ("マッチョ", "Matcho"),
+            ("ゼンイ", "Zen'i"),
+        ] {
+            assert_eq!(romanize(katakana), romaji, "{}", katakana);
+        }
+    }
+
+    #[test]
+    fn japanese_names() {
+        let charizard = pokedex::find_by_id("charizard").unwrap();
+        assert_eq!(
+            japanese_name(&charizard),
+            Some(JapaneseName {
+                katakana: String::from("リザードン"),
+                romaji: String::from("Rizādon"),
+                trademark: String::from("Lizardon"),
+            })
+        );
+
+        // Every name romanizes without leftover kana
+        for pokemon in pokedex::entries().unwrap() {
+            let name = japanese_name(pokemon).unwrap();
+            assert!(
+                name.romaji
+                    .chars()
+                    .all(|c| !('\u{30A0}'..='\u{30FF}').contains(&c)),
+                "{}",
+                name.romaji
+            );
+        }
+    }
+}
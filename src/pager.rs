@@ -0,0 +1,70 @@
+use std::env;
+use std::process::{Command, Stdio};
+
+use console::Term;
+
+use crate::terminal;
+
+/// Set on the child process so it prints directly (with colors) instead of
+/// paging again.
+const PAGED_ENV: &str = "PKMN_PAGED";
+
+/// Whether this is the child process started by [`page_output`], whose output
+/// goes to the pager rather than straight to the terminal.
+pub fn is_paged() -> bool {
+    env::var_os(PAGED_ENV).is_some()
+}
+
+/// Re-runs the current command with its output piped through `$PAGER`
+/// (`less -R` by default). Returns `false` when paging isn't possible, e.g.
+/// when stdout isn't a terminal, in which case the caller prints directly.
+pub fn page_output() -> bool {
+    if is_paged() {
+        console::set_colors_enabled(!terminal::no_color());
+        return false;
+    }
+
+    if !Term::stdout().is_term() {
+        return false;
+    }
+
+    let pager = env::var("PAGER").unwrap_or_else(|_| String::from("less -R"));
+    let mut pager_args = pager.split_whitespace();
+    let pager_program = match pager_args.next() {
+        Some(program) => program,
+        None => return false,
+    };
+
+    let exe = match env::current_exe() {
+        Ok(exe) => exe,
+        Err(err) => {
+            warn!("can't page output: {}", err);
+            return false;
+        }
+    };
+
+    let mut pager = match Command::new(pager_program)
+        .args(pager_args)
+        .stdin(Stdio::piped())
+        .spawn()
+    {
+        Ok(pager) => pager,
+        Err(err) => {
+            info!("pager \"{}\" unavailable: {}", pager_program, err);
+            return false;
+        }
+    };
+
+    let status = Command::new(exe)
+        .args(env::args_os().skip(1))
+        .env(PAGED_ENV, "1")
+        .stdout(pager.stdin.take().map_or_else(Stdio::inherit, Stdio::from))
+        .status();
+
+    if let Err(err) = status {
+        warn!("failed to run paged command: {}", err);
+    }
+
+    let _ = pager.wait();
+    true
+}
@@ -0,0 +1,123 @@
+//! A minimal interactive list for choosing between close search results.
+
+use std::io;
+
+use console::{style, Key, Term};
+
+/// How far below the best match's similarity a result can be and still be
+/// offered as an alternative.
+const CLOSE_MATCH_MARGIN: f64 = 0.05;
+
+/// Number of leading `similarities` (sorted best first) close enough to the
+/// best to be worth choosing between. An exact best match is never ambiguous.
+pub fn close_matches(similarities: &[f64]) -> usize {
+    match similarities.first() {
+        Some(best) if *best < 1.0 => similarities
+            .iter()
+            .take_while(|similarity| best - *similarity <= CLOSE_MATCH_MARGIN)
+            .count(),
+        Some(_) => 1,
+        None => 0,
+    }
+}
+
+enum Action {
+    Move(usize),
+    Select,
+    Cancel,
+    Ignore,
+}
+
+fn handle_key(key: &Key, selected: usize, len: usize) -> Action {
+    match key {
+        Key::ArrowUp | Key::Char('k') => Action::Move((selected + len - 1) % len),
+        Key::ArrowDown | Key::Char('j') | Key::Tab => Action::Move((selected + 1) % len),
+        Key::Char(digit @ '1'..='9') => match digit.to_digit(10) {
+            Some(number) if (number as usize) <= len => Action::Move(number as usize - 1),
+            _ => Action::Ignore,
+        },
+        Key::Enter => Action::Select,
+        Key::Escape | Key::Char('q') => Action::Cancel,
+        _ => Action::Ignore,
+    }
+}
+
+fn render(term: &Term, items: &[String], selected: usize) -> io::Result<()> {
+    for (i, item) in items.iter().enumerate() {
+        if i == selected {
+            term.write_line(&format!(
+                "{} {}",
+                style(">").cyan(),
+                style(item).cyan().bold()
+            ))?;
+        } else {
+            term.write_line(&format!("  {}", item))?;
+        }
+    }
+    Ok(())
+}
+
+/// Hides the cursor until dropped, so it comes back however the picker ends,
+/// including on an error.
+struct HiddenCursor<'a>(&'a Term);
+
+impl<'a> HiddenCursor<'a> {
+    fn new(term: &'a Term) -> io::Result<Self> {
+        term.hide_cursor()?;
+        Ok(HiddenCursor(term))
+    }
+}
+
+impl Drop for HiddenCursor<'_> {
+    fn drop(&mut self) {
+        let _ = self.0.show_cursor();
+    }
+}
+
+/// Lets the user choose one of `items` with the arrow keys (or j/k, or its
+/// number), returning its index, or `None` if they cancel.
+pub fn pick(prompt: &str, items: &[String]) -> io::Result<Option<usize>> {
+    let term = Term::stdout();
+    let mut selected = 0;
+
+    term.write_line(&format!("{} {}", style("?").yellow(), prompt))?;
+    let _cursor = HiddenCursor::new(&term)?;
+    render(&term, items, selected)?;
+
+    let result = loop {
+        match handle_key(&term.read_key()?, selected, items.len()) {
+            Action::Move(index) => selected = index,
+            Action::Select => break Some(selected),
+            Action::Cancel => break None,
+            Action::Ignore => continue,
+        }
+        term.clear_last_lines(items.len())?;
+        render(&term, items, selected)?;
+    };
+
+    term.clear_last_lines(items.len() + 1)?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ambiguous_matches() {
+        assert_eq!(close_matches(&[0.9, 0.9, 0.87, 0.8]), 3);
+        assert_eq!(close_matches(&[0.97, 0.88]), 1);
+        assert_eq!(close_matches(&[1.0, 0.99]), 1);
+        assert_eq!(close_matches(&[]), 0);
+    }
+
+    #[test]
+    fn keys() {
+        assert!(matches!(handle_key(&Key::ArrowUp, 0, 3), Action::Move(2)));
+        assert!(matches!(handle_key(&Key::Char('j'), 2, 3), Action::Move(0)));
+        assert!(matches!(handle_key(&Key::Char('2'), 0, 3), Action::Move(1)));
+        assert!(matches!(handle_key(&Key::Char('4'), 0, 3), Action::Ignore));
+        assert!(matches!(handle_key(&Key::Enter, 1, 3), Action::Select));
+        assert!(matches!(handle_key(&Key::Escape, 1, 3), Action::Cancel));
+    }
+}
@@ -1,8 +1,21 @@
-use std::cmp::Ordering;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+use std::sync::{Arc, OnceLock};
 
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
+use crate::calc::stats::Stat;
+use crate::columns::Columns;
+use crate::computed;
+use crate::error::{self, PkmnError};
+use crate::fuzzy::{self, Match, MatchScore};
+use crate::generation::latest_generation;
+use crate::intern;
+use crate::types::Type;
+
+#[derive(Serialize)]
 pub struct EggCycleStats {
     pub cycles: u16,
     pub max_steps: u16,
@@ -21,12 +34,12 @@ impl EggCycleStats {
     }
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 pub enum PokemonStatus {
     Normal,
     Legendary,
     Mythical,
-    #[serde(rename(deserialize = "Sub Legendary"))]
+    #[serde(rename = "Sub Legendary")]
     SubLegendary,
 }
 
@@ -41,21 +54,29 @@ impl PokemonStatus {
     }
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 pub struct Pokemon {
     pub pokedex_number: u16,
     pub name: String,
+    pub german_name: String,
+    pub japanese_name: String,
     pub generation: u8,
     pub status: PokemonStatus,
-    pub species: String,
-    pub type_1: String,
-    pub type_2: String,
+    #[serde(deserialize_with = "intern::deserialize")]
+    pub species: Arc<str>,
+    #[serde(deserialize_with = "intern::deserialize")]
+    pub type_1: Arc<str>,
+    #[serde(deserialize_with = "intern::deserialize")]
+    pub type_2: Arc<str>,
     pub height_m: Option<f32>,
     pub weight_kg: Option<f32>,
     pub abilities_number: u8,
-    pub ability_1: String,
-    pub ability_2: String,
-    pub ability_hidden: String,
+    #[serde(deserialize_with = "intern::deserialize")]
+    pub ability_1: Arc<str>,
+    #[serde(deserialize_with = "intern::deserialize")]
+    pub ability_2: Arc<str>,
+    #[serde(deserialize_with = "intern::deserialize")]
+    pub ability_hidden: Arc<str>,
     pub total_points: u16,
     pub hp: u16,
     pub attack: u16,
@@ -66,32 +87,121 @@ pub struct Pokemon {
     pub catch_rate: Option<u16>,
     pub base_friendship: Option<u16>,
     pub base_experience: Option<u16>,
-    pub growth_rate: String,
+    #[serde(deserialize_with = "intern::deserialize")]
+    pub growth_rate: Arc<str>,
     pub egg_type_number: u8,
-    pub egg_type_1: String,
-    pub egg_type_2: String,
+    #[serde(deserialize_with = "intern::deserialize")]
+    pub egg_type_1: Arc<str>,
+    #[serde(deserialize_with = "intern::deserialize")]
+    pub egg_type_2: Arc<str>,
     pub percentage_male: Option<f32>,
     pub egg_cycles: Option<u16>,
 }
 
+/// Former typings by National Pokédex number, with the last generation
+/// each applied in.
+const PAST_TYPES: [(u16, u8, &[Type]); 24] = [
+    (35, 5, &[Type::Normal]),
+    (36, 5, &[Type::Normal]),
+    (39, 5, &[Type::Normal]),
+    (40, 5, &[Type::Normal]),
+    (81, 1, &[Type::Electric]),
+    (82, 1, &[Type::Electric]),
+    (122, 5, &[Type::Psychic]),
+    (173, 5, &[Type::Normal]),
+    (174, 5, &[Type::Normal]),
+    (175, 5, &[Type::Normal]),
+    (176, 5, &[Type::Normal, Type::Flying]),
+    (183, 5, &[Type::Water]),
+    (184, 5, &[Type::Water]),
+    (209, 5, &[Type::Normal]),
+    (210, 5, &[Type::Normal]),
+    (280, 5, &[Type::Psychic]),
+    (281, 5, &[Type::Psychic]),
+    (282, 5, &[Type::Psychic]),
+    (298, 5, &[Type::Normal]),
+    (303, 5, &[Type::Steel]),
+    (439, 5, &[Type::Psychic]),
+    (468, 5, &[Type::Normal, Type::Flying]),
+    (546, 5, &[Type::Grass]),
+    (547, 5, &[Type::Grass]),
+];
+
 impl Pokemon {
+    pub fn types(&self) -> Vec<Type> {
+        [&self.type_1, &self.type_2]
+            .iter()
+            .filter_map(|name| Type::from_name(name))
+            .collect()
+    }
+
+    /// Types as they were in `generation`, for the species whose typing
+    /// changed later (Magnemite gained Steel in Gen 2, and the Fairy type
+    /// retyped a few in Gen 6). Forms that debuted after the change keep
+    /// their own typing.
+    pub fn types_in(&self, generation: u8) -> Vec<Type> {
+        PAST_TYPES
+            .iter()
+            .find(|(number, last_generation, _)| {
+                *number == self.pokedex_number
+                    && generation <= *last_generation
+                    && self.introduced_in() <= *last_generation
+            })
+            .map_or_else(|| self.types(), |(_, _, types)| types.to_vec())
+    }
+
+    /// The generation this entry was introduced in. Unlike `generation`,
+    /// which is the species', forms count from their own debut, e.g. 7 for
+    /// Alolan Rattata.
+    pub fn introduced_in(&self) -> u8 {
+        let form_generation = match self.name.split(' ').next() {
+            Some("Mega") | Some("Primal") => 6,
+            Some("Alolan") | Some("Partner") => 7,
+            Some("Galarian") => 8,
+            _ => 0,
+        };
+        self.generation.max(form_generation)
+    }
+
+    /// The region of a regional form, e.g. `Alolan` for Alolan Rattata.
+    /// Regional forms are kept apart from their species where other forms
+    /// (e.g. Megas) aren't, since they have their own typing and debut.
+    pub fn regional_form(&self) -> Option<&str> {
+        self.name
+            .split(' ')
+            .next()
+            .filter(|word| ["Alolan", "Galarian", "Hisuian", "Paldean"].contains(word))
+    }
+
+    /// Damage multiplier of each attacking type against this Pokémon, from
+    /// most to least effective. Abilities (e.g. Levitate) aren't considered.
+    pub fn type_defenses(&self) -> Vec<(Type, f32)> {
+        let types = self.types();
+        let mut defenses = Type::ALL
+            .iter()
+            .map(|attacker| (*attacker, attacker.effectiveness_against(&types)))
+            .collect::<Vec<(Type, f32)>>();
+        defenses.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
+        defenses
+    }
+
     pub fn egg_cycle_stats(&self) -> Option<EggCycleStats> {
-        self.egg_cycles.map(|cycles| EggCycleStats::new(cycles))
+        self.egg_cycles.map(EggCycleStats::new)
     }
 
-    fn sprite_name_slug(&self) -> String {
-        let mega_re = Regex::new("^mega-(?P<name>.+?)(?P<xy>-x|-y)?$").unwrap();
-        let n = self
-            .name
-            .to_lowercase()
-            .replace(" ", "-")
-            .replace(".", "")
-            .replace(":", "")
-            .replace("'", "")
-            .replace("é", "e") // TODO: do this for all types
-            .replace("♀", "-f")
-            .replace("♂", "-m");
-        mega_re.replace(&n, "$name-mega$xy").to_string()
+    /// Canonical identifier, e.g. `charizard-mega-x` or `vulpix-alola`,
+    /// matching the sprite repository's (and mostly PokéAPI's) naming.
+    pub fn sprite_name_slug(&self) -> String {
+        static MEGA_RE: OnceLock<Regex> = OnceLock::new();
+        static REGIONAL_RE: OnceLock<Regex> = OnceLock::new();
+        let mega_re =
+            MEGA_RE.get_or_init(|| Regex::new("^mega-(?P<name>.+?)(?P<xy>-x|-y)?$").unwrap());
+        let regional_re = REGIONAL_RE.get_or_init(|| {
+            Regex::new("^(?P<region>alola|galar|hisui)(n|an|ian)-(?P<name>.+)$").unwrap()
+        });
+        let n = slug(&self.name);
+        let n = mega_re.replace(&n, "$name-mega$xy");
+        regional_re.replace(&n, "$name-$region").to_string()
     }
 
     pub fn sprite_url(&self) -> String {
@@ -102,57 +212,516 @@ impl Pokemon {
     }
 }
 
-pub struct MatchScore {
-    pub distance: usize,
-    pub similarity: f64,
+/// A name as lowercase words joined by hyphens, without punctuation.
+fn slug(name: &str) -> String {
+    name.to_lowercase()
+        .replace(" ", "-")
+        .replace(".", "")
+        .replace(":", "")
+        .replace("'", "")
+        .replace("é", "e") // TODO: do this for all types
+        .replace("♀", "-f")
+        .replace("♂", "-m")
+}
+
+pub struct PokeMatch {
+    pub pokemon: Pokemon,
+    pub score: MatchScore,
+}
+
+pub static POKEDEX_CSV: &[u8] = include_bytes!("../data/pokedex.csv");
+
+static POKEDEX: OnceLock<Vec<Pokemon>> = OnceLock::new();
+
+/// Fingerprint of the Pokédex used instead of the embedded one, if any.
+static REPLACED_VERSION: OnceLock<String> = OnceLock::new();
+
+pub(crate) fn load() -> Result<Vec<Pokemon>, PkmnError> {
+    error::read_csv("pokedex.csv", POKEDEX_CSV)
+}
+
+/// Every Pokémon, in Pokédex order. The embedded CSV is parsed on first use
+/// and shared from then on.
+pub fn entries() -> Result<&'static [Pokemon], PkmnError> {
+    if let Some(entries) = POKEDEX.get() {
+        return Ok(entries);
+    }
+    let entries = load()?;
+    Ok(POKEDEX.get_or_init(|| entries))
+}
+
+/// The entries lookups go through, which find nothing if the Pokédex doesn't
+/// read; [`entries`] says why.
+fn loaded() -> &'static [Pokemon] {
+    entries().unwrap_or_default()
+}
+
+/// Uses `entries` instead of the embedded Pokédex, e.g. a newer dataset
+/// the user downloaded, identified by `version` (see
+/// [`crate::provenance::fingerprint`]). Only possible before the Pokédex is
+/// first used, so the entries are handed back otherwise.
+pub fn replace_entries(entries: Vec<Pokemon>, version: String) -> Result<(), Vec<Pokemon>> {
+    POKEDEX.set(entries)?;
+    REPLACED_VERSION.get_or_init(|| version);
+    Ok(())
+}
+
+/// The version given to [`replace_entries`], if the embedded Pokédex was
+/// replaced.
+pub fn replaced_version() -> Option<&'static str> {
+    REPLACED_VERSION.get().map(String::as_str)
+}
+
+/// An owned copy of [`entries`].
+pub fn all_pokemon() -> Result<Vec<Pokemon>, PkmnError> {
+    entries().map(<[Pokemon]>::to_vec)
+}
+
+/// Other entries sharing the Pokémon's National Pokédex number, e.g. Mega
+/// Evolutions and regional variants.
+pub fn forms_of(pokemon: &Pokemon) -> Vec<Pokemon> {
+    loaded()
+        .iter()
+        .filter(|form| form.pokedex_number == pokemon.pokedex_number && form.name != pokemon.name)
+        .cloned()
+        .collect()
+}
+
+/// The species' base form, which forms like Mega Evolutions and regional
+/// variants share trivia with. The Pokémon itself if it's the base form.
+pub fn species_of(pokemon: &Pokemon) -> Option<&'static Pokemon> {
+    loaded()
+        .iter()
+        .find(|entry| entry.pokedex_number == pokemon.pokedex_number)
+}
+
+/// Finds a Pokémon by its exact slug (see [`Pokemon::sprite_name_slug`]) or
+/// National Pokédex number, without any fuzzy matching. The slug of the name
+/// as written, e.g. `alolan-vulpix`, is accepted too, as older versions used
+/// those for regional variants.
+pub fn find_by_id(id: &str) -> Option<Pokemon> {
+    let id = id.trim().to_lowercase();
+
+    match id.parse::<u16>() {
+        Ok(number) => loaded()
+            .iter()
+            .find(|pokemon| pokemon.pokedex_number == number),
+        Err(_) => loaded()
+            .iter()
+            .find(|pokemon| pokemon.sprite_name_slug() == id)
+            .or_else(|| loaded().iter().find(|pokemon| slug(&pokemon.name) == id)),
+    }
+    .cloned()
+}
+
+/// The form's name within its species, e.g. `Alolan` for Alolan Vulpix,
+/// `Mega X` for Mega Charizard X or `Attack` for Deoxys Attack Forme: the
+/// words of its name the species' other forms don't share. `None` for
+/// Pokémon with one form, and base forms named after the species alone.
+pub fn form_name(pokemon: &Pokemon) -> Option<String> {
+    let species = loaded()
+        .iter()
+        .filter(|entry| entry.pokedex_number == pokemon.pokedex_number)
+        .collect::<Vec<&Pokemon>>();
+    let form = pokemon
+        .name
+        .split(' ')
+        .filter(|word| {
+            !species
+                .iter()
+                .all(|entry| entry.name.split(' ').any(|other| other == *word))
+        })
+        .collect::<Vec<&str>>()
+        .join(" ");
+    Some(form).filter(|form| !form.is_empty())
+}
+
+/// What `--form` takes to pick out the form, e.g. `alolan` or `mega-x`.
+pub fn form_slug(pokemon: &Pokemon) -> Option<String> {
+    form_name(pokemon).map(|form| slug(&form))
+}
+
+/// The form of the Pokémon's species named `query`, e.g. `alolan`, `mega-x`
+/// or `therian`. A form whose name starts with `query` is taken when none
+/// matches it exactly, so `alola` finds Alolan forms too.
+pub fn find_form(pokemon: &Pokemon, query: &str) -> Option<Pokemon> {
+    let compact = |slug: &str| slug.replace('-', "");
+    let query = compact(&slug(query.trim()));
+    let forms = loaded()
+        .iter()
+        .filter(|entry| entry.pokedex_number == pokemon.pokedex_number)
+        .filter_map(|entry| Some((entry, compact(&form_slug(entry)?))))
+        .collect::<Vec<(&Pokemon, String)>>();
+    forms
+        .iter()
+        .find(|(_, form)| *form == query)
+        .or_else(|| forms.iter().find(|(_, form)| form.starts_with(&query)))
+        .map(|(entry, _)| (*entry).clone())
+}
+
+/// How a [`StatCondition`] compares a stat with its value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+    GreaterOrEqual,
+    Greater,
+}
+
+impl Comparison {
+    /// Operators, longest first so `>=` isn't read as `>`.
+    const OPERATORS: [(&'static str, Comparison); 7] = [
+        ("<=", Comparison::LessOrEqual),
+        (">=", Comparison::GreaterOrEqual),
+        ("==", Comparison::Equal),
+        ("!=", Comparison::NotEqual),
+        ("<", Comparison::Less),
+        (">", Comparison::Greater),
+        ("=", Comparison::Equal),
+    ];
+
+    fn holds<T: PartialOrd>(&self, left: T, right: T) -> bool {
+        match self {
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+            Comparison::Equal => left == right,
+            Comparison::NotEqual => left != right,
+            Comparison::GreaterOrEqual => left >= right,
+            Comparison::Greater => left > right,
+        }
+    }
+}
+
+/// Names a [`StatCondition`] can compare, as in `list --sort`.
+pub const STAT_NAMES: [&str; 7] = [
+    "hp",
+    "attack",
+    "defense",
+    "sp-attack",
+    "sp-defense",
+    "speed",
+    "total",
+];
+
+/// What a [`StatCondition`] compares.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Subject {
+    Stat(Stat),
+    Total,
+    /// A computed field, e.g. `bulk` defined as `hp * defense`. See
+    /// [`crate::computed`].
+    Field {
+        name: String,
+        expression: String,
+    },
+}
+
+/// A base stat or computed field compared with a value, e.g. `speed>=100`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatCondition {
+    pub subject: Subject,
+    pub comparison: Comparison,
+    pub value: f64,
 }
 
-impl MatchScore {
-    pub fn new(value: &str, query: &str) -> Self {
-        MatchScore {
-            distance: strsim::levenshtein(value, query),
-            similarity: strsim::jaro_winkler(value, query),
+impl StatCondition {
+    /// Parses a stat or one of the computed `fields` (name to expression),
+    /// an operator (`<`, `<=`, `=`, `!=`, `>=` or `>`) and a value, e.g.
+    /// `speed>=100`, `spa > 120`, `total<=500` or `bulk>10000`.
+    pub fn parse(term: &str, fields: &BTreeMap<String, String>) -> Result<Self, String> {
+        let (start, operator, comparison) = Comparison::OPERATORS
+            .iter()
+            .filter_map(|(operator, comparison)| {
+                term.find(operator)
+                    .map(|start| (start, *operator, *comparison))
+            })
+            .min_by_key(|(start, operator, _)| (*start, Reverse(operator.len())))
+            .ok_or_else(|| {
+                format!(
+                    "Expected a comparison in \"{}\", e.g. speed>=100",
+                    term.trim()
+                )
+            })?;
+        let name = term[..start].trim();
+        let value = term[start + operator.len()..].trim();
+
+        let subject = match (name.to_lowercase().as_str(), Stat::from_name(name)) {
+            ("total" | "bst", _) => Subject::Total,
+            (_, Some(stat)) => Subject::Stat(stat),
+            _ => match fields.get(name) {
+                Some(expression) => Subject::Field {
+                    name: name.to_string(),
+                    expression: expression.clone(),
+                },
+                None => {
+                    let names = STAT_NAMES
+                        .iter()
+                        .copied()
+                        .chain(fields.keys().map(String::as_str));
+                    return Err(match fuzzy::did_you_mean(names, name) {
+                        Some(suggestion) => format!(
+                            "Unknown stat \"{}\", did you mean \"{}\"?",
+                            name, suggestion
+                        ),
+                        None => format!("Unknown stat \"{}\"", name),
+                    });
+                }
+            },
+        };
+        let value = value
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| format!("\"{}\" isn't a stat value", value))?;
+        Ok(StatCondition {
+            subject,
+            comparison,
+            value,
+        })
+    }
+
+    /// The compared value for `pokemon`, if a computed field evaluates to a
+    /// number for it.
+    fn subject_value(&self, pokemon: &Pokemon) -> Option<f64> {
+        match &self.subject {
+            Subject::Stat(stat) => Some(stat.base(pokemon).into()),
+            Subject::Total => Some(pokemon.total_points.into()),
+            Subject::Field { expression, .. } => computed::evaluate(pokemon, expression)
+                .ok()?
+                .as_number()
+                .ok(),
         }
     }
 
-    fn compare(a: &MatchScore, b: &MatchScore) -> Ordering {
-        b.similarity
-            .partial_cmp(&a.similarity)
-            .unwrap()
-            .then(a.distance.cmp(&b.distance))
+    /// Whether `pokemon` meets the condition. Pokémon a computed field can't
+    /// be evaluated for never do.
+    pub fn matches(&self, pokemon: &Pokemon) -> bool {
+        self.subject_value(pokemon)
+            .is_some_and(|value| self.comparison.holds(value, self.value))
+    }
+
+    /// Clears `keep[i]` for each of `entries` that doesn't meet the
+    /// condition, reading stats from `columns` (built from the same
+    /// entries) rather than from each entry.
+    fn retain(&self, entries: &[Pokemon], columns: &Columns, keep: &mut [bool]) {
+        let column = match self.subject {
+            Subject::Stat(stat) => columns.stat_by_entry(stat),
+            Subject::Total => columns.totals_by_entry(),
+            Subject::Field { .. } => {
+                for (keep, pokemon) in keep.iter_mut().zip(entries) {
+                    *keep = *keep && self.matches(pokemon);
+                }
+                return;
+            }
+        };
+        for (keep, base) in keep.iter_mut().zip(column) {
+            *keep = *keep && self.comparison.holds(f64::from(*base), self.value);
+        }
     }
 }
 
-pub struct PokeMatch {
-    pub pokemon: Pokemon,
-    pub score: MatchScore,
+/// Constraints on which Pokémon a search or listing returns. The default
+/// filter matches every Pokémon.
+#[derive(Default)]
+pub struct Filter {
+    /// Types the Pokémon must have, e.g. `[Fire, Flying]` for Charizard.
+    pub types: Vec<Type>,
+    /// Generations the Pokémon (or form) must have been introduced in.
+    pub generations: Option<RangeInclusive<u8>>,
+    /// Conditions the Pokémon's base stats or computed fields must all meet.
+    pub stats: Vec<StatCondition>,
 }
 
-static POKEDEX_CSV: &[u8] = include_bytes!("../data/pokedex.csv");
+impl Filter {
+    /// A filter from the command line's `--type`, `--gen` and `--where`
+    /// values, with `--where` able to use the computed `fields` (name to
+    /// expression).
+    pub fn parse(
+        types: Option<&str>,
+        generations: Option<&str>,
+        stats: Option<&str>,
+        fields: &BTreeMap<String, String>,
+    ) -> Result<Self, String> {
+        Ok(Filter {
+            types: match types {
+                Some(value) => Filter::parse_types(value)?,
+                None => Vec::new(),
+            },
+            generations: match generations {
+                Some(value) => Some(Filter::parse_generations(value)?),
+                None => None,
+            },
+            stats: match stats {
+                Some(value) => Filter::parse_stats(value, fields)?,
+                None => Vec::new(),
+            },
+        })
+    }
 
-pub fn search_by_name(query: &str, limit: usize) -> Vec<PokeMatch> {
-    let search_query = query.to_lowercase();
-    let mut results = Vec::new();
-    let mut csv_reader = csv::Reader::from_reader(POKEDEX_CSV);
-
-    for result in csv_reader.deserialize() {
-        let pokemon: Pokemon = result.unwrap();
-        let match_score = MatchScore::new(&pokemon.name.to_lowercase(), &search_query);
-        results.push(PokeMatch {
-            pokemon,
-            score: match_score,
-        });
+    /// Parses stat conditions joined by `&&`, e.g.
+    /// `speed>=100 && attack>120`. See [`StatCondition::parse`].
+    pub fn parse_stats(
+        value: &str,
+        fields: &BTreeMap<String, String>,
+    ) -> Result<Vec<StatCondition>, String> {
+        if value.contains("||") {
+            return Err(String::from("Stat conditions can only be combined with &&"));
+        }
+        value
+            .split("&&")
+            .map(|term| StatCondition::parse(term, fields))
+            .collect()
+    }
+
+    /// Parses a generation, e.g. `1`, an inclusive range, e.g. `1-3`, or a
+    /// comparison, e.g. `<=3` or `>5`.
+    pub fn parse_generations(value: &str) -> Result<RangeInclusive<u8>, String> {
+        let latest = latest_generation();
+        let parse = |generation: &str| match generation.trim().parse::<u8>() {
+            Ok(generation) if (1..=latest).contains(&generation) => Ok(generation),
+            _ => Err(format!("Unknown generation \"{}\"", generation.trim())),
+        };
+        let value = value.trim();
+        let comparison = if let Some(last) = value.strip_prefix("<=") {
+            Some((1, parse(last)?))
+        } else if let Some(first) = value.strip_prefix(">=") {
+            Some((parse(first)?, latest))
+        } else if let Some(last) = value.strip_prefix('<') {
+            Some((1, parse(last)? - 1))
+        } else if let Some(first) = value.strip_prefix('>') {
+            Some((parse(first)? + 1, latest))
+        } else {
+            None
+        };
+
+        match comparison {
+            Some((first, last)) if first > last => Err(format!("No generations are {}", value)),
+            Some((first, last)) => Ok(first..=last),
+            None => {
+                let (first, last) = match value.split_once('-') {
+                    Some((first, last)) => (parse(first)?, parse(last)?),
+                    None => (parse(value)?, parse(value)?),
+                };
+                if first > last {
+                    return Err(format!("Generation range {}-{} is backwards", first, last));
+                }
+                Ok(first..=last)
+            }
+        }
+    }
+
+    /// Parses a filter written as one value, for commands that take it that
+    /// way: `key:value` terms separated by spaces, e.g. `gen:<=3 type:fire`.
+    pub fn parse_query(query: &str) -> Result<Self, String> {
+        let mut filter = Filter::default();
+        for term in query.split_whitespace() {
+            let (key, value) = term.split_once(':').unwrap_or((term, ""));
+            match key.to_lowercase().as_str() {
+                "gen" | "generation" => {
+                    filter.generations = Some(Filter::parse_generations(value)?)
+                }
+                "type" => filter.types = Filter::parse_types(value)?,
+                _ => {
+                    return Err(format!(
+                        "Unknown filter \"{}\", expected e.g. gen:<=3 or type:fire",
+                        term
+                    ))
+                }
+            }
+        }
+        Ok(filter)
+    }
+
+    /// Parses a comma-separated list of one or two types, e.g. `fire,flying`.
+    pub fn parse_types(value: &str) -> Result<Vec<Type>, String> {
+        let types = value
+            .split(',')
+            .map(|name| {
+                Type::from_name(name).ok_or_else(|| {
+                    let names = Type::ALL.iter().map(Type::name);
+                    match fuzzy::did_you_mean(names, name.trim()) {
+                        Some(suggestion) => format!(
+                            "Unknown type \"{}\", did you mean \"{}\"?",
+                            name.trim(),
+                            suggestion
+                        ),
+                        None => format!("Unknown type \"{}\"", name.trim()),
+                    }
+                })
+            })
+            .collect::<Result<Vec<Type>, String>>()?;
+
+        if types.len() > 2 {
+            return Err(String::from("Pokémon have at most two types"));
+        }
+        Ok(types)
     }
 
-    results.sort_by(|a, b| MatchScore::compare(&a.score, &b.score));
-    results.truncate(limit);
-    results
+    pub fn matches(&self, pokemon: &Pokemon) -> bool {
+        self.matches_kind(pokemon)
+            && self
+                .stats
+                .iter()
+                .all(|condition| condition.matches(pokemon))
+    }
+
+    /// Whether the Pokémon's types and generation match, leaving out the
+    /// stat conditions.
+    fn matches_kind(&self, pokemon: &Pokemon) -> bool {
+        let types = pokemon.types();
+        let generation = pokemon.introduced_in();
+        self.types.iter().all(|t| types.contains(t))
+            && self
+                .generations
+                .as_ref()
+                .is_none_or(|generations| generations.contains(&generation))
+    }
+}
+
+/// Every Pokémon the filter matches, in Pokédex order. Stat conditions are
+/// checked a column at a time against [`Columns::pokedex`].
+pub fn filter(filter: &Filter) -> Vec<Pokemon> {
+    let entries = loaded();
+    let mut keep = vec![true; entries.len()];
+    for condition in &filter.stats {
+        condition.retain(entries, Columns::pokedex(), &mut keep);
+    }
+    entries
+        .iter()
+        .zip(keep)
+        .filter(|(pokemon, keep)| *keep && filter.matches_kind(pokemon))
+        .map(|(pokemon, _)| pokemon.clone())
+        .collect()
+}
+
+pub fn search_by_name(query: &str, limit: usize) -> Vec<PokeMatch> {
+    search_by_name_filtered(query, limit, &Filter::default())
+}
+
+/// Fuzzy search by name among the Pokémon the filter matches.
+pub fn search_by_name_filtered(query: &str, limit: usize, filter: &Filter) -> Vec<PokeMatch> {
+    let candidates = loaded().iter().filter(|pokemon| filter.matches(pokemon));
+    fuzzy::search(candidates, |pokemon| pokemon.name.clone(), query, limit)
+        .into_iter()
+        .map(|Match { value, score }| PokeMatch {
+            pokemon: value.clone(),
+            score,
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn parses_once() {
+        assert!(std::ptr::eq(entries().unwrap(), entries().unwrap()));
+        assert_eq!(all_pokemon().unwrap().len(), entries().unwrap().len());
+    }
+
     #[test]
     fn single_match() {
         let results = search_by_name("x", 1);
@@ -173,6 +742,181 @@ mod tests {
         assert_eq!(result.score.distance, 0);
     }
 
+    #[test]
+    fn parse_types() {
+        assert_eq!(
+            Filter::parse_types("fire, Flying"),
+            Ok(vec![Type::Fire, Type::Flying])
+        );
+        assert_eq!(
+            Filter::parse_types("fyre"),
+            Err(String::from(
+                "Unknown type \"fyre\", did you mean \"Fire\"?"
+            ))
+        );
+        assert!(Filter::parse_types("fire,water,grass").is_err());
+    }
+
+    #[test]
+    fn filtered_search() {
+        let water = Filter {
+            types: vec![Type::Water],
+            ..Filter::default()
+        };
+        let result = &search_by_name_filtered("charizard", 1, &water)[0];
+        assert!(result.pokemon.types().contains(&Type::Water));
+
+        let dual = Filter {
+            types: vec![Type::Fire, Type::Flying],
+            ..Filter::default()
+        };
+        let names = filter(&dual)
+            .into_iter()
+            .map(|pokemon| pokemon.name)
+            .collect::<Vec<String>>();
+        assert!(names.contains(&String::from("Charizard")));
+        assert!(!names.contains(&String::from("Charmander")));
+    }
+
+    #[test]
+    fn parse_generations() {
+        assert_eq!(Filter::parse_generations("1"), Ok(1..=1));
+        assert_eq!(Filter::parse_generations("1-3"), Ok(1..=3));
+        assert!(Filter::parse_generations("3-1").is_err());
+        assert!(Filter::parse_generations("0").is_err());
+        assert!(Filter::parse_generations("kanto").is_err());
+        assert_eq!(Filter::parse_generations("<=3"), Ok(1..=3));
+        assert_eq!(Filter::parse_generations("<3"), Ok(1..=2));
+        assert_eq!(Filter::parse_generations(">=7"), Ok(7..=9));
+        assert_eq!(Filter::parse_generations(">8"), Ok(9..=9));
+        assert_eq!(Filter::parse_generations("9"), Ok(9..=9));
+        assert!(Filter::parse_generations("<1").is_err());
+        assert!(Filter::parse_generations(">9").is_err());
+        assert!(Filter::parse_generations("10").is_err());
+    }
+
+    #[test]
+    fn parses_stat_conditions() {
+        assert_eq!(
+            Filter::parse_stats("speed>=100 && SpA > 120", &BTreeMap::new()),
+            Ok(vec![
+                StatCondition {
+                    subject: Subject::Stat(Stat::Speed),
+                    comparison: Comparison::GreaterOrEqual,
+                    value: 100.0,
+                },
+                StatCondition {
+                    subject: Subject::Stat(Stat::SpAttack),
+                    comparison: Comparison::Greater,
+                    value: 120.0,
+                },
+            ])
+        );
+        let total = StatCondition::parse("total != 600", &BTreeMap::new()).unwrap();
+        assert_eq!(
+            (total.subject, total.comparison),
+            (Subject::Total, Comparison::NotEqual)
+        );
+        assert_eq!(
+            StatCondition::parse("hp=1", &BTreeMap::new())
+                .unwrap()
+                .comparison,
+            Comparison::Equal
+        );
+
+        assert_eq!(
+            StatCondition::parse("sped>100", &BTreeMap::new()),
+            Err(String::from(
+                "Unknown stat \"sped\", did you mean \"speed\"?"
+            ))
+        );
+        assert!(StatCondition::parse("speed 100", &BTreeMap::new()).is_err());
+        assert!(StatCondition::parse("speed>fast", &BTreeMap::new()).is_err());
+        assert!(Filter::parse_stats("speed>100 &&", &BTreeMap::new()).is_err());
+        assert!(Filter::parse_stats("speed>100 || attack>100", &BTreeMap::new()).is_err());
+    }
+
+    #[test]
+    fn filters_by_stats() {
+        let fast = Filter::parse(
+            None,
+            Some("1"),
+            Some("speed>=120 && attack>100"),
+            &BTreeMap::new(),
+        )
+        .unwrap();
+        let names = filter(&fast)
+            .into_iter()
+            .map(|pokemon| pokemon.name)
+            .collect::<Vec<String>>();
+        assert_eq!(names, ["Aerodactyl", "Mewtwo"]);
+        let strong = Filter::parse(None, None, Some("total>1125"), &BTreeMap::new()).unwrap();
+        assert!(filter(&strong).is_empty());
+    }
+
+    #[test]
+    fn filters_by_computed_fields() {
+        let fields = BTreeMap::from([(String::from("bulk"), String::from("hp * defense"))]);
+        let bulky = Filter::parse(None, Some("1"), Some("bulk >= 10450"), &fields).unwrap();
+        let names = filter(&bulky)
+            .into_iter()
+            .map(|pokemon| pokemon.name)
+            .collect::<Vec<String>>();
+        assert_eq!(names, ["Slowbro", "Rhydon"]);
+        assert!(bulky.matches(&find_by_id("rhydon").unwrap()));
+        assert!(!bulky.matches(&find_by_id("pikachu").unwrap()));
+
+        assert_eq!(
+            StatCondition::parse("blk>1", &fields),
+            Err(String::from("Unknown stat \"blk\", did you mean \"bulk\"?"))
+        );
+    }
+
+    #[test]
+    fn parses_queries() {
+        let filter = Filter::parse_query("gen:<=3  TYPE:fire,flying").unwrap();
+        assert_eq!(filter.generations, Some(1..=3));
+        assert_eq!(filter.types, vec![Type::Fire, Type::Flying]);
+        assert!(Filter::parse_query("").unwrap().generations.is_none());
+        assert!(Filter::parse_query("color:red").is_err());
+        assert!(Filter::parse_query("gen:kanto").is_err());
+    }
+
+    #[test]
+    fn filters_forms_by_their_own_generation() {
+        let kanto = Filter::parse(None, Some("1"), None, &BTreeMap::new()).unwrap();
+        assert!(filter(&kanto)
+            .iter()
+            .all(|pokemon| !pokemon.name.starts_with("Alolan")));
+        assert_eq!(find_by_id("charizard-mega-x").unwrap().introduced_in(), 6);
+
+        let names =
+            filter(&Filter::parse(Some("fire"), Some("1-2"), None, &BTreeMap::new()).unwrap())
+                .into_iter()
+                .map(|pokemon| pokemon.name)
+                .collect::<Vec<String>>();
+        assert!(names.contains(&String::from("Typhlosion")));
+        assert!(!names.contains(&String::from("Blaziken")));
+    }
+
+    #[test]
+    fn regional_forms() {
+        let form = |id| find_by_id(id).unwrap().regional_form().map(String::from);
+        assert_eq!(form("vulpix-alola").as_deref(), Some("Alolan"));
+        assert_eq!(form("vulpix"), None);
+        assert_eq!(form("charizard-mega-x"), None);
+    }
+
+    #[test]
+    fn type_defenses() {
+        let gyarados = find_by_id("gyarados").unwrap();
+        let defenses = gyarados.type_defenses();
+        assert_eq!(defenses.len(), Type::ALL.len());
+        assert_eq!(defenses[0], (Type::Electric, 4.0));
+        assert!(defenses.contains(&(Type::Ground, 0.0)));
+        assert!(defenses.contains(&(Type::Fighting, 0.5)));
+    }
+
     #[test]
     fn close_match() {
         let result = &search_by_name("charzad", 1)[0];
@@ -189,22 +933,109 @@ mod tests {
         assert_eq!(result.score.distance, 5);
     }
 
+    #[test]
+    fn matches_exhaustive_search() {
+        for query in ["x", "char", "mr mime", "garchomp", "pikachu", "zzz"] {
+            let mut expected = all_pokemon()
+                .unwrap()
+                .into_iter()
+                .map(|pokemon| MatchScore::new(&pokemon.name.to_lowercase(), query))
+                .collect::<Vec<MatchScore>>();
+            expected.sort_by(MatchScore::compare);
+
+            let results = search_by_name(query, 10);
+            for (result, expected) in results.iter().zip(expected) {
+                assert_eq!(result.score.similarity, expected.similarity);
+                assert_eq!(result.score.distance, expected.distance);
+            }
+        }
+    }
+
+    #[test]
+    fn zero_limit() {
+        assert!(search_by_name("x", 0).is_empty());
+    }
+
+    #[test]
+    fn find_by_slug() {
+        let pokemon = find_by_id("charizard-mega-x").unwrap();
+        assert_eq!(pokemon.name, "Mega Charizard X");
+    }
+
+    #[test]
+    fn find_by_pokedex_number() {
+        let pokemon = find_by_id("25").unwrap();
+        assert_eq!(pokemon.name, "Pikachu");
+    }
+
+    #[test]
+    fn find_by_unknown_id() {
+        assert!(find_by_id("charzard").is_none());
+    }
+
+    #[test]
+    fn forms() {
+        let charizard = search_by_name("charizard", 1).remove(0).pokemon;
+        let names = forms_of(&charizard)
+            .into_iter()
+            .map(|form| form.name)
+            .collect::<Vec<String>>();
+        assert_eq!(names, vec!["Mega Charizard X", "Mega Charizard Y"]);
+    }
+
+    #[test]
+    fn form_names() {
+        let form = |id: &str| form_name(&find_by_id(id).unwrap());
+        assert_eq!(form("vulpix"), None);
+        assert_eq!(form("bulbasaur"), None);
+        assert_eq!(form("vulpix-alola").as_deref(), Some("Alolan"));
+        assert_eq!(form("charizard-mega-x").as_deref(), Some("Mega X"));
+        assert_eq!(form("386").as_deref(), Some("Normal"));
+        assert_eq!(form("heat-rotom").as_deref(), Some("Heat"));
+        assert_eq!(
+            form("darmanitan-zen-mode-galar").as_deref(),
+            Some("Galarian Zen")
+        );
+    }
+
+    #[test]
+    fn finds_forms() {
+        let vulpix = find_by_id("vulpix").unwrap();
+        let form = |query: &str| find_form(&vulpix, query).map(|form| form.name);
+        assert_eq!(form("alolan").as_deref(), Some("Alolan Vulpix"));
+        assert_eq!(form("Alola").as_deref(), Some("Alolan Vulpix"));
+        assert_eq!(form("galarian"), None);
+
+        let charizard = find_by_id("charizard").unwrap();
+        assert_eq!(
+            find_form(&charizard, "megay").unwrap().name,
+            "Mega Charizard Y"
+        );
+        let landorus = search_by_name("landorus", 1).remove(0).pokemon;
+        assert_eq!(
+            find_form(&landorus, "therian").unwrap().name,
+            "Landorus Therian Forme"
+        );
+    }
+
     impl Pokemon {
         fn default() -> Self {
             Pokemon {
                 pokedex_number: 0,
                 name: "".to_string(),
+                german_name: "".to_string(),
+                japanese_name: "".to_string(),
                 generation: 0,
                 status: PokemonStatus::Normal,
-                species: "".to_string(),
-                type_1: "".to_string(),
-                type_2: "".to_string(),
+                species: Arc::from(""),
+                type_1: Arc::from(""),
+                type_2: Arc::from(""),
                 height_m: None,
                 weight_kg: None,
                 abilities_number: 0,
-                ability_1: "".to_string(),
-                ability_2: "".to_string(),
-                ability_hidden: "".to_string(),
+                ability_1: Arc::from(""),
+                ability_2: Arc::from(""),
+                ability_hidden: Arc::from(""),
                 total_points: 0,
                 hp: 0,
                 attack: 0,
@@ -215,10 +1046,10 @@ mod tests {
                 catch_rate: None,
                 base_friendship: None,
                 base_experience: None,
-                growth_rate: "".to_string(),
+                growth_rate: Arc::from(""),
                 egg_type_number: 0,
-                egg_type_1: "".to_string(),
-                egg_type_2: "".to_string(),
+                egg_type_1: Arc::from(""),
+                egg_type_2: Arc::from(""),
                 percentage_male: None,
                 egg_cycles: None,
             }
@@ -315,6 +1146,22 @@ mod tests {
         assert_eq!(pkmn.sprite_name_slug(), "mewtwo-mega-y");
     }
 
+    #[test]
+    fn sprite_name_slug_regional() {
+        for (name, slug) in [
+            ("Alolan Vulpix", "vulpix-alola"),
+            ("Galarian Mr. Mime", "mr-mime-galar"),
+            ("Hisuian Growlithe", "growlithe-hisui"),
+        ] {
+            let pkmn = Pokemon {
+                name: String::from(name),
+                ..Pokemon::default()
+            };
+            assert_eq!(pkmn.sprite_name_slug(), slug);
+        }
+        assert_eq!(find_by_id("alolan-vulpix").unwrap().name, "Alolan Vulpix");
+    }
+
     #[test]
     fn egg_cycle_stats() {
         let stats = EggCycleStats::new(17);
@@ -322,45 +1169,4 @@ mod tests {
         assert_eq!(stats.max_steps, 4369);
         assert_eq!(stats.min_steps, 4113);
     }
-
-    #[test]
-    fn match_compare_equal() {
-        let a = MatchScore {
-            distance: 1,
-            similarity: 1.0,
-        };
-        let b = MatchScore {
-            distance: 1,
-            similarity: 1.0,
-        };
-        assert_eq!(MatchScore::compare(&a, &b), Ordering::Equal);
-    }
-
-    #[test]
-    fn match_compare_similarity_is_highest_priority() {
-        let a = MatchScore {
-            distance: 1,
-            similarity: 0.75,
-        };
-        let b = MatchScore {
-            distance: 2,
-            similarity: 0.25,
-        };
-        assert_eq!(MatchScore::compare(&a, &b), Ordering::Less);
-        assert_eq!(MatchScore::compare(&b, &a), Ordering::Greater);
-    }
-
-    #[test]
-    fn match_compare_distance_is_tie_breaker() {
-        let a = MatchScore {
-            distance: 2,
-            similarity: 0.5,
-        };
-        let b = MatchScore {
-            distance: 1,
-            similarity: 0.5,
-        };
-        assert_eq!(MatchScore::compare(&b, &a), Ordering::Less);
-        assert_eq!(MatchScore::compare(&a, &b), Ordering::Greater);
-    }
 }
@@ -1,12 +1,48 @@
+use std::collections::BTreeMap;
 use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use console::{pad_str, style, Alignment, StyledObject};
+use console::{measure_text_width, pad_str, style, Alignment, Color, StyledObject};
+use image::imageops::FilterType;
+use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+
+use crate::calc::stats::MAX_BASE_STAT;
+use crate::terminal::{Capabilities, ColorSupport, ImageRenderer};
+
+/// Bars stop growing past this many columns on wide terminals.
+const MAX_BAR_WIDTH: usize = 32;
+
+/// Paragraphs stop growing past this many columns on wide terminals, and keep
+/// this many clear on either side on narrow ones.
+const MAX_PARAGRAPH_WIDTH: usize = 64;
+const PARAGRAPH_MARGIN: usize = 2;
+
+/// Whether any failure was printed, for the exit status.
+static FAILED: AtomicBool = AtomicBool::new(false);
+
+/// Whether a failure was printed, so the command should exit unsuccessfully.
+pub fn failed() -> bool {
+    FAILED.load(Ordering::Relaxed)
+}
 
 pub struct Printer {
     pub width: u16,
+    pub capabilities: Capabilities,
 }
 
 impl Printer {
+    /// A printer for the current terminal.
+    pub fn new() -> Self {
+        Printer::with_capabilities(Capabilities::detect())
+    }
+
+    pub fn with_capabilities(capabilities: Capabilities) -> Self {
+        Printer {
+            width: capabilities.width,
+            capabilities,
+        }
+    }
+
     pub fn center(&self, message: &str) -> String {
         pad_str(message, self.width.into(), Alignment::Center, None).to_string()
     }
@@ -15,39 +51,575 @@ impl Printer {
         println!("{}", self.center(&message.to_string()));
     }
 
+    /// Prints `text` centered, wrapped to lines that fit the terminal, each
+    /// styled by `styled`.
+    pub fn print_paragraph<F>(&self, text: &str, styled: F)
+    where
+        F: Fn(String) -> StyledObject<String>,
+    {
+        let width = usize::from(self.width)
+            .saturating_sub(PARAGRAPH_MARGIN * 2)
+            .min(MAX_PARAGRAPH_WIDTH);
+        for line in wrap(text, width) {
+            self.print_center(styled(line));
+        }
+    }
+
     pub fn print_info<T1: Display, T2: Display>(&self, label: T1, info: T2) {
         let left_width = usize::from((self.width / 2) - 1);
         println!("{:>width$}  {}", style(label), info, width = left_width);
     }
 
+    /// A label with two values side by side, e.g. for comparing Pokémon.
+    pub fn print_columns<T1: Display, T2: Display>(&self, label: &str, left: T1, right: T2) {
+        let label_width = usize::from(self.width / 4);
+        let column_width = (usize::from(self.width) - label_width) / 2 - 2;
+        println!(
+            "{}  {}  {}",
+            pad_str(label, label_width, Alignment::Right, None),
+            pad_str(&left.to_string(), column_width, Alignment::Left, None),
+            right
+        );
+    }
+
+    /// A bar `value / max` of the way across `width` columns.
+    pub fn bar(&self, value: u16, max: u16, width: usize) -> String {
+        let filled = match max {
+            0 => 0,
+            max => (usize::from(value.min(max)) * width + usize::from(max) / 2) / usize::from(max),
+        };
+        let block = match self.capabilities.unicode {
+            true => "█",
+            false => "#",
+        };
+        // Keep a sliver visible for tiny values, e.g. Shuckle's Attack
+        block.repeat(filled.max(usize::from(value > 0)))
+    }
+
+    /// Color of a base stat's bar, by the thresholds stat sites use: red for
+    /// poor stats, through yellow, to green and cyan for outstanding ones.
+    fn stat_color(&self, value: u16) -> Color {
+        let ansi256 = self.capabilities.colors >= ColorSupport::Ansi256;
+        match value {
+            0..=29 => Color::Red,
+            30..=59 if ansi256 => Color::Color256(208),
+            30..=59 => Color::Red,
+            60..=89 => Color::Yellow,
+            90..=119 if ansi256 => Color::Color256(112),
+            90..=149 => Color::Green,
+            _ => Color::Cyan,
+        }
+    }
+
+    /// An info row with a base stat and a bar for it, fitted to the width the
+    /// value column has left.
+    pub fn print_stat<T: Display>(&self, label: T, value: u16) {
+        let value_width = 4;
+        let right_width = usize::from(self.width - self.width / 2 - 1);
+        let width = right_width
+            .saturating_sub(value_width + 1)
+            .min(MAX_BAR_WIDTH);
+        let bar = self.bar(value, MAX_BASE_STAT, width);
+        self.print_info(
+            label,
+            format!(
+                "{} {}",
+                pad_str(
+                    &style(value).cyan().to_string(),
+                    value_width,
+                    Alignment::Left,
+                    None
+                ),
+                style(bar).fg(self.stat_color(value))
+            ),
+        );
+    }
+
     pub fn print_section_heading(&self, heading: &str) {
         self.print_info(style(heading).bold(), "");
     }
 
+    /// Prints a failure on stderr and marks the command as failed.
     pub fn print_failure(&self, message: &str) {
-        println!();
-        println!("{}", style(self.center(message)).red());
-        println!();
+        FAILED.store(true, Ordering::Relaxed);
+        eprintln!();
+        eprintln!("{}", style(self.center(message)).red().for_stderr());
+        eprintln!();
     }
 
-    pub fn print_image(&self, image: &image::DynamicImage, width: u16) -> Result<(), ()> {
+    /// Prints a warning on stderr, about something that went wrong without
+    /// failing the command.
+    pub fn print_warning(&self, message: &str) {
+        eprintln!("{}", style(self.center(message)).yellow().for_stderr());
+    }
+
+    /// Prints an image centered, if the terminal can show images at all.
+    pub fn print_image(&self, image: &DynamicImage, width: u16) -> Result<(), ()> {
+        let renderer = self.capabilities.images;
+        let width = width.min(self.width);
+        let margin = " ".repeat(usize::from((self.width - width) / 2));
+        let lines = match renderer {
+            ImageRenderer::None => {
+                info!("not printing image, the terminal can't show it");
+                return Ok(());
+            }
+            ImageRenderer::Kitty | ImageRenderer::Iterm => Vec::new(),
+            ImageRenderer::Sixel => {
+                vec![sixel(&scale(image, u32::from(width) * SIXEL_CELL_WIDTH, 1))]
+            }
+            ImageRenderer::Blocks => {
+                blocks(&scale(image, width.into(), 1), self.capabilities.colors)
+            }
+            ImageRenderer::Ascii => ascii(&scale(image, width.into(), 2), self.capabilities.colors),
+        };
+        if !lines.is_empty() {
+            println!();
+            for line in lines {
+                match line.is_empty() {
+                    true => println!(),
+                    false => println!("{}{}", margin, line),
+                }
+            }
+            return Ok(());
+        }
+
         let conf = viuer::Config {
             transparent: true,
             absolute_offset: false,
             x: (self.width - width) / 2,
             y: 0,
             width: Some(width.into()),
+            truecolor: self.capabilities.colors == ColorSupport::TrueColor,
+            use_kitty: renderer == ImageRenderer::Kitty,
+            use_iterm: renderer == ImageRenderer::Iterm,
             ..Default::default()
         };
 
         println!();
-        match viuer::print(&image, &conf) {
+        match viuer::print(image, &conf) {
             Err(_) => Err(()),
             Ok(_) => Ok(()),
         }
     }
 }
 
+/// Pixels a terminal cell is assumed to be wide when sizing sixel images,
+/// which are drawn in pixels rather than columns.
+const SIXEL_CELL_WIDTH: u32 = 8;
+
+/// Alpha below which a pixel is left out as transparent.
+const MIN_ALPHA: u8 = 128;
+
+/// Characters of ASCII art, from the faintest to the brightest pixels.
+const ASCII_RAMP: &[u8] = b" .:-=+*#%@";
+
+/// `image` resized to `width` pixels across, keeping its aspect ratio with
+/// rows `squash` times fewer, as terminal cells are about twice as tall as
+/// they're wide.
+fn scale(image: &DynamicImage, width: u32, squash: u32) -> RgbaImage {
+    let (image_width, image_height) = image.dimensions();
+    let height = (u64::from(image_height) * u64::from(width)
+        / u64::from(image_width.max(1))
+        / u64::from(squash)) as u32;
+    image
+        .resize_exact(width.max(1), height.max(1), FilterType::Triangle)
+        .to_rgba8()
+}
+
+fn is_visible(pixel: &Rgba<u8>) -> bool {
+    pixel[3] >= MIN_ALPHA
+}
+
+/// Level of a color channel in the 6×6×6 color cube of 256-color terminals.
+fn cube_level(value: u8) -> u8 {
+    ((u16::from(value) * 5 + 127) / 255) as u8
+}
+
+/// The nearest of the 256 colors, from the color cube or the grayscale ramp.
+fn ansi256(pixel: &Rgba<u8>) -> u8 {
+    let [r, g, b, _] = pixel.0;
+    if r == g && g == b {
+        return match r {
+            0..=7 => 16,
+            // The ramp stops at 238, so lighter grays are nearer white
+            247..=255 => 231,
+            gray => 232 + ((gray - 8) / 10).min(23),
+        };
+    }
+    16 + 36 * cube_level(r) + 6 * cube_level(g) + cube_level(b)
+}
+
+/// The nearest of the 16 standard colors, as their xterm defaults.
+fn ansi16(pixel: &Rgba<u8>) -> u8 {
+    const PALETTE: [[u8; 3]; 16] = [
+        [0, 0, 0],
+        [205, 0, 0],
+        [0, 205, 0],
+        [205, 205, 0],
+        [0, 0, 238],
+        [205, 0, 205],
+        [0, 205, 205],
+        [229, 229, 229],
+        [127, 127, 127],
+        [255, 0, 0],
+        [0, 255, 0],
+        [255, 255, 0],
+        [92, 92, 255],
+        [255, 0, 255],
+        [0, 255, 255],
+        [255, 255, 255],
+    ];
+    let distance = |color: &[u8; 3]| -> u32 {
+        color
+            .iter()
+            .zip(pixel.0.iter())
+            .map(|(a, b)| (i32::from(*a) - i32::from(*b)).pow(2) as u32)
+            .sum()
+    };
+    (0..16u8)
+        .min_by_key(|index| distance(&PALETTE[usize::from(*index)]))
+        .unwrap_or(0)
+}
+
+/// The escape sequence setting the foreground (or background) to the pixel's
+/// color, as near as the terminal's colors allow.
+fn ansi_color(pixel: &Rgba<u8>, colors: ColorSupport, background: bool) -> String {
+    let layer = if background { 4 } else { 3 };
+    match colors {
+        ColorSupport::None => String::new(),
+        ColorSupport::Ansi16 => match ansi16(pixel) {
+            index @ 0..=7 => format!("\x1b[{}{}m", layer, index),
+            index => format!("\x1b[{}{}m", layer + 6, index - 8),
+        },
+        ColorSupport::Ansi256 => format!("\x1b[{}8;5;{}m", layer, ansi256(pixel)),
+        ColorSupport::TrueColor => {
+            format!("\x1b[{}8;2;{};{};{}m", layer, pixel[0], pixel[1], pixel[2])
+        }
+    }
+}
+
+/// Draws two rows of pixels per line with Unicode half blocks.
+fn blocks(image: &RgbaImage, colors: ColorSupport) -> Vec<String> {
+    let reset = match colors {
+        ColorSupport::None => "",
+        _ => "\x1b[0m",
+    };
+    (0..image.height())
+        .step_by(2)
+        .map(|y| {
+            let mut line = String::new();
+            for x in 0..image.width() {
+                let top = image.get_pixel(x, y);
+                let bottom = match y + 1 < image.height() {
+                    true => *image.get_pixel(x, y + 1),
+                    false => Rgba([0, 0, 0, 0]),
+                };
+                let cell = match (is_visible(top), is_visible(&bottom)) {
+                    (false, false) => String::from(" "),
+                    (true, false) => format!("{}▀{}", ansi_color(top, colors, false), reset),
+                    (false, true) => format!("{}▄{}", ansi_color(&bottom, colors, false), reset),
+                    (true, true) => format!(
+                        "{}{}▀{}",
+                        ansi_color(top, colors, false),
+                        ansi_color(&bottom, colors, true),
+                        reset
+                    ),
+                };
+                line.push_str(&cell);
+            }
+            line.trim_end().to_string()
+        })
+        .collect()
+}
+
+/// Draws a pixel per character, denser for brighter pixels and colored if
+/// the terminal has colors. Visible pixels are never blank, so the outline
+/// shows even where the sprite is dark.
+fn ascii(image: &RgbaImage, colors: ColorSupport) -> Vec<String> {
+    let reset = match colors {
+        ColorSupport::None => "",
+        _ => "\x1b[0m",
+    };
+    image
+        .rows()
+        .map(|row| {
+            let mut line = String::new();
+            for pixel in row {
+                if !is_visible(pixel) {
+                    line.push(' ');
+                    continue;
+                }
+                let [r, g, b, _] = pixel.0;
+                let luma = (299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b)) / 1000;
+                let steps = ASCII_RAMP.len() as u32 - 1;
+                let index = 1 + luma * (steps - 1) / 255;
+                let character = char::from(ASCII_RAMP[index as usize]);
+                match colors {
+                    ColorSupport::None => line.push(character),
+                    _ => line.push_str(&format!(
+                        "{}{}{}",
+                        ansi_color(pixel, colors, false),
+                        character,
+                        reset
+                    )),
+                }
+            }
+            line.trim_end().to_string()
+        })
+        .collect()
+}
+
+/// Encodes the image as sixels, with the colors of the 6×6×6 cube and
+/// transparent pixels left as the terminal's background.
+fn sixel(image: &RgbaImage) -> String {
+    let (width, height) = image.dimensions();
+    let mut out = format!("\x1bP0;1;0q\"1;1;{};{}", width, height);
+    for index in 0..216u16 {
+        let percent = |level: u16| level * 100 / 5;
+        out.push_str(&format!(
+            "#{};2;{};{};{}",
+            index,
+            percent(index / 36),
+            percent(index / 6 % 6),
+            percent(index % 6)
+        ));
+    }
+
+    for top in (0..height).step_by(6) {
+        // Each color's sixels across the band, a bit per row
+        let mut bands: BTreeMap<u8, Vec<u8>> = BTreeMap::new();
+        for x in 0..width {
+            for row in 0..6.min(height - top) {
+                let pixel = image.get_pixel(x, top + row);
+                if !is_visible(pixel) {
+                    continue;
+                }
+                let [r, g, b, _] = pixel.0;
+                let color = 36 * cube_level(r) + 6 * cube_level(g) + cube_level(b);
+                bands
+                    .entry(color)
+                    .or_insert_with(|| vec![0; width as usize])[x as usize] |= 1 << row;
+            }
+        }
+        for (color, sixels) in bands {
+            out.push_str(&format!("#{}", color));
+            let end = sixels
+                .iter()
+                .rposition(|bits| *bits != 0)
+                .map_or(0, |last| last + 1);
+            let sixels = &sixels[..end];
+            let mut start = 0;
+            while start < sixels.len() {
+                let run = sixels[start..]
+                    .iter()
+                    .take_while(|bits| **bits == sixels[start])
+                    .count();
+                let character = char::from(63 + sixels[start]);
+                match run {
+                    1..=3 => out.extend(std::iter::repeat_n(character, run)),
+                    _ => out.push_str(&format!("!{}{}", run, character)),
+                }
+                start += run;
+            }
+            out.push('$');
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+impl Default for Printer {
+    fn default() -> Self {
+        Printer::new()
+    }
+}
+
+/// Splits `text` into lines at most `width` columns wide, measuring each
+/// character by how many columns it takes, so wide characters count twice.
+/// Lines break at spaces, and between wide characters, since Japanese and
+/// Chinese text has no spaces; words longer than a line are split anywhere.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_width = 0;
+
+    for word in text.split_whitespace() {
+        // Pieces of the word that can go on separate lines
+        let mut pieces: Vec<String> = Vec::new();
+        let mut was_wide = false;
+        for character in word.chars() {
+            let is_wide = measure_text_width(character.encode_utf8(&mut [0; 4])) > 1;
+            match pieces.last_mut() {
+                Some(piece) if !is_wide && !was_wide => piece.push(character),
+                _ => pieces.push(character.to_string()),
+            }
+            was_wide = is_wide;
+        }
+
+        for (index, piece) in pieces.into_iter().enumerate() {
+            let gap = usize::from(index == 0 && !line.is_empty());
+            let piece_width = measure_text_width(&piece);
+            if line_width + gap + piece_width <= width {
+                if gap == 1 {
+                    line.push(' ');
+                }
+                line.push_str(&piece);
+                line_width += gap + piece_width;
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0;
+            }
+            for character in piece.chars() {
+                let character_width = measure_text_width(character.encode_utf8(&mut [0; 4]));
+                if line_width + character_width > width && !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                    line_width = 0;
+                }
+                line.push(character);
+                line_width += character_width;
+            }
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
 pub fn styled_empty_value() -> StyledObject<String> {
     style(String::from("-")).dim()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terminal::ImageRenderer;
+
+    fn printer(unicode: bool, colors: ColorSupport) -> Printer {
+        Printer::with_capabilities(Capabilities {
+            is_term: true,
+            colors,
+            unicode,
+            images: ImageRenderer::None,
+            width: 80,
+        })
+    }
+
+    #[test]
+    fn bars_scale_to_width() {
+        let unicode = printer(true, ColorSupport::Ansi256);
+        assert_eq!(unicode.bar(255, 255, 10), "█".repeat(10));
+        assert_eq!(unicode.bar(128, 255, 10), "█".repeat(5));
+        assert_eq!(unicode.bar(5, 255, 10), "█");
+        assert_eq!(unicode.bar(0, 255, 10), "");
+        assert_eq!(unicode.bar(300, 255, 4), "█".repeat(4));
+        assert_eq!(printer(false, ColorSupport::None).bar(255, 255, 3), "###");
+    }
+
+    #[test]
+    fn wraps_by_display_width() {
+        assert_eq!(
+            wrap("It stores energy in its cheeks", 12),
+            vec!["It stores", "energy in", "its cheeks"]
+        );
+        assert_eq!(wrap("Ferroseed", 4), vec!["Ferr", "osee", "d"]);
+        assert_eq!(wrap("  ", 10), Vec::<String>::new());
+
+        // Each kana takes two columns
+        let lines = wrap("ほっぺたの りょうがわに ちいさい でんきぶくろを もつ。", 10);
+        assert_eq!(lines[0], "ほっぺたの");
+        assert!(lines.iter().all(|line| measure_text_width(line) <= 10));
+        assert_eq!(
+            wrap("でんきポケモン Pikachu", 9),
+            vec!["でんきポ", "ケモン", "Pikachu"]
+        );
+    }
+
+    fn pixels(width: u32, height: u32, colors: &[[u8; 4]]) -> RgbaImage {
+        RgbaImage::from_fn(width, height, |x, y| Rgba(colors[(y * width + x) as usize]))
+    }
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+    const CLEAR: [u8; 4] = [0, 0, 0, 0];
+
+    #[test]
+    fn renders_blocks() {
+        let image = pixels(3, 2, &[RED, CLEAR, RED, CLEAR, RED, WHITE]);
+        assert_eq!(
+            blocks(&image, ColorSupport::Ansi256),
+            vec![
+                "\x1b[38;5;196m▀\x1b[0m\x1b[38;5;196m▄\x1b[0m\x1b[38;5;196m\x1b[48;5;231m▀\x1b[0m"
+            ]
+        );
+        assert_eq!(blocks(&image, ColorSupport::None), vec!["▀▄▀"]);
+        assert_eq!(
+            blocks(&pixels(1, 1, &[RED]), ColorSupport::TrueColor),
+            vec!["\x1b[38;2;255;0;0m▀\x1b[0m"]
+        );
+        assert_eq!(
+            blocks(&pixels(1, 1, &[RED]), ColorSupport::Ansi16),
+            vec!["\x1b[91m▀\x1b[0m"]
+        );
+    }
+
+    #[test]
+    fn grayscale_ramp() {
+        let gray = |value| ansi256(&Rgba([value, value, value, 255]));
+        assert_eq!(gray(0), 16);
+        assert_eq!(gray(8), 232);
+        assert_eq!(gray(238), 255);
+        assert_eq!(gray(246), 255);
+        for value in 247..=255 {
+            assert_eq!(gray(value), 231);
+        }
+    }
+
+    #[test]
+    fn renders_ascii() {
+        let black = [0, 0, 0, 255];
+        let image = pixels(4, 1, &[WHITE, black, CLEAR, RED]);
+        assert_eq!(ascii(&image, ColorSupport::None), vec!["@. -"]);
+        assert_eq!(
+            ascii(&pixels(2, 1, &[CLEAR, CLEAR]), ColorSupport::None),
+            vec![""]
+        );
+    }
+
+    #[test]
+    fn renders_sixels() {
+        let image = pixels(
+            5,
+            2,
+            &[RED, RED, RED, RED, CLEAR, RED, RED, RED, RED, CLEAR],
+        );
+        let sixels = sixel(&image);
+        assert!(sixels.starts_with("\x1bP0;1;0q\"1;1;5;2#0;2;0;0;0"));
+        // Red is cube color 180, both rows set (0b11 + 63 = 'B') four times
+        assert!(sixels.ends_with("#180!4B$-\x1b\\"));
+    }
+
+    #[test]
+    fn stat_colors_degrade() {
+        assert_eq!(
+            printer(true, ColorSupport::Ansi256).stat_color(45),
+            Color::Color256(208)
+        );
+        assert_eq!(
+            printer(true, ColorSupport::Ansi16).stat_color(45),
+            Color::Red
+        );
+        assert_eq!(
+            printer(true, ColorSupport::Ansi16).stat_color(100),
+            Color::Green
+        );
+        assert_eq!(
+            printer(true, ColorSupport::Ansi16).stat_color(200),
+            Color::Cyan
+        );
+    }
+}
@@ -0,0 +1,135 @@
+//! Where each value printed for a Pokémon comes from, for `--provenance`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Serialize, Serializer};
+
+use crate::pokedex::{self, Pokemon, POKEDEX_CSV};
+
+/// The dataset the Pokédex fields are read from.
+pub const POKEDEX_DATASET: &str = "pokedex.csv";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Source {
+    /// A dataset compiled into the binary.
+    Embedded(&'static str),
+    /// Computed by pkmn from other fields.
+    Derived,
+    /// A computed field from the config file.
+    Config,
+    /// Fetched at runtime with `--online`, e.g. from `pokeapi`.
+    Online(&'static str),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Embedded(dataset) => write!(f, "embedded:{}", dataset),
+            Source::Derived => f.write_str("derived"),
+            Source::Config => f.write_str("config"),
+            Source::Online(api) => write!(f, "online:{}", api),
+        }
+    }
+}
+
+impl Serialize for Source {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Stable fingerprint of a dataset's contents (64-bit FNV-1a), identifying
+/// the version of the data a result came from.
+pub fn fingerprint(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Version of the Pokédex in use: the embedded one's, unless it was
+/// replaced (see [`crate::pokedex::replace_entries`]).
+pub fn pokedex_version() -> String {
+    match pokedex::replaced_version() {
+        Some(version) => version.to_string(),
+        None => fingerprint(POKEDEX_CSV),
+    }
+}
+
+#[derive(Serialize)]
+pub struct Provenance {
+    pub data_version: String,
+    /// Source of each field, by name.
+    pub fields: BTreeMap<String, Source>,
+}
+
+impl Provenance {
+    pub fn new<I: IntoIterator<Item = (String, Source)>>(fields: I) -> Self {
+        Provenance {
+            data_version: pokedex_version(),
+            fields: fields.into_iter().collect(),
+        }
+    }
+
+    /// Every field of a Pokémon's Pokédex entry, by its serialized name.
+    pub fn pokedex_fields(pokemon: &Pokemon) -> Vec<(String, Source)> {
+        match serde_json::to_value(pokemon) {
+            Ok(serde_json::Value::Object(fields)) => fields
+                .into_iter()
+                .map(|(name, _)| (name, Source::Embedded(POKEDEX_DATASET)))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// One-line summary of the sources, for the card's footer.
+    pub fn summary(&self) -> String {
+        let mut sources = self.fields.values().copied().collect::<Vec<Source>>();
+        sources.sort();
+        sources.dedup();
+
+        let sources = sources
+            .iter()
+            .map(|source| match source {
+                Source::Embedded(POKEDEX_DATASET) => format!(
+                    "{} ({}, version {})",
+                    POKEDEX_DATASET,
+                    match pokedex::replaced_version() {
+                        Some(_) => "downloaded",
+                        None => "embedded",
+                    },
+                    &self.data_version[..8]
+                ),
+                Source::Embedded(dataset) => format!("{} (embedded)", dataset),
+                Source::Derived => String::from("derived values"),
+                Source::Config => String::from("config fields"),
+                Source::Online(api) => format!("{} (fetched live)", api),
+            })
+            .collect::<Vec<String>>();
+        format!("Data: {}", sources.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprint_is_stable() {
+        assert_eq!(fingerprint(b""), "cbf29ce484222325");
+        assert_eq!(fingerprint(b"a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn summary_lists_each_source_once() {
+        let provenance = Provenance::new([
+            (String::from("name"), Source::Embedded(POKEDEX_DATASET)),
+            (String::from("hp"), Source::Embedded(POKEDEX_DATASET)),
+            (String::from("slug"), Source::Derived),
+        ]);
+        assert!(provenance
+            .summary()
+            .starts_with("Data: pokedex.csv (embedded, version "));
+    }
+}
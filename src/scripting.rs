@@ -0,0 +1,210 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rhai::serde::{from_dynamic, to_dynamic};
+use rhai::{Array, Dynamic, Engine, Map, Scope, AST};
+
+use crate::config;
+use crate::pokedex::Pokemon;
+
+/// A custom card section registered by a user script.
+pub struct ScriptSection {
+    pub heading: String,
+    pub rows: Vec<(String, String)>,
+}
+
+/// User scripts loaded from `<config dir>/scripts/*.rhai`.
+///
+/// Each script may define any of the following hooks, all of which receive
+/// the Pokémon record as an object map:
+///
+/// - `process(pokemon)` returns a (modified) record that replaces the original
+/// - `fields(pokemon)` returns a map of computed field names to values
+/// - `sections(pokemon)` returns an array of `#{ heading: "...", rows: [[label, value]] }`
+pub struct Scripts {
+    engine: Engine,
+    scripts: Vec<(PathBuf, AST)>,
+}
+
+impl Scripts {
+    pub fn load() -> Self {
+        let mut scripts = Scripts {
+            engine: Engine::new(),
+            scripts: Vec::new(),
+        };
+
+        if let Some(dir) = config::config_dir().map(|dir| dir.join("scripts")) {
+            if let Err(err) = scripts.load_dir(&dir) {
+                info!("no scripts loaded from \"{}\": {}", dir.display(), err);
+            }
+        }
+
+        scripts
+    }
+
+    fn load_dir(&mut self, dir: &Path) -> Result<(), Box<dyn Error>> {
+        let mut paths = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "rhai"))
+            .collect::<Vec<PathBuf>>();
+        paths.sort();
+
+        for path in paths {
+            match self.engine.compile_file(path.clone()) {
+                Ok(ast) => {
+                    info!("loaded script \"{}\"", path.display());
+                    self.scripts.push((path, ast));
+                }
+                Err(err) => warn!("script \"{}\" failed to compile: {}", path.display(), err),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn call(&self, path: &Path, ast: &AST, hook: &str, pokemon: &Pokemon) -> Option<Dynamic> {
+        if !ast.iter_functions().any(|func| func.name == hook) {
+            return None;
+        }
+
+        let record = match to_dynamic(pokemon) {
+            Ok(record) => record,
+            Err(err) => {
+                warn!(
+                    "failed to convert \"{}\" for scripts: {}",
+                    pokemon.name, err
+                );
+                return None;
+            }
+        };
+
+        match self
+            .engine
+            .call_fn::<Dynamic>(&mut Scope::new(), ast, hook, (record,))
+        {
+            Ok(value) => Some(value),
+            Err(err) => {
+                warn!("{}() in \"{}\" failed: {}", hook, path.display(), err);
+                None
+            }
+        }
+    }
+
+    pub fn process(&self, pokemon: Pokemon) -> Pokemon {
+        self.scripts.iter().fold(pokemon, |pokemon, (path, ast)| {
+            match self.call(path, ast, "process", &pokemon) {
+                None => pokemon,
+                Some(value) => match from_dynamic::<Pokemon>(&value) {
+                    Ok(processed) => processed,
+                    Err(err) => {
+                        warn!(
+                            "process() in \"{}\" returned an invalid record: {}",
+                            path.display(),
+                            err
+                        );
+                        pokemon
+                    }
+                },
+            }
+        })
+    }
+
+    pub fn fields(&self, pokemon: &Pokemon) -> Vec<(String, String)> {
+        self.scripts
+            .iter()
+            .filter_map(|(path, ast)| self.call(path, ast, "fields", pokemon))
+            .filter_map(|value| value.try_cast::<Map>())
+            .flat_map(|map| {
+                map.into_iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+            })
+            .collect()
+    }
+
+    pub fn sections(&self, pokemon: &Pokemon) -> Vec<ScriptSection> {
+        self.scripts
+            .iter()
+            .filter_map(|(path, ast)| self.call(path, ast, "sections", pokemon))
+            .filter_map(|value| value.try_cast::<Array>())
+            .flatten()
+            .filter_map(|section| section.try_cast::<Map>())
+            .map(|section| ScriptSection {
+                heading: section
+                    .get("heading")
+                    .map(|heading| heading.to_string())
+                    .unwrap_or_default(),
+                rows: section
+                    .get("rows")
+                    .and_then(|rows| rows.clone().try_cast::<Array>())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|row| row.try_cast::<Array>())
+                    .map(|row| {
+                        let mut cells = row.into_iter().map(|cell| cell.to_string());
+                        (
+                            cells.next().unwrap_or_default(),
+                            cells.next().unwrap_or_default(),
+                        )
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::search_by_name;
+
+    fn scripts(source: &str) -> Scripts {
+        let engine = Engine::new();
+        let ast = engine.compile(source).unwrap();
+        Scripts {
+            engine,
+            scripts: vec![(PathBuf::from("test.rhai"), ast)],
+        }
+    }
+
+    fn garchomp() -> Pokemon {
+        search_by_name("garchomp", 1).remove(0).pokemon
+    }
+
+    #[test]
+    fn process_hook() {
+        let scripts = scripts(r#"fn process(p) { p.species = "Land Shark"; p }"#);
+        assert_eq!(&*scripts.process(garchomp()).species, "Land Shark");
+    }
+
+    #[test]
+    fn fields_hook() {
+        let scripts = scripts("fn fields(p) { #{ physical_bulk: p.hp * p.defense } }");
+        let fields = scripts.fields(&garchomp());
+        assert_eq!(
+            fields,
+            vec![("physical_bulk".to_string(), "10260".to_string())]
+        );
+    }
+
+    #[test]
+    fn sections_hook() {
+        let scripts =
+            scripts(r#"fn sections(p) { [#{ heading: "Notes", rows: [["Tier", "OU"]] }] }"#);
+        let sections = scripts.sections(&garchomp());
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].heading, "Notes");
+        assert_eq!(
+            sections[0].rows,
+            vec![("Tier".to_string(), "OU".to_string())]
+        );
+    }
+
+    #[test]
+    fn missing_hooks_are_ignored() {
+        let scripts = scripts("fn unrelated() { 1 }");
+        assert_eq!(&*scripts.process(garchomp()).species, "Mach Pokémon");
+        assert!(scripts.fields(&garchomp()).is_empty());
+        assert!(scripts.sections(&garchomp()).is_empty());
+    }
+}
@@ -0,0 +1,97 @@
+use std::error::Error;
+
+use serde::Deserialize;
+use serde_json::json;
+
+const GISTS_URL: &str = "https://api.github.com/gists";
+
+/// Where `pkmn share` uploads cards, from the `[share]` table of the config.
+///
+/// ```toml
+/// [share]
+/// endpoint = "https://paste.example.com/"
+/// github_token = "ghp_…"
+/// ```
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct ShareConfig {
+    /// Paste service that takes the raw text as a POST body and responds with
+    /// the paste's URL.
+    pub endpoint: Option<String>,
+    /// Token with the `gist` scope, for uploading secret gists instead.
+    /// Falls back to the `GITHUB_TOKEN` environment variable.
+    pub github_token: Option<String>,
+}
+
+impl ShareConfig {
+    pub fn github_token(&self) -> Option<String> {
+        self.github_token
+            .clone()
+            .or_else(|| std::env::var("GITHUB_TOKEN").ok())
+    }
+}
+
+/// Uploads `content` to a paste service, returning the paste's URL.
+pub async fn upload_paste(endpoint: &str, content: String) -> Result<String, Box<dyn Error>> {
+    info!("uploading paste to \"{}\"", endpoint);
+
+    let res = reqwest::Client::new()
+        .post(endpoint)
+        .header("Content-Type", "text/plain; charset=utf-8")
+        .body(content)
+        .send()
+        .await?;
+
+    match res.status() {
+        status if status.is_success() => Ok(res.text().await?.trim().to_string()),
+        status => Err(Box::<dyn Error>::from(status.to_string())),
+    }
+}
+
+#[derive(Deserialize)]
+struct Gist {
+    html_url: String,
+}
+
+fn gist_body(filename: &str, content: &str) -> serde_json::Value {
+    json!({
+        "description": "Shared with pkmn",
+        "public": false,
+        "files": { filename: { "content": content } },
+    })
+}
+
+/// Uploads `content` as a secret gist, returning the gist's URL.
+pub async fn upload_gist(
+    token: &str,
+    filename: &str,
+    content: &str,
+) -> Result<String, Box<dyn Error>> {
+    info!("uploading gist \"{}\"", filename);
+
+    let res = reqwest::Client::new()
+        .post(GISTS_URL)
+        .header("Accept", "application/vnd.github.v3+json")
+        .header("Authorization", format!("token {}", token))
+        .header("User-Agent", env!("CARGO_PKG_NAME"))
+        .json(&gist_body(filename, content))
+        .send()
+        .await?;
+
+    match res.status() {
+        status if status.is_success() => Ok(res.json::<Gist>().await?.html_url),
+        status => Err(Box::<dyn Error>::from(status.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gist_request() {
+        let body = gist_body("pikachu.md", "# Pikachu");
+        assert_eq!(body["public"], false);
+        assert_eq!(body["files"]["pikachu.md"]["content"], "# Pikachu");
+    }
+}
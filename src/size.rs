@@ -0,0 +1,100 @@
+use std::borrow::Cow;
+
+use crate::columns::{percentile, Columns};
+use crate::pokedex::Pokemon;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SizeClass {
+    Tiny,
+    Small,
+    Medium,
+    Large,
+    Huge,
+}
+
+impl SizeClass {
+    pub const NAMES: [&'static str; 5] = ["tiny", "small", "medium", "large", "huge"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "tiny" => Some(SizeClass::Tiny),
+            "small" => Some(SizeClass::Small),
+            "medium" => Some(SizeClass::Medium),
+            "large" => Some(SizeClass::Large),
+            "huge" => Some(SizeClass::Huge),
+            _ => None,
+        }
+    }
+
+    /// Buckets a height percentile: the smallest and largest 10% are tiny
+    /// and huge, the middle 30% medium.
+    fn from_percentile(percentile: f64) -> Self {
+        match percentile {
+            p if p < 10.0 => SizeClass::Tiny,
+            p if p < 35.0 => SizeClass::Small,
+            p if p < 65.0 => SizeClass::Medium,
+            p if p < 90.0 => SizeClass::Large,
+            _ => SizeClass::Huge,
+        }
+    }
+}
+
+/// Height and weight distributions across the Pokédex.
+pub struct SizeStats<'a> {
+    columns: Cow<'a, Columns>,
+}
+
+impl SizeStats<'_> {
+    pub fn new(pokemon: &[Pokemon]) -> Self {
+        SizeStats {
+            columns: Cow::Owned(Columns::new(pokemon)),
+        }
+    }
+
+    /// Distributions of the whole Pokédex, shared by every caller.
+    pub fn pokedex() -> SizeStats<'static> {
+        SizeStats {
+            columns: Cow::Borrowed(Columns::pokedex()),
+        }
+    }
+
+    /// Percentage of Pokémon shorter than `pokemon`.
+    pub fn height_percentile(&self, pokemon: &Pokemon) -> Option<f64> {
+        pokemon
+            .height_m
+            .map(|height| percentile(self.columns.heights(), height))
+    }
+
+    /// Percentage of Pokémon lighter than `pokemon`.
+    pub fn weight_percentile(&self, pokemon: &Pokemon) -> Option<f64> {
+        pokemon
+            .weight_kg
+            .map(|weight| percentile(self.columns.weights(), weight))
+    }
+
+    pub fn size_class(&self, pokemon: &Pokemon) -> Option<SizeClass> {
+        self.height_percentile(pokemon)
+            .map(SizeClass::from_percentile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::{all_pokemon, find_by_id};
+
+    #[test]
+    fn size_classes() {
+        let stats = SizeStats::new(&all_pokemon().unwrap());
+        let class = |id| stats.size_class(&find_by_id(id).unwrap());
+        assert_eq!(class("joltik"), Some(SizeClass::Tiny));
+        assert_eq!(class("wailord"), Some(SizeClass::Huge));
+    }
+
+    #[test]
+    fn weight_percentile() {
+        let stats = SizeStats::new(&all_pokemon().unwrap());
+        let snorlax = stats.weight_percentile(&find_by_id("snorlax").unwrap());
+        assert!(snorlax.unwrap() > 95.0);
+    }
+}
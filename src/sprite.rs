@@ -0,0 +1,283 @@
+//! Finding a Pokémon's sprite: the sprite cache first, then the first URL of a
+//! fallback chain that exists, checked concurrently with HEAD requests.
+
+use std::error::Error;
+use std::time::Duration;
+
+use image::{load_from_memory, DynamicImage};
+use serde::Serialize;
+
+use crate::download;
+use crate::pokedex::{self, Pokemon};
+use crate::sprite_cache::SpriteCache;
+
+/// Last resort, which has a sprite for every National Pokédex number but not
+/// for alternate forms.
+const POKEAPI_SPRITE_URL: &str =
+    "https://raw.githubusercontent.com/PokeAPI/sprites/master/sprites/pokemon/{number}.png";
+
+/// Black and White's sprites, which cover Generations 1 to 5.
+const GEN5_SPRITE_URL: &str = "https://raw.githubusercontent.com/PokeAPI/sprites/master/sprites/pokemon/versions/generation-v/black-white/{number}.png";
+
+const ARTWORK_URL: &str = "https://raw.githubusercontent.com/PokeAPI/sprites/master/sprites/pokemon/other/official-artwork/{number}.png";
+
+/// A set of sprites to link to, e.g. for a manifest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpriteStyle {
+    /// The card's sprites, which include alternate forms.
+    Default,
+    /// Black and White's sprites, by Pokédex number.
+    Gen5,
+    /// Official artwork, by Pokédex number.
+    Artwork,
+}
+
+impl SpriteStyle {
+    pub const NAMES: [&'static str; 3] = ["default", "gen5", "artwork"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(SpriteStyle::Default),
+            "gen5" => Some(SpriteStyle::Gen5),
+            "artwork" => Some(SpriteStyle::Artwork),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SpriteStyle::Default => "default",
+            SpriteStyle::Gen5 => "gen5",
+            SpriteStyle::Artwork => "artwork",
+        }
+    }
+
+    /// The Pokémon's sprite in this style. Styles by Pokédex number only
+    /// have base forms, and Gen 5 sprites stop at Generation 5.
+    pub fn url(&self, pokemon: &Pokemon) -> Option<String> {
+        let number = pokemon.pokedex_number.to_string();
+        let is_base_form =
+            pokedex::find_by_id(&number).is_some_and(|base| base.name == pokemon.name);
+        match self {
+            SpriteStyle::Default => Some(pokemon.sprite_url()),
+            SpriteStyle::Gen5 if is_base_form && pokemon.generation <= 5 => {
+                Some(GEN5_SPRITE_URL.replace("{number}", &number))
+            }
+            SpriteStyle::Artwork if is_base_form => Some(ARTWORK_URL.replace("{number}", &number)),
+            _ => None,
+        }
+    }
+}
+
+/// URLs to try, in order of preference: the form's own sprite, its base
+/// form's, then PokéAPI's by Pokédex number.
+pub fn candidate_urls(pokemon: &Pokemon) -> Vec<String> {
+    let mut urls = vec![pokemon.sprite_url()];
+    if let Some(base) = pokedex::find_by_id(&pokemon.pokedex_number.to_string()) {
+        urls.push(base.sprite_url());
+    }
+    urls.push(POKEAPI_SPRITE_URL.replace("{number}", &pokemon.pokedex_number.to_string()));
+    urls.dedup();
+    urls
+}
+
+#[derive(Serialize)]
+pub struct Attempt {
+    pub url: String,
+    /// HTTP status, if the server answered.
+    pub status: Option<u16>,
+    pub error: Option<String>,
+}
+
+impl Attempt {
+    fn found(&self) -> bool {
+        matches!(self.status, Some(200..=299))
+    }
+}
+
+/// Outcome of checking the fallback chain.
+#[derive(Serialize)]
+pub struct SpriteCheck {
+    pub slug: String,
+    /// The first URL in the chain that exists.
+    pub url: Option<String>,
+    pub attempts: Vec<Attempt>,
+}
+
+impl SpriteCheck {
+    /// Whether any server answered, i.e. the sprite is missing rather than
+    /// unreachable (e.g. offline).
+    fn reached_server(&self) -> bool {
+        self.attempts.iter().any(|attempt| attempt.status.is_some())
+    }
+}
+
+/// How long to wait for each HEAD request, so a dropped connection doesn't
+/// hold up the card or an audit.
+pub const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Sends a HEAD request to every URL of the fallback chain at once.
+pub async fn check(pokemon: &Pokemon) -> SpriteCheck {
+    let client = crate::http_client();
+    let urls = candidate_urls(pokemon);
+    let requests = urls
+        .iter()
+        .map(|url| tokio::spawn(client.head(url).timeout(CHECK_TIMEOUT).send()))
+        .collect::<Vec<_>>();
+
+    let mut attempts = Vec::new();
+    for (url, request) in urls.into_iter().zip(requests) {
+        let attempt = match request.await {
+            Ok(Ok(res)) => Attempt {
+                url,
+                status: Some(res.status().as_u16()),
+                error: None,
+            },
+            Ok(Err(err)) => Attempt {
+                url,
+                status: None,
+                error: Some(err.to_string()),
+            },
+            Err(err) => Attempt {
+                url,
+                status: None,
+                error: Some(err.to_string()),
+            },
+        };
+        info!(
+            "sprite {}: {:?} {:?}",
+            attempt.url, attempt.status, attempt.error
+        );
+        attempts.push(attempt);
+    }
+
+    SpriteCheck {
+        slug: pokemon.sprite_name_slug(),
+        url: attempts
+            .iter()
+            .find(|attempt| attempt.found())
+            .map(|attempt| attempt.url.clone()),
+        attempts,
+    }
+}
+
+pub enum SpriteError {
+    /// No URL in the chain has a sprite.
+    NotFound(SpriteCheck),
+    /// The network is unreachable, e.g. when offline.
+    Offline(Box<dyn Error>),
+    Failed(Box<dyn Error>),
+}
+
+impl From<Box<dyn Error>> for SpriteError {
+    fn from(err: Box<dyn Error>) -> Self {
+        match is_network_error(err.as_ref()) {
+            true => SpriteError::Offline(err),
+            false => SpriteError::Failed(err),
+        }
+    }
+}
+
+/// Whether `err` comes from failing to reach a server at all (no connection,
+/// DNS failure, timeout), rather than from its response.
+pub fn is_network_error(err: &(dyn Error + 'static)) -> bool {
+    match err.downcast_ref::<reqwest::Error>() {
+        Some(err) => err.is_connect() || err.is_timeout() || err.is_request(),
+        None => false,
+    }
+}
+
+/// A Pokémon's sprite from the sprite cache, downloading (and caching) it if
+/// it's missing, doesn't decode, or `refresh` is set.
+pub async fn load(pokemon: &Pokemon, refresh: bool) -> Result<DynamicImage, SpriteError> {
+    let slug = pokemon.sprite_name_slug();
+    let cache = SpriteCache::open();
+
+    if let (false, Some(cache)) = (refresh, &cache) {
+        if let Some(bytes) = cache.get(&slug) {
+            match load_from_memory(&bytes) {
+                Ok(image) => {
+                    info!("using cached sprite for \"{}\"", slug);
+                    return Ok(image);
+                }
+                Err(err) => {
+                    warn!(
+                        "cached sprite for \"{}\" is corrupt, downloading it again: {}",
+                        slug, err
+                    );
+                    cache.remove(&slug);
+                }
+            }
+        }
+    }
+
+    let check = check(pokemon).await;
+    let url = match &check.url {
+        Some(url) => url,
+        None if check.reached_server() => return Err(SpriteError::NotFound(check)),
+        None => {
+            let error = check.attempts.into_iter().find_map(|attempt| attempt.error);
+            return Err(SpriteError::Offline(
+                error
+                    .unwrap_or_else(|| String::from("no sprite URLs"))
+                    .into(),
+            ));
+        }
+    };
+
+    let bytes = download(url).await?;
+    let image = load_from_memory(&bytes).map_err(|err| SpriteError::Failed(err.into()))?;
+    if let Some(cache) = cache {
+        if let Err(err) = cache.put(&slug, &bytes) {
+            warn!("couldn't cache sprite: {}", err);
+        }
+        crate::cache::prune();
+    }
+    Ok(image)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_form_has_no_duplicates() {
+        let pikachu = pokedex::find_by_id("pikachu").unwrap();
+        let urls = candidate_urls(&pikachu);
+        assert_eq!(urls.len(), 2);
+        assert!(urls[0].ends_with("/pikachu.png"));
+        assert!(urls[1].ends_with("/pokemon/25.png"));
+    }
+
+    #[test]
+    fn styles() {
+        let pikachu = pokedex::find_by_id("pikachu").unwrap();
+        let alolan = pokedex::find_by_id("raichu-alola").unwrap();
+        let zeraora = pokedex::find_by_id("zeraora").unwrap();
+
+        assert_eq!(SpriteStyle::Default.url(&alolan), Some(alolan.sprite_url()));
+        assert!(SpriteStyle::Gen5
+            .url(&pikachu)
+            .unwrap()
+            .ends_with("/black-white/25.png"));
+        assert_eq!(SpriteStyle::Gen5.url(&alolan), None);
+        assert_eq!(SpriteStyle::Gen5.url(&zeraora), None);
+        assert!(SpriteStyle::Artwork
+            .url(&zeraora)
+            .unwrap()
+            .ends_with("/official-artwork/807.png"));
+        for name in SpriteStyle::NAMES {
+            assert_eq!(SpriteStyle::from_name(name).unwrap().name(), name);
+        }
+    }
+
+    #[test]
+    fn forms_fall_back_to_base_form() {
+        let mega = pokedex::find_by_id("charizard-mega-x").unwrap();
+        let urls = candidate_urls(&mega);
+        assert_eq!(urls.len(), 3);
+        assert!(urls[0].ends_with("/charizard-mega-x.png"));
+        assert!(urls[1].ends_with("/charizard.png"));
+        assert!(urls[2].ends_with("/pokemon/6.png"));
+    }
+}
@@ -0,0 +1,71 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::cache;
+
+/// Downloaded sprites, kept in `<cache dir>/pkmn/sprites` (e.g.
+/// `~/.cache/pkmn/sprites` on Linux) so repeated lookups work offline.
+pub struct SpriteCache {
+    dir: PathBuf,
+}
+
+impl SpriteCache {
+    pub fn open() -> Option<Self> {
+        let dir = cache::cache_dir()?.join("sprites");
+        Some(SpriteCache { dir })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, slug: &str) -> PathBuf {
+        self.dir.join(format!("{}.png", slug))
+    }
+
+    /// The cached sprite, marking it as used so it's the last to go when the
+    /// cache is pruned.
+    pub fn get(&self, slug: &str) -> Option<Vec<u8>> {
+        let path = self.path(slug);
+        let bytes = fs::read(&path).ok()?;
+        cache::touch(&path);
+        Some(bytes)
+    }
+
+    /// Caches a sprite, writing it beside the final path first so an
+    /// interrupted write never leaves a truncated sprite behind.
+    pub fn put(&self, slug: &str, bytes: &[u8]) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let partial = self.dir.join(format!("{}.png.part", slug));
+        fs::write(&partial, bytes)?;
+        fs::rename(partial, self.path(slug))
+    }
+
+    /// Drops a cached sprite, e.g. one that turned out to be corrupt.
+    pub fn remove(&self, slug: &str) {
+        if let Err(err) = fs::remove_file(self.path(slug)) {
+            warn!("couldn't remove cached sprite \"{}\": {}", slug, err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let dir = std::env::temp_dir().join(format!("pkmn-sprite-cache-{}", std::process::id()));
+        let cache = SpriteCache { dir: dir.clone() };
+
+        assert_eq!(cache.get("pikachu"), None);
+        cache.put("pikachu", b"png").unwrap();
+        assert_eq!(cache.get("pikachu").as_deref(), Some(&b"png"[..]));
+        assert!(!cache.dir().join("pikachu.png.part").exists());
+        cache.remove("pikachu");
+        assert_eq!(cache.get("pikachu"), None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,124 @@
+use serde::Deserialize;
+
+use crate::error::{self, PkmnError};
+
+/// How a status condition works in a range of generations.
+#[derive(Deserialize)]
+pub struct StatusMechanics {
+    pub status: String,
+    pub first_generation: u8,
+    /// `None` if it still applies in the latest generation.
+    pub last_generation: Option<u8>,
+    /// Damage per turn as a fraction of max HP, `1 / damage_denominator`.
+    /// `None` for conditions that don't deal damage.
+    pub damage_denominator: Option<u16>,
+    /// Whether the damage grows by that fraction every turn, like Toxic.
+    pub escalating: bool,
+    pub notes: String,
+}
+
+/// Toxic's damage stops growing at 15/16 of max HP from Generation 5.
+const TOXIC_CAP_GENERATION: u8 = 5;
+const TOXIC_MAX_COUNTER: u16 = 15;
+
+pub static STATUSES_CSV: &[u8] = include_bytes!("../data/statuses.csv");
+
+pub(crate) fn load() -> Result<Vec<StatusMechanics>, PkmnError> {
+    error::read_csv("statuses.csv", STATUSES_CSV)
+}
+
+fn all_mechanics() -> Vec<StatusMechanics> {
+    load().unwrap_or_default()
+}
+
+pub fn status_names() -> Vec<String> {
+    let mut names = all_mechanics()
+        .into_iter()
+        .map(|mechanics| mechanics.status)
+        .collect::<Vec<String>>();
+    names.dedup();
+    names
+}
+
+/// Mechanics of `status` in `generation`.
+pub fn mechanics(status: &str, generation: u8) -> Option<StatusMechanics> {
+    let status = status.trim().to_lowercase();
+    all_mechanics().into_iter().find(|mechanics| {
+        mechanics.status == status
+            && mechanics.first_generation <= generation
+            && mechanics
+                .last_generation
+                .is_none_or(|last| generation <= last)
+    })
+}
+
+impl StatusMechanics {
+    /// Damage taken on each of `turns` turns by a Pokémon with `max_hp`,
+    /// stopping once it faints. Each tick deals at least 1 HP.
+    pub fn damage_per_turn(&self, max_hp: u16, turns: u16) -> Vec<u16> {
+        let denominator = match self.damage_denominator {
+            Some(denominator) => denominator,
+            None => return Vec::new(),
+        };
+
+        let mut remaining = max_hp;
+        let mut damage = Vec::new();
+        for turn in 1..=turns {
+            if remaining == 0 {
+                break;
+            }
+            let counter = match (self.escalating, self.first_generation) {
+                (false, _) => 1,
+                (true, generation) if generation >= TOXIC_CAP_GENERATION => {
+                    turn.min(TOXIC_MAX_COUNTER)
+                }
+                (true, _) => turn,
+            };
+            let tick = (u32::from(max_hp) * u32::from(counter) / u32::from(denominator)).max(1);
+            let tick = (tick as u16).min(remaining);
+            remaining -= tick;
+            damage.push(tick);
+        }
+        damage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn by_generation() {
+        assert_eq!(mechanics("burn", 1).unwrap().damage_denominator, Some(16));
+        assert_eq!(mechanics("Burn", 4).unwrap().damage_denominator, Some(8));
+        assert_eq!(mechanics("burn", 8).unwrap().damage_denominator, Some(16));
+        assert!(mechanics("burn", 6).unwrap().notes.contains("Facade"));
+        assert!(!mechanics("burn", 5).unwrap().notes.contains("Facade"));
+        assert!(!mechanics("poison", 1).unwrap().notes.contains("Steel"));
+        assert!(mechanics("paralysis", 6)
+            .unwrap()
+            .notes
+            .contains("Electric"));
+        assert!(mechanics("sleep", 8).is_none());
+        assert_eq!(status_names(), vec!["burn", "poison", "toxic", "paralysis"]);
+    }
+
+    #[test]
+    fn toxic() {
+        let toxic = mechanics("toxic", 8).unwrap();
+        assert_eq!(toxic.damage_per_turn(312, 5), vec![19, 39, 58, 78, 97]);
+        // 19 + 39 + 58 + 78 + 97 = 291, so the sixth tick only takes what's left
+        assert_eq!(toxic.damage_per_turn(312, 10).iter().sum::<u16>(), 312);
+        assert_eq!(toxic.damage_per_turn(312, 10).len(), 6);
+    }
+
+    #[test]
+    fn minimum_damage() {
+        let poison = mechanics("poison", 8).unwrap();
+        assert_eq!(poison.damage_per_turn(5, 3), vec![1, 1, 1]);
+        assert!(mechanics("paralysis", 8)
+            .unwrap()
+            .damage_per_turn(100, 3)
+            .is_empty());
+    }
+}
@@ -0,0 +1,77 @@
+use crate::commands;
+use crate::fuzzy;
+use crate::pokedex::{self, Pokemon};
+
+/// Minimum similarity for an unknown command's arguments to be taken for a
+/// Pokémon's name, stricter than "did you mean" hints since it offers to run a
+/// search.
+const POKEMON_SIMILARITY: f64 = 0.9;
+
+/// What a mistyped command line (e.g. `pkmn serch pikachu`) was likely meant
+/// to be.
+pub struct Suggestion {
+    pub command: Option<String>,
+    pub pokemon: Option<Pokemon>,
+}
+
+fn looks_like_pokemon(query: &str) -> Option<Pokemon> {
+    pokedex::search_by_name(query, 1)
+        .into_iter()
+        .find(|result| result.score.similarity >= POKEMON_SIMILARITY)
+        .map(|result| result.pokemon)
+}
+
+/// Suggests the subcommand nearest to `word`, and a Pokémon if the arguments
+/// after it, up to the first flag (or `word` itself, when there are none), look
+/// like a name.
+pub fn suggest(word: &str, rest: &[String]) -> Suggestion {
+    let names = commands::all()
+        .iter()
+        .map(|command| command.get_name().to_string())
+        .collect::<Vec<String>>();
+    let command = fuzzy::did_you_mean(&names, word);
+
+    let query = rest
+        .iter()
+        .take_while(|arg| !arg.starts_with('-'))
+        .map(String::as_str)
+        .collect::<Vec<&str>>()
+        .join(" ");
+    let pokemon = if query.is_empty() {
+        looks_like_pokemon(word)
+    } else {
+        looks_like_pokemon(&query)
+    };
+
+    Suggestion { command, pokemon }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn command_and_pokemon() {
+        let suggestion = suggest("serch", &args(&["pikachu"]));
+        assert_eq!(suggestion.command.as_deref(), Some("search"));
+        assert_eq!(suggestion.pokemon.unwrap().name, "Pikachu");
+    }
+
+    #[test]
+    fn bare_pokemon_name() {
+        let suggestion = suggest("charzard", &[]);
+        assert!(suggestion.command.is_none());
+        assert_eq!(suggestion.pokemon.unwrap().name, "Charizard");
+    }
+
+    #[test]
+    fn command_only() {
+        let suggestion = suggest("lsit", &args(&["--color", "red"]));
+        assert_eq!(suggestion.command.as_deref(), Some("list"));
+        assert!(suggestion.pokemon.is_none());
+    }
+}
@@ -0,0 +1,348 @@
+//! The whole Pokédex as typed columns, for `pkmn export pokedex`. Each column
+//! has one type in every format, so integers stay integers and missing values
+//! are nulls rather than empty strings a reader has to guess about.
+
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::io::Write;
+
+use evalexpr::Value;
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+
+use crate::classification::{self, Classification};
+use crate::computed;
+use crate::error::PkmnError;
+use crate::pokedex::{Pokemon, PokemonStatus};
+
+#[derive(Debug, PartialEq)]
+pub enum Values {
+    Int(Vec<Option<i64>>),
+    Float(Vec<Option<f64>>),
+    Text(Vec<Option<String>>),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Column {
+    pub name: String,
+    pub values: Values,
+}
+
+pub struct Table {
+    pub columns: Vec<Column>,
+    rows: usize,
+}
+
+/// Widens a Pokédex value keeping the decimal it was written as, e.g. 0.4
+/// rather than 0.4000000059604645.
+fn widen(value: f32) -> f64 {
+    value.to_string().parse().unwrap_or_else(|_| value.into())
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    match value {
+        "" => None,
+        value => Some(value.to_string()),
+    }
+}
+
+fn status_name(status: &PokemonStatus) -> &'static str {
+    match status {
+        PokemonStatus::Normal => "Normal",
+        PokemonStatus::Legendary => "Legendary",
+        PokemonStatus::Mythical => "Mythical",
+        PokemonStatus::SubLegendary => "Sub Legendary",
+    }
+}
+
+/// A computed field's column: integers if every value is one, floats if
+/// they're all numbers, otherwise text.
+fn computed_values(values: Vec<Option<Value>>) -> Values {
+    if values.iter().flatten().all(Value::is_int) {
+        Values::Int(
+            values
+                .iter()
+                .map(|value| value.as_ref()?.as_int().ok())
+                .collect(),
+        )
+    } else if values.iter().flatten().all(Value::is_number) {
+        Values::Float(
+            values
+                .iter()
+                .map(|value| value.as_ref()?.as_number().ok())
+                .collect(),
+        )
+    } else {
+        Values::Text(
+            values
+                .iter()
+                .map(|value| Some(value.as_ref()?.to_string()))
+                .collect(),
+        )
+    }
+}
+
+impl Table {
+    /// Every Pokédex field, then the derived ones (`slug`, egg cycle steps and
+    /// classification) and the config's computed `fields`.
+    pub fn pokedex(
+        pokemon: &[Pokemon],
+        fields: &BTreeMap<String, String>,
+    ) -> Result<Self, PkmnError> {
+        let int = |name: &str, value: fn(&Pokemon) -> Option<i64>| Column {
+            name: name.to_string(),
+            values: Values::Int(pokemon.iter().map(value).collect()),
+        };
+        let float = |name: &str, value: fn(&Pokemon) -> Option<f32>| Column {
+            name: name.to_string(),
+            values: Values::Float(pokemon.iter().map(|p| value(p).map(widen)).collect()),
+        };
+        let text = |name: &str, value: fn(&Pokemon) -> Option<String>| Column {
+            name: name.to_string(),
+            values: Values::Text(pokemon.iter().map(value).collect()),
+        };
+        let classifications = classification::all_classifications()?
+            .into_iter()
+            .map(|classification| (classification.pokedex_number, classification))
+            .collect::<HashMap<u16, Classification>>();
+        let classification = |name: &str, value: fn(&Classification) -> Option<&String>| Column {
+            name: name.to_string(),
+            values: Values::Text(
+                pokemon
+                    .iter()
+                    .map(|p| value(classifications.get(&p.pokedex_number)?).cloned())
+                    .collect(),
+            ),
+        };
+
+        let mut columns = vec![
+            int("pokedex_number", |p| Some(p.pokedex_number.into())),
+            text("name", |p| non_empty(&p.name)),
+            text("german_name", |p| non_empty(&p.german_name)),
+            text("japanese_name", |p| non_empty(&p.japanese_name)),
+            int("generation", |p| Some(p.generation.into())),
+            text("status", |p| Some(status_name(&p.status).to_string())),
+            text("species", |p| non_empty(&p.species)),
+            text("type_1", |p| non_empty(&p.type_1)),
+            text("type_2", |p| non_empty(&p.type_2)),
+            float("height_m", |p| p.height_m),
+            float("weight_kg", |p| p.weight_kg),
+            int("abilities_number", |p| Some(p.abilities_number.into())),
+            text("ability_1", |p| non_empty(&p.ability_1)),
+            text("ability_2", |p| non_empty(&p.ability_2)),
+            text("ability_hidden", |p| non_empty(&p.ability_hidden)),
+            int("total_points", |p| Some(p.total_points.into())),
+            int("hp", |p| Some(p.hp.into())),
+            int("attack", |p| Some(p.attack.into())),
+            int("defense", |p| Some(p.defense.into())),
+            int("sp_attack", |p| Some(p.sp_attack.into())),
+            int("sp_defense", |p| Some(p.sp_defense.into())),
+            int("speed", |p| Some(p.speed.into())),
+            int("catch_rate", |p| p.catch_rate.map(i64::from)),
+            int("base_friendship", |p| p.base_friendship.map(i64::from)),
+            int("base_experience", |p| p.base_experience.map(i64::from)),
+            text("growth_rate", |p| non_empty(&p.growth_rate)),
+            int("egg_type_number", |p| Some(p.egg_type_number.into())),
+            text("egg_type_1", |p| non_empty(&p.egg_type_1)),
+            text("egg_type_2", |p| non_empty(&p.egg_type_2)),
+            float("percentage_male", |p| p.percentage_male),
+            int("egg_cycles", |p| p.egg_cycles.map(i64::from)),
+            text("slug", |p| Some(p.sprite_name_slug())),
+            int("egg_cycle_min_steps", |p| {
+                p.egg_cycle_stats().map(|stats| stats.min_steps.into())
+            }),
+            int("egg_cycle_max_steps", |p| {
+                p.egg_cycle_stats().map(|stats| stats.max_steps.into())
+            }),
+            classification("color", |c| Some(&c.color)),
+            classification("shape", |c| c.shape.as_ref()),
+            classification("habitat", |c| c.habitat.as_ref()),
+        ];
+
+        let mut computed = fields
+            .keys()
+            .map(|name| (name, Vec::with_capacity(pokemon.len())))
+            .collect::<BTreeMap<&String, Vec<Option<Value>>>>();
+        for p in pokemon {
+            let mut evaluated = computed::evaluate_all(p, fields)
+                .into_iter()
+                .collect::<BTreeMap<String, Value>>();
+            for (name, values) in computed.iter_mut() {
+                values.push(evaluated.remove(*name));
+            }
+        }
+        columns.extend(computed.into_iter().map(|(name, values)| Column {
+            name: format!("fields.{}", name),
+            values: computed_values(values),
+        }));
+
+        Ok(Table {
+            columns,
+            rows: pokemon.len(),
+        })
+    }
+
+    /// Writes a header row, then each row with missing values left empty.
+    pub fn write_csv<W: Write>(&self, writer: W) -> Result<(), Box<dyn Error>> {
+        let mut csv_writer = csv::Writer::from_writer(writer);
+        csv_writer.write_record(self.columns.iter().map(|column| &column.name))?;
+        for row in 0..self.rows {
+            csv_writer.write_record(self.columns.iter().map(|column| match &column.values {
+                Values::Int(values) => values[row].map(|v| v.to_string()).unwrap_or_default(),
+                Values::Float(values) => values[row].map(|v| v.to_string()).unwrap_or_default(),
+                Values::Text(values) => values[row].clone().unwrap_or_default(),
+            }))?;
+        }
+        csv_writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes an array with an object per row, its keys in column order.
+    pub fn write_json<W: Write>(&self, mut writer: W) -> Result<(), Box<dyn Error>> {
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writeln!(writer)?;
+        Ok(())
+    }
+
+    /// Writes a Parquet file with a column per field: 64-bit integers and
+    /// floats, and UTF-8 strings, all nullable.
+    #[cfg(feature = "parquet")]
+    pub fn write_parquet<W: Write + Send>(&self, writer: W) -> Result<(), Box<dyn Error>> {
+        use std::sync::Arc;
+
+        use arrow_array::{ArrayRef, Float64Array, Int64Array, RecordBatch, StringArray};
+        use arrow_schema::{DataType, Field, Schema};
+        use parquet::arrow::ArrowWriter;
+        use parquet::basic::Compression;
+        use parquet::file::properties::WriterProperties;
+
+        let (fields, arrays): (Vec<Field>, Vec<ArrayRef>) = self
+            .columns
+            .iter()
+            .map(|column| {
+                let (data_type, array): (DataType, ArrayRef) = match &column.values {
+                    Values::Int(values) => {
+                        (DataType::Int64, Arc::new(Int64Array::from(values.clone())))
+                    }
+                    Values::Float(values) => (
+                        DataType::Float64,
+                        Arc::new(Float64Array::from(values.clone())),
+                    ),
+                    Values::Text(values) => {
+                        (DataType::Utf8, Arc::new(StringArray::from(values.clone())))
+                    }
+                };
+                (Field::new(&column.name, data_type, true), array)
+            })
+            .unzip();
+        let batch = RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?;
+
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        let mut parquet_writer = ArrowWriter::try_new(writer, batch.schema(), Some(properties))?;
+        parquet_writer.write(&batch)?;
+        parquet_writer.close()?;
+        Ok(())
+    }
+}
+
+struct Row<'a>(&'a Table, usize);
+
+impl Serialize for Row<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Row(table, row) = self;
+        let mut map = serializer.serialize_map(Some(table.columns.len()))?;
+        for column in &table.columns {
+            match &column.values {
+                Values::Int(values) => map.serialize_entry(&column.name, &values[*row])?,
+                Values::Float(values) => map.serialize_entry(&column.name, &values[*row])?,
+                Values::Text(values) => map.serialize_entry(&column.name, &values[*row])?,
+            }
+        }
+        map.end()
+    }
+}
+
+impl Serialize for Table {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.rows))?;
+        for row in 0..self.rows {
+            seq.serialize_element(&Row(self, row))?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::{entries, find_by_id};
+
+    fn column<'a>(table: &'a Table, name: &str) -> &'a Values {
+        &table
+            .columns
+            .iter()
+            .find(|column| column.name == name)
+            .unwrap()
+            .values
+    }
+
+    #[test]
+    fn typed_columns() {
+        let pokemon = vec![
+            find_by_id("pikachu").unwrap(),
+            find_by_id("magnemite").unwrap(),
+        ];
+        let fields = BTreeMap::from([
+            (String::from("bulk"), String::from("hp * defense")),
+            (String::from("ratio"), String::from("attack / 2.0")),
+        ]);
+        let table = Table::pokedex(&pokemon, &fields).unwrap();
+
+        assert_eq!(
+            column(&table, "height_m"),
+            &Values::Float(vec![Some(0.4), Some(0.3)])
+        );
+        assert_eq!(
+            column(&table, "percentage_male"),
+            &Values::Float(vec![Some(50.0), None])
+        );
+        assert_eq!(
+            column(&table, "type_2"),
+            &Values::Text(vec![None, Some(String::from("Steel"))])
+        );
+        assert_eq!(
+            column(&table, "fields.bulk"),
+            &Values::Int(vec![Some(1400), Some(1750)])
+        );
+        assert_eq!(
+            column(&table, "fields.ratio"),
+            &Values::Float(vec![Some(27.5), Some(17.5)])
+        );
+    }
+
+    #[test]
+    fn writes_formats() {
+        let table = Table::pokedex(&entries().unwrap()[..2], &BTreeMap::new()).unwrap();
+
+        let mut csv = Vec::new();
+        table.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.starts_with("pokedex_number,name,german_name,"));
+        assert_eq!(csv.lines().count(), 3);
+
+        let mut json = Vec::new();
+        table.write_json(&mut json).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(json[0]["name"], "Bulbasaur");
+        assert_eq!(json[1]["hp"], 60);
+        assert!(json[0]["fields.bulk"].is_null());
+
+        #[cfg(feature = "parquet")]
+        {
+            let mut parquet = Vec::new();
+            table.write_parquet(&mut parquet).unwrap();
+            assert!(parquet.starts_with(b"PAR1") && parquet.ends_with(b"PAR1"));
+        }
+    }
+}
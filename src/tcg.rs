@@ -0,0 +1,212 @@
+//! Printed trading card game cards, from the Pokémon TCG API. Responses are
+//! kept in `<cache dir>/pkmn/tcg` for a day, as market prices change.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use serde::Deserialize;
+
+use crate::cache::cache_dir;
+use crate::pokedex::Pokemon;
+use crate::sprite::is_network_error;
+
+const API_URL: &str = "https://api.pokemontcg.io/v2/cards";
+
+const MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Deserialize)]
+struct Response {
+    data: Vec<Card>,
+}
+
+#[derive(Deserialize)]
+pub struct Card {
+    /// Number within the set, e.g. `4` or `TG03`.
+    pub number: String,
+    pub rarity: Option<String>,
+    pub set: CardSet,
+    pub images: CardImages,
+    tcgplayer: Option<TcgPlayer>,
+}
+
+#[derive(Deserialize)]
+pub struct CardSet {
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct CardImages {
+    pub large: String,
+}
+
+#[derive(Deserialize)]
+struct TcgPlayer {
+    /// Prices by printing, e.g. `normal` or `reverseHolofoil`.
+    #[serde(default)]
+    prices: BTreeMap<String, Prices>,
+}
+
+#[derive(Deserialize)]
+struct Prices {
+    market: Option<f64>,
+}
+
+impl Card {
+    /// The lowest TCGplayer market price in US dollars, and the printing it's
+    /// for.
+    pub fn market_price(&self) -> Option<(&str, f64)> {
+        self.tcgplayer
+            .as_ref()?
+            .prices
+            .iter()
+            .filter_map(|(printing, prices)| Some((printing.as_str(), prices.market?)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+    }
+}
+
+/// `reverseHolofoil` → `reverse holofoil`
+pub fn printing_name(printing: &str) -> String {
+    printing
+        .chars()
+        .flat_map(|c| match c.is_uppercase() {
+            true => vec![' ', c.to_ascii_lowercase()],
+            false => vec![c],
+        })
+        .collect()
+}
+
+/// Search query for the cards named exactly after the Pokémon.
+fn query(pokemon: &Pokemon) -> String {
+    format!("name:\"{}\"", pokemon.name.replace('"', ""))
+}
+
+fn parse(bytes: &[u8]) -> Result<Vec<Card>, serde_json::Error> {
+    serde_json::from_slice::<Response>(bytes).map(|response| response.data)
+}
+
+fn cache_path(pokemon: &Pokemon) -> Option<PathBuf> {
+    let dir = cache_dir()?.join("tcg");
+    Some(dir.join(format!("{}.json", pokemon.sprite_name_slug())))
+}
+
+fn is_fresh(modified: SystemTime) -> bool {
+    SystemTime::now()
+        .duration_since(modified)
+        .is_ok_and(|age| age < MAX_AGE)
+}
+
+async fn fetch(pokemon: &Pokemon) -> Result<Vec<u8>, Box<dyn Error>> {
+    info!("fetching TCG cards for \"{}\"", pokemon.name);
+
+    let res = reqwest::Client::new()
+        .get(API_URL)
+        .query(&[
+            ("q", query(pokemon).as_str()),
+            ("orderBy", "-set.releaseDate"),
+            ("select", "number,rarity,set,images,tcgplayer"),
+        ])
+        .send()
+        .await?;
+
+    match res.status() {
+        status if status.is_success() => Ok(res.bytes().await?.to_vec()),
+        status => Err(Box::<dyn Error>::from(status.to_string())),
+    }
+}
+
+/// Cards of `pokemon`, newest set first. A cached response is used while it's
+/// less than a day old (unless `refresh` is set), or whenever the API can't be
+/// reached.
+pub async fn cards(pokemon: &Pokemon, refresh: bool) -> Result<Vec<Card>, Box<dyn Error>> {
+    let path = cache_path(pokemon);
+    let cached = path.as_ref().and_then(|path| {
+        let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok()?;
+        Some((fs::read(path).ok()?, modified))
+    });
+
+    if let Some((bytes, modified)) = &cached {
+        if !refresh && is_fresh(*modified) {
+            info!("using cached TCG cards for \"{}\"", pokemon.name);
+            return Ok(parse(bytes)?);
+        }
+    }
+
+    let bytes = match fetch(pokemon).await {
+        Ok(bytes) => bytes,
+        Err(err) if is_network_error(err.as_ref()) => match cached {
+            Some((bytes, _)) => {
+                warn!("using stale TCG cards, the API is unreachable: {}", err);
+                return Ok(parse(&bytes)?);
+            }
+            None => return Err(err),
+        },
+        Err(err) => return Err(err),
+    };
+
+    let cards = parse(&bytes)?;
+    if let Some(path) = path {
+        let written = match path.parent() {
+            Some(dir) => fs::create_dir_all(dir).and_then(|_| fs::write(&path, &bytes)),
+            None => fs::write(&path, &bytes),
+        };
+        if let Err(err) = written {
+            warn!("couldn't cache TCG cards: {}", err);
+        }
+        crate::cache::prune();
+    }
+    Ok(cards)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::find_by_id;
+
+    const RESPONSE: &[u8] = br#"{
+        "data": [
+            {
+                "name": "Charizard",
+                "number": "4",
+                "rarity": "Rare Holo",
+                "set": {"name": "Base", "releaseDate": "1999/01/09"},
+                "images": {"small": "https://example.com/4.png", "large": "https://example.com/4_hires.png"},
+                "tcgplayer": {"prices": {
+                    "holofoil": {"market": 412.5},
+                    "1stEditionHolofoil": {"market": 9800.0}
+                }}
+            },
+            {
+                "name": "Charizard",
+                "number": "TG03",
+                "set": {"name": "Lost Origin Trainer Gallery", "releaseDate": "2022/09/09"},
+                "images": {"small": "https://example.com/TG03.png", "large": "https://example.com/TG03_hires.png"}
+            }
+        ],
+        "page": 1
+    }"#;
+
+    #[test]
+    fn parses_cards() {
+        let cards = parse(RESPONSE).unwrap();
+        assert_eq!(cards.len(), 2);
+        assert_eq!(cards[0].set.name, "Base");
+        assert_eq!(cards[0].market_price(), Some(("holofoil", 412.5)));
+        assert_eq!(cards[1].rarity, None);
+        assert_eq!(cards[1].market_price(), None);
+    }
+
+    #[test]
+    fn printing_names() {
+        assert_eq!(printing_name("reverseHolofoil"), "reverse holofoil");
+        assert_eq!(printing_name("normal"), "normal");
+    }
+
+    #[test]
+    fn queries_exact_names() {
+        let mr_mime = find_by_id("mr-mime").unwrap();
+        assert_eq!(query(&mr_mime), "name:\"Mr. Mime\"");
+    }
+}
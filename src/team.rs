@@ -0,0 +1,660 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::config::config_dir;
+use crate::moves::{self, Flag};
+use crate::pokedex::{Pokemon, PokemonStatus};
+use crate::types::Type;
+
+/// Best multiplier a Pokémon's same-type (STAB) attacks deal to `defender`.
+pub fn stab_effectiveness(pokemon: &Pokemon, defender: Type) -> f32 {
+    pokemon
+        .types()
+        .into_iter()
+        .map(|attacker| attacker.effectiveness(defender))
+        .fold(0.0, f32::max)
+}
+
+/// Types that at least one member hits super effectively with a STAB attack.
+pub fn stab_coverage(team: &[&Pokemon]) -> Vec<Type> {
+    Type::ALL
+        .iter()
+        .copied()
+        .filter(|defender| {
+            team.iter()
+                .any(|pokemon| stab_effectiveness(pokemon, *defender) > 1.0)
+        })
+        .collect()
+}
+
+/// How many members of a team an attacking type hits super effectively, and
+/// how many resist it (immunities included).
+#[derive(Debug, PartialEq)]
+pub struct Matchup {
+    pub attacker: Type,
+    pub weak: usize,
+    pub resist: usize,
+}
+
+impl Matchup {
+    /// More than one member is weak to the type, and they outnumber the
+    /// members that resist it.
+    pub fn is_shared_weakness(&self) -> bool {
+        self.weak > 1 && self.weak > self.resist
+    }
+}
+
+/// The team's matchup against each attacking type, in [`Type::ALL`] order.
+pub fn defensive_matchups(team: &[&Pokemon]) -> Vec<Matchup> {
+    Type::ALL
+        .iter()
+        .map(|attacker| {
+            let multipliers = team
+                .iter()
+                .map(|pokemon| attacker.effectiveness_against(&pokemon.types()))
+                .collect::<Vec<f32>>();
+            Matchup {
+                attacker: *attacker,
+                weak: multipliers.iter().filter(|m| **m > 1.0).count(),
+                resist: multipliers.iter().filter(|m| **m < 1.0).count(),
+            }
+        })
+        .collect()
+}
+
+/// Distinct typings among `pokedex`, in order of first appearance.
+pub fn typings(pokedex: &[Pokemon]) -> Vec<Vec<Type>> {
+    let mut typings: Vec<Vec<Type>> = Vec::new();
+    for pokemon in pokedex {
+        let mut typing = pokemon.types();
+        typing.sort();
+        if !typing.is_empty() && !typings.contains(&typing) {
+            typings.push(typing);
+        }
+    }
+    typings
+}
+
+/// Typings that no member of `core` hits super effectively with a STAB attack.
+pub fn core_gaps(core: &[&Pokemon], typings: &[Vec<Type>]) -> Vec<Vec<Type>> {
+    typings
+        .iter()
+        .filter(|typing| {
+            !core.iter().any(|pokemon| {
+                pokemon
+                    .types()
+                    .into_iter()
+                    .any(|attacker| attacker.effectiveness_against(typing) > 1.0)
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+/// Attacking types by how many of `gaps` they hit super effectively, best
+/// first, leaving out those that hit none.
+pub fn gap_fillers(gaps: &[Vec<Type>]) -> Vec<(Type, usize)> {
+    let mut fillers = Type::ALL
+        .iter()
+        .map(|attacker| {
+            let covered = gaps
+                .iter()
+                .filter(|typing| attacker.effectiveness_against(typing) > 1.0)
+                .count();
+            (*attacker, covered)
+        })
+        .filter(|(_, covered)| *covered > 0)
+        .collect::<Vec<(Type, usize)>>();
+    fillers.sort_by(|(_, a), (_, b)| b.cmp(a));
+    fillers
+}
+
+/// A constraint on team members, e.g. `type:water` or `pokemon:pikachu`.
+pub enum Requirement {
+    Type(Type),
+    Pokemon(String),
+}
+
+impl Requirement {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let (kind, argument) = value
+            .split_once(':')
+            .ok_or_else(|| format!("Expected kind:value, e.g. type:water, got \"{}\"", value))?;
+
+        match kind.trim().to_lowercase().as_str() {
+            "type" => Type::from_name(argument)
+                .map(Requirement::Type)
+                .ok_or_else(|| format!("Unknown type \"{}\"", argument)),
+            "pokemon" => Ok(Requirement::Pokemon(argument.trim().to_lowercase())),
+            _ => Err(format!(
+                "Unknown requirement \"{}\" (expected type or pokemon)",
+                kind
+            )),
+        }
+    }
+
+    fn is_met_by(&self, pokemon: &Pokemon) -> bool {
+        match self {
+            Requirement::Type(required) => pokemon.types().contains(required),
+            Requirement::Pokemon(name) => pokemon.name.to_lowercase() == *name,
+        }
+    }
+}
+
+pub struct TeamConstraints {
+    pub size: usize,
+    /// Maximum sum of the members' base stat totals.
+    pub budget: Option<u32>,
+    pub requirements: Vec<Requirement>,
+    /// Whether the team's STABs must hit every type super effectively.
+    pub cover_all_types: bool,
+    pub allow_legendaries: bool,
+}
+
+/// A team saved as `<config dir>/teams/<name>.toml`:
+///
+/// ```toml
+/// [[members]]
+/// pokemon = "Garchomp"
+/// moves = ["Stealth Rock", "Earthquake", "Dragon Claw", "Swords Dance"]
+/// ```
+#[derive(Deserialize)]
+pub struct SavedTeam {
+    pub members: Vec<TeamMember>,
+}
+
+#[derive(Deserialize)]
+pub struct TeamMember {
+    pub pokemon: String,
+    #[serde(default)]
+    pub moves: Vec<String>,
+}
+
+impl SavedTeam {
+    /// Loads a saved team by name, or from a path to a team file.
+    pub fn load(name: &str) -> Result<Self, String> {
+        let path = match Path::new(name).is_file() {
+            true => Path::new(name).to_path_buf(),
+            false => config_dir()
+                .ok_or("No config directory to load teams from")?
+                .join("teams")
+                .join(format!("{}.toml", name)),
+        };
+
+        let contents = fs::read_to_string(&path)
+            .map_err(|err| format!("Can't read team \"{}\": {}", path.display(), err))?;
+        SavedTeam::parse(&contents)
+            .map_err(|err| format!("Invalid team \"{}\": {}", path.display(), err))
+    }
+
+    fn parse(contents: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(contents)
+    }
+}
+
+/// Support roles a team usually wants covered by at least one move.
+#[derive(Clone, Copy)]
+pub enum Role {
+    Hazards,
+    HazardRemoval,
+    SpeedControl,
+    Pivot,
+}
+
+impl Role {
+    pub const ALL: [Role; 4] = [
+        Role::Hazards,
+        Role::HazardRemoval,
+        Role::SpeedControl,
+        Role::Pivot,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Role::Hazards => "Hazards",
+            Role::HazardRemoval => "Hazard Removal",
+            Role::SpeedControl => "Speed Control",
+            Role::Pivot => "Pivoting",
+        }
+    }
+
+    fn flag(&self) -> Flag {
+        match self {
+            Role::Hazards => Flag::Hazard,
+            Role::HazardRemoval => Flag::HazardRemoval,
+            Role::SpeedControl => Flag::SpeedControl,
+            Role::Pivot => Flag::Pivot,
+        }
+    }
+
+    /// Members' moves filling this role, as (Pokémon, move) pairs. Moves
+    /// missing from the moves dataset are skipped.
+    pub fn providers(&self, team: &SavedTeam) -> Vec<(String, String)> {
+        team.members
+            .iter()
+            .flat_map(|member| {
+                member
+                    .moves
+                    .iter()
+                    .filter_map(|name| moves::find_move(name))
+                    .filter(|m| m.has_flag(self.flag()))
+                    .map(move |m| (member.pokemon.clone(), m.name))
+            })
+            .collect()
+    }
+}
+
+/// Number of partial teams kept at each step of the search.
+const BEAM_WIDTH: usize = 48;
+
+/// A Pokémon eligible for the team, with its contributions precomputed as
+/// bit sets over [`Type::ALL`] and the constraints' requirements.
+struct PoolEntry<'a> {
+    pokemon: &'a Pokemon,
+    coverage: u32,
+    requirements: u32,
+}
+
+impl<'a> PoolEntry<'a> {
+    fn new(pokemon: &'a Pokemon, constraints: &TeamConstraints) -> Self {
+        let coverage = stab_coverage(&[pokemon])
+            .into_iter()
+            .fold(0, |mask, pokemon_type| mask | 1 << pokemon_type as u32);
+        let requirements = constraints
+            .requirements
+            .iter()
+            .enumerate()
+            .filter(|(_, requirement)| requirement.is_met_by(pokemon))
+            .fold(0, |mask, (i, _)| mask | 1 << i);
+
+        PoolEntry {
+            pokemon,
+            coverage,
+            requirements,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Candidate<'a> {
+    members: Vec<&'a Pokemon>,
+    total: u32,
+    coverage: u32,
+    requirements: u32,
+}
+
+impl<'a> Candidate<'a> {
+    /// Search ranking: requirements first, then coverage, then raw stats.
+    fn score(&self) -> (u32, u32, u32) {
+        (
+            self.requirements.count_ones(),
+            self.coverage.count_ones(),
+            self.total,
+        )
+    }
+
+    fn key(&self) -> Vec<u16> {
+        let mut key = self
+            .members
+            .iter()
+            .map(|pokemon| pokemon.pokedex_number)
+            .collect::<Vec<u16>>();
+        key.sort_unstable();
+        key
+    }
+
+    fn is_valid(&self, constraints: &TeamConstraints) -> bool {
+        self.members.len() == constraints.size
+            && self.requirements.count_ones() as usize == constraints.requirements.len()
+            && (!constraints.cover_all_types
+                || self.coverage.count_ones() as usize == Type::ALL.len())
+    }
+}
+
+fn is_eligible(pokemon: &Pokemon, allow_legendaries: bool) -> bool {
+    // Mega Evolutions and Primal Reversions can't all be used at once
+    let temporary_form = pokemon.name.starts_with("Mega ") || pokemon.name.starts_with("Primal ");
+    let legendary = !matches!(pokemon.status, PokemonStatus::Normal);
+    !temporary_form && (allow_legendaries || !legendary)
+}
+
+/// Beam search for teams of distinct species meeting `constraints`, best first.
+pub fn optimize<'a>(
+    pokedex: &'a [Pokemon],
+    constraints: &TeamConstraints,
+    limit: usize,
+) -> Vec<Vec<&'a Pokemon>> {
+    let pool = pokedex
+        .iter()
+        .filter(|pokemon| is_eligible(pokemon, constraints.allow_legendaries))
+        .map(|pokemon| PoolEntry::new(pokemon, constraints))
+        .collect::<Vec<PoolEntry>>();
+
+    let min_total = match pool
+        .iter()
+        .map(|entry| u32::from(entry.pokemon.total_points))
+        .min()
+    {
+        Some(min_total) => min_total,
+        None => return Vec::new(),
+    };
+
+    let mut beam = vec![Candidate {
+        members: Vec::new(),
+        total: 0,
+        coverage: 0,
+        requirements: 0,
+    }];
+
+    for slot in 0..constraints.size {
+        let open_slots = (constraints.size - slot - 1) as u32;
+        let mut seen = HashSet::new();
+        let mut next = Vec::new();
+
+        for candidate in &beam {
+            for entry in &pool {
+                if candidate
+                    .members
+                    .iter()
+                    .any(|member| member.pokedex_number == entry.pokemon.pokedex_number)
+                {
+                    continue;
+                }
+
+                let total = candidate.total + u32::from(entry.pokemon.total_points);
+                if let Some(budget) = constraints.budget {
+                    if total + open_slots * min_total > budget {
+                        continue;
+                    }
+                }
+
+                next.push((candidate, entry, total));
+            }
+        }
+
+        // Rank before building the expanded teams, so only survivors allocate
+        next.sort_by_cached_key(|(candidate, entry, total)| {
+            std::cmp::Reverse((
+                (candidate.requirements | entry.requirements).count_ones(),
+                (candidate.coverage | entry.coverage).count_ones(),
+                *total,
+            ))
+        });
+
+        beam = next
+            .into_iter()
+            .map(|(candidate, entry, total)| {
+                let mut members = candidate.members.clone();
+                members.push(entry.pokemon);
+                Candidate {
+                    members,
+                    total,
+                    coverage: candidate.coverage | entry.coverage,
+                    requirements: candidate.requirements | entry.requirements,
+                }
+            })
+            .filter(|expanded| seen.insert(expanded.key()))
+            .take(BEAM_WIDTH)
+            .collect();
+    }
+
+    beam.sort_by_key(|candidate| std::cmp::Reverse(candidate.score()));
+    beam.into_iter()
+        .filter(|candidate| candidate.is_valid(constraints))
+        .take(limit)
+        .map(|candidate| candidate.members)
+        .collect()
+}
+
+/// Attacking types the Pokémon resists or is immune to, as a bit set over
+/// [`Type::ALL`].
+fn resistances(pokemon: &Pokemon) -> u32 {
+    let types = pokemon.types();
+    Type::ALL
+        .iter()
+        .filter(|attacker| attacker.effectiveness_against(&types) < 1.0)
+        .fold(0, |mask, attacker| mask | 1 << *attacker as u32)
+}
+
+/// Pokémon whose resistances together cover every attacking type.
+pub struct DefensiveCore<'a> {
+    pub members: Vec<&'a Pokemon>,
+    /// Attacking types resisted by more than one member.
+    pub redundancy: u32,
+    pub total: u32,
+}
+
+impl<'a> DefensiveCore<'a> {
+    fn new(members: Vec<&'a Pokemon>) -> Option<Self> {
+        let masks = members
+            .iter()
+            .map(|pokemon| resistances(pokemon))
+            .collect::<Vec<u32>>();
+        let resisted = masks.iter().fold(0, |mask, member| mask | member);
+        if resisted.count_ones() as usize != Type::ALL.len() {
+            return None;
+        }
+
+        let redundancy = (0..Type::ALL.len())
+            .filter(|bit| masks.iter().filter(|mask| *mask & 1 << bit != 0).count() > 1)
+            .count() as u32;
+        let total = members
+            .iter()
+            .map(|pokemon| u32::from(pokemon.total_points))
+            .sum();
+
+        Some(DefensiveCore {
+            members,
+            redundancy,
+            total,
+        })
+    }
+}
+
+/// Cores of `size` Pokémon including `anchor`, most redundant first, then by
+/// base stat total. Only the strongest Pokémon of each typing is considered,
+/// since others of the same typing resist the same types.
+pub fn defensive_cores<'a>(
+    anchor: &'a Pokemon,
+    pokedex: &'a [Pokemon],
+    size: usize,
+    allow_legendaries: bool,
+    limit: usize,
+) -> Vec<DefensiveCore<'a>> {
+    let mut pool: Vec<&Pokemon> = Vec::new();
+    for pokemon in pokedex.iter().filter(|pokemon| {
+        pokemon.pokedex_number != anchor.pokedex_number && is_eligible(pokemon, allow_legendaries)
+    }) {
+        let mut typing = pokemon.types();
+        typing.sort();
+        let same_typing = pool.iter_mut().find(|other| {
+            let mut other_typing = other.types();
+            other_typing.sort();
+            other_typing == typing
+        });
+        match same_typing {
+            Some(other) if other.total_points < pokemon.total_points => *other = pokemon,
+            Some(_) => {}
+            None => pool.push(pokemon),
+        }
+    }
+
+    let mut cores = Vec::new();
+    match size {
+        2 => {
+            for partner in &pool {
+                cores.extend(DefensiveCore::new(vec![anchor, *partner]));
+            }
+        }
+        3 => {
+            for (i, first) in pool.iter().enumerate() {
+                for second in &pool[i + 1..] {
+                    cores.extend(DefensiveCore::new(vec![anchor, *first, *second]));
+                }
+            }
+        }
+        _ => {}
+    }
+
+    cores.sort_by_key(|core| std::cmp::Reverse((core.redundancy, core.total)));
+    cores.truncate(limit);
+    cores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::{all_pokemon, find_by_id};
+
+    #[test]
+    fn roles() {
+        let team = SavedTeam::parse(
+            r#"
+            [[members]]
+            pokemon = "Garchomp"
+            moves = ["Stealth Rock", "Earthquake", "Dragon Claw", "Swords Dance"]
+
+            [[members]]
+            pokemon = "Scizor"
+            moves = ["U-turn", "bullet-punch", "Not A Move"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Role::Hazards.providers(&team),
+            vec![(String::from("Garchomp"), String::from("Stealth Rock"))]
+        );
+        assert_eq!(Role::Pivot.providers(&team).len(), 1);
+        assert!(Role::SpeedControl.providers(&team).is_empty());
+        assert!(Role::HazardRemoval.providers(&team).is_empty());
+    }
+
+    fn constraints() -> TeamConstraints {
+        TeamConstraints {
+            size: 6,
+            budget: Some(3000),
+            requirements: vec![Requirement::parse("type:water").unwrap()],
+            cover_all_types: true,
+            allow_legendaries: false,
+        }
+    }
+
+    #[test]
+    fn coverage() {
+        let charizard = find_by_id("charizard").unwrap();
+        let coverage = stab_coverage(&[&charizard]);
+        assert!(coverage.contains(&Type::Grass));
+        assert!(coverage.contains(&Type::Fighting));
+        assert!(!coverage.contains(&Type::Water));
+    }
+
+    #[test]
+    fn shared_weaknesses() {
+        let team = ["charizard", "gyarados", "venusaur"].map(|id| find_by_id(id).unwrap());
+        let matchups = defensive_matchups(&team.iter().collect::<Vec<&Pokemon>>());
+        let matchup = |attacker: Type| &matchups[attacker as usize];
+
+        assert_eq!(
+            *matchup(Type::Rock),
+            Matchup {
+                attacker: Type::Rock,
+                weak: 2,
+                resist: 0
+            }
+        );
+        assert!(matchup(Type::Electric).is_shared_weakness());
+        // Both flyers are immune
+        assert_eq!(matchup(Type::Ground).resist, 2);
+        assert!(!matchup(Type::Ice).is_shared_weakness());
+        assert_eq!(
+            matchups
+                .iter()
+                .filter(|matchup| matchup.is_shared_weakness())
+                .map(|matchup| matchup.attacker)
+                .collect::<Vec<Type>>(),
+            vec![Type::Electric, Type::Rock]
+        );
+    }
+
+    #[test]
+    fn gaps_of_core() {
+        let pokedex = all_pokemon().unwrap();
+        let typings = typings(&pokedex);
+        let venusaur = find_by_id("venusaur").unwrap();
+        let charizard = find_by_id("charizard").unwrap();
+        let blastoise = find_by_id("blastoise").unwrap();
+
+        let gaps = core_gaps(&[&venusaur, &charizard, &blastoise], &typings);
+        assert!(gaps.contains(&vec![Type::Water, Type::Dragon]));
+        assert!(!gaps.contains(&vec![Type::Grass]));
+
+        let fillers = gap_fillers(&gaps);
+        assert!(fillers
+            .iter()
+            .any(|(attacker, _)| *attacker == Type::Dragon));
+    }
+
+    #[test]
+    fn parse_requirements() {
+        assert!(matches!(
+            Requirement::parse("type:Water"),
+            Ok(Requirement::Type(Type::Water))
+        ));
+        assert!(Requirement::parse("type:sound").is_err());
+        assert!(Requirement::parse("water").is_err());
+    }
+
+    #[test]
+    fn optimized_teams_meet_constraints() {
+        let pokedex = all_pokemon().unwrap();
+        let constraints = constraints();
+        let teams = optimize(&pokedex, &constraints, 3);
+
+        assert!(!teams.is_empty());
+        for team in teams {
+            assert_eq!(team.len(), 6);
+            let total: u32 = team
+                .iter()
+                .map(|pokemon| u32::from(pokemon.total_points))
+                .sum();
+            assert!(total <= 3000);
+            assert!(team
+                .iter()
+                .any(|pokemon| pokemon.types().contains(&Type::Water)));
+            assert_eq!(stab_coverage(&team).len(), Type::ALL.len());
+        }
+    }
+
+    #[test]
+    fn impossible_budget() {
+        let pokedex = all_pokemon().unwrap();
+        let constraints = TeamConstraints {
+            budget: Some(100),
+            ..constraints()
+        };
+        assert!(optimize(&pokedex, &constraints, 3).is_empty());
+    }
+
+    #[test]
+    fn defensive_cores_resist_everything() {
+        let pokedex = all_pokemon().unwrap();
+        let toxapex = find_by_id("toxapex").unwrap();
+        let cores = defensive_cores(&toxapex, &pokedex, 3, false, 5);
+
+        assert_eq!(cores.len(), 5);
+        for core in &cores {
+            assert_eq!(core.members[0].name, "Toxapex");
+            for attacker in Type::ALL {
+                assert!(core
+                    .members
+                    .iter()
+                    .any(|pokemon| attacker.effectiveness_against(&pokemon.types()) < 1.0));
+            }
+        }
+        assert!(cores[0].redundancy >= cores[4].redundancy);
+    }
+}
@@ -0,0 +1,144 @@
+//! Terastallization (Generation 9): a Pokémon's defensive typing becomes its
+//! Tera type alone, while its attacks keep their original STAB and gain STAB
+//! in the Tera type too.
+
+use crate::pokedex::Pokemon;
+use crate::types::Type;
+
+/// STAB bonus for a Tera type matching one of the Pokémon's original types.
+const STACKED_STAB: f32 = 2.0;
+const STAB: f32 = 1.5;
+
+/// Same-type attack bonus for a move of `move_type` once terastallized.
+pub fn stab_multiplier(pokemon: &Pokemon, tera_type: Type, move_type: Type) -> f32 {
+    let original = pokemon.types().contains(&move_type);
+    match (original, move_type == tera_type) {
+        (true, true) => STACKED_STAB,
+        (true, false) | (false, true) => STAB,
+        (false, false) => 1.0,
+    }
+}
+
+/// How a Tera type would change a Pokémon's matchups.
+pub struct TeraOption {
+    pub tera_type: Type,
+    /// Attacking types that are super effective against the Tera type.
+    pub weaknesses: Vec<Type>,
+    /// Attacking types the Tera type resists or is immune to.
+    pub resistances: Vec<Type>,
+    /// Types newly hit super effectively by STAB attacks, i.e. not already
+    /// covered by the original types.
+    pub new_coverage: Vec<Type>,
+    /// Whether the Tera type matches an original type, stacking STAB.
+    pub stacks_stab: bool,
+    /// Higher is a better defensive pivot given the team.
+    pub defensive_score: i32,
+}
+
+impl TeraOption {
+    fn new(pokemon: &Pokemon, tera_type: Type, team: &[&Pokemon]) -> Self {
+        let weaknesses = Type::ALL
+            .iter()
+            .copied()
+            .filter(|attacker| attacker.effectiveness(tera_type) > 1.0)
+            .collect::<Vec<Type>>();
+        let resistances = Type::ALL
+            .iter()
+            .copied()
+            .filter(|attacker| attacker.effectiveness(tera_type) < 1.0)
+            .collect::<Vec<Type>>();
+
+        let original = pokemon.types();
+        let new_coverage = Type::ALL
+            .iter()
+            .copied()
+            .filter(|defender| tera_type.effectiveness(*defender) > 1.0)
+            .filter(|defender| {
+                !original
+                    .iter()
+                    .any(|attacker| attacker.effectiveness(*defender) > 1.0)
+            })
+            .collect();
+
+        // Each resistance earns a point and each weakness costs one, plus one
+        // more for every teammate weak to that type
+        let weight = |attacker: &Type| {
+            1 + team
+                .iter()
+                .filter(|member| attacker.effectiveness_against(&member.types()) > 1.0)
+                .count() as i32
+        };
+        let defensive_score = resistances.iter().map(weight).sum::<i32>()
+            - weaknesses.iter().map(weight).sum::<i32>();
+
+        TeraOption {
+            tera_type,
+            weaknesses,
+            resistances,
+            new_coverage,
+            stacks_stab: original.contains(&tera_type),
+            defensive_score,
+        }
+    }
+}
+
+/// Every Tera type for `pokemon`, best defensive pivot for `team` first.
+/// `team` may be empty, in which case it's resistances minus weaknesses.
+pub fn options(pokemon: &Pokemon, team: &[&Pokemon]) -> Vec<TeraOption> {
+    let mut options = Type::ALL
+        .iter()
+        .map(|tera_type| TeraOption::new(pokemon, *tera_type, team))
+        .collect::<Vec<TeraOption>>();
+    options.sort_by(|a, b| {
+        b.defensive_score
+            .cmp(&a.defensive_score)
+            .then(b.resistances.len().cmp(&a.resistances.len()))
+    });
+    options
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::find_by_id;
+
+    #[test]
+    fn stab() {
+        let garchomp = find_by_id("garchomp").unwrap();
+        assert_eq!(stab_multiplier(&garchomp, Type::Dragon, Type::Dragon), 2.0);
+        assert_eq!(stab_multiplier(&garchomp, Type::Steel, Type::Ground), 1.5);
+        assert_eq!(stab_multiplier(&garchomp, Type::Steel, Type::Steel), 1.5);
+        assert_eq!(stab_multiplier(&garchomp, Type::Steel, Type::Fire), 1.0);
+    }
+
+    #[test]
+    fn steel_pivot() {
+        let garchomp = find_by_id("garchomp").unwrap();
+        let options = options(&garchomp, &[]);
+        let best = &options[0];
+        assert_eq!(best.tera_type, Type::Steel);
+        assert_eq!(
+            best.weaknesses,
+            vec![Type::Fire, Type::Fighting, Type::Ground]
+        );
+
+        let dragon = options
+            .iter()
+            .find(|option| option.tera_type == Type::Dragon)
+            .unwrap();
+        assert!(dragon.stacks_stab);
+        assert!(dragon.new_coverage.is_empty());
+    }
+
+    #[test]
+    fn team_context() {
+        let garchomp = find_by_id("garchomp").unwrap();
+        let dragonite = find_by_id("dragonite").unwrap();
+        let salamence = find_by_id("salamence").unwrap();
+        let team = [&dragonite, &salamence];
+
+        // Both teammates are weak to Ice, so resisting it counts for more
+        let options = options(&garchomp, &team);
+        assert!(options[0].resistances.contains(&Type::Ice));
+    }
+}
@@ -0,0 +1,447 @@
+//! What the terminal can display, detected once and used by `Printer` to pick
+//! renderers, so output degrades gracefully instead of each feature checking
+//! for itself.
+
+use std::env;
+use std::fmt;
+use std::sync::OnceLock;
+
+use console::Term;
+use serde::Deserialize;
+
+use crate::pager;
+
+/// Widest layout the card is designed for.
+pub const MAX_WIDTH: u16 = 80;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorSupport {
+    None,
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+impl ColorSupport {
+    /// Names the config's `colors` accepts.
+    pub const NAMES: [&'static str; 4] = ["none", "16", "256", "truecolor"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(ColorSupport::None),
+            "16" => Some(ColorSupport::Ansi16),
+            "256" => Some(ColorSupport::Ansi256),
+            "truecolor" => Some(ColorSupport::TrueColor),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ColorSupport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ColorSupport::None => "None",
+            ColorSupport::Ansi16 => "16 colors",
+            ColorSupport::Ansi256 => "256 colors",
+            ColorSupport::TrueColor => "True color",
+        })
+    }
+}
+
+/// How images (e.g. sprites) are drawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageRenderer {
+    Kitty,
+    Iterm,
+    Sixel,
+    /// Colored Unicode half blocks, which work in most modern terminals.
+    Blocks,
+    /// Characters by brightness, colored if the terminal has colors, which
+    /// work in any terminal.
+    Ascii,
+    /// Images aren't shown at all, e.g. when output is piped.
+    None,
+}
+
+impl ImageRenderer {
+    /// Names `--sprite-renderer` accepts, where `auto` means detecting it.
+    pub const NAMES: [&'static str; 6] = ["auto", "kitty", "iterm", "sixel", "blocks", "ascii"];
+
+    /// The renderer named, or `None` for `auto`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "kitty" => Some(ImageRenderer::Kitty),
+            "iterm" => Some(ImageRenderer::Iterm),
+            "sixel" => Some(ImageRenderer::Sixel),
+            "blocks" => Some(ImageRenderer::Blocks),
+            "ascii" => Some(ImageRenderer::Ascii),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ImageRenderer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ImageRenderer::Kitty => "Kitty graphics protocol",
+            ImageRenderer::Iterm => "iTerm inline images",
+            ImageRenderer::Sixel => "Sixel graphics",
+            ImageRenderer::Blocks => "Unicode half blocks",
+            ImageRenderer::Ascii => "ASCII art",
+            ImageRenderer::None => "Not shown",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Whether output goes to a terminal rather than a pipe or file.
+    pub is_term: bool,
+    pub colors: ColorSupport,
+    pub unicode: bool,
+    pub images: ImageRenderer,
+    /// Columns available to the layout, at most [`MAX_WIDTH`].
+    pub width: u16,
+}
+
+/// Whether colors are turned off, by `--no-color` or the `NO_COLOR`
+/// convention (<https://no-color.org>).
+pub fn no_color() -> bool {
+    env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// Turns colors off for the rest of the run, including the child process
+/// started for paging.
+pub fn disable_colors() {
+    env::set_var("NO_COLOR", "1");
+    console::set_colors_enabled(false);
+    console::set_colors_enabled_stderr(false);
+}
+
+/// Terminal settings under `[terminal]` in the config, for when detection
+/// gets it wrong.
+///
+/// ```toml
+/// [terminal]
+/// colors = "256"
+/// sprite_renderer = "blocks"
+/// ```
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct TerminalConfig {
+    /// One of [`ColorSupport::NAMES`].
+    pub colors: Option<String>,
+    /// One of [`ImageRenderer::NAMES`].
+    pub sprite_renderer: Option<String>,
+}
+
+/// Capabilities chosen by flags or the config rather than detected. They
+/// only apply when output goes to a terminal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Overrides {
+    pub colors: Option<ColorSupport>,
+    pub images: Option<ImageRenderer>,
+}
+
+impl Overrides {
+    /// Overrides from the config, replaced by any given on the command line:
+    /// `--no-color` and a `--sprite-renderer` other than `auto`.
+    pub fn new(
+        config: &TerminalConfig,
+        no_color_flag: bool,
+        sprite_renderer: Option<&str>,
+    ) -> Self {
+        let colors = match no_color_flag {
+            true => Some(ColorSupport::None),
+            false => config.colors.as_deref().and_then(|name| {
+                let colors = ColorSupport::from_name(name);
+                if colors.is_none() {
+                    warn!(
+                        "ignoring unknown terminal colors \"{}\" in the config, expected one of: {}",
+                        name,
+                        ColorSupport::NAMES.join(", ")
+                    );
+                }
+                colors
+            }),
+        };
+        let images = sprite_renderer
+            .and_then(ImageRenderer::from_name)
+            .or_else(|| {
+                let name = config.sprite_renderer.as_deref()?;
+                if !ImageRenderer::NAMES.contains(&name) {
+                    warn!(
+                        "ignoring unknown sprite renderer \"{}\" in the config, expected one of: {}",
+                        name,
+                        ImageRenderer::NAMES.join(", ")
+                    );
+                }
+                ImageRenderer::from_name(name)
+            });
+        Overrides { colors, images }
+    }
+}
+
+static OVERRIDES: OnceLock<Overrides> = OnceLock::new();
+
+/// Sets the overrides [`Capabilities::detect`] applies for the rest of the
+/// run; call it before anything prints.
+pub fn set_overrides(overrides: Overrides) {
+    if OVERRIDES.set(overrides).is_err() {
+        warn!("terminal overrides were already set");
+    }
+}
+
+/// Environment variables capabilities are detected from, for reporting.
+pub const ENV_VARS: [&str; 9] = [
+    "NO_COLOR",
+    "TERM",
+    "COLORTERM",
+    "TERM_PROGRAM",
+    "LC_TERMINAL",
+    "KITTY_WINDOW_ID",
+    "LC_ALL",
+    "LC_CTYPE",
+    "LANG",
+];
+
+impl Capabilities {
+    pub fn detect() -> Self {
+        let term = Term::stdout();
+        let columns = term.size_checked().map(|(_, columns)| columns);
+        // Paged output is piped to the pager, but still ends up on the terminal
+        let is_term = term.is_term() || pager::is_paged();
+        let overrides = OVERRIDES.get().copied().unwrap_or_default();
+        Capabilities::from_env(|name| env::var(name).ok(), is_term, columns, overrides)
+    }
+
+    /// Capabilities from environment variables (looked up with `var`),
+    /// whether stdout is a terminal, and its width if known. `overrides`
+    /// take precedence over what the environment says.
+    pub fn from_env<F>(var: F, is_term: bool, columns: Option<u16>, overrides: Overrides) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let term = var("TERM").unwrap_or_default();
+        let colorterm = var("COLORTERM").unwrap_or_default();
+        let term_program = var("TERM_PROGRAM").unwrap_or_default();
+
+        let no_color = var("NO_COLOR").is_some_and(|value| !value.is_empty());
+        let colors = if !is_term {
+            ColorSupport::None
+        } else if let Some(colors) = overrides.colors {
+            colors
+        } else if term == "dumb" || no_color {
+            ColorSupport::None
+        } else if colorterm.contains("truecolor") || colorterm.contains("24bit") {
+            ColorSupport::TrueColor
+        } else if term.contains("256color") {
+            ColorSupport::Ansi256
+        } else {
+            ColorSupport::Ansi16
+        };
+
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .find_map(|name| var(name).filter(|value| !value.is_empty()))
+            .unwrap_or_default()
+            .to_lowercase();
+        let unicode = cfg!(windows) || locale.contains("utf-8") || locale.contains("utf8");
+
+        let images = if !is_term {
+            ImageRenderer::None
+        } else if let Some(images) = overrides.images {
+            images
+        } else if term == "xterm-kitty" || var("KITTY_WINDOW_ID").is_some() {
+            ImageRenderer::Kitty
+        } else if ["iTerm.app", "WezTerm", "mintty"].contains(&term_program.as_str())
+            || var("LC_TERMINAL").as_deref() == Some("iTerm2")
+        {
+            ImageRenderer::Iterm
+        } else if term.contains("sixel") || term.starts_with("foot") || term.starts_with("mlterm") {
+            ImageRenderer::Sixel
+        } else if unicode && colors >= ColorSupport::Ansi256 {
+            ImageRenderer::Blocks
+        } else {
+            ImageRenderer::Ascii
+        };
+
+        Capabilities {
+            is_term,
+            colors,
+            unicode,
+            images,
+            width: columns.unwrap_or(MAX_WIDTH).min(MAX_WIDTH),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect_with(
+        vars: &[(&str, &str)],
+        is_term: bool,
+        columns: Option<u16>,
+        overrides: Overrides,
+    ) -> Capabilities {
+        Capabilities::from_env(
+            |name| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            },
+            is_term,
+            columns,
+            overrides,
+        )
+    }
+
+    fn detect(vars: &[(&str, &str)], is_term: bool, columns: Option<u16>) -> Capabilities {
+        detect_with(vars, is_term, columns, Overrides::default())
+    }
+
+    const UTF8: (&str, &str) = ("LANG", "en_US.UTF-8");
+
+    struct Case {
+        vars: &'static [(&'static str, &'static str)],
+        is_term: bool,
+        colors: ColorSupport,
+        unicode: bool,
+        images: ImageRenderer,
+    }
+
+    #[test]
+    fn degradation_matrix() {
+        use ColorSupport::*;
+        use ImageRenderer as Images;
+
+        let cases = [
+            Case {
+                vars: &[("TERM", "xterm-kitty"), UTF8],
+                is_term: true,
+                colors: Ansi16,
+                unicode: true,
+                images: Images::Kitty,
+            },
+            Case {
+                vars: &[
+                    ("TERM_PROGRAM", "iTerm.app"),
+                    ("COLORTERM", "truecolor"),
+                    UTF8,
+                ],
+                is_term: true,
+                colors: TrueColor,
+                unicode: true,
+                images: Images::Iterm,
+            },
+            Case {
+                vars: &[("TERM", "xterm-256color"), UTF8],
+                is_term: true,
+                colors: Ansi256,
+                unicode: true,
+                images: Images::Blocks,
+            },
+            Case {
+                vars: &[("TERM", "foot"), UTF8],
+                is_term: true,
+                colors: Ansi16,
+                unicode: true,
+                images: Images::Sixel,
+            },
+            Case {
+                vars: &[("TERM", "xterm-256color"), ("LANG", "C")],
+                is_term: true,
+                colors: Ansi256,
+                unicode: false,
+                images: Images::Ascii,
+            },
+            Case {
+                vars: &[("TERM", "xterm"), UTF8],
+                is_term: true,
+                colors: Ansi16,
+                unicode: true,
+                images: Images::Ascii,
+            },
+            Case {
+                vars: &[("TERM", "dumb"), UTF8],
+                is_term: true,
+                colors: None,
+                unicode: true,
+                images: Images::Ascii,
+            },
+            Case {
+                vars: &[("TERM", "xterm-kitty"), UTF8],
+                is_term: false,
+                colors: None,
+                unicode: true,
+                images: Images::None,
+            },
+            Case {
+                vars: &[
+                    ("TERM", "xterm-256color"),
+                    ("LC_ALL", "C.UTF-8"),
+                    ("LANG", "C"),
+                ],
+                is_term: true,
+                colors: Ansi256,
+                unicode: true,
+                images: Images::Blocks,
+            },
+            Case {
+                vars: &[("TERM", "xterm-256color"), ("NO_COLOR", "1"), UTF8],
+                is_term: true,
+                colors: None,
+                unicode: true,
+                images: Images::Ascii,
+            },
+            Case {
+                vars: &[("TERM", "xterm-256color"), ("NO_COLOR", ""), UTF8],
+                is_term: true,
+                colors: Ansi256,
+                unicode: true,
+                images: Images::Blocks,
+            },
+        ];
+
+        for case in cases {
+            let capabilities = detect(case.vars, case.is_term, Some(120));
+            assert_eq!(capabilities.colors, case.colors, "{:?}", case.vars);
+            assert_eq!(capabilities.unicode, case.unicode, "{:?}", case.vars);
+            assert_eq!(capabilities.images, case.images, "{:?}", case.vars);
+        }
+    }
+
+    #[test]
+    fn width() {
+        assert_eq!(detect(&[], true, Some(200)).width, MAX_WIDTH);
+        assert_eq!(detect(&[], true, Some(60)).width, 60);
+        assert_eq!(detect(&[], false, None).width, MAX_WIDTH);
+    }
+
+    #[test]
+    fn overrides_win_over_the_environment() {
+        let config = TerminalConfig {
+            colors: Some(String::from("truecolor")),
+            sprite_renderer: Some(String::from("kitty")),
+        };
+        let vars = [("TERM", "xterm"), ("NO_COLOR", "1"), UTF8];
+
+        let configured = detect_with(&vars, true, None, Overrides::new(&config, false, None));
+        assert_eq!(configured.colors, ColorSupport::TrueColor);
+        assert_eq!(configured.images, ImageRenderer::Kitty);
+
+        let flags = Overrides::new(&config, true, Some("blocks"));
+        let flagged = detect_with(&vars, true, None, flags);
+        assert_eq!(flagged.colors, ColorSupport::None);
+        assert_eq!(flagged.images, ImageRenderer::Blocks);
+
+        // Piped output stays plain whatever the overrides say
+        let piped = detect_with(&vars, false, None, flags);
+        assert_eq!(piped.images, ImageRenderer::None);
+
+        let auto = Overrides::new(&TerminalConfig::default(), false, Some("auto"));
+        assert_eq!(auto, Overrides::default());
+    }
+}
@@ -0,0 +1,133 @@
+//! Exporting and importing the trackers kept in the config directory (hunts
+//! and learning profiles) as CSV or JSON. Each tracker has a flat record type
+//! whose fields are the columns, the same in both formats, so history can be
+//! analyzed in other tools or moved over from a spreadsheet.
+
+use std::error::Error;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Trackers that can be exported and imported.
+pub const TRACKERS: [&str; 2] = ["hunts", "learn"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+impl Format {
+    pub const NAMES: [&'static str; 2] = ["csv", "json"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "csv" => Some(Format::Csv),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+
+    /// The format a file's extension names, if any.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        Format::from_name(path.extension()?.to_str()?)
+    }
+}
+
+/// Writes `records` as CSV with a header row, or as a JSON array.
+pub fn write<T: Serialize, W: Write>(
+    records: &[T],
+    format: Format,
+    mut writer: W,
+) -> Result<(), Box<dyn Error>> {
+    match format {
+        Format::Csv => {
+            let mut csv_writer = csv::Writer::from_writer(writer);
+            for record in records {
+                csv_writer.serialize(record)?;
+            }
+            csv_writer.flush()?;
+        }
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut writer, records)?;
+            writeln!(writer)?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads records written by [`write`], or by hand: CSV columns can be in any
+/// order, and those with defaults can be left out.
+pub fn read<T: DeserializeOwned, R: Read>(
+    reader: R,
+    format: Format,
+) -> Result<Vec<T>, Box<dyn Error>> {
+    match format {
+        Format::Csv => {
+            let mut csv_reader = csv::Reader::from_reader(reader);
+            let mut records = Vec::new();
+            for (row, result) in csv_reader.deserialize().enumerate() {
+                // Row 1 is the header
+                records.push(result.map_err(|err| format!("row {}: {}", row + 2, err))?);
+            }
+            Ok(records)
+        }
+        Format::Json => Ok(serde_json::from_reader(reader)?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct Record {
+        name: String,
+        #[serde(default)]
+        count: u32,
+    }
+
+    #[test]
+    fn round_trips() {
+        let records = vec![
+            Record {
+                name: String::from("Charmander, shiny"),
+                count: 312,
+            },
+            Record {
+                name: String::from("Ralts"),
+                count: 0,
+            },
+        ];
+        for format in [Format::Csv, Format::Json] {
+            let mut bytes = Vec::new();
+            write(&records, format, &mut bytes).unwrap();
+            let read: Vec<Record> = read(bytes.as_slice(), format).unwrap();
+            assert_eq!(read, records, "{:?}", format);
+        }
+
+        let mut csv = Vec::new();
+        write(&records, Format::Csv, &mut csv).unwrap();
+        assert!(String::from_utf8(csv)
+            .unwrap()
+            .starts_with("name,count\n\"Charmander, shiny\",312\n"));
+    }
+
+    #[test]
+    fn reads_spreadsheets() {
+        let records: Vec<Record> = read("name\nRalts\n".as_bytes(), Format::Csv).unwrap();
+        assert_eq!(records[0].count, 0);
+
+        let err = read::<Record, _>("name,count\nRalts,lots\n".as_bytes(), Format::Csv);
+        assert!(err.unwrap_err().to_string().starts_with("row 2:"));
+
+        assert_eq!(
+            Format::from_path(Path::new("hunts.JSON")),
+            Some(Format::Json)
+        );
+        assert_eq!(Format::from_path(Path::new("hunts")), None);
+    }
+}
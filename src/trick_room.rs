@@ -0,0 +1,95 @@
+use crate::calc::stats::{stat, Nature, Stat};
+use crate::pokedex::Pokemon;
+
+/// The level Pokémon are assessed at.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    /// Doubles, at level 50.
+    Vgc,
+    /// Smogon-style singles, at level 100.
+    Singles,
+}
+
+impl Format {
+    pub const NAMES: [&'static str; 2] = ["vgc", "singles"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "vgc" => Some(Format::Vgc),
+            "singles" => Some(Format::Singles),
+            _ => None,
+        }
+    }
+
+    pub fn level(&self) -> u16 {
+        match self {
+            Format::Vgc => 50,
+            Format::Singles => 100,
+        }
+    }
+}
+
+/// How well a Pokémon works under Trick Room, where the slowest move first.
+pub struct Viability {
+    /// Speed with 0 IVs, no EVs and a Speed-lowering nature.
+    pub min_speed: u16,
+    /// The better of the base Attack and Sp. Attack.
+    pub offense: u16,
+    /// Geometric mean of base HP and the average base defense.
+    pub bulk: f64,
+    /// `offense + bulk - 2 * min_speed`: strong, sturdy and slow scores
+    /// highest.
+    pub score: f64,
+}
+
+impl Viability {
+    pub fn new(pokemon: &Pokemon, format: Format) -> Self {
+        let hindering = Nature::from_name("Brave").unwrap();
+        let min_speed = stat(Stat::Speed, pokemon.speed, 0, 0, format.level(), &hindering);
+        let offense = pokemon.attack.max(pokemon.sp_attack);
+        let defenses = f64::from(pokemon.defense + pokemon.sp_defense) / 2.0;
+        let bulk = (f64::from(pokemon.hp) * defenses).sqrt();
+
+        Viability {
+            min_speed,
+            offense,
+            bulk,
+            score: f64::from(offense) + bulk - 2.0 * f64::from(min_speed),
+        }
+    }
+}
+
+/// `pokemon` with their viability, most viable first.
+pub fn rank(pokemon: Vec<&Pokemon>, format: Format) -> Vec<(&Pokemon, Viability)> {
+    let mut ranked = pokemon
+        .into_iter()
+        .map(|pokemon| (pokemon, Viability::new(pokemon, format)))
+        .collect::<Vec<(&Pokemon, Viability)>>();
+    ranked.sort_by(|(_, a), (_, b)| b.score.partial_cmp(&a.score).unwrap());
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::find_by_id;
+
+    #[test]
+    fn min_speed() {
+        let torkoal = find_by_id("torkoal").unwrap();
+        assert_eq!(Viability::new(&torkoal, Format::Vgc).min_speed, 22);
+        assert_eq!(Viability::new(&torkoal, Format::Singles).min_speed, 40);
+    }
+
+    #[test]
+    fn ranks_slow_and_strong_first() {
+        let snorlax = find_by_id("snorlax").unwrap();
+        let shuckle = find_by_id("shuckle").unwrap();
+        let jolteon = find_by_id("jolteon").unwrap();
+        let names = rank(vec![&jolteon, &shuckle, &snorlax], Format::Vgc)
+            .into_iter()
+            .map(|(pokemon, _)| pokemon.name.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(names, vec!["Snorlax", "Shuckle", "Jolteon"]);
+    }
+}
@@ -0,0 +1,149 @@
+use std::fmt;
+
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum Type {
+    Normal,
+    Fire,
+    Water,
+    Electric,
+    Grass,
+    Ice,
+    Fighting,
+    Poison,
+    Ground,
+    Flying,
+    Psychic,
+    Bug,
+    Rock,
+    Ghost,
+    Dragon,
+    Dark,
+    Steel,
+    Fairy,
+}
+
+use Type::*;
+
+/// Damage multipliers from Generation 6 onwards, indexed by attacking then
+/// defending type, in the order of [`Type::ALL`].
+#[rustfmt::skip]
+const CHART: [[f32; 18]; 18] = {
+    const H: f32 = 0.5;
+    [
+        [1., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1., H, 0., 1., 1., H, 1.], // Normal
+        [1., H, H, 1., 2., 2., 1., 1., 1., 1., 1., 2., H, 1., H, 1., 2., 1.], // Fire
+        [1., 2., H, 1., H, 1., 1., 1., 2., 1., 1., 1., 2., 1., H, 1., 1., 1.], // Water
+        [1., 1., 2., H, H, 1., 1., 1., 0., 2., 1., 1., 1., 1., H, 1., 1., 1.], // Electric
+        [1., H, 2., 1., H, 1., 1., H, 2., H, 1., H, 2., 1., H, 1., H, 1.], // Grass
+        [1., H, H, 1., 2., H, 1., 1., 2., 2., 1., 1., 1., 1., 2., 1., H, 1.], // Ice
+        [2., 1., 1., 1., 1., 2., 1., H, 1., H, H, H, 2., 0., 1., 2., 2., H], // Fighting
+        [1., 1., 1., 1., 2., 1., 1., H, H, 1., 1., 1., H, H, 1., 1., 0., 2.], // Poison
+        [1., 2., 1., 2., H, 1., 1., 2., 1., 0., 1., H, 2., 1., 1., 1., 2., 1.], // Ground
+        [1., 1., 1., H, 2., 1., 2., 1., 1., 1., 1., 2., H, 1., 1., 1., H, 1.], // Flying
+        [1., 1., 1., 1., 1., 1., 2., 2., 1., 1., H, 1., 1., 1., 1., 0., H, 1.], // Psychic
+        [1., H, 1., 1., 2., 1., H, H, 1., H, 2., 1., 1., H, 1., 2., H, H], // Bug
+        [1., 2., 1., 1., 1., 2., H, 1., H, 2., 1., 2., 1., 1., 1., 1., H, 1.], // Rock
+        [0., 1., 1., 1., 1., 1., 1., 1., 1., 1., 2., 1., 1., 2., 1., H, 1., 1.], // Ghost
+        [1., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1., 1., 2., 1., H, 0.], // Dragon
+        [1., 1., 1., 1., 1., 1., H, 1., 1., 1., 2., 1., 1., 2., 1., H, 1., H], // Dark
+        [1., H, H, H, 1., 2., 1., 1., 1., 1., 1., 1., 2., 1., 1., 1., H, 2.], // Steel
+        [1., H, 1., 1., 1., 1., 2., H, 1., 1., 1., 1., 1., 1., 2., 2., H, 1.], // Fairy
+    ]
+};
+
+impl Type {
+    pub const ALL: [Type; 18] = [
+        Normal, Fire, Water, Electric, Grass, Ice, Fighting, Poison, Ground, Flying, Psychic, Bug,
+        Rock, Ghost, Dragon, Dark, Steel, Fairy,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Normal => "Normal",
+            Fire => "Fire",
+            Water => "Water",
+            Electric => "Electric",
+            Grass => "Grass",
+            Ice => "Ice",
+            Fighting => "Fighting",
+            Poison => "Poison",
+            Ground => "Ground",
+            Flying => "Flying",
+            Psychic => "Psychic",
+            Bug => "Bug",
+            Rock => "Rock",
+            Ghost => "Ghost",
+            Dragon => "Dragon",
+            Dark => "Dark",
+            Steel => "Steel",
+            Fairy => "Fairy",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Type::ALL
+            .iter()
+            .find(|pokemon_type| pokemon_type.name().eq_ignore_ascii_case(name.trim()))
+            .copied()
+    }
+
+    /// The generation the type was added in: Dark and Steel in 2, Fairy in 6.
+    pub fn introduced_in(&self) -> u8 {
+        match self {
+            Dark | Steel => 2,
+            Fairy => 6,
+            _ => 1,
+        }
+    }
+
+    /// Damage multiplier of a move of this type against a single type.
+    pub fn effectiveness(self, defender: Type) -> f32 {
+        CHART[self as usize][defender as usize]
+    }
+
+    /// Damage multiplier of a move of this type against a (dual-)typed Pokémon.
+    pub fn effectiveness_against(self, defenders: &[Type]) -> f32 {
+        defenders
+            .iter()
+            .map(|defender| self.effectiveness(*defender))
+            .product()
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names() {
+        for pokemon_type in Type::ALL {
+            assert_eq!(Type::from_name(pokemon_type.name()), Some(pokemon_type));
+        }
+        assert_eq!(Type::from_name("water"), Some(Water));
+        assert_eq!(Type::from_name("Sound"), None);
+        assert_eq!(Fairy.introduced_in(), 6);
+    }
+
+    #[test]
+    fn single_type() {
+        assert_eq!(Fire.effectiveness(Grass), 2.0);
+        assert_eq!(Fire.effectiveness(Water), 0.5);
+        assert_eq!(Normal.effectiveness(Ghost), 0.0);
+        assert_eq!(Dragon.effectiveness(Fairy), 0.0);
+    }
+
+    #[test]
+    fn dual_type() {
+        assert_eq!(Ice.effectiveness_against(&[Dragon, Ground]), 4.0);
+        assert_eq!(Electric.effectiveness_against(&[Water, Ground]), 0.0);
+        assert_eq!(Fighting.effectiveness_against(&[Bug, Flying]), 0.25);
+    }
+}
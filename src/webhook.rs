@@ -0,0 +1,122 @@
+//! Webhooks POSTed on tracker events, e.g. to get a Discord message or flip a
+//! smart light when a hunt passes 50% odds, without anything polling pkmn.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// How long to wait for each webhook, so a slow one doesn't hold up the
+/// command that triggered it.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// URLs to POST to per event, from the `[webhooks]` table of the config.
+///
+/// ```toml
+/// [webhooks]
+/// hunt_milestone = ["https://discord.com/api/webhooks/…"]
+/// ```
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    pub hunt_milestone: Vec<String>,
+}
+
+/// Something that happened to a tracker, sent as JSON tagged with its
+/// `event` name.
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    /// The active hunt passed one of [`crate::hunt::THRESHOLDS`].
+    HuntMilestone {
+        pokemon: String,
+        chance: f64,
+        attempts: u32,
+        odds: u32,
+    },
+}
+
+impl Event {
+    fn urls<'a>(&self, config: &'a WebhookConfig) -> &'a [String] {
+        match self {
+            Event::HuntMilestone { .. } => &config.hunt_milestone,
+        }
+    }
+
+    /// A sentence describing the event, sent as `content` too, which is what
+    /// chat services like Discord show.
+    pub fn summary(&self) -> String {
+        match self {
+            Event::HuntMilestone {
+                pokemon,
+                chance,
+                attempts,
+                odds,
+            } => format!(
+                "The {} hunt passed {:.0}% odds of a shiny after {} attempts at 1/{}",
+                pokemon,
+                chance * 100.0,
+                attempts,
+                odds
+            ),
+        }
+    }
+
+    fn body(&self) -> serde_json::Value {
+        let mut body = serde_json::to_value(self).expect("events serialize to JSON");
+        if let serde_json::Value::Object(fields) = &mut body {
+            fields.insert(String::from("content"), self.summary().into());
+        }
+        body
+    }
+}
+
+/// POSTs `event` to each of its webhooks, returning why each that failed did
+/// rather than failing the command that triggered it.
+pub async fn notify(config: &WebhookConfig, event: &Event) -> Vec<String> {
+    let client = reqwest::Client::builder()
+        .timeout(TIMEOUT)
+        .build()
+        .unwrap_or_default();
+    let body = event.body();
+    let mut failures = Vec::new();
+    for url in event.urls(config) {
+        info!("posting webhook to \"{}\"", url);
+        match client.post(url).json(&body).send().await {
+            Ok(res) if res.status().is_success() => {}
+            Ok(res) => failures.push(format!("Webhook \"{}\" responded {}", url, res.status())),
+            Err(err) => failures.push(format!("Couldn't post webhook \"{}\": {}", url, err)),
+        }
+    }
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bodies() {
+        let event = Event::HuntMilestone {
+            pokemon: String::from("Charmander"),
+            chance: 0.5,
+            attempts: 2839,
+            odds: 4096,
+        };
+        assert_eq!(
+            event.body(),
+            serde_json::json!({
+                "event": "hunt_milestone",
+                "pokemon": "Charmander",
+                "chance": 0.5,
+                "attempts": 2839,
+                "odds": 4096,
+                "content": "The Charmander hunt passed 50% odds of a shiny after 2839 attempts at 1/4096",
+            })
+        );
+
+        let config = WebhookConfig {
+            hunt_milestone: vec![String::from("https://example.com/hook")],
+        };
+        assert_eq!(event.urls(&config).len(), 1);
+    }
+}